@@ -602,4 +602,8 @@ impl StatusItemView for ActivityIndicator {
         _: &mut Context<Self>,
     ) {
     }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("activity_indicator")
+    }
 }
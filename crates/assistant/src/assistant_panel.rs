@@ -35,7 +35,7 @@ use ui::{prelude::*, ContextMenu, PopoverMenu, Tooltip};
 use util::{maybe, ResultExt};
 use workspace::DraggedTab;
 use workspace::{
-    dock::{DockPosition, Panel, PanelEvent},
+    dock::{CloseReason, DockPosition, Panel, PanelEvent},
     pane, DraggedSelection, Pane, ShowConfiguration, ToggleZoom, Workspace,
 };
 use zed_actions::assistant::{DeployPromptLibrary, InlineAssist, ToggleFocus};
@@ -385,7 +385,9 @@ impl AssistantPanel {
     ) {
         let update_model_summary = match event {
             pane::Event::Remove { .. } => {
-                cx.emit(PanelEvent::Close);
+                cx.emit(PanelEvent::Close {
+                    reason: CloseReason::User,
+                });
                 false
             }
             pane::Event::ZoomIn => {
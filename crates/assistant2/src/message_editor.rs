@@ -5,10 +5,10 @@ use editor::actions::MoveUp;
 use editor::{Editor, EditorElement, EditorEvent, EditorStyle};
 use fs::Fs;
 use git::ExpandCommitEditor;
-use git_ui::git_panel;
+use git_ui::git_panel::GitPanel;
 use gpui::{
-    Animation, AnimationExt, App, DismissEvent, Entity, Focusable, Subscription, TextStyle,
-    WeakEntity,
+    Animation, AnimationExt, App, ClickEvent, DismissEvent, Entity, Focusable, Subscription,
+    TextStyle, WeakEntity,
 };
 use language_model::LanguageModelRegistry;
 use language_model_selector::ToggleModelSelector;
@@ -329,6 +329,14 @@ impl MessageEditor {
         })
         .detach_and_notify_err(window, cx);
     }
+
+    fn focus_git_panel(&mut self, _: &ClickEvent, window: &mut Window, cx: &mut Context<Self>) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.focus_panel::<GitPanel>(window, cx);
+            })
+            .ok();
+    }
 }
 
 impl Focusable for MessageEditor {
@@ -452,13 +460,7 @@ impl Render for MessageEditor {
                                                 IconName::GitBranchSmall,
                                             )
                                             .icon_size(IconSize::Small)
-                                            .on_click(
-                                                |_ev, _window, cx| {
-                                                    cx.defer(|cx| {
-                                                        cx.dispatch_action(&git_panel::ToggleFocus)
-                                                    });
-                                                },
-                                            ),
+                                            .on_click(cx.listener(Self::focus_git_panel)),
                                         )
                                         .child(
                                             Label::new(format!(
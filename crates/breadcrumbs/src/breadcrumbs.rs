@@ -1,12 +1,12 @@
 use editor::Editor;
 use gpui::{
-    Context, Element, EventEmitter, Focusable, IntoElement, ParentElement, Render, StyledText,
-    Subscription, Window,
+    AnyElement, Context, Corner, EventEmitter, Focusable, IntoElement, ParentElement, Render,
+    StyledText, Subscription, WeakEntity, Window,
 };
 use itertools::Itertools;
-use std::cmp;
+use std::{cmp, mem};
 use theme::ActiveTheme;
-use ui::{prelude::*, ButtonLike, ButtonStyle, Label, Tooltip};
+use ui::{prelude::*, ButtonLike, ButtonStyle, ContextMenu, Label, PopoverMenu, Tooltip};
 use workspace::{
     item::{BreadcrumbText, ItemEvent, ItemHandle},
     ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
@@ -32,6 +32,93 @@ impl Breadcrumbs {
             subscription: Default::default(),
         }
     }
+
+    fn activate_breadcrumb(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(active_item) = self.active_item.as_ref() {
+            active_item.activate_breadcrumb(ix, window, cx);
+        }
+    }
+
+    fn render_segment(
+        segment: BreadcrumbText,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> StyledText {
+        let mut text_style = window.text_style();
+        if let Some(font) = segment.font {
+            text_style.font_family = font.family;
+            text_style.font_features = font.features;
+            text_style.font_style = font.style;
+            text_style.font_weight = font.weight;
+        }
+        text_style.color = Color::Muted.color(cx);
+
+        StyledText::new(segment.text.replace('\n', "⏎"))
+            .with_default_highlights(&text_style, segment.highlights.unwrap_or_default())
+    }
+
+    fn render_overflow_menu(
+        hidden_segments: Vec<(usize, String)>,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let this = cx.entity().downgrade();
+        PopoverMenu::new("breadcrumb-overflow")
+            .menu(move |window, cx| {
+                let this = this.clone();
+                let hidden_segments = hidden_segments.clone();
+                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for (ix, text) in hidden_segments {
+                        let this = this.clone();
+                        menu = menu.entry(text, None, move |window, cx| {
+                            this.update(cx, |this, cx| this.activate_breadcrumb(ix, window, cx))
+                                .ok();
+                        });
+                    }
+                    menu
+                }))
+            })
+            .trigger(
+                ButtonLike::new("breadcrumb-overflow-trigger")
+                    .style(ButtonStyle::Transparent)
+                    .child(Label::new("⋯").color(Color::Muted)),
+            )
+            .attach(Corner::BottomLeft)
+            .into_any_element()
+    }
+
+    fn toggle_outline_on_click(button: ButtonLike, editor: WeakEntity<Editor>) -> ButtonLike {
+        button
+            .on_click({
+                let editor = editor.clone();
+                move |_, window, cx| {
+                    if let Some((editor, callback)) = editor
+                        .upgrade()
+                        .zip(zed_actions::outline::TOGGLE_OUTLINE.get())
+                    {
+                        callback(editor.to_any(), window, cx);
+                    }
+                }
+            })
+            .tooltip(move |window, cx| {
+                if let Some(editor) = editor.upgrade() {
+                    let focus_handle = editor.read(cx).focus_handle(cx);
+                    Tooltip::for_action_in(
+                        "Show Symbol Outline",
+                        &zed_actions::outline::ToggleOutline,
+                        &focus_handle,
+                        window,
+                        cx,
+                    )
+                } else {
+                    Tooltip::for_action(
+                        "Show Symbol Outline",
+                        &zed_actions::outline::ToggleOutline,
+                        window,
+                        cx,
+                    )
+                }
+            })
+    }
 }
 
 impl EventEmitter<ToolbarItemEvent> for Breadcrumbs {}
@@ -50,9 +137,12 @@ impl Render for Breadcrumbs {
             return element;
         };
 
-        let Some(mut segments) = active_item.breadcrumbs(cx.theme(), cx) else {
+        let Some(segments) = active_item.breadcrumbs(cx.theme(), cx) else {
             return element;
         };
+        let editor = active_item
+            .downcast::<Editor>()
+            .map(|editor| editor.downgrade());
 
         let prefix_end_ix = cmp::min(segments.len(), MAX_SEGMENTS / 2);
         let suffix_start_ix = cmp::max(
@@ -60,81 +150,44 @@ impl Render for Breadcrumbs {
             segments.len().saturating_sub(MAX_SEGMENTS / 2),
         );
 
-        if suffix_start_ix > prefix_end_ix {
-            segments.splice(
-                prefix_end_ix..suffix_start_ix,
-                Some(BreadcrumbText {
-                    text: "⋯".into(),
-                    highlights: None,
-                    font: None,
-                }),
-            );
-        }
-
-        let highlighted_segments = segments.into_iter().map(|segment| {
-            let mut text_style = window.text_style();
-            if let Some(font) = segment.font {
-                text_style.font_family = font.family;
-                text_style.font_features = font.features;
-                text_style.font_style = font.style;
-                text_style.font_weight = font.weight;
+        let mut hidden_segments = Vec::new();
+        let mut elements = Vec::new();
+        for (ix, segment) in segments.into_iter().enumerate() {
+            if (prefix_end_ix..suffix_start_ix).contains(&ix) {
+                hidden_segments.push((ix, segment.text.replace('\n', "⏎")));
+                if ix + 1 == suffix_start_ix {
+                    elements.push(Self::render_overflow_menu(
+                        mem::take(&mut hidden_segments),
+                        cx,
+                    ));
+                }
+                continue;
             }
-            text_style.color = Color::Muted.color(cx);
 
-            StyledText::new(segment.text.replace('\n', "⏎"))
-                .with_default_highlights(&text_style, segment.highlights.unwrap_or_default())
-                .into_any()
-        });
-        let breadcrumbs = Itertools::intersperse_with(highlighted_segments, || {
+            let text = Self::render_segment(segment, window, cx);
+            let button = ButtonLike::new(("breadcrumb", ix))
+                .child(text)
+                .style(ButtonStyle::Transparent);
+            // Clicking an editor's path shows the outline of its symbols.
+            let button =
+                match editor.clone().filter(|_| ix == 0) {
+                    Some(editor) => Self::toggle_outline_on_click(button, editor),
+                    None => button.on_click(cx.listener(move |this, _, window, cx| {
+                        this.activate_breadcrumb(ix, window, cx)
+                    })),
+                };
+            elements.push(button.into_any_element());
+        }
+
+        let breadcrumbs = Itertools::intersperse_with(elements.into_iter(), || {
             Label::new("›").color(Color::Placeholder).into_any_element()
         });
 
-        let breadcrumbs_stack = h_flex().gap_1().children(breadcrumbs);
-
-        match active_item
-            .downcast::<Editor>()
-            .map(|editor| editor.downgrade())
-        {
-            Some(editor) => element.child(
-                ButtonLike::new("toggle outline view")
-                    .child(breadcrumbs_stack)
-                    .style(ButtonStyle::Transparent)
-                    .on_click({
-                        let editor = editor.clone();
-                        move |_, window, cx| {
-                            if let Some((editor, callback)) = editor
-                                .upgrade()
-                                .zip(zed_actions::outline::TOGGLE_OUTLINE.get())
-                            {
-                                callback(editor.to_any(), window, cx);
-                            }
-                        }
-                    })
-                    .tooltip(move |window, cx| {
-                        if let Some(editor) = editor.upgrade() {
-                            let focus_handle = editor.read(cx).focus_handle(cx);
-                            Tooltip::for_action_in(
-                                "Show Symbol Outline",
-                                &zed_actions::outline::ToggleOutline,
-                                &focus_handle,
-                                window,
-                                cx,
-                            )
-                        } else {
-                            Tooltip::for_action(
-                                "Show Symbol Outline",
-                                &zed_actions::outline::ToggleOutline,
-                                window,
-                                cx,
-                            )
-                        }
-                    }),
-            ),
-            None => element
-                // Match the height of the `ButtonLike` in the other arm.
-                .h(rems_from_px(22.))
-                .child(breadcrumbs_stack),
-        }
+        element
+            // Keep the height of a `ButtonLike` segment even when the segments use a taller
+            // buffer font, so the toolbar doesn't change height between items.
+            .h(rems_from_px(22.))
+            .child(h_flex().gap_1().children(breadcrumbs))
     }
 }
 
@@ -0,0 +1,83 @@
+use call::ActiveCall;
+use gpui::{AnyView, App, Context, Entity, Render, Subscription, WeakEntity, Window};
+use std::sync::Arc;
+use ui::{prelude::*, Avatar, Facepile, Tooltip};
+use workspace::{call_controls::CallControls, Workspace};
+
+pub(crate) fn init(cx: &mut App) {
+    workspace::call_controls::set_call_controls(Arc::new(CollabCallControls), cx);
+}
+
+struct CollabCallControls;
+
+impl CallControls for CollabCallControls {
+    fn facepile(
+        &self,
+        _: &mut Workspace,
+        _: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Option<AnyView> {
+        let active_call = ActiveCall::try_global(cx)?;
+        let workspace = cx.entity().downgrade();
+        let facepile: Entity<CallFacepile> = cx.new(|cx| CallFacepile {
+            workspace,
+            active_call: active_call.clone(),
+            _observe_active_call: cx.observe(&active_call, |_, _, cx| cx.notify()),
+        });
+        Some(facepile.into())
+    }
+}
+
+/// The faces of the other people in the current call. Clicking a face follows that person.
+struct CallFacepile {
+    workspace: WeakEntity<Workspace>,
+    active_call: Entity<ActiveCall>,
+    _observe_active_call: Subscription,
+}
+
+impl Render for CallFacepile {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(room) = self.active_call.read(cx).room().cloned() else {
+            return Facepile::empty();
+        };
+        let mut participants = room
+            .read(cx)
+            .remote_participants()
+            .values()
+            .map(|participant| {
+                (
+                    participant.participant_index,
+                    participant.peer_id,
+                    participant.user.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        participants.sort_by_key(|(participant_index, _, _)| participant_index.0);
+
+        Facepile::new(
+            participants
+                .into_iter()
+                .map(|(participant_index, peer_id, user)| {
+                    let workspace = self.workspace.clone();
+                    let player_color = cx
+                        .theme()
+                        .players()
+                        .color_for_participant(participant_index.0);
+                    div()
+                        .id(("call-participant", participant_index.0 as usize))
+                        .cursor_pointer()
+                        .child(
+                            Avatar::new(user.avatar_uri.clone()).border_color(player_color.cursor),
+                        )
+                        .tooltip(Tooltip::text(format!("Follow {}", user.github_login)))
+                        .on_click(move |_, window, cx| {
+                            workspace
+                                .update(cx, |workspace, cx| workspace.follow(peer_id, window, cx))
+                                .ok();
+                        })
+                        .into_any_element()
+                })
+                .collect(),
+        )
+    }
+}
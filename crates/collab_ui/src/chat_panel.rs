@@ -27,7 +27,7 @@ use ui::{
 };
 use util::{ResultExt, TryFutureExt};
 use workspace::{
-    dock::{DockPosition, Panel, PanelEvent},
+    dock::{CloseReason, DockPosition, Panel, PanelEvent},
     Workspace,
 };
 
@@ -170,7 +170,9 @@ impl ChatPanel {
                     }
                     room::Event::RoomLeft { channel_id } => {
                         if channel_id == &this.channel_id(cx) {
-                            cx.emit(PanelEvent::Close)
+                            cx.emit(PanelEvent::Close {
+                                reason: CloseReason::Automatic,
+                            })
                         }
                     }
                     _ => {}
@@ -1,3 +1,4 @@
+mod call_controls;
 pub mod channel_view;
 pub mod chat_panel;
 pub mod collab_panel;
@@ -27,6 +28,7 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut App) {
     NotificationPanelSettings::register(cx);
     MessageEditorSettings::register(cx);
 
+    call_controls::init(cx);
     channel_view::init(cx);
     chat_panel::init(cx);
     collab_panel::init(cx);
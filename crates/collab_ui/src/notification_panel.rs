@@ -155,7 +155,10 @@ impl NotificationPanel {
 
             let mut old_dock_position = this.position(window, cx);
             this.subscriptions.extend([
-                cx.observe(&this.notification_store, |_, _, cx| cx.notify()),
+                cx.observe(&this.notification_store, |_, _, cx| {
+                    cx.emit(PanelEvent::LabelChanged);
+                    cx.notify();
+                }),
                 cx.subscribe_in(
                     &this.notification_store,
                     window,
@@ -29,6 +29,12 @@ impl KeyValueStore {
             DELETE FROM kv_store WHERE key = (?)
         }
     }
+
+    query! {
+        pub async fn delete_kvps_with_prefix(prefix: String) -> Result<()> {
+            DELETE FROM kv_store WHERE substr(key, 1, length(?1)) = ?1
+        }
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +64,19 @@ mod tests {
 
         db.delete_kvp("key-1".to_string()).await.unwrap();
         assert_eq!(db.read_kvp("key-1").unwrap(), None);
+
+        db.write_kvp("key-3".to_string(), "three".to_string())
+            .await
+            .unwrap();
+        db.write_kvp("other-key".to_string(), "other".to_string())
+            .await
+            .unwrap();
+        db.delete_kvps_with_prefix("key-".to_string())
+            .await
+            .unwrap();
+        assert_eq!(db.read_kvp("key-2").unwrap(), None);
+        assert_eq!(db.read_kvp("key-3").unwrap(), None);
+        assert_eq!(db.read_kvp("other-key").unwrap(), Some("other".to_string()));
     }
 }
 
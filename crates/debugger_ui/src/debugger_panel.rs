@@ -23,7 +23,7 @@ use terminal_view::terminal_panel::TerminalPanel;
 use ui::prelude::*;
 use util::ResultExt;
 use workspace::{
-    dock::{DockPosition, Panel, PanelEvent},
+    dock::{CloseReason, DockPosition, Panel, PanelEvent},
     pane, Continue, Disconnect, Pane, Pause, Restart, StepBack, StepInto, StepOut, StepOver, Stop,
     ToggleIgnoreBreakpoints, Workspace,
 };
@@ -415,7 +415,9 @@ impl DebugPanel {
         cx: &mut Context<Self>,
     ) {
         match event {
-            pane::Event::Remove { .. } => cx.emit(PanelEvent::Close),
+            pane::Event::Remove { .. } => cx.emit(PanelEvent::Close {
+                reason: CloseReason::User,
+            }),
             pane::Event::ZoomIn => cx.emit(PanelEvent::ZoomIn),
             pane::Event::ZoomOut => cx.emit(PanelEvent::ZoomOut),
             pane::Event::AddItem { item } => {
@@ -198,4 +198,8 @@ impl StatusItemView for DiagnosticIndicator {
         }
         cx.notify();
     }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("diagnostics")
+    }
 }
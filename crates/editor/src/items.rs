@@ -2,8 +2,9 @@ use crate::{
     editor_settings::SeedQuerySetting,
     persistence::{SerializedEditor, DB},
     scroll::ScrollAnchor,
-    Anchor, Autoscroll, Editor, EditorEvent, EditorSettings, ExcerptId, ExcerptRange, FormatTarget,
-    MultiBuffer, MultiBufferSnapshot, NavigationData, SearchWithinRange, ToPoint as _,
+    Anchor, Autoscroll, Editor, EditorEvent, EditorMode, EditorSettings, ExcerptId, ExcerptRange,
+    FormatTarget, MultiBuffer, MultiBufferSnapshot, NavigationData, SearchWithinRange,
+    ToPoint as _,
 };
 use anyhow::{anyhow, Context as _, Result};
 use collections::HashSet;
@@ -604,6 +605,19 @@ impl Item for Editor {
         Some(file_path.into())
     }
 
+    fn is_read_only(&self, cx: &App) -> bool {
+        self.read_only(cx)
+    }
+
+    fn can_toggle_read_only(&self, cx: &App) -> bool {
+        self.mode == EditorMode::Full && !self.buffer.read(cx).read_only()
+    }
+
+    fn set_read_only(&mut self, read_only: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_read_only(read_only);
+        cx.notify();
+    }
+
     fn telemetry_event_text(&self) -> Option<&'static str> {
         None
     }
@@ -933,6 +947,25 @@ impl Item for Editor {
         Some(breadcrumbs)
     }
 
+    fn activate_breadcrumb(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        // The first breadcrumb is the path, the others are the symbols containing the cursor.
+        let Some(symbol_ix) = ix.checked_sub(1) else {
+            return;
+        };
+        let cursor = self.selections.newest_anchor().head();
+        let Some((_, symbols)) = self.buffer().read(cx).symbols_containing(cursor, None, cx) else {
+            return;
+        };
+        let Some(symbol) = symbols.get(symbol_ix) else {
+            return;
+        };
+        let position = symbol.range.start;
+        self.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+            s.select_ranges([position..position])
+        });
+        window.focus(&self.focus_handle);
+    }
+
     fn added_to_workspace(
         &mut self,
         workspace: &mut Workspace,
@@ -1739,8 +1772,10 @@ mod tests {
     use fs::Fs;
 
     use super::*;
+    use crate::test::editor_test_context::EditorTestContext;
     use fs::MTime;
     use gpui::{App, VisualTestContext};
+    use indoc::indoc;
     use language::{LanguageMatcher, TestFile};
     use project::FakeFs;
     use std::path::{Path, PathBuf};
@@ -1941,4 +1976,62 @@ mod tests {
             });
         }
     }
+
+    #[gpui::test]
+    async fn test_activate_breadcrumb(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorTestContext::new(cx).await;
+        let language = language::Language::new(
+            language::LanguageConfig {
+                name: "Rust".into(),
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_outline_query(indoc! {r#"
+            (mod_item "mod" @context name: (_) @name) @item
+            (function_item "fn" @context name: (_) @name) @item
+        "#})
+        .unwrap();
+        cx.update_buffer(|buffer, cx| buffer.set_language(Some(Arc::new(language)), cx));
+        cx.set_state(indoc! {"
+            mod outer {
+                fn inner() {
+                    let x = ˇ1;
+                }
+            }
+        "});
+        cx.run_until_parked();
+
+        // The breadcrumbs are the path, `mod outer` and `fn inner`.
+        cx.update_editor(|editor, window, cx| editor.activate_breadcrumb(2, window, cx));
+        cx.assert_editor_state(indoc! {"
+            mod outer {
+                ˇfn inner() {
+                    let x = 1;
+                }
+            }
+        "});
+        cx.update_editor(|editor, window, cx| editor.activate_breadcrumb(1, window, cx));
+        cx.assert_editor_state(indoc! {"
+            ˇmod outer {
+                fn inner() {
+                    let x = 1;
+                }
+            }
+        "});
+
+        // The path and the segments that are gone don't move the cursor.
+        cx.update_editor(|editor, window, cx| {
+            editor.activate_breadcrumb(0, window, cx);
+            editor.activate_breadcrumb(2, window, cx);
+        });
+        cx.assert_editor_state(indoc! {"
+            ˇmod outer {
+                fn inner() {
+                    let x = 1;
+                }
+            }
+        "});
+    }
 }
@@ -67,7 +67,7 @@ use workspace::{AppState, OpenOptions, OpenVisible};
 
 use notifications::status_toast::{StatusToast, ToastIcon};
 use workspace::{
-    dock::{DockPosition, Panel, PanelEvent},
+    dock::{CloseReason, DockPosition, Panel, PanelEvent},
     notifications::DetachAndPromptErr,
     Workspace,
 };
@@ -713,7 +713,9 @@ impl GitPanel {
     }
 
     fn close_panel(&mut self, _: &Close, _window: &mut Window, cx: &mut Context<Self>) {
-        cx.emit(PanelEvent::Close);
+        cx.emit(PanelEvent::Close {
+            reason: CloseReason::User,
+        });
     }
 
     fn focus_in(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -270,6 +270,14 @@ impl StatusItemView for CursorPosition {
 
         cx.notify();
     }
+
+    fn is_essential(&self) -> bool {
+        true
+    }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("cursor_position")
+    }
 }
 
 #[derive(Clone, Copy, Default, PartialEq, JsonSchema, Deserialize, Serialize)]
@@ -1020,6 +1020,15 @@ impl Window {
         view
     }
 
+    /// Replaces the root entity of the window with an existing one.
+    pub fn set_root<E>(&mut self, view: Entity<E>)
+    where
+        E: 'static + Render,
+    {
+        self.root = Some(view.into());
+        self.refresh();
+    }
+
     /// Returns the root entity of the window, if it has one.
     pub fn root<E>(&self) -> Option<Option<Entity<E>>>
     where
@@ -121,4 +121,8 @@ impl StatusItemView for ImageInfo {
         }
         cx.notify();
     }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("image_info")
+    }
 }
@@ -737,6 +737,10 @@ impl StatusItemView for InlineCompletionButton {
         }
         cx.notify();
     }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("edit_prediction_button")
+    }
 }
 
 impl SupermavenButtonStatus {
@@ -1,12 +1,13 @@
 use editor::Editor;
 use gpui::{
-    div, Context, Entity, IntoElement, ParentElement, Render, Subscription, WeakEntity, Window,
+    div, Action, App, Context, Entity, IntoElement, ParentElement, Render, Subscription,
+    WeakEntity, Window,
 };
 use language::LanguageName;
-use ui::{Button, ButtonCommon, Clickable, FluentBuilder, LabelSize, Tooltip};
-use workspace::{item::ItemHandle, StatusItemView, Workspace};
+use ui::{Button, ButtonCommon, ContextMenu, FluentBuilder, LabelSize, Tooltip};
+use workspace::{hide_status_item, item::ItemHandle, StatusItemView, Workspace};
 
-use crate::{LanguageSelector, Toggle};
+use crate::Toggle;
 
 pub struct ActiveBufferLanguage {
     active_language: Option<Option<LanguageName>>,
@@ -38,7 +39,7 @@ impl ActiveBufferLanguage {
 }
 
 impl Render for ActiveBufferLanguage {
-    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
         div().when_some(self.active_language.as_ref(), |el, active_language| {
             let active_language_text = if let Some(active_language_text) = active_language {
                 active_language_text.to_string()
@@ -46,16 +47,10 @@ impl Render for ActiveBufferLanguage {
                 "Unknown".to_string()
             };
 
+            // Clicks are handled by the status bar, which dispatches the click action.
             el.child(
                 Button::new("change-language", active_language_text)
                     .label_size(LabelSize::Small)
-                    .on_click(cx.listener(|this, _, window, cx| {
-                        if let Some(workspace) = this.workspace.upgrade() {
-                            workspace.update(cx, |workspace, cx| {
-                                LanguageSelector::toggle(workspace, window, cx)
-                            });
-                        }
-                    }))
                     .tooltip(|window, cx| {
                         Tooltip::for_action("Select Language", &Toggle, window, cx)
                     }),
@@ -82,4 +77,29 @@ impl StatusItemView for ActiveBufferLanguage {
 
         cx.notify();
     }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("active_language")
+    }
+
+    fn click_action(&self, _: &App) -> Option<Box<dyn Action>> {
+        self.active_language.as_ref().map(|_| Toggle.boxed_clone())
+    }
+
+    fn context_menu(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<ContextMenu>> {
+        self.active_language.as_ref()?;
+        let name = self.status_item_name()?;
+        let fs = self.workspace.upgrade()?.read(cx).app_state().fs.clone();
+        Some(ContextMenu::build(window, cx, move |menu, _, _| {
+            menu.action("Select Language…", Toggle.boxed_clone())
+                .separator()
+                .entry("Hide Language Indicator", None, move |_, cx| {
+                    hide_status_item(name, fs.clone(), cx)
+                })
+        }))
+    }
 }
@@ -0,0 +1,181 @@
+use anyhow::Result;
+use gpui::{actions, AnyElement, App, Context, Task, WeakEntity, Window};
+use picker::{
+    named_entries::{NamedEntries, NamedEntriesDelegate, NamedEntriesModal},
+    Picker,
+};
+use ui::{prelude::*, Tooltip};
+use workspace::{SerializedLayout, Workspace, WORKSPACE_DB};
+
+actions!(layout_manager, [ManageLayouts]);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(register).detach();
+}
+
+pub type LayoutManager = NamedEntriesModal<Layouts>;
+
+fn register(workspace: &mut Workspace, _window: Option<&mut Window>, _: &mut Context<Workspace>) {
+    workspace.register_action(|workspace, _: &ManageLayouts, window, cx| {
+        let workspace_handle = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            LayoutManager::new(
+                Layouts {
+                    workspace: workspace_handle,
+                },
+                window,
+                cx,
+            )
+        });
+    });
+}
+
+/// The layouts saved by name, which can be applied to the workspace.
+pub struct Layouts {
+    workspace: WeakEntity<Workspace>,
+}
+
+impl Layouts {
+    fn duplicate(
+        delegate: &NamedEntriesDelegate<Self>,
+        name: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<NamedEntriesDelegate<Self>>>,
+    ) {
+        let mut new_name = format!("{name} copy");
+        let mut copy_ix = 2;
+        while delegate
+            .stored_entries()
+            .iter()
+            .any(|layout| layout.name == new_name)
+        {
+            new_name = format!("{name} copy {copy_ix}");
+            copy_ix += 1;
+        }
+        let duplicate = cx.background_spawn(WORKSPACE_DB.duplicate_named_layout(name, new_name));
+        delegate.update_entries("Failed to duplicate layout", duplicate, window, cx);
+    }
+
+    fn toggle_default(
+        delegate: &NamedEntriesDelegate<Self>,
+        layout_name: String,
+        is_default: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<NamedEntriesDelegate<Self>>>,
+    ) {
+        // Setting a name no layout has clears the default.
+        let name = if is_default {
+            String::new()
+        } else {
+            layout_name
+        };
+        let set_default = cx.background_spawn(WORKSPACE_DB.set_default_named_layout(name));
+        delegate.update_entries("Failed to set the default layout", set_default, window, cx);
+    }
+}
+
+impl NamedEntries for Layouts {
+    type Entry = SerializedLayout;
+
+    const KIND: &'static str = "layout";
+
+    fn name(layout: &SerializedLayout) -> &str {
+        &layout.name
+    }
+
+    fn save_label(name: &str) -> String {
+        format!("Save current layout as “{name}”")
+    }
+
+    fn load(&self, cx: &mut App) -> Task<Result<Vec<SerializedLayout>>> {
+        cx.background_spawn(WORKSPACE_DB.named_layouts())
+    }
+
+    fn save(&self, name: String, window: &mut Window, cx: &mut App) -> Task<Result<()>> {
+        self.workspace
+            .update(cx, |workspace, cx| workspace.save_layout(name, window, cx))
+            .unwrap_or_else(|error| Task::ready(Err(error)))
+    }
+
+    fn open(
+        &self,
+        layout: &SerializedLayout,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        Task::ready(self.workspace.update(cx, |workspace, cx| {
+            workspace.apply_layout(layout, window, cx)
+        }))
+    }
+
+    fn delete(&self, name: String, cx: &mut App) -> Task<Result<()>> {
+        cx.background_spawn(WORKSPACE_DB.delete_named_layout(name))
+    }
+
+    fn rename(&self, old_name: String, new_name: String, cx: &mut App) -> Task<Result<()>> {
+        cx.background_spawn(WORKSPACE_DB.rename_named_layout(old_name, new_name))
+    }
+
+    fn render_details(&self, layout: &SerializedLayout) -> Option<AnyElement> {
+        layout.is_default.then(|| {
+            Label::new("default")
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .into_any_element()
+        })
+    }
+
+    fn render_actions(
+        &self,
+        ix: usize,
+        layout: &SerializedLayout,
+        cx: &mut Context<Picker<NamedEntriesDelegate<Self>>>,
+    ) -> Vec<AnyElement> {
+        let is_default = layout.is_default;
+        let duplicate_name = layout.name.clone();
+        let default_name = layout.name.clone();
+        vec![
+            IconButton::new("rename-layout", IconName::Pencil)
+                .icon_size(IconSize::Small)
+                .on_click(cx.listener(move |picker, _, window, cx| {
+                    cx.stop_propagation();
+                    picker.delegate.start_rename(ix, window, cx);
+                }))
+                .tooltip(Tooltip::text("Rename Layout"))
+                .into_any_element(),
+            IconButton::new("duplicate-layout", IconName::Copy)
+                .icon_size(IconSize::Small)
+                .on_click(cx.listener(move |picker, _, window, cx| {
+                    cx.stop_propagation();
+                    Self::duplicate(&picker.delegate, duplicate_name.clone(), window, cx);
+                }))
+                .tooltip(Tooltip::text("Duplicate Layout"))
+                .into_any_element(),
+            IconButton::new(
+                "default-layout",
+                if is_default {
+                    IconName::StarFilled
+                } else {
+                    IconName::Star
+                },
+            )
+            .icon_size(IconSize::Small)
+            .on_click(cx.listener(move |picker, _, window, cx| {
+                cx.stop_propagation();
+                Self::toggle_default(
+                    &picker.delegate,
+                    default_name.clone(),
+                    is_default,
+                    window,
+                    cx,
+                );
+            }))
+            .tooltip(Tooltip::text(if is_default {
+                "Unset as Default"
+            } else {
+                "Set as Default for New Windows"
+            }))
+            .into_any_element(),
+        ]
+    }
+}
@@ -0,0 +1,406 @@
+//! A picker over entries the user saved under a name, such as layouts or sessions. Typing a
+//! name that no entry has offers to save the current state under it.
+
+use crate::{Picker, PickerDelegate};
+use anyhow::{anyhow, Result};
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render,
+    Task, WeakEntity, Window,
+};
+use std::sync::Arc;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing, Tooltip};
+use util::{capitalize, ResultExt};
+use workspace::{notifications::DetachAndPromptErr, ModalView};
+
+/// The entries listed by a [`NamedEntriesModal`], and how they are stored and opened.
+pub trait NamedEntries: Sized + 'static {
+    type Entry: 'static;
+
+    /// What an entry is called in the picker's texts, e.g. "layout".
+    const KIND: &'static str;
+
+    fn name(entry: &Self::Entry) -> &str;
+
+    /// The label of the entry that saves the current state under the name typed into the query.
+    fn save_label(name: &str) -> String;
+
+    fn load(&self, cx: &mut App) -> Task<Result<Vec<Self::Entry>>>;
+
+    /// Saves the current state under `name`, replacing any entry with the same name.
+    fn save(&self, name: String, window: &mut Window, cx: &mut App) -> Task<Result<()>>;
+
+    fn open(&self, entry: &Self::Entry, window: &mut Window, cx: &mut App) -> Task<Result<()>>;
+
+    fn delete(&self, name: String, cx: &mut App) -> Task<Result<()>>;
+
+    /// Renames an entry. Only called for entries whose actions offer to rename them, with
+    /// [`NamedEntriesDelegate::start_rename`].
+    fn rename(&self, _old_name: String, _new_name: String, _cx: &mut App) -> Task<Result<()>> {
+        Task::ready(Err(anyhow!("{}s can't be renamed", Self::KIND)))
+    }
+
+    /// Shown after the name of the entry.
+    fn render_details(&self, _entry: &Self::Entry) -> Option<AnyElement> {
+        None
+    }
+
+    /// Buttons shown before the delete button of the entry at `ix`, while it's selected or
+    /// hovered.
+    fn render_actions(
+        &self,
+        _ix: usize,
+        _entry: &Self::Entry,
+        _cx: &mut Context<Picker<NamedEntriesDelegate<Self>>>,
+    ) -> Vec<AnyElement> {
+        Vec::new()
+    }
+}
+
+/// A modal with a picker over the entries of `E`.
+pub struct NamedEntriesModal<E: NamedEntries> {
+    picker: Entity<Picker<NamedEntriesDelegate<E>>>,
+}
+
+impl<E: NamedEntries> NamedEntriesModal<E> {
+    pub fn new(named_entries: E, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let modal = cx.entity().downgrade();
+        let picker = cx.new(|cx| {
+            let delegate = NamedEntriesDelegate::new(modal, named_entries);
+            Picker::uniform_list(delegate, window, cx)
+        });
+        picker.update(cx, |picker, cx| picker.delegate.reload(window, cx));
+        Self { picker }
+    }
+}
+
+impl<E: NamedEntries> Render for NamedEntriesModal<E> {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl<E: NamedEntries> Focusable for NamedEntriesModal<E> {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl<E: NamedEntries> EventEmitter<DismissEvent> for NamedEntriesModal<E> {}
+impl<E: NamedEntries> ModalView for NamedEntriesModal<E> {}
+
+enum PickerEntry {
+    Stored(StringMatch),
+    /// A name typed into the query, used to save the current state or to rename an entry.
+    New(String),
+}
+
+pub struct NamedEntriesDelegate<E: NamedEntries> {
+    modal: WeakEntity<NamedEntriesModal<E>>,
+    named_entries: E,
+    stored_entries: Vec<E::Entry>,
+    entries: Vec<PickerEntry>,
+    selected_index: usize,
+    /// The name of the entry being renamed, whose new name is typed into the query editor.
+    renaming: Option<String>,
+}
+
+impl<E: NamedEntries> NamedEntriesDelegate<E> {
+    fn new(modal: WeakEntity<NamedEntriesModal<E>>, named_entries: E) -> Self {
+        Self {
+            modal,
+            named_entries,
+            stored_entries: Vec::new(),
+            entries: Vec::new(),
+            selected_index: 0,
+            renaming: None,
+        }
+    }
+
+    /// The entries as they were last loaded.
+    pub fn stored_entries(&self) -> &[E::Entry] {
+        &self.stored_entries
+    }
+
+    fn reload(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let load = self.named_entries.load(cx);
+        cx.spawn_in(window, async move |picker, cx| {
+            let stored_entries = load.await.log_err()?;
+            picker
+                .update_in(cx, |picker, window, cx| {
+                    picker.delegate.stored_entries = stored_entries;
+                    picker.refresh(window, cx);
+                })
+                .ok()
+        })
+        .detach();
+    }
+
+    /// Waits for an operation on the stored entries, then refreshes the list. Failures are
+    /// shown to the user with the given message.
+    pub fn update_entries(
+        &self,
+        error_message: &str,
+        update: Task<Result<()>>,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        cx.spawn_in(window, async move |picker, cx| {
+            let result = update.await;
+            picker
+                .update_in(cx, |picker, window, cx| picker.delegate.reload(window, cx))
+                .ok();
+            result
+        })
+        .detach_and_prompt_err(error_message, window, cx, |_, _, _| None);
+    }
+
+    fn stored_entry(&self, ix: usize) -> Option<&E::Entry> {
+        match self.entries.get(ix)? {
+            PickerEntry::Stored(string_match) => self.stored_entries.get(string_match.candidate_id),
+            PickerEntry::New(_) => None,
+        }
+    }
+
+    /// Puts the name of the entry at `ix` into the query editor, to be edited and confirmed
+    /// as its new name.
+    pub fn start_rename(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.stored_entry(ix) else {
+            return;
+        };
+        let name = E::name(entry).to_string();
+        self.renaming = Some(name.clone());
+        cx.defer_in(window, move |picker, window, cx| {
+            picker.set_query(name, window, cx);
+            picker.refresh_placeholder(window, cx);
+        });
+    }
+
+    fn delete(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.stored_entry(ix) else {
+            return;
+        };
+        let delete = self.named_entries.delete(E::name(entry).to_string(), cx);
+        self.update_entries(&format!("Failed to delete {}", E::KIND), delete, window, cx);
+    }
+}
+
+impl<E: NamedEntries> PickerDelegate for NamedEntriesDelegate<E> {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        if self.renaming.is_some() {
+            format!("Enter a new name for the {}…", E::KIND).into()
+        } else {
+            format!("Search or name a new {}…", E::KIND).into()
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let query = query.trim().to_string();
+        if self.renaming.is_some() {
+            self.entries = if query.is_empty() {
+                Vec::new()
+            } else {
+                vec![PickerEntry::New(query)]
+            };
+            self.selected_index = 0;
+            return Task::ready(());
+        }
+
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .stored_entries
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| StringMatchCandidate::new(id, E::name(entry)))
+            .collect::<Vec<_>>();
+        let has_exact_match = self
+            .stored_entries
+            .iter()
+            .any(|entry| E::name(entry) == query);
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut entries = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| {
+                        PickerEntry::Stored(StringMatch {
+                            candidate_id: candidate.id,
+                            string: candidate.string,
+                            positions: Vec::new(),
+                            score: 0.0,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+                .into_iter()
+                .map(PickerEntry::Stored)
+                .collect()
+            };
+            if !query.is_empty() && !has_exact_match {
+                entries.push(PickerEntry::New(query));
+            }
+
+            picker
+                .update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.entries = entries;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.entries.len().saturating_sub(1));
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        match self.entries.get(self.selected_index) {
+            Some(PickerEntry::New(name)) => {
+                let name = name.clone();
+                if let Some(old_name) = self.renaming.take() {
+                    // Entries are stored by name, so renaming onto another entry's name fails.
+                    let name_taken = name != old_name
+                        && self
+                            .stored_entries
+                            .iter()
+                            .any(|entry| E::name(entry) == name);
+                    let rename = if name_taken {
+                        Task::ready(Err(anyhow!("A {} named “{name}” already exists", E::KIND)))
+                    } else {
+                        self.named_entries.rename(old_name, name, cx)
+                    };
+                    self.update_entries(
+                        &format!("Failed to rename {}", E::KIND),
+                        rename,
+                        window,
+                        cx,
+                    );
+                } else {
+                    let save = self.named_entries.save(name, window, cx);
+                    self.update_entries(&format!("Failed to save {}", E::KIND), save, window, cx);
+                }
+                cx.defer_in(window, |picker, window, cx| {
+                    picker.set_query("", window, cx);
+                    picker.refresh_placeholder(window, cx);
+                });
+            }
+            Some(PickerEntry::Stored(string_match)) => {
+                if let Some(entry) = self.stored_entries.get(string_match.candidate_id) {
+                    self.named_entries
+                        .open(entry, window, cx)
+                        .detach_and_prompt_err(
+                            &format!("Failed to open {}", E::KIND),
+                            window,
+                            cx,
+                            |_, _, _| None,
+                        );
+                }
+                self.dismissed(window, cx);
+            }
+            None => {}
+        }
+    }
+
+    fn dismissed(&mut self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if self.renaming.take().is_some() {
+            cx.defer_in(window, |picker, window, cx| {
+                picker.set_query("", window, cx);
+                picker.refresh_placeholder(window, cx);
+            });
+            return;
+        }
+        self.modal
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+        match self.entries.get(ix)? {
+            PickerEntry::New(name) => {
+                let label = match &self.renaming {
+                    Some(old_name) => format!("Rename “{old_name}” to “{name}”"),
+                    None => E::save_label(name),
+                };
+                Some(
+                    item.start_slot(Icon::new(IconName::Plus).color(Color::Muted))
+                        .child(Label::new(label)),
+                )
+            }
+            PickerEntry::Stored(string_match) => {
+                let entry = self.stored_entries.get(string_match.candidate_id)?;
+                let actions = h_flex()
+                    .gap_1()
+                    .children(self.named_entries.render_actions(ix, entry, cx))
+                    .child(
+                        IconButton::new("delete-entry", IconName::Trash)
+                            .icon_size(IconSize::Small)
+                            .on_click(cx.listener(move |picker, _, window, cx| {
+                                cx.stop_propagation();
+                                picker.delegate.delete(ix, window, cx);
+                            }))
+                            .tooltip(Tooltip::text(format!("Delete {}", capitalize(E::KIND)))),
+                    );
+                Some(
+                    item.child(
+                        h_flex()
+                            .gap_2()
+                            .child(HighlightedLabel::new(
+                                E::name(entry).to_string(),
+                                string_match.positions.clone(),
+                            ))
+                            .children(self.named_entries.render_details(entry)),
+                    )
+                    .map(|item| {
+                        if selected {
+                            item.end_slot(actions)
+                        } else {
+                            item.end_hover_slot(actions)
+                        }
+                    }),
+                )
+            }
+        }
+    }
+}
@@ -18,6 +18,7 @@ use workspace::ModalView;
 
 mod head;
 pub mod highlighted_match_with_paths;
+pub mod named_entries;
 
 enum ElementContainer {
     List(ListState),
@@ -4766,6 +4766,14 @@ impl Panel for ProjectPanel {
         cx.notify();
     }
 
+    fn has_custom_size(&self, _: &Window, _: &App) -> bool {
+        self.width.is_some()
+    }
+
+    fn preferred_sizes(&self, _: &Window, cx: &App) -> Vec<Pixels> {
+        vec![ProjectPanelSettings::get_global(cx).default_width]
+    }
+
     fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
         ProjectPanelSettings::get_global(cx)
             .button
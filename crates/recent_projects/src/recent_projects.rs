@@ -680,7 +680,7 @@ mod tests {
             .unwrap();
 
         assert!(
-            !cx.has_pending_prompt(),
+            !has_pending_prompt(&workspace, cx),
             "Should have no pending prompt on dirty project before opening the new recent project"
         );
         cx.dispatch_action(*workspace, menu::Confirm);
@@ -693,12 +693,17 @@ mod tests {
             })
             .unwrap();
         assert!(
-            cx.has_pending_prompt(),
+            has_pending_prompt(&workspace, cx),
             "Dirty workspace should prompt before opening the new recent project"
         );
-        cx.simulate_prompt_answer("Cancel");
+        workspace
+            .update(cx, |workspace, _, cx| {
+                workspace.simulate_prompt_answer("Cancel", cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
         assert!(
-            !cx.has_pending_prompt(),
+            !has_pending_prompt(&workspace, cx),
             "Should have no pending prompt after cancelling"
         );
         workspace
@@ -711,6 +716,12 @@ mod tests {
             .unwrap();
     }
 
+    fn has_pending_prompt(workspace: &WindowHandle<Workspace>, cx: &mut TestAppContext) -> bool {
+        workspace
+            .read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_some())
+            .unwrap()
+    }
+
     fn open_recent_projects(
         workspace: &WindowHandle<Workspace>,
         cx: &mut TestAppContext,
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use db::kvp::KEY_VALUE_STORE;
+use futures::future::Shared;
 use gpui::{AnyWindowHandle, AppContext as _, Context, Subscription, Task, WindowId};
 use util::ResultExt;
 use uuid::Uuid;
@@ -9,21 +10,35 @@ pub struct Session {
     session_id: String,
     old_session_id: Option<String>,
     old_window_ids: Option<Vec<WindowId>>,
+    old_session_crashed: bool,
 }
 
 const SESSION_ID_KEY: &str = "session_id";
 const SESSION_WINDOW_STACK_KEY: &str = "session_window_stack";
+/// Written when a session starts and deleted when the app quits, so finding it on startup
+/// means the previous session ended without quitting.
+const SESSION_RUNNING_KEY: &str = "session_running";
 
 impl Session {
     pub async fn new() -> Self {
         let old_session_id = KEY_VALUE_STORE.read_kvp(SESSION_ID_KEY).ok().flatten();
 
+        let old_session_crashed = KEY_VALUE_STORE
+            .read_kvp(SESSION_RUNNING_KEY)
+            .ok()
+            .flatten()
+            .is_some();
+
         let session_id = Uuid::new_v4().to_string();
 
         KEY_VALUE_STORE
             .write_kvp(SESSION_ID_KEY.to_string(), session_id.clone())
             .await
             .log_err();
+        KEY_VALUE_STORE
+            .write_kvp(SESSION_RUNNING_KEY.to_string(), session_id.clone())
+            .await
+            .log_err();
 
         let old_window_ids = KEY_VALUE_STORE
             .read_kvp(SESSION_WINDOW_STACK_KEY)
@@ -40,6 +55,7 @@ impl Session {
             session_id,
             old_session_id,
             old_window_ids,
+            old_session_crashed,
         }
     }
 
@@ -49,6 +65,7 @@ impl Session {
             session_id: Uuid::new_v4().to_string(),
             old_session_id: None,
             old_window_ids: None,
+            old_session_crashed: false,
         }
     }
 
@@ -57,8 +74,17 @@ impl Session {
     }
 }
 
+/// What the user chose to do with the unsaved changes restored after the last session ended
+/// without quitting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnsavedChangesAnswer {
+    Restore,
+    Discard,
+}
+
 pub struct AppSession {
     session: Session,
+    unsaved_changes_answer: Option<Shared<Task<UnsavedChangesAnswer>>>,
     _serialization_task: Option<Task<()>>,
     _subscriptions: Vec<Subscription>,
 }
@@ -79,17 +105,23 @@ impl AppSession {
 
         Self {
             session,
+            unsaved_changes_answer: None,
             _subscriptions,
             _serialization_task,
         }
     }
 
     fn app_will_quit(&mut self, cx: &mut Context<Self>) -> Task<()> {
-        if let Some(windows) = cx.window_stack() {
-            cx.background_spawn(store_window_stack(windows))
-        } else {
-            Task::ready(())
-        }
+        let windows = cx.window_stack();
+        cx.background_spawn(async move {
+            if let Some(windows) = windows {
+                store_window_stack(windows).await;
+            }
+            KEY_VALUE_STORE
+                .delete_kvp(SESSION_RUNNING_KEY.to_string())
+                .await
+                .log_err();
+        })
     }
 
     pub fn id(&self) -> &str {
@@ -100,6 +132,27 @@ impl AppSession {
         self.session.old_session_id.as_deref()
     }
 
+    /// Whether the last session ended without the app quitting, e.g. because it crashed.
+    pub fn last_session_crashed(&self) -> bool {
+        self.session.old_session_crashed
+    }
+
+    /// The answer to whether to keep the unsaved changes restored after the last session
+    /// crashed, once the user was asked. It's asked only once and applies to every workspace
+    /// restored in this session.
+    pub fn unsaved_changes_answer(&self) -> Option<Shared<Task<UnsavedChangesAnswer>>> {
+        self.unsaved_changes_answer.clone()
+    }
+
+    pub fn set_unsaved_changes_answer(&mut self, answer: Shared<Task<UnsavedChangesAnswer>>) {
+        self.unsaved_changes_answer = Some(answer);
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn simulate_last_session_crashed(&mut self) {
+        self.session.old_session_crashed = true;
+    }
+
     pub fn last_session_window_stack(&self) -> Option<Vec<WindowId>> {
         self.session.old_window_ids.clone()
     }
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use gpui::{actions, AnyElement, App, Context, Task, WeakEntity, Window};
+use picker::named_entries::{NamedEntries, NamedEntriesModal};
+use ui::prelude::*;
+use workspace::{SerializedNamedSession, Workspace, WORKSPACE_DB};
+
+actions!(session_manager, [ManageSessions]);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(register).detach();
+}
+
+/// A modal listing the named sessions, to save the current workspace as one or to reopen
+/// one in place of it.
+pub type SessionManager = NamedEntriesModal<Sessions>;
+
+fn register(workspace: &mut Workspace, _window: Option<&mut Window>, _: &mut Context<Workspace>) {
+    workspace.register_action(|workspace, _: &ManageSessions, window, cx| {
+        let workspace_handle = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            SessionManager::new(
+                Sessions {
+                    workspace: workspace_handle,
+                },
+                window,
+                cx,
+            )
+        });
+    });
+}
+
+/// The sessions saved by name, each of which can replace the workspace.
+pub struct Sessions {
+    workspace: WeakEntity<Workspace>,
+}
+
+impl NamedEntries for Sessions {
+    type Entry = SerializedNamedSession;
+
+    const KIND: &'static str = "session";
+
+    fn name(session: &SerializedNamedSession) -> &str {
+        &session.name
+    }
+
+    fn save_label(name: &str) -> String {
+        format!("Save current workspace as “{name}”")
+    }
+
+    fn load(&self, cx: &mut App) -> Task<Result<Vec<SerializedNamedSession>>> {
+        cx.spawn(async move |_| WORKSPACE_DB.named_sessions().await)
+    }
+
+    fn save(&self, name: String, window: &mut Window, cx: &mut App) -> Task<Result<()>> {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.save_named_session(name, window, cx)
+            })
+            .unwrap_or_else(|error| Task::ready(Err(error)))
+    }
+
+    fn open(
+        &self,
+        session: &SerializedNamedSession,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        let session = session.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_named_session(session, window, cx)
+            })
+            .unwrap_or_else(|error| Task::ready(Err(error)))
+    }
+
+    fn delete(&self, name: String, cx: &mut App) -> Task<Result<()>> {
+        cx.background_spawn(WORKSPACE_DB.delete_named_session(name))
+    }
+
+    fn render_details(&self, session: &SerializedNamedSession) -> Option<AnyElement> {
+        let roots = session
+            .paths
+            .iter()
+            .filter_map(|path| path.file_name()?.to_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(
+            Label::new(roots)
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .into_any_element(),
+        )
+    }
+}
@@ -0,0 +1,292 @@
+//! A picker over the items open in all panes of the workspace, most recently activated first,
+//! and the files of items closed since, by when they were closed. The chosen item is opened in
+//! the active pane.
+
+use collections::HashSet;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, rems, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    ParentElement, Render, Styled, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{ModalView, RecentItem, Workspace};
+
+const PANEL_WIDTH_REMS: f32 = 34.;
+
+actions!(recent_items, [Toggle]);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(RecentItems::register).detach();
+}
+
+pub struct RecentItems {
+    picker: Entity<Picker<RecentItemsDelegate>>,
+}
+
+impl ModalView for RecentItems {}
+
+impl EventEmitter<DismissEvent> for RecentItems {}
+
+impl Focusable for RecentItems {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl RecentItems {
+    fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(|workspace, _: &Toggle, window, cx| {
+            // A file open in several panes is listed once, for its most recent activation.
+            let mut seen_paths = HashSet::default();
+            let items = workspace
+                .recent_items(cx)
+                .into_iter()
+                .filter(|recent_item| match recent_item {
+                    RecentItem::Open { item, .. } => item
+                        .project_path(cx)
+                        .map_or(true, |project_path| seen_paths.insert(project_path)),
+                    RecentItem::Closed(_) => true,
+                })
+                .collect::<Vec<_>>();
+            let active_item_id = workspace.active_item(cx).map(|item| item.item_id());
+            let active_item_first = matches!(
+                items.first(),
+                Some(RecentItem::Open { item, .. }) if Some(item.item_id()) == active_item_id
+            );
+            let weak_workspace = cx.entity().downgrade();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                let delegate = RecentItemsDelegate::new(
+                    cx.entity().downgrade(),
+                    weak_workspace,
+                    items,
+                    active_item_first,
+                    window,
+                    cx,
+                );
+                Self {
+                    picker: cx.new(|cx| Picker::uniform_list(delegate, window, cx)),
+                }
+            });
+        });
+    }
+}
+
+impl Render for RecentItems {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("RecentItems")
+            .w(rems(PANEL_WIDTH_REMS))
+            .child(self.picker.clone())
+    }
+}
+
+pub struct RecentItemsDelegate {
+    recent_items: WeakEntity<RecentItems>,
+    workspace: WeakEntity<Workspace>,
+    items: Vec<RecentItem>,
+    active_item_first: bool,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl RecentItemsDelegate {
+    fn new(
+        recent_items: WeakEntity<RecentItems>,
+        workspace: WeakEntity<Workspace>,
+        items: Vec<RecentItem>,
+        active_item_first: bool,
+        window: &mut Window,
+        cx: &mut Context<RecentItems>,
+    ) -> Self {
+        // Items are matched by their path, so files with the same name can be told apart.
+        let candidates = items
+            .iter()
+            .enumerate()
+            .map(|(id, recent_item)| {
+                let label = match recent_item {
+                    RecentItem::Open { item, .. } => item
+                        .project_path(cx)
+                        .map(|project_path| project_path.path.to_string_lossy().to_string())
+                        .or_else(|| {
+                            item.tab_content_text(window, cx)
+                                .map(|text| text.to_string())
+                        })
+                        .unwrap_or_else(|| "untitled".to_string()),
+                    RecentItem::Closed(project_path) => {
+                        project_path.path.to_string_lossy().to_string()
+                    }
+                };
+                StringMatchCandidate::new(id, &label)
+            })
+            .collect();
+        Self {
+            recent_items,
+            workspace,
+            items,
+            active_item_first,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for RecentItemsDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search recent items…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No recent items".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+        cx.notify();
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                // Without a query the active item usually comes first, so start on the one before
+                // it. A file closed since the active item was activated comes before it instead.
+                delegate.selected_index =
+                    if query.is_empty() && delegate.active_item_first && matches.len() > 1 {
+                        1
+                    } else {
+                        0
+                    };
+                delegate.matches = matches;
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(recent_item) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.items.get(mat.candidate_id))
+        else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let active_pane = workspace.active_pane().clone();
+                let item = match recent_item {
+                    RecentItem::Open { item, .. } => item,
+                    RecentItem::Closed(project_path) => {
+                        workspace
+                            .open_path(
+                                project_path.clone(),
+                                Some(active_pane.downgrade()),
+                                true,
+                                window,
+                                cx,
+                            )
+                            .detach_and_log_err(cx);
+                        return;
+                    }
+                };
+                if let Some(ix) = active_pane.read(cx).index_for_item(item.as_ref()) {
+                    active_pane.update(cx, |pane, cx| {
+                        pane.activate_item(ix, true, true, window, cx);
+                    });
+                } else if let Some(project_path) = item.project_path(cx) {
+                    workspace
+                        .open_path(
+                            project_path,
+                            Some(active_pane.downgrade()),
+                            true,
+                            window,
+                            cx,
+                        )
+                        .detach_and_log_err(cx);
+                } else {
+                    // Items without a file can't be opened again, so go to where they are.
+                    workspace.activate_item(item.as_ref(), true, true, window, cx);
+                }
+            })
+            .log_err();
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.recent_items
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let label = HighlightedLabel::new(mat.string.clone(), mat.positions.clone());
+        // Closed items are told apart by having no icon and a muted label.
+        let (icon, label) = match self.items.get(mat.candidate_id)? {
+            RecentItem::Open { item, .. } => (item.tab_icon(window, cx), label),
+            RecentItem::Closed(_) => (None, label.color(Color::Muted)),
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot::<Icon>(icon)
+                .child(label),
+        )
+    }
+}
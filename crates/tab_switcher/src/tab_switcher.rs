@@ -1,3 +1,4 @@
+pub mod recent_items;
 #[cfg(test)]
 mod tab_switcher_tests;
 
@@ -18,6 +19,7 @@ use ui::{prelude::*, ListItem, ListItemSpacing, Tooltip};
 use util::ResultExt;
 use workspace::{
     item::{ItemHandle, ItemSettings, TabContentParams},
+    item_decorations::ItemDecorations,
     pane::{render_item_indicator, tab_details, Event as PaneEvent},
     ModalView, Pane, SaveIntent, Workspace,
 };
@@ -43,6 +45,7 @@ impl ModalView for TabSwitcher {}
 
 pub fn init(cx: &mut App) {
     cx.observe_new(TabSwitcher::register).detach();
+    recent_items::init(cx);
 }
 
 impl TabSwitcher {
@@ -72,11 +75,7 @@ impl TabSwitcher {
         cx: &mut Context<Workspace>,
     ) {
         let mut weak_pane = workspace.active_pane().downgrade();
-        for dock in [
-            workspace.left_dock(),
-            workspace.bottom_dock(),
-            workspace.right_dock(),
-        ] {
+        for dock in workspace.all_docks() {
             dock.update(cx, |this, cx| {
                 let Some(panel) = this
                     .active_panel()
@@ -395,6 +394,12 @@ impl PickerDelegate for TabSwitcherDelegate {
             detail: Some(tab_match.detail),
             selected: true,
             preview: tab_match.preview,
+            label_color: ItemDecorations::try_global(cx).and_then(|decorations| {
+                decorations
+                    .read(cx)
+                    .decoration(tab_match.item.as_ref(), cx)
+                    .label_color
+            }),
         };
         let label = tab_match.item.tab_content(params, window, cx);
 
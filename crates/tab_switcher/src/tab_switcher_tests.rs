@@ -6,7 +6,7 @@ use project::{Project, ProjectPath};
 use serde_json::json;
 use std::path::Path;
 use util::path;
-use workspace::{AppState, Workspace};
+use workspace::{AppState, SaveIntent, SplitDirection, Workspace};
 
 #[ctor::ctor]
 fn init_logger() {
@@ -283,6 +283,107 @@ async fn test_close_preserves_selected_position(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_recent_items_open_in_active_pane(cx: &mut gpui::TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                "1.txt": "First file",
+                "2.txt": "Second file",
+                "3.txt": "Third file",
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+    open_buffer("1.txt", &workspace, cx).await;
+    open_buffer("2.txt", &workspace, cx).await;
+    let right_pane = workspace.update_in(cx, |workspace, window, cx| {
+        let left_pane = workspace.active_pane().clone();
+        let right_pane = workspace.split_pane(left_pane, SplitDirection::Right, window, cx);
+        window.focus(&right_pane.focus_handle(cx));
+        right_pane
+    });
+    open_buffer("3.txt", &workspace, cx).await;
+    let active_path = |cx: &mut VisualTestContext| {
+        right_pane.update(cx, |pane, cx| {
+            pane.active_item()
+                .and_then(|item| item.project_path(cx))
+                .map(|project_path| project_path.path.to_string_lossy().to_string())
+        })
+    };
+
+    // Without a query, the item activated before the current one is chosen, even though it's
+    // in another pane.
+    cx.dispatch_action(recent_items::Toggle);
+    cx.run_until_parked();
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+    assert_eq!(active_path(cx).as_deref(), Some("2.txt"));
+    assert_recent_items_is_closed(&workspace, cx);
+
+    // Items are filtered by their path.
+    cx.dispatch_action(recent_items::Toggle);
+    cx.run_until_parked();
+    cx.simulate_input("1");
+    cx.run_until_parked();
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+    assert_eq!(active_path(cx).as_deref(), Some("1.txt"));
+    right_pane.update(cx, |pane, _| assert_eq!(pane.items_len(), 3));
+}
+
+#[gpui::test]
+async fn test_recent_items_reopen_closed_item(cx: &mut gpui::TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                "1.txt": "First file",
+                "2.txt": "Second file",
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+    let tab_1 = open_buffer("1.txt", &workspace, cx).await;
+    open_buffer("2.txt", &workspace, cx).await;
+    let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+    pane.update_in(cx, |pane, window, cx| {
+        pane.close_item_by_id(tab_1.item_id(), SaveIntent::Close, window, cx)
+    })
+    .await
+    .unwrap();
+
+    // The closed file is listed first, as it was closed after the open one was activated, and
+    // choosing it opens it again.
+    cx.dispatch_action(recent_items::Toggle);
+    cx.run_until_parked();
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+    let active_path = pane.update(cx, |pane, cx| {
+        pane.active_item()
+            .and_then(|item| item.project_path(cx))
+            .map(|project_path| project_path.path.to_string_lossy().to_string())
+    });
+    assert_eq!(active_path.as_deref(), Some("1.txt"));
+    pane.update(cx, |pane, _| assert_eq!(pane.items_len(), 2));
+    assert_recent_items_is_closed(&workspace, cx);
+}
+
 fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
     cx.update(|cx| {
         let state = AppState::test(cx);
@@ -380,3 +481,15 @@ fn assert_tab_switcher_is_closed(workspace: Entity<Workspace>, cx: &mut VisualTe
         );
     });
 }
+
+#[track_caller]
+fn assert_recent_items_is_closed(workspace: &Entity<Workspace>, cx: &mut VisualTestContext) {
+    workspace.update(cx, |workspace, cx| {
+        assert!(
+            workspace
+                .active_modal::<recent_items::RecentItems>(cx)
+                .is_none(),
+            "recent items picker is still open"
+        );
+    });
+}
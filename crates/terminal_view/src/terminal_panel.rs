@@ -31,7 +31,7 @@ use ui::{
 };
 use util::{ResultExt, TryFutureExt};
 use workspace::{
-    dock::{DockPosition, Panel, PanelEvent, PanelHandle},
+    dock::{CloseReason, DockPosition, Panel, PanelEvent, PanelHandle},
     item::SerializableItem,
     move_active_item, move_item, pane,
     ui::IconName,
@@ -46,6 +46,8 @@ use anyhow::{anyhow, Context as _, Result};
 use zed_actions::assistant::InlineAssist;
 
 const TERMINAL_PANEL_KEY: &str = "TerminalPanel";
+/// The number of lines at the bottom of the active terminal shown in the panel's preview.
+const PREVIEW_LINE_COUNT: usize = 12;
 
 actions!(terminal_panel, [ToggleFocus]);
 
@@ -347,7 +349,10 @@ impl TerminalPanel {
     ) {
         match event {
             pane::Event::ActivateItem { .. } => self.serialize(cx),
-            pane::Event::RemovedItem { .. } => self.serialize(cx),
+            pane::Event::RemovedItem { .. } => {
+                self.serialize(cx);
+                cx.emit(PanelEvent::LabelChanged);
+            }
             pane::Event::Remove { focus_on_pane } => {
                 let pane_count_before_removal = self.center.panes().len();
                 let _removal_result = self.center.remove(&pane);
@@ -355,7 +360,9 @@ impl TerminalPanel {
                     self.center.first_pane().update(cx, |pane, cx| {
                         pane.set_zoomed(false, cx);
                     });
-                    cx.emit(PanelEvent::Close);
+                    cx.emit(PanelEvent::Close {
+                        reason: CloseReason::User,
+                    });
                 } else {
                     if let Some(focus_on_pane) =
                         focus_on_pane.as_ref().or_else(|| self.center.panes().pop())
@@ -389,6 +396,7 @@ impl TerminalPanel {
                     })
                 }
                 self.serialize(cx);
+                cx.emit(PanelEvent::LabelChanged);
             }
             pane::Event::Split(direction) => {
                 let Some(new_pane) = self.new_pane_with_cloned_active_terminal(window, cx) else {
@@ -471,20 +479,8 @@ impl TerminalPanel {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
-        let Some(terminal_panel) = workspace.panel::<Self>(cx) else {
-            return;
-        };
-
-        terminal_panel
-            .update(cx, |panel, cx| {
-                panel.add_terminal(
-                    TerminalKind::Shell(Some(action.working_directory.clone())),
-                    RevealStrategy::Always,
-                    window,
-                    cx,
-                )
-            })
-            .detach_and_log_err(cx);
+        let kind = TerminalKind::Shell(Some(action.working_directory.clone()));
+        Self::add_terminal_once_loaded(workspace, kind, window, cx);
     }
 
     fn spawn_task(&mut self, task: &SpawnInTerminal, window: &mut Window, cx: &mut Context<Self>) {
@@ -588,17 +584,33 @@ impl TerminalPanel {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
-        let Some(terminal_panel) = workspace.panel::<Self>(cx) else {
-            return;
-        };
-
         let kind = TerminalKind::Shell(default_working_directory(workspace, cx));
+        Self::add_terminal_once_loaded(workspace, kind, window, cx);
+    }
 
-        terminal_panel
-            .update(cx, |this, cx| {
-                this.add_terminal(kind, RevealStrategy::Always, window, cx)
-            })
-            .detach_and_log_err(cx);
+    /// Adds a terminal to the terminal panel, waiting for the panel to be loaded first so that
+    /// terminals requested while the workspace is still opening aren't dropped.
+    fn add_terminal_once_loaded(
+        workspace: &mut Workspace,
+        kind: TerminalKind,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let terminal_panel = workspace.open_panel_async::<Self>(window, cx);
+        cx.spawn_in(window, async move |_, cx| {
+            // Without a terminal panel, e.g. because it failed to load, there's nowhere to add
+            // the terminal.
+            let Ok(terminal_panel) = terminal_panel.await else {
+                return Ok(());
+            };
+            terminal_panel
+                .update_in(cx, |panel, window, cx| {
+                    panel.add_terminal(kind, RevealStrategy::Always, window, cx)
+                })?
+                .await?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
     }
 
     fn terminals_for_task(
@@ -1397,10 +1409,58 @@ impl Panel for TerminalPanel {
         })
     }
 
+    fn has_custom_size(&self, window: &Window, cx: &App) -> bool {
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => self.width.is_some(),
+            DockPosition::Bottom => self.height.is_some(),
+        }
+    }
+
+    fn preferred_sizes(&self, window: &Window, cx: &App) -> Vec<Pixels> {
+        let settings = TerminalSettings::get_global(cx);
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => vec![settings.default_width],
+            DockPosition::Bottom => vec![settings.default_height],
+        }
+    }
+
     fn is_zoomed(&self, _window: &Window, cx: &App) -> bool {
         self.active_pane.read(cx).is_zoomed()
     }
 
+    fn render_preview(&mut self, _: &mut Window, cx: &mut Context<Self>) -> Option<AnyElement> {
+        // The lines are read out of the terminal's grid rather than rendering its view, which
+        // would resize the terminal, and its PTY, to fit the preview.
+        let terminal_view = self
+            .active_pane
+            .read(cx)
+            .active_item()?
+            .downcast::<TerminalView>()?;
+        let lines = terminal_view
+            .read(cx)
+            .terminal()
+            .read(cx)
+            .last_n_non_empty_lines(PREVIEW_LINE_COUNT);
+        let font_family = TerminalSettings::get_global(cx).font_family.clone();
+        Some(
+            v_flex()
+                .size_full()
+                .justify_end()
+                .p_2()
+                .font_buffer(cx)
+                .when_some(font_family, |this, font_family| {
+                    this.font_family(font_family)
+                })
+                .text_ui_xs(cx)
+                .children(
+                    lines
+                        .into_iter()
+                        .map(|line| div().whitespace_nowrap().overflow_hidden().child(line)),
+                )
+                .into_any_element(),
+        )
+    }
+
     fn set_zoomed(&mut self, zoomed: bool, _: &mut Window, cx: &mut Context<Self>) {
         for pane in self.center.panes() {
             pane.update(cx, |pane, cx| {
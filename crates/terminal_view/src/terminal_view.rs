@@ -36,6 +36,7 @@ use workspace::{
     item::{
         BreadcrumbText, Item, ItemEvent, SerializableItem, TabContentParams, TabTooltipContent,
     },
+    pane::PaneWorkingDirectory,
     register_serializable_item,
     searchable::{Direction, SearchEvent, SearchOptions, SearchableItem, SearchableItemHandle},
     CloseActiveItem, NewCenterTerminal, NewTerminal, OpenOptions, OpenVisible, ToolbarItemLocation,
@@ -1742,11 +1743,18 @@ impl SearchableItem for TerminalView {
 pub(crate) fn default_working_directory(workspace: &Workspace, cx: &App) -> Option<PathBuf> {
     match &TerminalSettings::get_global(cx).working_directory {
         WorkingDirectory::CurrentProjectDirectory => workspace
-            .project()
+            .active_pane()
             .read(cx)
-            .active_project_directory(cx)
-            .as_deref()
-            .map(Path::to_path_buf),
+            .context::<PaneWorkingDirectory>()
+            .map(|directory| directory.0)
+            .or_else(|| {
+                workspace
+                    .project()
+                    .read(cx)
+                    .active_project_directory(cx)
+                    .as_deref()
+                    .map(Path::to_path_buf)
+            }),
         WorkingDirectory::FirstProjectDirectory => first_project_directory(workspace, cx),
         WorkingDirectory::AlwaysHome => None,
         WorkingDirectory::Always { directory } => {
@@ -21,8 +21,8 @@ use feature_flags::{FeatureFlagAppExt, ZedPro};
 use git_ui::onboarding::GitBanner;
 use gpui::{
     actions, div, px, Action, AnyElement, App, Context, Decorations, Element, Entity,
-    InteractiveElement, Interactivity, IntoElement, MouseButton, ParentElement, Render, Stateful,
-    StatefulInteractiveElement, Styled, Subscription, WeakEntity, Window,
+    ExternalPaths, InteractiveElement, Interactivity, IntoElement, MouseButton, ParentElement,
+    Render, Stateful, StatefulInteractiveElement, Styled, Subscription, WeakEntity, Window,
 };
 use project::Project;
 use rpc::proto;
@@ -174,6 +174,22 @@ impl Render for TitleBar {
                     .border_color(titlebar_color),
             })
             .bg(titlebar_color)
+            // Folders dropped on the title bar are added to the project.
+            .when(self.project.read(cx).is_local(), |this| {
+                this.drag_over::<ExternalPaths>(|style, _, _, cx| {
+                    style.bg(cx.theme().colors().drop_target_background)
+                })
+                .on_drop(cx.listener(
+                    |this, paths: &ExternalPaths, window, cx| {
+                        let paths = paths.paths().to_vec();
+                        this.workspace
+                            .update(cx, |workspace, cx| {
+                                workspace.add_paths_to_project(paths, window, cx).detach()
+                            })
+                            .log_err();
+                    },
+                ))
+            })
             .content_stretch()
             .child(
                 div()
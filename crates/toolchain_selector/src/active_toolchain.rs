@@ -182,4 +182,8 @@ impl StatusItemView for ActiveToolchain {
         }
         cx.notify();
     }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("active_toolchain")
+    }
 }
@@ -92,6 +92,7 @@ impl ParentElement for TabBar {
 impl RenderOnce for TabBar {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
         div()
+            .debug_selector(|| format!("TAB_BAR-{}", self.id))
             .id(self.id)
             .group("tab_bar")
             .flex()
@@ -129,4 +129,8 @@ impl StatusItemView for ModeIndicator {
         _cx: &mut Context<Self>,
     ) {
     }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("vim_mode")
+    }
 }
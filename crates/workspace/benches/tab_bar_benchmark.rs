@@ -0,0 +1,62 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use gpui::{AppContext as _, TestAppContext};
+use project::{FakeFs, Project};
+use settings::SettingsStore;
+use std::sync::Mutex;
+use theme::LoadThemes;
+use workspace::{item::test::TestItem, Workspace};
+
+/// Creates an app context like `#[gpui::test]` does, with a single seeded dispatcher.
+fn test_app_context() -> TestAppContext {
+    let dispatcher = Mutex::new(None);
+    gpui::run_test(
+        1,
+        &[],
+        0,
+        &mut |test_dispatcher, _| *dispatcher.lock().unwrap() = Some(test_dispatcher),
+        None,
+    );
+    let dispatcher = dispatcher.into_inner().unwrap().unwrap();
+    TestAppContext::new(dispatcher, None)
+}
+
+fn tab_bar_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render_tab_bar");
+    for tab_count in [50, 500] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(tab_count),
+            &tab_count,
+            |b, &tab_count| {
+                let mut cx = test_app_context();
+                cx.update(|cx| {
+                    let settings_store = SettingsStore::test(cx);
+                    cx.set_global(settings_store);
+                    theme::init(LoadThemes::JustBase, cx);
+                    workspace::init_settings(cx);
+                    Project::init_settings(cx);
+                });
+                let fs = FakeFs::new(cx.executor());
+                let project = cx.executor().block_test(Project::test(fs, None, &mut cx));
+                let (workspace, cx) = cx
+                    .add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+                let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+                pane.update_in(cx, |pane, window, cx| {
+                    for ix in 0..tab_count {
+                        let item = cx.new(|cx| TestItem::new(cx).with_label(&format!("{ix}")));
+                        pane.add_item(Box::new(item), false, false, None, window, cx);
+                    }
+                });
+                cx.run_until_parked();
+
+                b.iter(|| {
+                    cx.update(|window, _| window.refresh());
+                    cx.run_until_parked();
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tab_bar_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+//! The parts of the workspace chrome that the collaboration code contributes during calls.
+//!
+//! The workspace only knows about calls through [`ActiveCall`](call::ActiveCall), so the
+//! buttons to mute or share the screen and the faces of the people in the call come from the
+//! collaboration code, which installs its [`CallControls`] with [`set_call_controls`]. Which
+//! pane a remote collaborator is active in is tracked by the workspace itself, see
+//! [`Workspace::collaborator_pane`].
+
+use crate::{slots::WorkspaceSlot, Workspace};
+use gpui::{AnyView, App, Context, Global, Window};
+use std::sync::Arc;
+
+pub trait CallControls: 'static {
+    /// Adds items to the status bar of a new workspace, such as buttons to mute the
+    /// microphone or to share the screen. See [`Workspace::status_bar`].
+    fn add_status_items(
+        &self,
+        _workspace: &mut Workspace,
+        _window: &mut Window,
+        _cx: &mut Context<Workspace>,
+    ) {
+    }
+
+    /// Creates the view showing the people in the current call for a new workspace. It's
+    /// rendered at the end of the status bar.
+    fn facepile(
+        &self,
+        _workspace: &mut Workspace,
+        _window: &mut Window,
+        _cx: &mut Context<Workspace>,
+    ) -> Option<AnyView> {
+        None
+    }
+}
+
+struct GlobalCallControls(Arc<dyn CallControls>);
+
+impl Global for GlobalCallControls {}
+
+/// Installs the call controls of the workspaces opened from now on, replacing any previous
+/// ones.
+pub fn set_call_controls(call_controls: Arc<dyn CallControls>, cx: &mut App) {
+    cx.set_global(GlobalCallControls(call_controls));
+}
+
+impl Workspace {
+    pub(crate) fn add_call_controls(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(call_controls) = cx
+            .try_global::<GlobalCallControls>()
+            .map(|call_controls| call_controls.0.clone())
+        else {
+            return;
+        };
+        call_controls.add_status_items(self, window, cx);
+        if let Some(facepile) = call_controls.facepile(self, window, cx) {
+            self.register_slot_view(WorkspaceSlot::Facepile, facepile, cx);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Structural snapshots of the workspace chrome, for catching layout regressions in tests.
+//!
+//! The chrome elements (docks, center, status bars, tab bars) record their bounds via
+//! debug selectors when painted. [`chrome_snapshot`] turns those bounds into a tree,
+//! nesting every element inside the smallest element that contains it:
+//!
+//! ```text
+//! WORKSPACE 1024x720 @ 0,0
+//!   DOCK-left 300x720 @ 0,0
+//!   CENTER 724x720 @ 300,0
+//!     TAB_BAR-tab_bar 724x32 @ 300,0
+//! STATUS_BAR 1024x32 @ 0,720
+//! ```
+//!
+//! [`assert_chrome_snapshot`] compares such a snapshot against a golden file in
+//! `test_data/chrome_snapshots`. Golden files are only written when `UPDATE_CHROME_SNAPSHOTS`
+//! is set, so a missing one fails the test instead of silently passing.
+
+use gpui::{Bounds, Pixels, VisualTestContext};
+use std::{fmt::Write as _, path::PathBuf};
+
+/// The debug selectors of the chrome elements included in a snapshot.
+pub const CHROME_SELECTORS: &[&str] = &[
+    "WORKSPACE",
+    "DOCK-left",
+    "DOCK-bottom",
+    "DOCK-bottom right",
+    "DOCK-right",
+    "CENTER",
+    "SECONDARY_STATUS_BAR",
+    "TAB_BAR-tab_bar",
+    "TAB_BAR-vertical_tab_bar",
+    "STATUS_BAR",
+    "STATUS_BAR-left",
+    "STATUS_BAR-right",
+];
+
+/// Renders the window and returns the tree of chrome elements that were painted, with their bounds.
+pub fn chrome_snapshot(cx: &mut VisualTestContext) -> String {
+    crate::test::draw(cx);
+
+    let mut elements = CHROME_SELECTORS
+        .iter()
+        .filter_map(|selector| Some((*selector, cx.debug_bounds(selector)?)))
+        // Closed docks are still laid out, but with no size.
+        .filter(|(_, bounds)| area(bounds) > 0.)
+        .collect::<Vec<_>>();
+    // Place larger elements first, so that parents precede their children.
+    elements.sort_by(|(_, a), (_, b)| area(b).total_cmp(&area(a)));
+
+    let parents = (0..elements.len())
+        .map(|ix| {
+            (0..ix)
+                .rev()
+                .find(|parent_ix| contains(&elements[*parent_ix].1, &elements[ix].1))
+        })
+        .collect::<Vec<_>>();
+
+    let mut snapshot = String::new();
+    write_children(None, 0, &elements, &parents, &mut snapshot);
+    snapshot
+}
+
+/// Asserts that the current chrome snapshot matches the golden file with the given name.
+pub fn assert_chrome_snapshot(name: &str, cx: &mut VisualTestContext) {
+    let snapshot = chrome_snapshot(cx);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("test_data/chrome_snapshots")
+        .join(format!("{name}.snap"));
+
+    if std::env::var_os("UPDATE_CHROME_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &snapshot).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|error| {
+        panic!(
+            "no golden file for chrome snapshot {name:?} at {path:?} ({error}), \
+            set UPDATE_CHROME_SNAPSHOTS=1 to record it:\n{snapshot}"
+        )
+    });
+    assert_eq!(
+        expected, snapshot,
+        "chrome snapshot {name:?} changed, set UPDATE_CHROME_SNAPSHOTS=1 to accept the new layout"
+    );
+}
+
+fn area(bounds: &Bounds<Pixels>) -> f32 {
+    f32::from(bounds.size.width) * f32::from(bounds.size.height)
+}
+
+fn contains(outer: &Bounds<Pixels>, inner: &Bounds<Pixels>) -> bool {
+    outer.origin.x <= inner.origin.x
+        && outer.origin.y <= inner.origin.y
+        && outer.bottom_right().x >= inner.bottom_right().x
+        && outer.bottom_right().y >= inner.bottom_right().y
+}
+
+/// Writes the children of the given element depth-first, with siblings ordered by position.
+fn write_children(
+    parent: Option<usize>,
+    depth: usize,
+    elements: &[(&str, Bounds<Pixels>)],
+    parents: &[Option<usize>],
+    snapshot: &mut String,
+) {
+    let mut children = (0..elements.len())
+        .filter(|ix| parents[*ix] == parent)
+        .collect::<Vec<_>>();
+    children.sort_by(|a, b| {
+        let (a, b) = (elements[*a].1.origin, elements[*b].1.origin);
+        f32::from(a.y)
+            .total_cmp(&f32::from(b.y))
+            .then(f32::from(a.x).total_cmp(&f32::from(b.x)))
+    });
+
+    for child in children {
+        let (selector, bounds) = &elements[child];
+        writeln!(
+            snapshot,
+            "{}{selector} {:.0}x{:.0} @ {:.0},{:.0}",
+            "  ".repeat(depth),
+            f32::from(bounds.size.width),
+            f32::from(bounds.size.height),
+            f32::from(bounds.origin.x),
+            f32::from(bounds.origin.y),
+        )
+        .unwrap();
+        write_children(Some(child), depth + 1, elements, parents, snapshot);
+    }
+}
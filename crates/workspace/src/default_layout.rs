@@ -0,0 +1,159 @@
+//! The dock layout of workspaces that are opened for the first time.
+//!
+//! Without one, whether a dock starts out open and which of its panels is shown depends on
+//! the order in which the panels happen to be registered. A [`DefaultLayout`] describes the
+//! docks up front instead: it's applied before the panels arrive, and each dock shows its
+//! panel with the given size and open state once that panel is registered.
+
+use crate::{
+    dock::{BottomDockSlot, DockPosition, PanelHandle},
+    persistence::model::DockData,
+    Workspace,
+};
+use gpui::{Context, Global, Pixels, Window};
+
+/// The layout of a new workspace's docks. The app sets it as a global, and it's only used
+/// for workspaces that have never been opened before, i.e. that have neither a serialized
+/// layout nor a saved default one.
+#[derive(Clone, Debug, Default)]
+pub struct DefaultLayout {
+    docks: Vec<DefaultDock>,
+}
+
+#[derive(Clone, Debug)]
+struct DefaultDock {
+    position: DockPosition,
+    bottom_slot: BottomDockSlot,
+    panel: String,
+    open: bool,
+    size: Option<Pixels>,
+}
+
+impl Global for DefaultLayout {}
+
+impl DefaultLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows the panel with the given persistent name in the dock at the given position,
+    /// and opens that dock if `open` is true. Docks without a panel start out closed.
+    pub fn dock(self, position: DockPosition, panel: impl Into<String>, open: bool) -> Self {
+        self.dock_in_slot(position, BottomDockSlot::Left, panel.into(), open)
+    }
+
+    /// Like [`Self::dock`], but for the bottom right dock. The panel is moved into that dock
+    /// when it's registered.
+    pub fn bottom_right_dock(self, panel: impl Into<String>, open: bool) -> Self {
+        self.dock_in_slot(
+            DockPosition::Bottom,
+            BottomDockSlot::Right,
+            panel.into(),
+            open,
+        )
+    }
+
+    fn dock_in_slot(
+        mut self,
+        position: DockPosition,
+        bottom_slot: BottomDockSlot,
+        panel: String,
+        open: bool,
+    ) -> Self {
+        self.docks
+            .retain(|dock| (dock.position, dock.bottom_slot) != (position, bottom_slot));
+        self.docks.push(DefaultDock {
+            position,
+            bottom_slot,
+            panel,
+            open,
+            size: None,
+        });
+        self
+    }
+
+    /// Sets the size of the panel added last with [`Self::dock`] or
+    /// [`Self::bottom_right_dock`]. Panels keep a size they already have, e.g. one restored
+    /// from a previous session, see [`crate::Panel::has_custom_size`].
+    pub fn size(mut self, size: Pixels) -> Self {
+        if let Some(dock) = self.docks.last_mut() {
+            dock.size = Some(size);
+        }
+        self
+    }
+
+    fn dock_data(&self, position: DockPosition, bottom_slot: BottomDockSlot) -> DockData {
+        self.docks
+            .iter()
+            .find(|dock| dock.position == position && dock.bottom_slot == bottom_slot)
+            .map(|dock| DockData {
+                visible: dock.open,
+                active_panel: Some(dock.panel.clone()),
+                zoom: false,
+            })
+            .unwrap_or_default()
+    }
+
+    fn bottom_right_panels(&self) -> impl Iterator<Item = &str> {
+        self.docks
+            .iter()
+            .filter(|dock| dock.bottom_slot == BottomDockSlot::Right)
+            .map(|dock| dock.panel.as_str())
+    }
+
+    fn panel_size(&self, position: DockPosition, panel: &str) -> Option<Pixels> {
+        self.docks
+            .iter()
+            .find(|dock| dock.position == position && dock.panel == panel)?
+            .size
+    }
+}
+
+impl Workspace {
+    /// Lays out the docks as described by the given layout. Panels that are registered
+    /// afterwards are placed according to it as well.
+    pub fn apply_default_layout(
+        &mut self,
+        layout: DefaultLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let bottom_right_panels = layout
+            .bottom_right_panels()
+            .map(|panel| panel.to_string())
+            .collect::<Vec<_>>();
+        self.restore_bottom_right_panels(&bottom_right_panels, window, cx);
+        for dock in self.all_docks() {
+            dock.update(cx, |dock, cx| {
+                dock.serialized_dock = Some(layout.dock_data(dock.position(), dock.bottom_slot()));
+                dock.restore_state(window, cx);
+            });
+        }
+        self.default_layout = Some(layout);
+        for dock in self.all_docks() {
+            let panels = dock.read(cx).panels().cloned().collect::<Vec<_>>();
+            for panel in panels {
+                self.apply_default_panel_size(&*panel, window, cx);
+            }
+        }
+        cx.notify();
+    }
+
+    pub(crate) fn apply_default_panel_size(
+        &self,
+        panel: &dyn PanelHandle,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(layout) = self.default_layout.as_ref() else {
+            return;
+        };
+        if panel.has_custom_size(window, cx) {
+            return;
+        }
+        let position = panel.position(window, cx);
+        if let Some(size) = layout.panel_size(position, panel.persistent_name()) {
+            panel.set_size(Some(size), window, cx);
+        }
+    }
+}
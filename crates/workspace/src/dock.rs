@@ -1,26 +1,92 @@
+use crate::feedback::give_feedback;
+use crate::metrics::{record_metric, WorkspaceMetric};
 use crate::persistence::model::DockData;
-use crate::{status_bar::StatusItemView, Workspace};
+use crate::{status_bar::StatusItemView, workspace_settings::StatusBarSettings, Workspace};
 use crate::{DraggedDock, Event, ModalLayer, Pane};
 use client::proto;
+use collections::HashMap;
 use gpui::{
-    deferred, div, px, Action, AnyView, App, Axis, Context, Corner, Entity, EntityId, EventEmitter,
-    FocusHandle, Focusable, IntoElement, KeyContext, MouseButton, MouseDownEvent, MouseUpEvent,
-    ParentElement, Render, SharedString, StyleRefinement, Styled, Subscription, WeakEntity, Window,
+    actions, anchored, canvas, deferred, div, point, px, size, Action, AnyView, App, Axis, Bounds,
+    Context, Corner, DismissEvent, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
+    IntoElement, KeyContext, MouseButton, MouseDownEvent, MouseUpEvent, ParentElement, Pixels,
+    Render, SharedString, StyleRefinement, Styled, Subscription, Task, WeakEntity, Window,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use settings::SettingsStore;
-use std::sync::Arc;
-use ui::{h_flex, ContextMenu, Divider, DividerColor, IconButton, Tooltip};
+use settings::{Settings as _, SettingsStore};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use ui::{
+    h_flex, ContextMenu, Divider, DividerColor, IconButton, KeyBinding, PopoverMenu, Tooltip,
+};
 use ui::{prelude::*, right_click_menu};
 
+actions!(dock, [DeployPanelMenu]);
+
 pub(crate) const RESIZE_HANDLE_SIZE: Pixels = Pixels(6.);
+/// The width of the line drawn between a dock and the center.
+const DIVIDER_SIZE: Pixels = Pixels(1.);
+/// How close a dock being resized has to get to a snap point to snap onto it.
+pub(crate) const SNAP_THRESHOLD: Pixels = Pixels(8.);
+/// The size of the part of a panel shown when hovering its button while it's hidden.
+const PANEL_PREVIEW_WIDTH: Pixels = Pixels(360.);
+const PANEL_PREVIEW_HEIGHT: Pixels = Pixels(220.);
 
 pub enum PanelEvent {
     ZoomIn,
     ZoomOut,
     Activate,
-    Close,
+    /// The panel asks to be closed. The panel can still veto this in [`Panel::should_close`].
+    Close {
+        reason: CloseReason,
+    },
+    /// The panel's [`Panel::icon_label`] changed, so its dock button needs to be redrawn.
+    LabelChanged,
+    /// The panel asks its dock to briefly show it at a larger size.
+    /// See [`PanelHandle::request_temporary_size`].
+    RequestTemporarySize {
+        size: Pixels,
+        duration: TemporarySizeDuration,
+    },
+}
+
+/// How long a temporary panel size lasts before the panel shrinks back to its own size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemporarySizeDuration {
+    /// Until the given amount of time has passed.
+    For(Duration),
+    /// Until focus leaves the panel.
+    UntilBlur,
+}
+
+/// Why a panel is being closed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The user closed the panel, e.g. by pressing escape or toggling its dock.
+    User,
+    /// The workspace is being closed or the app is quitting.
+    WorkspaceShutdown,
+    /// The panel is being removed from its dock.
+    PanelRemoved,
+    /// The panel closes itself because of something the user didn't ask for, e.g. the call it
+    /// shows has ended. Vetoing this isn't reported to the user.
+    Automatic,
+}
+
+pub enum DockEvent {
+    /// The panel at the given index became the dock's active panel.
+    ActivePanelChanged { index: usize },
+    /// The dock was opened (`true`) or closed (`false`).
+    OpenChanged(bool),
+    /// The icon label of the panel at the given index changed.
+    PanelLabelChanged { index: usize },
+    /// A panel was added to or removed from the dock, or the panels removed by the user that
+    /// would be restored into it changed.
+    PanelsChanged,
+    /// The given panel kept the dock from being closed, see [`Panel::should_close`].
+    CloseVetoed(Arc<dyn PanelHandle>),
 }
 
 pub use proto::PanelId;
@@ -32,20 +98,63 @@ pub trait Panel: Focusable + EventEmitter<PanelEvent> + Render + Sized {
     fn set_position(&mut self, position: DockPosition, window: &mut Window, cx: &mut Context<Self>);
     fn size(&self, window: &Window, cx: &App) -> Pixels;
     fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>);
+    /// Whether the panel has a size of its own, set by the user or restored from a previous
+    /// session, rather than its default one. The [`crate::default_layout::DefaultLayout`]
+    /// doesn't override such a size.
+    fn has_custom_size(&self, _window: &Window, _cx: &App) -> bool {
+        false
+    }
+    /// Sizes the dock snaps to while this panel is being resized, in addition to
+    /// a quarter, a third and half of the window.
+    fn preferred_sizes(&self, _window: &Window, _cx: &App) -> Vec<Pixels> {
+        Vec::new()
+    }
     fn icon(&self, window: &Window, cx: &App) -> Option<ui::IconName>;
     fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<&'static str>;
     fn toggle_action(&self) -> Box<dyn Action>;
     fn icon_label(&self, _window: &Window, _: &App) -> Option<String> {
         None
     }
+    /// A small rendering of the panel's content, shown when hovering its button while it's
+    /// hidden. It shouldn't contain the panel's own view, which isn't laid out for the size of
+    /// the preview and may be rendered by its dock at the same time.
+    fn render_preview(
+        &mut self,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<AnyElement> {
+        None
+    }
     fn is_zoomed(&self, _window: &Window, _cx: &App) -> bool {
         false
     }
     fn starts_open(&self, _window: &Window, _cx: &App) -> bool {
         false
     }
+    /// Whether the user may remove this panel from its dock.
+    fn can_close(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+    /// Whether the line between the dock and the center is drawn while this panel is
+    /// visible. When hidden, it still appears while hovered if the dock can be resized.
+    fn show_divider(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+    /// Called before the panel is closed. Returning false keeps the panel open.
+    fn should_close(
+        &mut self,
+        _reason: CloseReason,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> bool {
+        true
+    }
     fn set_zoomed(&mut self, _zoomed: bool, _window: &mut Window, _cx: &mut Context<Self>) {}
     fn set_active(&mut self, _active: bool, _window: &mut Window, _cx: &mut Context<Self>) {}
+    /// Called when the panel is shown or hidden, i.e. when its dock opens or closes while it's
+    /// the active panel, or when it's activated or deactivated while its dock is open. Hidden
+    /// panels aren't rendered, so they can suspend expensive work until they're shown again.
+    fn set_visible(&mut self, _visible: bool, _window: &mut Window, _cx: &mut Context<Self>) {}
     fn pane(&self) -> Option<Entity<Pane>> {
         None
     }
@@ -64,17 +173,50 @@ pub trait PanelHandle: Send + Sync {
     fn is_zoomed(&self, window: &Window, cx: &App) -> bool;
     fn set_zoomed(&self, zoomed: bool, window: &mut Window, cx: &mut App);
     fn set_active(&self, active: bool, window: &mut Window, cx: &mut App);
+    fn set_visible(&self, visible: bool, window: &mut Window, cx: &mut App);
     fn remote_id(&self) -> Option<proto::PanelId>;
     fn pane(&self, cx: &App) -> Option<Entity<Pane>>;
     fn size(&self, window: &Window, cx: &App) -> Pixels;
     fn set_size(&self, size: Option<Pixels>, window: &mut Window, cx: &mut App);
+    fn has_custom_size(&self, window: &Window, cx: &App) -> bool;
+    fn preferred_sizes(&self, window: &Window, cx: &App) -> Vec<Pixels>;
     fn icon(&self, window: &Window, cx: &App) -> Option<ui::IconName>;
     fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<&'static str>;
     fn toggle_action(&self, window: &Window, cx: &App) -> Box<dyn Action>;
     fn icon_label(&self, window: &Window, cx: &App) -> Option<String>;
+    fn render_preview(&self, window: &mut Window, cx: &mut App) -> Option<AnyElement>;
     fn panel_focus_handle(&self, cx: &App) -> FocusHandle;
     fn to_any(&self) -> AnyView;
     fn activation_priority(&self, cx: &App) -> u32;
+    fn can_close(&self, window: &Window, cx: &App) -> bool;
+    fn show_divider(&self, window: &Window, cx: &App) -> bool;
+    fn should_close(&self, reason: CloseReason, window: &mut Window, cx: &mut App) -> bool;
+    fn remove_from_dock(&self, dock: &Entity<Dock>, window: &mut Window, cx: &mut App);
+    fn add_to_dock(&self, dock: &Entity<Dock>, window: &mut Window, cx: &mut App) -> usize;
+    /// Asks the panel's dock to show it at `size` until `duration` is over, e.g. to reveal
+    /// content that doesn't fit. The panel then shrinks back to its own size, which this
+    /// doesn't change. Sizes smaller than the panel's own size are ignored.
+    fn request_temporary_size(&self, size: Pixels, duration: TemporarySizeDuration, cx: &mut App);
+    fn move_to_dock(
+        &self,
+        from: &Entity<Dock>,
+        to: &Entity<Dock>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let was_visible = from
+            .read(cx)
+            .visible_panel()
+            .map_or(false, |panel| panel.panel_id() == self.panel_id());
+        self.remove_from_dock(from, window, cx);
+        let index = self.add_to_dock(to, window, cx);
+        if was_visible {
+            to.update(cx, |dock, cx| {
+                dock.set_open(true, window, cx);
+                dock.activate_panel(index, window, cx);
+            });
+        }
+    }
     fn move_to_next_position(&self, window: &mut Window, cx: &mut App) {
         let current_position = self.position(window, cx);
         let next_position = [
@@ -128,6 +270,10 @@ where
         self.update(cx, |this, cx| this.set_active(active, window, cx))
     }
 
+    fn set_visible(&self, visible: bool, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| this.set_visible(visible, window, cx))
+    }
+
     fn pane(&self, cx: &App) -> Option<Entity<Pane>> {
         self.read(cx).pane()
     }
@@ -144,6 +290,14 @@ where
         self.update(cx, |this, cx| this.set_size(size, window, cx))
     }
 
+    fn has_custom_size(&self, window: &Window, cx: &App) -> bool {
+        self.read(cx).has_custom_size(window, cx)
+    }
+
+    fn preferred_sizes(&self, window: &Window, cx: &App) -> Vec<Pixels> {
+        self.read(cx).preferred_sizes(window, cx)
+    }
+
     fn icon(&self, window: &Window, cx: &App) -> Option<ui::IconName> {
         self.read(cx).icon(window, cx)
     }
@@ -160,6 +314,10 @@ where
         self.read(cx).icon_label(window, cx)
     }
 
+    fn render_preview(&self, window: &mut Window, cx: &mut App) -> Option<AnyElement> {
+        self.update(cx, |this, cx| this.render_preview(window, cx))
+    }
+
     fn to_any(&self) -> AnyView {
         self.clone().into()
     }
@@ -171,6 +329,35 @@ where
     fn activation_priority(&self, cx: &App) -> u32 {
         self.read(cx).activation_priority()
     }
+
+    fn can_close(&self, window: &Window, cx: &App) -> bool {
+        self.read(cx).can_close(window, cx)
+    }
+
+    fn show_divider(&self, window: &Window, cx: &App) -> bool {
+        self.read(cx).show_divider(window, cx)
+    }
+
+    fn should_close(&self, reason: CloseReason, window: &mut Window, cx: &mut App) -> bool {
+        self.update(cx, |this, cx| this.should_close(reason, window, cx))
+    }
+
+    fn remove_from_dock(&self, dock: &Entity<Dock>, window: &mut Window, cx: &mut App) {
+        dock.update(cx, |dock, cx| dock.remove_panel(self, window, cx));
+    }
+
+    fn add_to_dock(&self, dock: &Entity<Dock>, window: &mut Window, cx: &mut App) -> usize {
+        dock.update(cx, |dock, cx| {
+            let workspace = dock.workspace.clone();
+            dock.add_panel(self.clone(), workspace, window, cx)
+        })
+    }
+
+    fn request_temporary_size(&self, size: Pixels, duration: TemporarySizeDuration, cx: &mut App) {
+        self.update(cx, |_, cx| {
+            cx.emit(PanelEvent::RequestTemporarySize { size, duration })
+        });
+    }
 }
 
 impl From<&dyn PanelHandle> for AnyView {
@@ -183,23 +370,47 @@ impl From<&dyn PanelHandle> for AnyView {
 /// Can contain multiple panels and show/hide itself with all contents.
 pub struct Dock {
     position: DockPosition,
+    bottom_slot: BottomDockSlot,
     panel_entries: Vec<PanelEntry>,
     workspace: WeakEntity<Workspace>,
     is_open: bool,
-    active_panel_index: Option<usize>,
+    opened_at: Option<Instant>,
+    /// The id of the active panel, rather than its index, so that it stays valid while
+    /// panels are added and removed.
+    active_panel_id: Option<EntityId>,
+    /// Ids of the panels that have been activated, most recently activated first.
+    activation_history: Vec<EntityId>,
+    /// The panel that was last told it's visible, see [`Panel::set_visible`].
+    visible_panel_id: Option<EntityId>,
     focus_handle: FocusHandle,
     pub(crate) serialized_dock: Option<DockData>,
     zoom_layer_open: bool,
     modal_layer: Entity<ModalLayer>,
+    temporary_size: Option<TemporarySize>,
+    /// The size the window resize policy shows the active panel at, by the panel's id. Unlike
+    /// a size set with [`Dock::resize_active_panel`], it isn't stored by the panel.
+    resized_size: Option<(EntityId, Pixels)>,
+    /// Where the dock was last painted, or None if it was hidden.
+    bounds: Option<Bounds<Pixels>>,
     _subscriptions: [Subscription; 2],
 }
 
+/// A size that a panel is shown at instead of its own size, until it expires.
+struct TemporarySize {
+    panel_id: EntityId,
+    size: Pixels,
+    _expire_after_delay: Option<Task<()>>,
+    _expire_on_blur: Option<Subscription>,
+}
+
 impl Focusable for Dock {
     fn focus_handle(&self, _: &App) -> FocusHandle {
         self.focus_handle.clone()
     }
 }
 
+impl EventEmitter<DockEvent> for Dock {}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum DockPosition {
@@ -209,7 +420,7 @@ pub enum DockPosition {
 }
 
 impl DockPosition {
-    fn label(&self) -> &'static str {
+    pub(crate) fn label(&self) -> &'static str {
         match self {
             Self::Left => "left",
             Self::Bottom => "bottom",
@@ -225,13 +436,81 @@ impl DockPosition {
     }
 }
 
+/// The bottom of the workspace can be split into two independent docks, each with its own
+/// panels. The left slot is the primary bottom dock.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BottomDockSlot {
+    #[default]
+    Left,
+    Right,
+}
+
+impl BottomDockSlot {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Left => "bottom",
+            Self::Right => "bottom right",
+        }
+    }
+}
+
 struct PanelEntry {
     panel: Arc<dyn PanelHandle>,
     _subscriptions: [Subscription; 3],
 }
 
+pub(crate) fn snap_size(
+    size: Pixels,
+    available_size: Pixels,
+    preferred_sizes: &[Pixels],
+) -> Pixels {
+    preferred_sizes
+        .iter()
+        .copied()
+        .chain([
+            available_size * 0.25,
+            available_size * (1. / 3.),
+            available_size * 0.5,
+        ])
+        .filter(|snap_point| (*snap_point - size).abs() <= SNAP_THRESHOLD)
+        .min_by_key(|snap_point| (*snap_point - size).abs())
+        .map_or(size, |snap_point| snap_point.round())
+}
+
 pub struct PanelButtons {
     dock: Entity<Dock>,
+    focus_handle: FocusHandle,
+    selected_index: Option<usize>,
+    context_menu: Option<(Entity<ContextMenu>, usize, Subscription)>,
+    state: PanelButtonsState,
+    pub(crate) labels: HashMap<EntityId, Entity<PanelButtonLabel>>,
+}
+
+/// The parts of a dock that its panel buttons show. The buttons are only redrawn when this
+/// changes, and the label of a button only when its panel reports a new one.
+#[derive(Default, PartialEq)]
+struct PanelButtonsState {
+    is_open: bool,
+    active_index: Option<usize>,
+    buttons: Vec<(EntityId, IconName, &'static str)>,
+    removed_panels: Vec<EntityId>,
+}
+
+/// Shows a panel's [`Panel::icon_label`] next to its button.
+pub(crate) struct PanelButtonLabel {
+    panel: Arc<dyn PanelHandle>,
+}
+
+impl Render for PanelButtonLabel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div().children(self.panel.icon_label(window, cx).map(|label| {
+            Label::new(label)
+                .size(LabelSize::XSmall)
+                .color(Color::Muted)
+                .ml_0p5()
+        }))
+    }
 }
 
 impl Dock {
@@ -240,6 +519,16 @@ impl Dock {
         modal_layer: Entity<ModalLayer>,
         window: &mut Window,
         cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        Self::new_in_slot(position, BottomDockSlot::Left, modal_layer, window, cx)
+    }
+
+    pub fn new_in_slot(
+        position: DockPosition,
+        bottom_slot: BottomDockSlot,
+        modal_layer: Entity<ModalLayer>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
     ) -> Entity<Self> {
         let focus_handle = cx.focus_handle();
         let workspace = cx.entity().clone();
@@ -258,15 +547,22 @@ impl Dock {
             });
             Self {
                 position,
+                bottom_slot,
                 workspace: workspace.downgrade(),
                 panel_entries: Default::default(),
-                active_panel_index: None,
+                active_panel_id: None,
+                activation_history: Vec::new(),
+                visible_panel_id: None,
                 is_open: false,
+                opened_at: None,
                 focus_handle: focus_handle.clone(),
                 _subscriptions: [focus_subscription, zoom_subscription],
                 serialized_dock: None,
                 zoom_layer_open: false,
                 modal_layer,
+                temporary_size: None,
+                resized_size: None,
+                bounds: None,
             }
         });
 
@@ -286,7 +582,7 @@ impl Dock {
                     workspace.zoomed = None;
                     workspace.zoomed_position = None;
                 }
-                cx.emit(Event::ZoomChanged);
+                workspace.zoom_changed(cx);
                 workspace.dismiss_zoomed_items_to_reveal(Some(position), window, cx);
                 workspace.update_active_view_for_followers(window, cx)
             }
@@ -299,15 +595,21 @@ impl Dock {
                     if panel.is_zoomed(window, cx) {
                         workspace.zoomed = Some(panel.to_any().downgrade());
                         workspace.zoomed_position = Some(position);
-                        cx.emit(Event::ZoomChanged);
+                        workspace.zoom_changed(cx);
                         return;
                     }
                 }
             }
-            if workspace.zoomed_position == Some(position) {
+            // Both bottom docks share a zoom position, so only reset the zoom if it belongs to us.
+            let zoomed_in_dock = workspace.zoomed.as_ref().map_or(true, |zoomed| {
+                zoomed
+                    .upgrade()
+                    .map_or(true, |view| dock.read(cx).contains_panel(view.entity_id()))
+            });
+            if workspace.zoomed_position == Some(position) && zoomed_in_dock {
                 workspace.zoomed = None;
                 workspace.zoomed_position = None;
-                cx.emit(Event::ZoomChanged);
+                workspace.zoom_changed(cx);
             }
         })
         .detach();
@@ -319,6 +621,17 @@ impl Dock {
         self.position
     }
 
+    pub fn bottom_slot(&self) -> BottomDockSlot {
+        self.bottom_slot
+    }
+
+    pub(crate) fn label(&self) -> &'static str {
+        match self.position {
+            DockPosition::Bottom => self.bottom_slot.label(),
+            position => position.label(),
+        }
+    }
+
     pub fn is_open(&self) -> bool {
         self.is_open
     }
@@ -327,12 +640,61 @@ impl Dock {
         !(self.zoom_layer_open || self.modal_layer.read(cx).has_active_modal())
     }
 
+    /// Where the dock was last painted, or None if it's closed.
+    pub fn bounds(&self) -> Option<Bounds<Pixels>> {
+        self.bounds
+    }
+
+    /// The hit target of the line between the dock and the center, which straddles the
+    /// dock's inner edge like the divider rendered by [`Self::render_divider`].
+    pub(crate) fn divider_bounds(&self, cx: &App) -> Option<Bounds<Pixels>> {
+        let bounds = self.bounds?;
+        let hit_size = if self.resizable(cx) {
+            RESIZE_HANDLE_SIZE
+        } else {
+            DIVIDER_SIZE
+        };
+        Some(match self.position {
+            DockPosition::Left => Bounds::new(
+                point(bounds.right() - hit_size / 2., bounds.top()),
+                size(hit_size, bounds.size.height),
+            ),
+            DockPosition::Right => Bounds::new(
+                point(bounds.left() - hit_size / 2., bounds.top()),
+                size(hit_size, bounds.size.height),
+            ),
+            DockPosition::Bottom => Bounds::new(
+                point(bounds.left(), bounds.top() - hit_size / 2.),
+                size(bounds.size.width, hit_size),
+            ),
+        })
+    }
+
     pub fn panel<T: Panel>(&self) -> Option<Entity<T>> {
         self.panel_entries
             .iter()
             .find_map(|entry| entry.panel.to_any().clone().downcast().ok())
     }
 
+    pub fn contains_panel(&self, panel_id: EntityId) -> bool {
+        self.panel_entries
+            .iter()
+            .any(|entry| entry.panel.panel_id() == panel_id)
+    }
+
+    pub fn panel_for_id(&self, panel_id: EntityId) -> Option<&Arc<dyn PanelHandle>> {
+        self.panel_entries
+            .iter()
+            .find(|entry| entry.panel.panel_id() == panel_id)
+            .map(|entry| &entry.panel)
+    }
+
+    pub fn panel_index_for_id(&self, panel_id: EntityId) -> Option<usize> {
+        self.panel_entries
+            .iter()
+            .position(|entry| entry.panel.panel_id() == panel_id)
+    }
+
     pub fn panel_index_for_type<T: Panel>(&self) -> Option<usize> {
         self.panel_entries
             .iter()
@@ -352,12 +714,14 @@ impl Dock {
     }
 
     fn active_panel_entry(&self) -> Option<&PanelEntry> {
-        self.active_panel_index
-            .and_then(|index| self.panel_entries.get(index))
+        let active_panel_id = self.active_panel_id?;
+        self.panel_entries
+            .iter()
+            .find(|entry| entry.panel.panel_id() == active_panel_id)
     }
 
     pub fn active_panel_index(&self) -> Option<usize> {
-        self.active_panel_index
+        self.panel_index_for_id(self.active_panel_id?)
     }
 
     pub fn set_open(&mut self, open: bool, window: &mut Window, cx: &mut Context<Self>) {
@@ -366,11 +730,68 @@ impl Dock {
             if let Some(active_panel) = self.active_panel_entry() {
                 active_panel.panel.set_active(open, window, cx);
             }
+            if open {
+                self.opened_at = Some(Instant::now());
+            } else if let Some(opened_at) = self.opened_at.take() {
+                record_metric(
+                    WorkspaceMetric::DockClosed {
+                        position: self.position,
+                        open_duration: opened_at.elapsed(),
+                    },
+                    cx,
+                );
+            }
 
+            self.update_visible_panel(window, cx);
+
+            let position = self.position;
+            give_feedback(cx, |feedback, cx| feedback.dock_toggled(position, open, cx));
+            cx.emit(DockEvent::OpenChanged(open));
             cx.notify();
         }
     }
 
+    /// Tells the previously and newly visible panels that their visibility changed.
+    fn update_visible_panel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let visible_panel_id = self.visible_panel().map(|panel| panel.panel_id());
+        if visible_panel_id == self.visible_panel_id {
+            return;
+        }
+        let previous_panel = self
+            .visible_panel_id
+            .and_then(|panel_id| self.panel_for_id(panel_id))
+            .cloned();
+        self.visible_panel_id = visible_panel_id;
+        if let Some(previous_panel) = previous_panel {
+            previous_panel.set_visible(false, window, cx);
+        }
+        if let Some(panel) = self.visible_panel().cloned() {
+            panel.set_visible(true, window, cx);
+        }
+    }
+
+    /// Closes the dock, unless its active panel vetoes it. Returns whether the dock is closed.
+    pub fn close(
+        &mut self,
+        reason: CloseReason,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !self.is_open {
+            return true;
+        }
+        if let Some(active_panel) = self.active_panel().cloned() {
+            if !active_panel.should_close(reason, window, cx) {
+                if reason != CloseReason::Automatic {
+                    cx.emit(DockEvent::CloseVetoed(active_panel));
+                }
+                return false;
+            }
+        }
+        self.set_open(false, window, cx);
+        true
+    }
+
     pub fn set_panel_zoomed(
         &mut self,
         panel: &AnyView,
@@ -415,10 +836,17 @@ impl Dock {
             cx.observe(&panel, |_, _, cx| cx.notify()),
             cx.observe_global_in::<SettingsStore>(window, {
                 let workspace = workspace.clone();
-                let panel = panel.clone();
+                let panel = panel.downgrade();
 
                 move |this, window, cx| {
-                    let new_position = panel.read(cx).position(window, cx);
+                    let Some(panel) = panel.upgrade() else {
+                        return;
+                    };
+                    let Ok(new_position) = workspace.update(cx, |workspace, cx| {
+                        workspace.valid_panel_position(&panel, window, cx)
+                    }) else {
+                        return;
+                    };
                     if new_position == this.position {
                         return;
                     }
@@ -427,12 +855,9 @@ impl Dock {
                         if panel.is_zoomed(window, cx) {
                             workspace.zoomed_position = Some(new_position);
                         }
-                        match new_position {
-                            DockPosition::Left => &workspace.left_dock,
-                            DockPosition::Bottom => &workspace.bottom_dock,
-                            DockPosition::Right => &workspace.right_dock,
-                        }
-                        .clone()
+                        workspace
+                            .dock_for_panel(T::persistent_name(), new_position)
+                            .clone()
                     }) else {
                         return;
                     };
@@ -453,6 +878,17 @@ impl Dock {
                             new_dock.activate_panel(index, window, cx);
                         }
                     });
+
+                    let from = this.position;
+                    workspace
+                        .update(cx, |_, cx| {
+                            cx.emit(Event::PanelMoved {
+                                panel: Arc::new(panel),
+                                from,
+                                to: new_position,
+                            })
+                        })
+                        .ok();
                 }
             }),
             cx.subscribe_in(
@@ -470,7 +906,7 @@ impl Dock {
                                 workspace.zoomed = Some(panel.downgrade().into());
                                 workspace.zoomed_position =
                                     Some(panel.read(cx).position(window, cx));
-                                cx.emit(Event::ZoomChanged);
+                                workspace.zoom_changed(cx);
                             })
                             .ok();
                     }
@@ -481,7 +917,7 @@ impl Dock {
                                 if workspace.zoomed_position == Some(this.position) {
                                     workspace.zoomed = None;
                                     workspace.zoomed_position = None;
-                                    cx.emit(Event::ZoomChanged);
+                                    workspace.zoom_changed(cx);
                                 }
                                 cx.notify();
                             })
@@ -498,14 +934,22 @@ impl Dock {
                             window.focus(&panel.read(cx).focus_handle(cx));
                         }
                     }
-                    PanelEvent::Close => {
+                    PanelEvent::Close { reason } => {
                         if this
                             .visible_panel()
                             .map_or(false, |p| p.panel_id() == Entity::entity_id(panel))
                         {
-                            this.set_open(false, window, cx);
+                            this.close(*reason, window, cx);
                         }
                     }
+                    PanelEvent::LabelChanged => {
+                        if let Some(index) = this.panel_index_for_id(Entity::entity_id(panel)) {
+                            cx.emit(DockEvent::PanelLabelChanged { index });
+                        }
+                    }
+                    PanelEvent::RequestTemporarySize { size, duration } => {
+                        this.set_temporary_size(panel, *size, *duration, window, cx);
+                    }
                 },
             ),
         ];
@@ -518,11 +962,6 @@ impl Dock {
             Ok(ix) => ix,
             Err(ix) => ix,
         };
-        if let Some(active_index) = self.active_panel_index.as_mut() {
-            if *active_index >= index {
-                *active_index += 1;
-            }
-        }
         self.panel_entries.insert(
             index,
             PanelEntry {
@@ -531,12 +970,14 @@ impl Dock {
             },
         );
 
-        self.restore_state(window, cx);
-        if panel.read(cx).starts_open(window, cx) {
+        // A layout to restore takes precedence over the panels' own preferences, so that the
+        // dock doesn't depend on the order in which its panels were added.
+        if !self.restore_state(window, cx) && panel.read(cx).starts_open(window, cx) {
             self.activate_panel(index, window, cx);
             self.set_open(true, window, cx);
         }
 
+        cx.emit(DockEvent::PanelsChanged);
         cx.notify();
         index
     }
@@ -571,19 +1012,19 @@ impl Dock {
             .iter()
             .position(|entry| entry.panel.panel_id() == Entity::entity_id(panel))
         {
-            if let Some(active_panel_index) = self.active_panel_index.as_mut() {
-                match panel_ix.cmp(active_panel_index) {
-                    std::cmp::Ordering::Less => {
-                        *active_panel_index -= 1;
-                    }
-                    std::cmp::Ordering::Equal => {
-                        self.active_panel_index = None;
-                        self.set_open(false, window, cx);
-                    }
-                    std::cmp::Ordering::Greater => {}
-                }
+            if self.active_panel_id == Some(Entity::entity_id(panel)) {
+                self.active_panel_id = None;
+                self.set_open(false, window, cx);
+            }
+            if self.visible_panel_id == Some(Entity::entity_id(panel)) {
+                self.visible_panel_id = None;
+                panel.update(cx, |panel, cx| panel.set_visible(false, window, cx));
             }
             self.panel_entries.remove(panel_ix);
+            self.activation_history
+                .retain(|panel_id| *panel_id != Entity::entity_id(panel));
+            self.clear_temporary_size(Entity::entity_id(panel), cx);
+            cx.emit(DockEvent::PanelsChanged);
             cx.notify();
         }
     }
@@ -592,19 +1033,61 @@ impl Dock {
         self.panel_entries.len()
     }
 
+    pub fn panels(&self) -> impl Iterator<Item = &Arc<dyn PanelHandle>> {
+        self.panel_entries.iter().map(|entry| &entry.panel)
+    }
+
+    /// The dock's panels, most recently activated first, followed by the panels that
+    /// were never activated in their dock order.
+    pub fn panels_by_recent_activation(&self) -> Vec<Arc<dyn PanelHandle>> {
+        let mut panels = self.panels().cloned().collect::<Vec<_>>();
+        panels.sort_by_key(|panel| {
+            self.activation_history
+                .iter()
+                .position(|panel_id| *panel_id == panel.panel_id())
+                .unwrap_or(usize::MAX)
+        });
+        panels
+    }
+
     pub fn activate_panel(&mut self, panel_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
-        if Some(panel_ix) != self.active_panel_index {
-            if let Some(active_panel) = self.active_panel_entry() {
-                active_panel.panel.set_active(false, window, cx);
-            }
+        let Some(panel_id) = self
+            .panel_entries
+            .get(panel_ix)
+            .map(|entry| entry.panel.panel_id())
+        else {
+            return;
+        };
+        if Some(panel_id) == self.active_panel_id {
+            return;
+        }
 
-            self.active_panel_index = Some(panel_ix);
-            if let Some(active_panel) = self.active_panel_entry() {
-                active_panel.panel.set_active(true, window, cx);
-            }
+        if let Some(active_panel) = self.active_panel_entry().map(|entry| entry.panel.clone()) {
+            active_panel.set_active(false, window, cx);
+        }
 
-            cx.notify();
+        // Deactivating the previous panel may have removed panels, so look the panel up
+        // by its id again rather than trusting `panel_ix`.
+        let Some(panel) = self.panel_for_id(panel_id).cloned() else {
+            return;
+        };
+        self.active_panel_id = Some(panel_id);
+        self.activation_history.retain(|id| *id != panel_id);
+        self.activation_history.insert(0, panel_id);
+        panel.set_active(true, window, cx);
+        self.update_visible_panel(window, cx);
+        record_metric(
+            WorkspaceMetric::PanelActivated {
+                panel: panel.persistent_name(),
+                position: self.position,
+            },
+            cx,
+        );
+
+        if let Some(index) = self.active_panel_index() {
+            cx.emit(DockEvent::ActivePanelChanged { index });
         }
+        cx.notify();
     }
 
     pub fn visible_panel(&self) -> Option<&Arc<dyn PanelHandle>> {
@@ -638,24 +1121,111 @@ impl Dock {
         self.panel_entries
             .iter()
             .find(|entry| entry.panel.panel_id() == panel.panel_id())
-            .map(|entry| entry.panel.size(window, cx))
+            .map(|entry| self.displayed_size(&entry.panel, window, cx))
     }
 
     pub fn active_panel_size(&self, window: &Window, cx: &App) -> Option<Pixels> {
         if self.is_open {
             self.active_panel_entry()
-                .map(|entry| entry.panel.size(window, cx))
+                .map(|entry| self.displayed_size(&entry.panel, window, cx))
         } else {
             None
         }
     }
 
+    /// The size the panel is shown at, which is its temporary size while it has one, or the
+    /// size the window resize policy gave it.
+    fn displayed_size(&self, panel: &Arc<dyn PanelHandle>, window: &Window, cx: &App) -> Pixels {
+        match (&self.temporary_size, self.resized_size) {
+            (Some(temporary_size), _) if temporary_size.panel_id == panel.panel_id() => {
+                temporary_size.size
+            }
+            (_, Some((panel_id, size))) if panel_id == panel.panel_id() => size,
+            _ => panel.size(window, cx),
+        }
+    }
+
+    /// Shows the active panel at the given size after the window was resized, without
+    /// changing the size the panel keeps, so that the next session starts from it again.
+    pub(crate) fn set_resized_active_panel_size(
+        &mut self,
+        size: Pixels,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(panel) = self.active_panel_entry().map(|entry| entry.panel.clone()) else {
+            return;
+        };
+        let size = size.max(RESIZE_HANDLE_SIZE).round();
+        self.resized_size = if size == panel.size(window, cx) {
+            None
+        } else {
+            Some((panel.panel_id(), size))
+        };
+        cx.notify();
+    }
+
+    fn set_temporary_size<T: Panel>(
+        &mut self,
+        panel: &Entity<T>,
+        size: Pixels,
+        duration: TemporarySizeDuration,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let panel_id = Entity::entity_id(panel);
+        if size <= panel.read(cx).size(window, cx) {
+            self.clear_temporary_size(panel_id, cx);
+            return;
+        }
+
+        let mut temporary_size = TemporarySize {
+            panel_id,
+            size: size.round(),
+            _expire_after_delay: None,
+            _expire_on_blur: None,
+        };
+        match duration {
+            TemporarySizeDuration::For(delay) => {
+                temporary_size._expire_after_delay =
+                    Some(cx.spawn_in(window, async move |this, cx| {
+                        cx.background_executor().timer(delay).await;
+                        this.update(cx, |this, cx| this.clear_temporary_size(panel_id, cx))
+                            .ok();
+                    }));
+            }
+            TemporarySizeDuration::UntilBlur => {
+                temporary_size._expire_on_blur = Some(cx.on_focus_out(
+                    &panel.read(cx).focus_handle(cx),
+                    window,
+                    move |this, _, _, cx| this.clear_temporary_size(panel_id, cx),
+                ));
+            }
+        }
+        self.temporary_size = Some(temporary_size);
+        cx.notify();
+    }
+
+    fn clear_temporary_size(&mut self, panel_id: EntityId, cx: &mut Context<Self>) {
+        if self
+            .temporary_size
+            .as_ref()
+            .is_some_and(|temporary_size| temporary_size.panel_id == panel_id)
+        {
+            self.temporary_size = None;
+            cx.notify();
+        }
+    }
+
     pub fn resize_active_panel(
         &mut self,
         size: Option<Pixels>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        // Resizing by hand ends any temporary size, so the panel doesn't shrink afterwards.
+        self.temporary_size = None;
+        self.resized_size = None;
         if let Some(entry) = self.active_panel_entry() {
             let size = size.map(|size| size.max(RESIZE_HANDLE_SIZE).round());
 
@@ -664,11 +1234,21 @@ impl Dock {
         }
     }
 
+    /// The sizes the visible panel prefers, which the dock snaps to while it's being resized.
+    pub fn preferred_sizes(&self, window: &Window, cx: &App) -> Vec<Pixels> {
+        self.visible_panel()
+            .map(|panel| panel.preferred_sizes(window, cx))
+            .unwrap_or_default()
+    }
+
     pub fn toggle_action(&self) -> Box<dyn Action> {
-        match self.position {
-            DockPosition::Left => crate::ToggleLeftDock.boxed_clone(),
-            DockPosition::Bottom => crate::ToggleBottomDock.boxed_clone(),
-            DockPosition::Right => crate::ToggleRightDock.boxed_clone(),
+        match (self.position, self.bottom_slot) {
+            (DockPosition::Left, _) => crate::ToggleLeftDock.boxed_clone(),
+            (DockPosition::Bottom, BottomDockSlot::Left) => crate::ToggleBottomDock.boxed_clone(),
+            (DockPosition::Bottom, BottomDockSlot::Right) => {
+                crate::ToggleBottomRightDock.boxed_clone()
+            }
+            (DockPosition::Right, _) => crate::ToggleRightDock.boxed_clone(),
         }
     }
 
@@ -689,88 +1269,102 @@ impl Dock {
     }
 }
 
+impl Dock {
+    /// The line between the dock and the center. When the dock is resizable, it is also the
+    /// resize handle, whose hit target is wider than the line, and it highlights on hover.
+    fn render_divider(
+        &self,
+        show_divider: bool,
+        resizable: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let position = self.position;
+        let colors = cx.theme().colors();
+        let hit_size = if resizable {
+            RESIZE_HANDLE_SIZE
+        } else {
+            DIVIDER_SIZE
+        };
+        // The line stays inside the dock, on the edge facing the center.
+        let line_offset = hit_size / 2.;
+        let line = div()
+            .absolute()
+            .when(show_divider, |this| this.bg(colors.border))
+            .when(resizable, |this| {
+                this.group_hover("dock-divider", |style| style.bg(colors.border_focused))
+            })
+            .map(|this| match position {
+                DockPosition::Left => this.right(line_offset).top_0().h_full().w(DIVIDER_SIZE),
+                DockPosition::Right => this.left(line_offset).top_0().h_full().w(DIVIDER_SIZE),
+                DockPosition::Bottom => this.top(line_offset).left_0().w_full().h(DIVIDER_SIZE),
+            });
+
+        let divider = div()
+            .id("resize-handle")
+            .group("dock-divider")
+            .absolute()
+            .map(|this| match position {
+                DockPosition::Left => this.right(-hit_size / 2.).top_0().h_full().w(hit_size),
+                DockPosition::Right => this.left(-hit_size / 2.).top_0().h_full().w(hit_size),
+                DockPosition::Bottom => this.top(-hit_size / 2.).left_0().w_full().h(hit_size),
+            })
+            .child(line)
+            .when(resizable, |this| {
+                this.on_drag(DraggedDock(position), |dock, _, _, cx| {
+                    cx.stop_propagation();
+                    cx.new(|_| dock.clone())
+                })
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|_, _: &MouseDownEvent, _, cx| {
+                        cx.stop_propagation();
+                    }),
+                )
+                .on_mouse_up(
+                    MouseButton::Left,
+                    cx.listener(|dock, e: &MouseUpEvent, window, cx| {
+                        if e.click_count == 2 {
+                            dock.resize_active_panel(None, window, cx);
+                            dock.workspace
+                                .update(cx, |workspace, cx| {
+                                    workspace.serialize_workspace(window, cx);
+                                })
+                                .ok();
+                            cx.stop_propagation();
+                        }
+                    }),
+                )
+                .occlude()
+                .map(|this| match position.axis() {
+                    Axis::Horizontal => this.cursor_col_resize(),
+                    Axis::Vertical => this.cursor_row_resize(),
+                })
+            });
+        deferred(divider)
+    }
+}
+
 impl Render for Dock {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let dispatch_context = Self::dispatch_context();
         if let Some(entry) = self.visible_entry() {
-            let size = entry.panel.size(window, cx);
+            let size = self.displayed_size(&entry.panel, window, cx);
 
-            let position = self.position;
-            let create_resize_handle = || {
-                let handle = div()
-                    .id("resize-handle")
-                    .on_drag(DraggedDock(position), |dock, _, _, cx| {
-                        cx.stop_propagation();
-                        cx.new(|_| dock.clone())
-                    })
-                    .on_mouse_down(
-                        MouseButton::Left,
-                        cx.listener(|_, _: &MouseDownEvent, _, cx| {
-                            cx.stop_propagation();
-                        }),
-                    )
-                    .on_mouse_up(
-                        MouseButton::Left,
-                        cx.listener(|dock, e: &MouseUpEvent, window, cx| {
-                            if e.click_count == 2 {
-                                dock.resize_active_panel(None, window, cx);
-                                dock.workspace
-                                    .update(cx, |workspace, cx| {
-                                        workspace.serialize_workspace(window, cx);
-                                    })
-                                    .ok();
-                                cx.stop_propagation();
-                            }
-                        }),
-                    )
-                    .occlude();
-                match self.position() {
-                    DockPosition::Left => deferred(
-                        handle
-                            .absolute()
-                            .right(-RESIZE_HANDLE_SIZE / 2.)
-                            .top(px(0.))
-                            .h_full()
-                            .w(RESIZE_HANDLE_SIZE)
-                            .cursor_col_resize(),
-                    ),
-                    DockPosition::Bottom => deferred(
-                        handle
-                            .absolute()
-                            .top(-RESIZE_HANDLE_SIZE / 2.)
-                            .left(px(0.))
-                            .w_full()
-                            .h(RESIZE_HANDLE_SIZE)
-                            .cursor_row_resize(),
-                    ),
-                    DockPosition::Right => deferred(
-                        handle
-                            .absolute()
-                            .top(px(0.))
-                            .left(-RESIZE_HANDLE_SIZE / 2.)
-                            .h_full()
-                            .w(RESIZE_HANDLE_SIZE)
-                            .cursor_col_resize(),
-                    ),
-                }
-            };
+            let show_divider = entry.panel.show_divider(window, cx);
+            let resizable = self.resizable(cx);
+            let this = cx.entity().downgrade();
 
             div()
+                .id("dock")
                 .key_context(dispatch_context)
                 .track_focus(&self.focus_handle(cx))
                 .flex()
                 .bg(cx.theme().colors().panel_background)
-                .border_color(cx.theme().colors().border)
                 .overflow_hidden()
                 .map(|this| match self.position().axis() {
                     Axis::Horizontal => this.w(size).h_full().flex_row(),
                     Axis::Vertical => this.h(size).w_full().flex_col(),
                 })
-                .map(|this| match self.position() {
-                    DockPosition::Left => this.border_r_1(),
-                    DockPosition::Right => this.border_l_1(),
-                    DockPosition::Bottom => this.border_t_1(),
-                })
                 .child(
                     div()
                         .map(|this| match self.position().axis() {
@@ -784,11 +1378,39 @@ impl Render for Dock {
                                 .cached(StyleRefinement::default().v_flex().size_full()),
                         ),
                 )
-                .when(self.resizable(cx), |this| {
-                    this.child(create_resize_handle())
-                })
+                .child(self.render_divider(show_divider, resizable, cx))
+                .child(
+                    canvas(
+                        move |bounds, _, cx| {
+                            this.update(cx, |this, _| this.bounds = Some(bounds)).ok();
+                        },
+                        |_, _, _, _| {},
+                    )
+                    .absolute()
+                    .size_full(),
+                )
+                .on_mouse_move(cx.listener(|this, event, window, cx| {
+                    let dock = cx.entity();
+                    this.workspace
+                        .update(cx, |workspace, cx| {
+                            workspace.dock_hovered(&dock, event, window, cx)
+                        })
+                        .ok();
+                }))
+                .on_hover(cx.listener(|this, hovered, _, cx| {
+                    if !hovered {
+                        let dock_id = cx.entity_id();
+                        this.workspace
+                            .update(cx, |workspace, _| {
+                                workspace.hover_focus_target_left(dock_id)
+                            })
+                            .ok();
+                    }
+                }))
         } else {
+            self.bounds = None;
             div()
+                .id("dock")
                 .key_context(dispatch_context)
                 .track_focus(&self.focus_handle(cx))
         }
@@ -796,18 +1418,316 @@ impl Render for Dock {
 }
 
 impl PanelButtons {
-    pub fn new(dock: Entity<Dock>, cx: &mut Context<Self>) -> Self {
-        cx.observe(&dock, |_, _, cx| cx.notify()).detach();
-        Self { dock }
+    pub fn new(dock: Entity<Dock>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        // Panels may hide their buttons depending on their settings.
+        cx.observe_global_in::<SettingsStore>(window, |this, window, cx| {
+            this.update_state(window, cx)
+        })
+        .detach();
+        cx.subscribe_in(&dock, window, |this, dock, event, window, cx| match event {
+            DockEvent::PanelLabelChanged { index } => {
+                let label = dock
+                    .read(cx)
+                    .panel_entries
+                    .get(*index)
+                    .and_then(|entry| this.labels.get(&entry.panel.panel_id()))
+                    .cloned();
+                if let Some(label) = label {
+                    label.update(cx, |_, cx| cx.notify());
+                }
+            }
+            DockEvent::ActivePanelChanged { .. }
+            | DockEvent::OpenChanged(_)
+            | DockEvent::PanelsChanged => this.update_state(window, cx),
+            DockEvent::CloseVetoed(_) => {}
+        })
+        .detach();
+        let mut this = Self {
+            dock,
+            focus_handle: cx.focus_handle(),
+            selected_index: None,
+            context_menu: None,
+            state: PanelButtonsState::default(),
+            labels: HashMap::default(),
+        };
+        this.update_state(window, cx);
+        this
+    }
+
+    /// The panels removed by the user that would be restored into this dock.
+    fn removed_panels(&self, window: &Window, cx: &App) -> Vec<Arc<dyn PanelHandle>> {
+        let Some(workspace) = self.dock.read(cx).workspace.upgrade() else {
+            return Vec::new();
+        };
+        let workspace = workspace.read(cx);
+        workspace
+            .removed_panels()
+            .iter()
+            .filter(|panel| {
+                let position = panel.position(window, cx);
+                *workspace.dock_for_panel(panel.persistent_name(), position) == self.dock
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Updates the label views for the dock's panels, and redraws the buttons if what they
+    /// show has changed.
+    fn update_state(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let dock = self.dock.read(cx);
+        let panels = dock.panels().cloned().collect::<Vec<_>>();
+        let removed_panels = self
+            .removed_panels(window, cx)
+            .iter()
+            .map(|panel| panel.panel_id())
+            .collect();
+        let state = PanelButtonsState {
+            is_open: dock.is_open,
+            active_index: dock.active_panel_index(),
+            buttons: panels
+                .iter()
+                .filter_map(|panel| {
+                    Some((
+                        panel.panel_id(),
+                        panel.icon(window, cx)?,
+                        panel.icon_tooltip(window, cx)?,
+                    ))
+                })
+                .collect(),
+            removed_panels,
+        };
+
+        self.labels
+            .retain(|panel_id, _| panels.iter().any(|panel| panel.panel_id() == *panel_id));
+        for panel in panels {
+            self.labels
+                .entry(panel.panel_id())
+                .or_insert_with(|| cx.new(|_| PanelButtonLabel { panel }));
+        }
+        if state != self.state {
+            self.state = state;
+            cx.notify();
+        }
+    }
+
+    /// Focuses this row of buttons for keyboard navigation, selecting the active panel's button.
+    /// Returns false if the dock has no panel buttons to focus.
+    pub fn focus(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let indices = self.button_indices(window, cx);
+        let active_index = self.dock.read(cx).active_panel_index();
+        let Some(&first_index) = indices.first() else {
+            return false;
+        };
+        self.selected_index = active_index
+            .filter(|index| indices.contains(index))
+            .or(Some(first_index));
+        window.focus(&self.focus_handle);
+        cx.notify();
+        true
+    }
+
+    /// The indices of the dock's panels that are shown as buttons.
+    fn button_indices(&self, window: &Window, cx: &App) -> Vec<usize> {
+        self.dock
+            .read(cx)
+            .panel_entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                entry.panel.icon(window, cx).is_some()
+                    && entry.panel.icon_tooltip(window, cx).is_some()
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_adjacent(false, window, cx);
     }
+
+    fn select_next(&mut self, _: &menu::SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        self.select_adjacent(true, window, cx);
+    }
+
+    fn select_adjacent(&mut self, forward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let indices = self.button_indices(window, cx);
+        if indices.is_empty() {
+            return;
+        }
+        let position = self
+            .selected_index
+            .and_then(|selected| indices.iter().position(|index| *index == selected));
+        let position = match position {
+            Some(position) if forward => (position + 1) % indices.len(),
+            Some(position) => (position + indices.len() - 1) % indices.len(),
+            None => 0,
+        };
+        self.selected_index = Some(indices[position]);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(selected_index) = self.selected_index else {
+            return;
+        };
+        let panel = self.dock.update(cx, |dock, cx| {
+            if selected_index >= dock.panels_len() {
+                return None;
+            }
+            dock.activate_panel(selected_index, window, cx);
+            dock.set_open(true, window, cx);
+            dock.active_panel().cloned()
+        });
+        if let Some(panel) = panel {
+            panel.panel_focus_handle(cx).focus(window);
+            self.dock
+                .read(cx)
+                .workspace
+                .update(cx, |workspace, cx| {
+                    workspace.serialize_workspace(window, cx)
+                })
+                .ok();
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected_index = None;
+        self.dock
+            .read(cx)
+            .workspace
+            .update(cx, |workspace, cx| {
+                let active_pane = workspace.active_pane().clone();
+                window.focus(&active_pane.focus_handle(cx));
+            })
+            .ok();
+        cx.notify();
+    }
+
+    fn deploy_panel_menu(
+        &mut self,
+        _: &DeployPanelMenu,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selected_index) = self.selected_index else {
+            return;
+        };
+        let dock = self.dock.read(cx);
+        let Some(entry) = dock.panel_entries.get(selected_index) else {
+            return;
+        };
+        let context_menu = panel_context_menu(
+            entry.panel.clone(),
+            dock.position,
+            dock.bottom_slot,
+            dock.workspace.clone(),
+            window,
+            cx,
+        );
+        window.focus(&context_menu.focus_handle(cx));
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                this.context_menu.take();
+                window.focus(&this.focus_handle);
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, selected_index, subscription));
+        cx.notify();
+    }
+}
+
+fn panel_context_menu(
+    panel: Arc<dyn PanelHandle>,
+    dock_position: DockPosition,
+    bottom_slot: BottomDockSlot,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Entity<ContextMenu> {
+    const POSITIONS: [DockPosition; 3] = [
+        DockPosition::Left,
+        DockPosition::Right,
+        DockPosition::Bottom,
+    ];
+
+    // The menu can outlive the panel, so its entries look the panel up when they're clicked
+    // rather than holding on to it.
+    let panel_id = panel.panel_id();
+
+    ContextMenu::build(window, cx, |mut menu, window, cx| {
+        for position in POSITIONS {
+            if position != dock_position && panel.position_is_valid(position, cx) {
+                let workspace = workspace.clone();
+                menu = menu.entry(
+                    format!("Dock {}", position.label()),
+                    None,
+                    move |window, cx| {
+                        let panel = workspace
+                            .upgrade()
+                            .and_then(|workspace| workspace.read(cx).panel_for_id(panel_id, cx));
+                        if let Some(panel) = panel {
+                            panel.set_position(position, window, cx);
+                        }
+                    },
+                )
+            }
+        }
+        if dock_position == DockPosition::Bottom {
+            let other_slot = match bottom_slot {
+                BottomDockSlot::Left => BottomDockSlot::Right,
+                BottomDockSlot::Right => BottomDockSlot::Left,
+            };
+            let workspace = workspace.clone();
+            menu = menu.entry(
+                format!("Dock {}", other_slot.label()),
+                None,
+                move |window, cx| {
+                    workspace
+                        .update(cx, |workspace, cx| {
+                            if let Some(panel) = workspace.panel_for_id(panel_id, cx) {
+                                workspace.move_panel_to_bottom_slot(panel, other_slot, window, cx);
+                            }
+                        })
+                        .ok();
+                },
+            )
+        }
+        if panel.can_close(window, cx) {
+            let workspace = workspace.clone();
+            menu = menu
+                .separator()
+                .entry("Remove from Dock", None, move |window, cx| {
+                    workspace
+                        .update(cx, |workspace, cx| {
+                            if let Some(panel) = workspace.panel_for_id(panel_id, cx) {
+                                workspace.remove_panel_from_dock(panel, window, cx);
+                            }
+                        })
+                        .ok();
+                });
+        }
+        menu
+    })
 }
 
 impl Render for PanelButtons {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_focused = self.focus_handle.is_focused(window);
         let dock = self.dock.read(cx);
-        let active_index = dock.active_panel_index;
+        let active_index = dock.active_panel_index();
         let is_open = dock.is_open;
         let dock_position = dock.position;
+        let bottom_slot = dock.bottom_slot;
+        let workspace = dock.workspace.clone();
+        let show_previews = StatusBarSettings::get_global(cx).panel_previews;
 
         let (menu_anchor, menu_attach) = match dock.position {
             DockPosition::Left => (Corner::BottomLeft, Corner::TopLeft),
@@ -823,13 +1743,15 @@ impl Render for PanelButtons {
                 let icon_tooltip = entry.panel.icon_tooltip(window, cx)?;
                 let name = entry.panel.persistent_name();
                 let panel = entry.panel.clone();
+                let workspace = workspace.clone();
 
                 let is_active_button = Some(i) == active_index && is_open;
+                // Panels that aren't shown can be peeked at by hovering their button.
+                let preview = (show_previews && !is_active_button).then(|| panel.clone());
                 let (action, tooltip) = if is_active_button {
                     let action = dock.toggle_action();
 
-                    let tooltip: SharedString =
-                        format!("Close {} dock", dock.position.label()).into();
+                    let tooltip: SharedString = format!("Close {} dock", dock.label()).into();
 
                     (action, tooltip)
                 } else {
@@ -838,63 +1760,176 @@ impl Render for PanelButtons {
                     (action, icon_tooltip.into())
                 };
 
+                let is_selected = is_focused && Some(i) == self.selected_index;
+                // A label is only redrawn when its panel reports a new one, not with the rest
+                // of the buttons.
+                let label = self
+                    .labels
+                    .get(&entry.panel.panel_id())
+                    .map(|label| AnyView::from(label.clone()).cached(StyleRefinement::default()));
+                let context_menu = self
+                    .context_menu
+                    .as_ref()
+                    .filter(|(_, ix, _)| *ix == i)
+                    .map(|(menu, _, _)| menu.clone());
+
                 Some(
-                    right_click_menu(name)
-                        .menu(move |window, cx| {
-                            const POSITIONS: [DockPosition; 3] = [
-                                DockPosition::Left,
-                                DockPosition::Right,
-                                DockPosition::Bottom,
-                            ];
-
-                            ContextMenu::build(window, cx, |mut menu, _, cx| {
-                                for position in POSITIONS {
-                                    if position != dock_position
-                                        && panel.position_is_valid(position, cx)
-                                    {
-                                        let panel = panel.clone();
-                                        menu = menu.entry(
-                                            format!("Dock {}", position.label()),
-                                            None,
-                                            move |window, cx| {
-                                                panel.set_position(position, window, cx);
-                                            },
-                                        )
-                                    }
-                                }
-                                menu
-                            })
+                    h_flex()
+                        .debug_selector(|| format!("PANEL_BUTTON-{name}"))
+                        .rounded_sm()
+                        .when(is_selected, |this| {
+                            this.bg(cx.theme().colors().element_selected)
                         })
-                        .anchor(menu_anchor)
-                        .attach(menu_attach)
-                        .trigger(
-                            IconButton::new(name, icon)
-                                .icon_size(IconSize::Small)
-                                .toggle_state(is_active_button)
-                                .on_click({
-                                    let action = action.boxed_clone();
-                                    move |_, window, cx| {
-                                        window.dispatch_action(action.boxed_clone(), cx)
-                                    }
+                        .child(
+                            right_click_menu(name)
+                                .menu(move |window, cx| {
+                                    panel_context_menu(
+                                        panel.clone(),
+                                        dock_position,
+                                        bottom_slot,
+                                        workspace.clone(),
+                                        window,
+                                        cx,
+                                    )
                                 })
-                                .tooltip(move |window, cx| {
-                                    Tooltip::for_action(tooltip.clone(), &*action, window, cx)
-                                }),
-                        ),
+                                .anchor(menu_anchor)
+                                .attach(menu_attach)
+                                .trigger(
+                                    IconButton::new(name, icon)
+                                        .icon_size(IconSize::Small)
+                                        .toggle_state(is_active_button)
+                                        .on_click({
+                                            let action = action.boxed_clone();
+                                            move |_, window, cx| {
+                                                window.dispatch_action(action.boxed_clone(), cx)
+                                            }
+                                        })
+                                        .tooltip(move |window, cx| match &preview {
+                                            Some(panel) => cx
+                                                .new(|_| PanelPreview {
+                                                    panel: panel.clone(),
+                                                    title: tooltip.clone(),
+                                                    action: action.boxed_clone(),
+                                                })
+                                                .into(),
+                                            None => Tooltip::for_action(
+                                                tooltip.clone(),
+                                                &*action,
+                                                window,
+                                                cx,
+                                            ),
+                                        }),
+                                ),
+                        )
+                        .children(label)
+                        .children(context_menu.map(|menu| {
+                            deferred(anchored().anchor(menu_anchor).child(menu)).with_priority(1)
+                        })),
                 )
             })
             .collect();
 
-        let has_buttons = !buttons.is_empty();
+        let removed_panels = self.removed_panels(window, cx);
+        let removed_panels_menu = (!removed_panels.is_empty()).then(|| {
+            let workspace = dock.workspace.clone();
+            PopoverMenu::new("removed-panels-menu")
+                .trigger_with_tooltip(
+                    IconButton::new("removed-panels", IconName::Ellipsis)
+                        .icon_size(IconSize::Small),
+                    Tooltip::text("Removed Panels"),
+                )
+                .anchor(menu_anchor)
+                .attach(menu_attach)
+                .menu(move |window, cx| {
+                    let removed_panels = removed_panels.clone();
+                    let workspace = workspace.clone();
+                    Some(ContextMenu::build(window, cx, |mut menu, window, cx| {
+                        for panel in removed_panels {
+                            let label = panel
+                                .icon_tooltip(window, cx)
+                                .unwrap_or(panel.persistent_name());
+                            let workspace = workspace.clone();
+                            menu =
+                                menu.entry(format!("Restore {label}"), None, move |window, cx| {
+                                    workspace
+                                        .update(cx, |workspace, cx| {
+                                            workspace.restore_removed_panel(
+                                                panel.panel_id(),
+                                                window,
+                                                cx,
+                                            );
+                                        })
+                                        .ok();
+                                });
+                        }
+                        menu
+                    }))
+                })
+        });
+
+        let has_buttons = !buttons.is_empty() || removed_panels_menu.is_some();
         h_flex()
+            .key_context("PanelButtons")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::deploy_panel_menu))
             .gap_1()
             .children(buttons)
+            .children(removed_panels_menu)
             .when(has_buttons && dock.position == DockPosition::Left, |this| {
                 this.child(Divider::vertical().color(DividerColor::Border))
             })
     }
 }
 
+/// A preview of a panel that isn't shown, in the tooltip of its button. Panels without a
+/// preview of their own only show their name and key binding.
+struct PanelPreview {
+    panel: Arc<dyn PanelHandle>,
+    title: SharedString,
+    action: Box<dyn Action>,
+}
+
+impl Render for PanelPreview {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let key_binding = KeyBinding::for_action(&*self.action, window, cx);
+        let name = self.panel.persistent_name();
+        let preview = self.panel.render_preview(window, cx);
+        v_flex()
+            .debug_selector(|| format!("PANEL_PREVIEW-{name}"))
+            .w(PANEL_PREVIEW_WIDTH)
+            .elevation_2(cx)
+            .overflow_hidden()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .gap_2()
+                    .justify_between()
+                    .child(Label::new(self.title.clone()))
+                    .children(key_binding),
+            )
+            .children(preview.map(|preview| {
+                div()
+                    .h(PANEL_PREVIEW_HEIGHT)
+                    .overflow_hidden()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .bg(cx.theme().colors().panel_background)
+                    .child(preview)
+            }))
+    }
+}
+
+impl Focusable for PanelButtons {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
 impl StatusItemView for PanelButtons {
     fn set_active_pane_item(
         &mut self,
@@ -904,19 +1939,40 @@ impl StatusItemView for PanelButtons {
     ) {
         // Nothing to do, panel buttons don't depend on the active center item
     }
+
+    fn is_essential(&self) -> bool {
+        true
+    }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("panel_buttons")
+    }
 }
 
 #[cfg(any(test, feature = "test-support"))]
 pub mod test {
     use super::*;
     use gpui::{actions, div, App, Context, Window};
+    use std::cell::Cell;
 
     pub struct TestPanel {
         pub position: DockPosition,
         pub zoomed: bool,
         pub active: bool,
+        pub visible: bool,
         pub focus_handle: FocusHandle,
         pub size: Pixels,
+        pub veto_close: bool,
+        pub invalid_positions: Vec<DockPosition>,
+        /// The pane that [`Placement::Dock`](crate::Placement::Dock) opens items in.
+        pub pane: Option<Entity<Pane>>,
+        /// Panels with an icon have a button in the status bar.
+        pub icon: Option<ui::IconName>,
+        pub starts_open: bool,
+        pub preferred_sizes: Vec<Pixels>,
+        /// How many times the panel's icon label was asked for, i.e. how many times the
+        /// label next to its button was rendered.
+        pub icon_label_requests: Cell<usize>,
     }
     actions!(test, [ToggleTestPanel]);
 
@@ -928,10 +1984,23 @@ pub mod test {
                 position,
                 zoomed: false,
                 active: false,
+                visible: false,
                 focus_handle: cx.focus_handle(),
                 size: px(300.),
+                veto_close: false,
+                invalid_positions: Vec::new(),
+                pane: None,
+                icon: None,
+                starts_open: false,
+                preferred_sizes: Vec::new(),
+                icon_label_requests: Cell::new(0),
             }
         }
+
+        pub fn with_icon(mut self, icon: ui::IconName) -> Self {
+            self.icon = Some(icon);
+            self
+        }
     }
 
     impl Render for TestPanel {
@@ -949,8 +2018,8 @@ pub mod test {
             self.position
         }
 
-        fn position_is_valid(&self, _: super::DockPosition) -> bool {
-            true
+        fn position_is_valid(&self, position: super::DockPosition) -> bool {
+            !self.invalid_positions.contains(&position)
         }
 
         fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
@@ -966,11 +2035,21 @@ pub mod test {
             self.size = size.unwrap_or(px(300.));
         }
 
+        fn preferred_sizes(&self, _window: &Window, _: &App) -> Vec<Pixels> {
+            self.preferred_sizes.clone()
+        }
+
         fn icon(&self, _window: &Window, _: &App) -> Option<ui::IconName> {
-            None
+            self.icon
         }
 
         fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+            self.icon.map(|_| "Test Panel")
+        }
+
+        fn icon_label(&self, _window: &Window, _: &App) -> Option<String> {
+            self.icon_label_requests
+                .set(self.icon_label_requests.get() + 1);
             None
         }
 
@@ -982,17 +2061,39 @@ pub mod test {
             self.zoomed
         }
 
+        fn starts_open(&self, _window: &Window, _cx: &App) -> bool {
+            self.starts_open
+        }
+
         fn set_zoomed(&mut self, zoomed: bool, _window: &mut Window, _cx: &mut Context<Self>) {
             self.zoomed = zoomed;
         }
 
-        fn set_active(&mut self, active: bool, _window: &mut Window, _cx: &mut Context<Self>) {
+        fn set_active(&mut self, active: bool, _window: &mut Window, cx: &mut Context<Self>) {
             self.active = active;
+            cx.notify();
+        }
+
+        fn set_visible(&mut self, visible: bool, _window: &mut Window, _cx: &mut Context<Self>) {
+            self.visible = visible;
+        }
+
+        fn should_close(
+            &mut self,
+            _reason: CloseReason,
+            _window: &mut Window,
+            _cx: &mut Context<Self>,
+        ) -> bool {
+            !self.veto_close
         }
 
         fn activation_priority(&self) -> u32 {
             100
         }
+
+        fn pane(&self) -> Option<Entity<Pane>> {
+            self.pane.clone()
+        }
     }
 
     impl Focusable for TestPanel {
@@ -0,0 +1,102 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A bundle of dock options that makes the docks behave like those of a familiar editor.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DockBehaviorPreset {
+    /// Use the individually configured `dock_options`.
+    #[default]
+    Custom,
+    /// Side bars that push the editor aside, with all panel buttons grouped on the left.
+    VsCode,
+    /// Tool windows that float over the editor and hide again once the editor is focused.
+    JetBrains,
+}
+
+impl DockBehaviorPreset {
+    /// Returns the dock options to use, given the individually configured ones.
+    pub fn apply(&self, options: DockOptions) -> DockOptions {
+        match self {
+            Self::Custom => options,
+            Self::VsCode => DockOptions::VS_CODE,
+            Self::JetBrains => DockOptions::JETBRAINS,
+        }
+    }
+}
+
+/// Where the buttons that toggle the panels of each dock are shown in the status bar.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DockButtonPlacement {
+    /// Show the buttons of the left dock on the left, and those of the other docks on the right.
+    #[default]
+    DockSide,
+    /// Show the buttons of all docks on the left.
+    Left,
+    /// Show the buttons of all docks on the right.
+    Right,
+}
+
+impl DockButtonPlacement {
+    /// Splits the buttons of the left, bottom, bottom right and right docks into the ones
+    /// shown on the left and the ones shown on the right of the status bar. Both sides are
+    /// ordered from the edge of the status bar inwards.
+    pub fn arrange<T: Clone>(&self, buttons: &[T; 4]) -> (Vec<T>, Vec<T>) {
+        let [left, bottom, bottom_right, right] = buttons.clone();
+        match self {
+            Self::DockSide => (vec![left], vec![right, bottom_right, bottom]),
+            Self::Left => (vec![left, bottom, bottom_right, right], Vec::new()),
+            Self::Right => (Vec::new(), vec![right, bottom_right, bottom, left]),
+        }
+    }
+}
+
+/// How the docks behave when they're toggled and while they're open.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(default)]
+pub struct DockOptions {
+    /// Whether an open dock closes when a pane in the center is focused.
+    ///
+    /// Default: false
+    pub auto_hide: bool,
+    /// Whether open docks are drawn over the center panes, instead of shrinking them.
+    ///
+    /// Default: false
+    pub overlay: bool,
+    /// Whether opening a dock focuses its active panel.
+    ///
+    /// Default: true
+    pub focus_on_toggle: bool,
+    /// Where the panel buttons are shown in the status bar.
+    ///
+    /// Default: dock_side
+    pub button_placement: DockButtonPlacement,
+}
+
+impl DockOptions {
+    pub const VS_CODE: Self = Self {
+        auto_hide: false,
+        overlay: false,
+        focus_on_toggle: true,
+        button_placement: DockButtonPlacement::Left,
+    };
+
+    pub const JETBRAINS: Self = Self {
+        auto_hide: true,
+        overlay: true,
+        focus_on_toggle: true,
+        button_placement: DockButtonPlacement::DockSide,
+    };
+}
+
+impl Default for DockOptions {
+    fn default() -> Self {
+        Self {
+            auto_hide: false,
+            overlay: false,
+            focus_on_toggle: true,
+            button_placement: DockButtonPlacement::DockSide,
+        }
+    }
+}
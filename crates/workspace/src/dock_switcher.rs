@@ -0,0 +1,217 @@
+use crate::{
+    dock::{Dock, PanelHandle},
+    ModalView, Workspace,
+};
+use gpui::{
+    impl_actions, App, Context, DismissEvent, EventEmitter, FocusHandle, Focusable, Modifiers,
+    ModifiersChangedEvent, Render, WeakEntity, Window,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+use ui::{prelude::*, ListItem, ListItemSpacing};
+
+const SWITCHER_WIDTH_REMS: f32 = 20.;
+
+/// Opens a switcher over the panels of the focused dock, ordered by most recent activation.
+/// While the switcher is open, repeating the action cycles through the panels.
+/// Bound to `ctrl-tab` in docks, where it takes precedence over the tab switcher.
+#[derive(PartialEq, Clone, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Toggle {
+    #[serde(default)]
+    pub select_last: bool,
+}
+
+impl_actions!(dock_switcher, [Toggle]);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(DockSwitcher::register).detach();
+}
+
+pub struct DockSwitcher {
+    dock: WeakEntity<Dock>,
+    panels: Vec<Arc<dyn PanelHandle>>,
+    selected_index: usize,
+    init_modifiers: Option<Modifiers>,
+    focus_handle: FocusHandle,
+}
+
+impl ModalView for DockSwitcher {}
+
+impl EventEmitter<DismissEvent> for DockSwitcher {}
+
+impl Focusable for DockSwitcher {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl DockSwitcher {
+    fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(|workspace, action: &Toggle, window, cx| {
+            let Some(switcher) = workspace.active_modal::<Self>(cx) else {
+                Self::open(action, workspace, window, cx);
+                return;
+            };
+
+            switcher.update(cx, |switcher, cx| {
+                switcher.cycle_selection(!action.select_last, cx)
+            });
+        });
+    }
+
+    fn open(
+        action: &Toggle,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(dock) = workspace
+            .all_docks()
+            .into_iter()
+            .find(|dock| dock.focus_handle(cx).contains_focused(window, cx))
+            .cloned()
+        else {
+            return;
+        };
+        let panels = dock.read(cx).panels_by_recent_activation();
+        if panels.is_empty() {
+            return;
+        }
+
+        let select_last = action.select_last;
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(dock.downgrade(), panels, select_last, window, cx)
+        });
+    }
+
+    fn new(
+        dock: WeakEntity<Dock>,
+        panels: Vec<Arc<dyn PanelHandle>>,
+        select_last: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        // The first panel is the active one, so start on the one that was active before it.
+        let selected_index = if select_last {
+            panels.len() - 1
+        } else {
+            1.min(panels.len() - 1)
+        };
+        Self {
+            dock,
+            panels,
+            selected_index,
+            init_modifiers: window.modifiers().modified().then_some(window.modifiers()),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn cycle_selection(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let len = self.panels.len();
+        self.selected_index = if forward {
+            (self.selected_index + 1) % len
+        } else {
+            (self.selected_index + len - 1) % len
+        };
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.cycle_selection(true, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.cycle_selection(false, cx);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.activate_selected_panel(window, cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn activate_selected_panel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let (Some(dock), Some(panel)) =
+            (self.dock.upgrade(), self.panels.get(self.selected_index))
+        {
+            dock.update(cx, |dock, cx| {
+                if let Some(index) = dock.panel_index_for_id(panel.panel_id()) {
+                    dock.activate_panel(index, window, cx);
+                    dock.set_open(true, window, cx);
+                }
+            });
+            panel.panel_focus_handle(cx).focus(window);
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn handle_modifiers_changed(
+        &mut self,
+        event: &ModifiersChangedEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(init_modifiers) = self.init_modifiers else {
+            return;
+        };
+        if !event.modified() || !init_modifiers.is_subset_of(event) {
+            self.init_modifiers = None;
+            self.activate_selected_panel(window, cx);
+        }
+    }
+}
+
+impl Render for DockSwitcher {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let items = self
+            .panels
+            .iter()
+            .enumerate()
+            .map(|(ix, panel)| {
+                let label = panel
+                    .icon_tooltip(window, cx)
+                    .unwrap_or(panel.persistent_name());
+                ListItem::new(ix)
+                    .inset(true)
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(ix == self.selected_index)
+                    .start_slot::<Icon>(
+                        panel
+                            .icon(window, cx)
+                            .map(|icon| Icon::new(icon).color(Color::Muted)),
+                    )
+                    .child(Label::new(label))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.selected_index = ix;
+                        this.activate_selected_panel(window, cx);
+                    }))
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("DockSwitcher")
+            .track_focus(&self.focus_handle)
+            .w(rems(SWITCHER_WIDTH_REMS))
+            .elevation_3(cx)
+            .py_1()
+            .on_modifiers_changed(cx.listener(Self::handle_modifiers_changed))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .children(items)
+    }
+}
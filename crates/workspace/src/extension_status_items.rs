@@ -0,0 +1,371 @@
+//! Status bar items registered by extensions.
+//!
+//! Extensions don't get to render into the status bar themselves. Instead, they describe their
+//! items as JSON (some text, an optional icon and the name of an action to dispatch on click),
+//! and the workspace renders them. Descriptions are validated when they're registered, and
+//! changes are applied in batches at most once per [`EXTENSION_STATUS_ITEM_UPDATE_INTERVAL`],
+//! so an extension that updates its items on every frame can't make the status bar churn.
+//! Changes to the same item within a batch replace each other, so they don't pile up either.
+//! Clicking an item can only dispatch one of [`EXTENSION_STATUS_ITEM_ACTIONS`].
+
+use crate::{ItemHandle, StatusItemView, Workspace};
+use anyhow::{anyhow, Context as _, Result};
+use gpui::{App, Context, Entity, IntoElement, Render, Task, Window};
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, sync::Arc, time::Duration};
+use ui::{prelude::*, Tooltip};
+use util::ResultExt;
+
+/// The shortest interval at which changes to extension status items are shown.
+pub const EXTENSION_STATUS_ITEM_UPDATE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The most status items a single extension can show.
+pub const MAX_STATUS_ITEMS_PER_EXTENSION: usize = 3;
+
+/// Longer texts are truncated, so a single item can't take over the status bar.
+const MAX_TEXT_LEN: usize = 48;
+
+/// The actions extension status items can dispatch when clicked. They only show or lay out
+/// parts of the app, so an extension can't use its item to make the user run anything else.
+pub const EXTENSION_STATUS_ITEM_ACTIONS: &[&str] = &[
+    "zed::Extensions",
+    "zed::OpenLog",
+    "workspace::ToggleBottomDock",
+    "workspace::ToggleLeftDock",
+    "workspace::ToggleRightDock",
+    "workspace::ToggleZoom",
+];
+
+/// The serialized description of an extension's status item.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExtensionStatusItemDescription {
+    /// Identifies the item among the extension's items. Registering another item with the
+    /// same id replaces it.
+    pub id: String,
+    pub text: String,
+    /// The name of an icon shown before the text, such as `"check"`.
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub tooltip: Option<String>,
+    /// The name of the action dispatched when the item is clicked, such as `"zed::OpenLog"`.
+    /// Must be one of [`EXTENSION_STATUS_ITEM_ACTIONS`].
+    #[serde(default)]
+    pub click_action: Option<String>,
+}
+
+struct ExtensionStatusItem {
+    extension_id: Arc<str>,
+    id: String,
+    text: SharedString,
+    icon: Option<IconName>,
+    tooltip: Option<SharedString>,
+    click_action: Option<String>,
+}
+
+/// A change that's waiting for the next batch of updates.
+enum PendingChange {
+    Register(ExtensionStatusItem),
+    Unregister { extension_id: Arc<str>, id: String },
+    UnregisterExtension { extension_id: Arc<str> },
+}
+
+impl PendingChange {
+    fn extension_id(&self) -> &Arc<str> {
+        match self {
+            PendingChange::Register(item) => &item.extension_id,
+            PendingChange::Unregister { extension_id, .. }
+            | PendingChange::UnregisterExtension { extension_id } => extension_id,
+        }
+    }
+
+    /// Whether this change only affects the item with the given id.
+    fn changes_item(&self, extension_id: &Arc<str>, id: &str) -> bool {
+        match self {
+            PendingChange::Register(item) => item.extension_id == *extension_id && item.id == id,
+            PendingChange::Unregister {
+                extension_id: id_of_extension,
+                id: item_id,
+            } => id_of_extension == extension_id && item_id == id,
+            PendingChange::UnregisterExtension { .. } => false,
+        }
+    }
+}
+
+/// The status bar item hosting all extension status items of a workspace.
+pub struct ExtensionStatusItems {
+    items: Vec<ExtensionStatusItem>,
+    pending_changes: Vec<PendingChange>,
+    apply_pending_changes: Option<Task<()>>,
+}
+
+impl ExtensionStatusItems {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            pending_changes: Vec::new(),
+            apply_pending_changes: None,
+        }
+    }
+
+    /// Validates the given JSON description and registers the item it describes, replacing
+    /// the extension's item with the same id.
+    pub fn register(
+        &mut self,
+        extension_id: &str,
+        description: &str,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let description: ExtensionStatusItemDescription = serde_json::from_str(description)
+            .context("invalid extension status item description")?;
+        let item = Self::validate(extension_id.into(), description, cx)?;
+        let item_count = self
+            .item_ids_after_pending_changes(&item.extension_id)
+            .into_iter()
+            .filter(|id| *id != item.id)
+            .count();
+        if item_count >= MAX_STATUS_ITEMS_PER_EXTENSION {
+            return Err(anyhow!(
+                "extension {extension_id} can't show more than {MAX_STATUS_ITEMS_PER_EXTENSION} status items"
+            ));
+        }
+
+        self.push_change(PendingChange::Register(item), cx);
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, extension_id: &str, id: &str, cx: &mut Context<Self>) {
+        self.push_change(
+            PendingChange::Unregister {
+                extension_id: extension_id.into(),
+                id: id.to_string(),
+            },
+            cx,
+        );
+    }
+
+    /// Removes all items of the given extension, for example when it's unloaded.
+    pub fn unregister_extension(&mut self, extension_id: &str, cx: &mut Context<Self>) {
+        self.push_change(
+            PendingChange::UnregisterExtension {
+                extension_id: extension_id.into(),
+            },
+            cx,
+        );
+    }
+
+    /// The ids of the given extension's items that are currently shown.
+    pub fn item_ids(&self, extension_id: &str) -> Vec<&str> {
+        self.items
+            .iter()
+            .filter(|item| &*item.extension_id == extension_id)
+            .map(|item| item.id.as_str())
+            .collect()
+    }
+
+    pub fn item_text(&self, extension_id: &str, id: &str) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|item| &*item.extension_id == extension_id && item.id == id)
+            .map(|item| item.text.as_ref())
+    }
+
+    fn validate(
+        extension_id: Arc<str>,
+        description: ExtensionStatusItemDescription,
+        cx: &App,
+    ) -> Result<ExtensionStatusItem> {
+        if description.id.is_empty() {
+            return Err(anyhow!("extension status items need an id"));
+        }
+        let icon = description
+            .icon
+            .map(|icon| IconName::from_str(&icon).map_err(|_| anyhow!("unknown icon {icon:?}")))
+            .transpose()?;
+        if let Some(action) = &description.click_action {
+            if !EXTENSION_STATUS_ITEM_ACTIONS.contains(&action.as_str()) {
+                return Err(anyhow!(
+                    "extension status items can't dispatch the action {action:?}"
+                ));
+            }
+            cx.build_action(action, None)
+                .map_err(|_| anyhow!("unknown action {action:?}"))?;
+        }
+
+        let mut text = description.text;
+        if let Some((ix, _)) = text.char_indices().nth(MAX_TEXT_LEN) {
+            text.truncate(ix);
+            text.push('…');
+        }
+        Ok(ExtensionStatusItem {
+            extension_id,
+            id: description.id,
+            text: text.into(),
+            icon,
+            tooltip: description.tooltip.map(Into::into),
+            click_action: description.click_action,
+        })
+    }
+
+    fn item_ids_after_pending_changes(&self, extension_id: &str) -> Vec<&str> {
+        let mut ids = self.item_ids(extension_id);
+        for change in &self.pending_changes {
+            match change {
+                PendingChange::Register(item) if &*item.extension_id == extension_id => {
+                    if !ids.contains(&item.id.as_str()) {
+                        ids.push(&item.id);
+                    }
+                }
+                PendingChange::Unregister {
+                    extension_id: id_of_extension,
+                    id,
+                } if &**id_of_extension == extension_id => {
+                    ids.retain(|item_id| *item_id != id.as_str())
+                }
+                PendingChange::UnregisterExtension {
+                    extension_id: id_of_extension,
+                } if &**id_of_extension == extension_id => ids.clear(),
+                _ => {}
+            }
+        }
+        ids
+    }
+
+    /// Queues the given change for the next batch, dropping the queued changes it supersedes.
+    fn push_change(&mut self, change: PendingChange, cx: &mut Context<Self>) {
+        match &change {
+            PendingChange::Register(ExtensionStatusItem {
+                extension_id, id, ..
+            })
+            | PendingChange::Unregister { extension_id, id } => {
+                self.pending_changes
+                    .retain(|pending| !pending.changes_item(extension_id, id));
+            }
+            PendingChange::UnregisterExtension { extension_id } => {
+                self.pending_changes
+                    .retain(|pending| pending.extension_id() != extension_id);
+            }
+        }
+        if let PendingChange::Unregister { extension_id, id } = &change {
+            let is_shown = self
+                .items
+                .iter()
+                .any(|item| item.extension_id == *extension_id && item.id == *id);
+            if !is_shown {
+                return;
+            }
+        }
+        self.pending_changes.push(change);
+        if self.apply_pending_changes.is_none() {
+            self.apply_pending_changes = Some(cx.spawn(async move |this, cx| {
+                cx.background_executor()
+                    .timer(EXTENSION_STATUS_ITEM_UPDATE_INTERVAL)
+                    .await;
+                this.update(cx, |this, cx| this.apply_pending_changes(cx))
+                    .ok();
+            }));
+        }
+    }
+
+    fn apply_pending_changes(&mut self, cx: &mut Context<Self>) {
+        self.apply_pending_changes = None;
+        for change in self.pending_changes.drain(..) {
+            match change {
+                PendingChange::Register(item) => {
+                    if let Some(existing) = self.items.iter_mut().find(|existing| {
+                        existing.extension_id == item.extension_id && existing.id == item.id
+                    }) {
+                        *existing = item;
+                    } else {
+                        self.items.push(item);
+                    }
+                }
+                PendingChange::Unregister { extension_id, id } => self
+                    .items
+                    .retain(|item| item.extension_id != extension_id || item.id != id),
+                PendingChange::UnregisterExtension { extension_id } => {
+                    self.items.retain(|item| item.extension_id != extension_id)
+                }
+            }
+        }
+        cx.notify();
+    }
+}
+
+impl Default for ExtensionStatusItems {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for ExtensionStatusItems {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .gap(DynamicSpacing::Base04.rems(cx))
+            // Without items, don't take up a slot in the status bar.
+            .when(self.items.is_empty(), |this| this.hidden())
+            .children(self.items.iter().enumerate().map(|(ix, item)| {
+                let click_action = item.click_action.clone();
+                Button::new(("extension-status-item", ix), item.text.clone())
+                    .label_size(LabelSize::Small)
+                    .when_some(item.icon, |this, icon| {
+                        this.icon(icon)
+                            .icon_size(IconSize::Small)
+                            .icon_position(IconPosition::Start)
+                    })
+                    .when_some(item.tooltip.clone(), |this, tooltip| {
+                        this.tooltip(Tooltip::text(tooltip))
+                    })
+                    .when_some(click_action, |this, action| {
+                        this.on_click(move |_, window, cx| {
+                            if let Some(action) = cx.build_action(&action, None).log_err() {
+                                window.dispatch_action(action, cx);
+                            }
+                        })
+                    })
+            }))
+    }
+}
+
+impl StatusItemView for ExtensionStatusItems {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    fn is_visible(&self, _cx: &App) -> bool {
+        !self.items.is_empty()
+    }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("extension_items")
+    }
+}
+
+impl Workspace {
+    pub fn extension_status_items(&self) -> &Entity<ExtensionStatusItems> {
+        &self.extension_status_items
+    }
+
+    /// Registers a status item of the given extension from its JSON description. See
+    /// [`ExtensionStatusItemDescription`] for the accepted fields.
+    pub fn register_extension_status_item(
+        &self,
+        extension_id: &str,
+        description: &str,
+        cx: &mut App,
+    ) -> Result<()> {
+        self.extension_status_items.update(cx, |items, cx| {
+            items.register(extension_id, description, cx)
+        })
+    }
+
+    pub fn unregister_extension_status_item(&self, extension_id: &str, id: &str, cx: &mut App) {
+        self.extension_status_items
+            .update(cx, |items, cx| items.unregister(extension_id, id, cx));
+    }
+}
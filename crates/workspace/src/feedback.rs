@@ -0,0 +1,43 @@
+use crate::{dock::DockPosition, notifications::NotificationId};
+use gpui::{App, Global};
+use std::sync::Arc;
+
+/// Hooks called when the workspace layout changes in a way the user may want to hear or feel,
+/// such as a dock opening.
+///
+/// The workspace doesn't give any feedback on its own, and all hooks do nothing by default;
+/// the application can install an implementation with [`set_workspace_feedback`] to play
+/// sounds or trigger the OS's haptic feedback.
+pub trait WorkspaceFeedback: 'static {
+    /// A dock was opened or closed.
+    fn dock_toggled(&self, _position: DockPosition, _is_open: bool, _cx: &mut App) {}
+
+    /// A pane or a panel was zoomed in or out.
+    fn zoom_changed(&self, _zoomed: bool, _cx: &mut App) {}
+
+    /// A notification was shown, or replaced a notification with the same id.
+    fn notification_shown(&self, _id: &NotificationId, _cx: &mut App) {}
+
+    /// A notification was dismissed.
+    fn notification_dismissed(&self, _id: &NotificationId, _cx: &mut App) {}
+}
+
+struct GlobalWorkspaceFeedback(Arc<dyn WorkspaceFeedback>);
+
+impl Global for GlobalWorkspaceFeedback {}
+
+/// Installs the feedback hooks of all workspaces, replacing any previous ones.
+pub fn set_workspace_feedback(feedback: Arc<dyn WorkspaceFeedback>, cx: &mut App) {
+    cx.set_global(GlobalWorkspaceFeedback(feedback));
+}
+
+/// Calls `f` with the installed feedback hooks, if there are any.
+pub(crate) fn give_feedback(cx: &mut App, f: impl FnOnce(&dyn WorkspaceFeedback, &mut App)) {
+    let Some(feedback) = cx
+        .try_global::<GlobalWorkspaceFeedback>()
+        .map(|feedback| feedback.0.clone())
+    else {
+        return;
+    };
+    f(feedback.as_ref(), cx);
+}
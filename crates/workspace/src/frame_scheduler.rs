@@ -0,0 +1,134 @@
+use crate::Workspace;
+use gpui::{Context, Window};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// How long deferred updates may run within a single frame before the remaining
+/// ones are pushed to the next frame.
+pub const DEFAULT_FRAME_BUDGET: Duration = Duration::from_millis(2);
+
+type DeferredUpdate = Box<dyn FnOnce(&mut Workspace, &mut Window, &mut Context<Workspace>)>;
+
+/// A queue of non-urgent UI updates that are processed within a per-frame time budget.
+///
+/// Panels that want to refresh after a project event (git status, diagnostics, collaboration
+/// state) can enqueue their work via [`Workspace::defer_in_window`] instead of running it
+/// immediately, so that several expensive refreshes don't all land in the same frame.
+pub(crate) struct FrameScheduler {
+    queue: VecDeque<DeferredUpdate>,
+    budget: Duration,
+    frame_scheduled: bool,
+}
+
+impl FrameScheduler {
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            budget,
+            frame_scheduled: false,
+        }
+    }
+
+    pub fn pending_updates(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+impl Default for FrameScheduler {
+    fn default() -> Self {
+        Self::new(DEFAULT_FRAME_BUDGET)
+    }
+}
+
+impl Workspace {
+    /// Schedules the given update to run on an upcoming frame, within the workspace's frame budget.
+    ///
+    /// Updates run in the order they were enqueued. At least one update runs per frame; the rest
+    /// are carried over to the following frames once the budget has been spent.
+    pub fn defer_in_window(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        update: impl FnOnce(&mut Self, &mut Window, &mut Context<Self>) + 'static,
+    ) {
+        self.frame_scheduler.queue.push_back(Box::new(update));
+        self.schedule_deferred_updates(window, cx);
+    }
+
+    /// Sets how long deferred updates may run within a single frame.
+    pub fn set_frame_budget(&mut self, budget: Duration) {
+        self.frame_scheduler.budget = budget;
+    }
+
+    pub fn pending_deferred_updates(&self) -> usize {
+        self.frame_scheduler.pending_updates()
+    }
+
+    fn schedule_deferred_updates(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.frame_scheduler.frame_scheduled || self.frame_scheduler.queue.is_empty() {
+            return;
+        }
+        self.frame_scheduler.frame_scheduled = true;
+        cx.on_next_frame(window, |this, window, cx| {
+            this.frame_scheduler.frame_scheduled = false;
+            this.process_deferred_updates(window, cx);
+        });
+    }
+
+    fn process_deferred_updates(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let started_at = Instant::now();
+        while let Some(update) = self.frame_scheduler.queue.pop_front() {
+            update(self, window, cx);
+            if started_at.elapsed() >= self.frame_scheduler.budget {
+                break;
+            }
+        }
+        self.schedule_deferred_updates(window, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::init_test;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[gpui::test]
+    async fn test_deferred_updates_respect_frame_budget(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let ran = Rc::new(RefCell::new(Vec::new()));
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.set_frame_budget(Duration::ZERO);
+            for ix in 0..3 {
+                let ran = ran.clone();
+                workspace.defer_in_window(window, cx, move |_, _, _| ran.borrow_mut().push(ix));
+            }
+            assert_eq!(workspace.pending_deferred_updates(), 3);
+        });
+        assert!(ran.borrow().is_empty());
+
+        // Test windows don't present frames, so each call below stands in for one. Once the
+        // budget is spent, the remaining updates wait for the next frame.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.process_deferred_updates(window, cx);
+            assert_eq!(workspace.pending_deferred_updates(), 2);
+        });
+        assert_eq!(*ran.borrow(), [0]);
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.set_frame_budget(DEFAULT_FRAME_BUDGET * 1000);
+            workspace.process_deferred_updates(window, cx);
+            assert_eq!(workspace.pending_deferred_updates(), 0);
+        });
+        assert_eq!(*ran.borrow(), [0, 1, 2]);
+    }
+}
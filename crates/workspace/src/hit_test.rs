@@ -0,0 +1,106 @@
+//! Finding the part of the workspace's layout at a point in the window, e.g. to decide what a
+//! drop or a right-click applies to, or to check what's under a simulated cursor in tests.
+//!
+//! Regions are looked up where they were last painted, so the result reflects the last frame.
+//! Overlays such as modals, notifications and zoomed items aren't taken into account.
+
+use crate::{dock::Dock, item::ItemHandle, Pane, Workspace};
+use gpui::{AnyView, App, Axis, Entity, Pixels, Point};
+
+/// A part of the workspace's layout, see [`Workspace::element_at`].
+#[derive(Clone)]
+pub enum WorkspaceRegion {
+    /// The tab of an item in a pane's tab bar.
+    Tab {
+        pane: Entity<Pane>,
+        item: Box<dyn ItemHandle>,
+    },
+    /// Any other part of a pane, such as its content or the empty end of its tab bar.
+    Pane(Entity<Pane>),
+    /// The handle between panes of the center that resizes them along `axis`.
+    PaneDivider {
+        axis: Axis,
+    },
+    /// The line between an open dock and the center, which resizes the dock.
+    DockDivider(Entity<Dock>),
+    /// A part of an open dock outside of its panel's pane, or in a panel without one.
+    Dock(Entity<Dock>),
+    StatusBarItem(AnyView),
+    /// Nothing of the above, such as the title bar or an empty part of the status bar.
+    None,
+}
+
+impl Workspace {
+    /// The part of the layout at the given position in the window, as of the last frame.
+    pub fn element_at(&self, position: Point<Pixels>, cx: &App) -> WorkspaceRegion {
+        if !self.zen_mode {
+            for status_bar in [&self.status_bar, &self.secondary_status_bar] {
+                if let Some(item) = status_bar.read(cx).item_at(position) {
+                    return WorkspaceRegion::StatusBarItem(item);
+                }
+            }
+        }
+
+        // A zoomed dock isn't rendered, so its bounds are from before it was zoomed.
+        let open_docks = self
+            .all_docks()
+            .into_iter()
+            .filter(|dock| {
+                let dock = dock.read(cx);
+                dock.bounds().is_some() && self.zoomed_position != Some(dock.position())
+            })
+            .collect::<Vec<_>>();
+
+        // Resize handles overlap what they resize, and are on top of it for the mouse too.
+        for dock in &open_docks {
+            let divider_bounds = dock.read(cx).divider_bounds(cx);
+            if divider_bounds.map_or(false, |bounds| bounds.contains(&position)) {
+                return WorkspaceRegion::DockDivider((*dock).clone());
+            }
+        }
+        let maximized_pane = self.maximized_pane();
+        if maximized_pane.is_none() {
+            if let Some(axis) = self.center.divider_at_pixel_position(position) {
+                return WorkspaceRegion::PaneDivider { axis };
+            }
+        }
+
+        for dock in &open_docks {
+            let dock_bounds = dock.read(cx).bounds();
+            if !dock_bounds.map_or(false, |bounds| bounds.contains(&position)) {
+                continue;
+            }
+            let pane = dock
+                .read(cx)
+                .visible_panel()
+                .and_then(|panel| panel.pane(cx))
+                .filter(|pane| pane.read(cx).bounds().contains(&position));
+            return match pane {
+                Some(pane) => pane_region(pane, position, cx),
+                None => WorkspaceRegion::Dock((*dock).clone()),
+            };
+        }
+
+        let center_panes = match maximized_pane {
+            Some(pane) => vec![pane],
+            None => self.center.panes().into_iter().cloned().collect(),
+        };
+        center_panes
+            .into_iter()
+            .find(|pane| pane.read(cx).bounds().contains(&position))
+            .map_or(WorkspaceRegion::None, |pane| {
+                pane_region(pane, position, cx)
+            })
+    }
+}
+
+fn pane_region(pane: Entity<Pane>, position: Point<Pixels>, cx: &App) -> WorkspaceRegion {
+    let item = pane
+        .read(cx)
+        .item_for_tab_at(position)
+        .map(|item| item.boxed_clone());
+    match item {
+        Some(item) => WorkspaceRegion::Tab { pane, item },
+        None => WorkspaceRegion::Pane(pane),
+    }
+}
@@ -11,6 +11,7 @@ use client::{
     proto::{self, PeerId},
     Client,
 };
+use fs::MTime;
 use futures::{channel::mpsc, StreamExt};
 use gpui::{
     Action, AnyElement, AnyView, App, Context, Entity, EntityId, EventEmitter, FocusHandle,
@@ -30,7 +31,7 @@ use std::{
     time::Duration,
 };
 use theme::Theme;
-use ui::{Color, Element as _, Icon, IntoElement, Label, LabelCommon};
+use ui::{Color, Element as _, Icon, IconName, IntoElement, Label, LabelCommon};
 use util::ResultExt;
 
 pub const LEADER_UPDATE_THROTTLE: Duration = Duration::from_millis(200);
@@ -122,7 +123,7 @@ pub struct PreviewTabsSettingsContent {
     /// Preview tabs do not stay open, are reused until explicitly set to be kept open opened (via double-click or editing) and show file names in italic.
     ///
     /// Default: true
-    enabled: Option<bool>,
+    pub enabled: Option<bool>,
     /// Whether to open tabs in preview mode when selected from the file finder.
     ///
     /// Default: false
@@ -173,12 +174,16 @@ pub struct TabContentParams {
     pub detail: Option<usize>,
     pub selected: bool,
     pub preview: bool,
+    /// The label color contributed by an [`ItemDecorationProvider`](crate::item_decorations::ItemDecorationProvider).
+    pub label_color: Option<Color>,
 }
 
 impl TabContentParams {
     /// Returns the text color to be used for the tab content.
     pub fn text_color(&self) -> Color {
-        if self.selected {
+        if let Some(label_color) = self.label_color {
+            label_color
+        } else if self.selected {
             Color::Default
         } else {
             Color::Muted
@@ -191,6 +196,14 @@ pub enum TabTooltipContent {
     Custom(Box<dyn Fn(&mut Window, &mut App) -> AnyView>),
 }
 
+/// A marker shown at the start of an item's tab, such as for a read-only file. It's shown in
+/// place of the dot marking unsaved changes and conflicts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TabDecoration {
+    Dot(Color),
+    Icon(IconName, Color),
+}
+
 pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
     type Event;
 
@@ -238,6 +251,15 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
         None
     }
 
+    /// Returns the marker shown at the start of the tab, replacing the default one for unsaved
+    /// changes and conflicts.
+    ///
+    /// By default this is a lock for items that are [read-only](Item::is_read_only).
+    fn tab_decoration(&self, cx: &App) -> Option<TabDecoration> {
+        self.is_read_only(cx)
+            .then_some(TabDecoration::Icon(IconName::FileLock, Color::Muted))
+    }
+
     fn to_item_events(_event: &Self::Event, _f: impl FnMut(ItemEvent)) {}
 
     fn deactivated(&mut self, _window: &mut Window, _: &mut Context<Self>) {}
@@ -288,6 +310,11 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
     fn can_save_as(&self, _: &App) -> bool {
         false
     }
+    /// Whether the item is saved automatically according to the `autosave` setting. Items
+    /// that should only be saved explicitly can opt out.
+    fn can_autosave(&self, _: &App) -> bool {
+        true
+    }
     fn save(
         &mut self,
         _format: bool,
@@ -314,6 +341,34 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
     ) -> Task<Result<()>> {
         unimplemented!("reload() must be implemented if can_save() returns true")
     }
+    /// Whether the workspace should [`reload`](Self::reload) the item when its file changes
+    /// on disk. Items that already follow their file, like editors through their buffer,
+    /// leave this off.
+    fn reload_on_external_change(&self, _: &App) -> bool {
+        false
+    }
+    /// The modification time of the item's file as of the item's last save or reload. A
+    /// change on disk with this modification time was made by the item itself, so it doesn't
+    /// reload the item.
+    fn saved_mtime(&self, _: &App) -> Option<MTime> {
+        None
+    }
+    /// The banner shown above the item when its file changed on disk while it had unsaved
+    /// changes. Returning `None` shows the pane's default banner, which offers to reload.
+    fn conflict_banner(&self, _window: &Window, _cx: &App) -> Option<AnyElement> {
+        None
+    }
+    /// Whether the item can't be edited, which is shown with a banner above it and, unless
+    /// [`Item::tab_decoration`] is overridden, a lock on its tab.
+    fn is_read_only(&self, _: &App) -> bool {
+        false
+    }
+    /// Whether the user can switch the item in and out of read-only mode, see
+    /// [`ToggleReadOnly`](crate::pane::ToggleReadOnly).
+    fn can_toggle_read_only(&self, _: &App) -> bool {
+        false
+    }
+    fn set_read_only(&mut self, _read_only: bool, _window: &mut Window, _cx: &mut Context<Self>) {}
 
     fn act_as_type<'a>(
         &'a self,
@@ -340,6 +395,10 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
         None
     }
 
+    /// Navigates to the breadcrumb at the given index of [`Self::breadcrumbs`] after it
+    /// was clicked.
+    fn activate_breadcrumb(&mut self, _ix: usize, _window: &mut Window, _cx: &mut Context<Self>) {}
+
     fn added_to_workspace(
         &mut self,
         _workspace: &mut Workspace,
@@ -352,6 +411,12 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
         true
     }
 
+    /// Views the item adds to the pane's toolbar, shown only while the item is active, such
+    /// as zoom buttons for an image.
+    fn toolbar_items(&self, _window: &Window, _cx: &App) -> Vec<(AnyView, ToolbarItemLocation)> {
+        Vec::new()
+    }
+
     fn pixel_position_of_cursor(&self, _: &App) -> Option<Point<Pixels>> {
         None
     }
@@ -445,9 +510,11 @@ pub trait ItemHandle: 'static + Send {
     ) -> gpui::Subscription;
     fn tab_description(&self, detail: usize, cx: &App) -> Option<SharedString>;
     fn tab_content(&self, params: TabContentParams, window: &Window, cx: &App) -> AnyElement;
+    fn tab_content_text(&self, window: &Window, cx: &App) -> Option<SharedString>;
     fn tab_icon(&self, window: &Window, cx: &App) -> Option<Icon>;
     fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString>;
     fn tab_tooltip_content(&self, cx: &App) -> Option<TabTooltipContent>;
+    fn tab_decoration(&self, cx: &App) -> Option<TabDecoration>;
     fn telemetry_event_text(&self, cx: &App) -> Option<&'static str>;
     fn dragged_tab_content(
         &self,
@@ -490,6 +557,7 @@ pub trait ItemHandle: 'static + Send {
     fn has_conflict(&self, cx: &App) -> bool;
     fn can_save(&self, cx: &App) -> bool;
     fn can_save_as(&self, cx: &App) -> bool;
+    fn can_autosave(&self, cx: &App) -> bool;
     fn save(
         &self,
         format: bool,
@@ -510,6 +578,12 @@ pub trait ItemHandle: 'static + Send {
         window: &mut Window,
         cx: &mut App,
     ) -> Task<Result<()>>;
+    fn reload_on_external_change(&self, cx: &App) -> bool;
+    fn saved_mtime(&self, cx: &App) -> Option<MTime>;
+    fn conflict_banner(&self, window: &Window, cx: &App) -> Option<AnyElement>;
+    fn is_read_only(&self, cx: &App) -> bool;
+    fn can_toggle_read_only(&self, cx: &App) -> bool;
+    fn set_read_only(&self, read_only: bool, window: &mut Window, cx: &mut App);
     fn act_as_type(&self, type_id: TypeId, cx: &App) -> Option<AnyView>;
     fn to_followable_item_handle(&self, cx: &App) -> Option<Box<dyn FollowableItemHandle>>;
     fn to_serializable_item_handle(&self, cx: &App) -> Option<Box<dyn SerializableItemHandle>>;
@@ -518,10 +592,13 @@ pub trait ItemHandle: 'static + Send {
         cx: &mut App,
         callback: Box<dyn FnOnce(&mut App) + Send>,
     ) -> gpui::Subscription;
+    fn on_notify(&self, cx: &mut App, callback: Box<dyn FnMut(&mut App)>) -> gpui::Subscription;
     fn to_searchable_item_handle(&self, cx: &App) -> Option<Box<dyn SearchableItemHandle>>;
     fn breadcrumb_location(&self, cx: &App) -> ToolbarItemLocation;
     fn breadcrumbs(&self, theme: &Theme, cx: &App) -> Option<Vec<BreadcrumbText>>;
+    fn activate_breadcrumb(&self, ix: usize, window: &mut Window, cx: &mut App);
     fn show_toolbar(&self, cx: &App) -> bool;
+    fn toolbar_items(&self, window: &Window, cx: &App) -> Vec<(AnyView, ToolbarItemLocation)>;
     fn pixel_position_of_cursor(&self, cx: &App) -> Option<Point<Pixels>>;
     fn downgrade_item(&self) -> Box<dyn WeakItemHandle>;
     fn workspace_settings<'a>(&self, cx: &'a App) -> &'a WorkspaceSettings;
@@ -571,6 +648,10 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.read(cx).tab_description(detail, cx)
     }
 
+    fn tab_content_text(&self, window: &Window, cx: &App) -> Option<SharedString> {
+        self.read(cx).tab_content_text(window, cx)
+    }
+
     fn tab_content(&self, params: TabContentParams, window: &Window, cx: &App) -> AnyElement {
         self.read(cx).tab_content(params, window, cx)
     }
@@ -587,6 +668,10 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.read(cx).tab_tooltip_text(cx)
     }
 
+    fn tab_decoration(&self, cx: &App) -> Option<TabDecoration> {
+        self.read(cx).tab_decoration(cx)
+    }
+
     fn dragged_tab_content(
         &self,
         params: TabContentParams,
@@ -803,7 +888,8 @@ impl<T: Item> ItemHandle for Entity<T> {
 
                         ItemEvent::UpdateTab => {
                             workspace.update_item_dirty_state(item, window, cx);
-                            pane.update(cx, |_, cx| {
+                            pane.update(cx, |pane, cx| {
+                                pane.invalidate_tab_details();
                                 cx.emit(pane::Event::ChangeItemTitle);
                                 cx.notify();
                             });
@@ -910,6 +996,10 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.read(cx).can_save_as(cx)
     }
 
+    fn can_autosave(&self, cx: &App) -> bool {
+        self.read(cx).can_autosave(cx)
+    }
+
     fn save(
         &self,
         format: bool,
@@ -939,6 +1029,30 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.update(cx, |item, cx| item.reload(project, window, cx))
     }
 
+    fn reload_on_external_change(&self, cx: &App) -> bool {
+        self.read(cx).reload_on_external_change(cx)
+    }
+
+    fn saved_mtime(&self, cx: &App) -> Option<MTime> {
+        self.read(cx).saved_mtime(cx)
+    }
+
+    fn conflict_banner(&self, window: &Window, cx: &App) -> Option<AnyElement> {
+        self.read(cx).conflict_banner(window, cx)
+    }
+
+    fn is_read_only(&self, cx: &App) -> bool {
+        self.read(cx).is_read_only(cx)
+    }
+
+    fn can_toggle_read_only(&self, cx: &App) -> bool {
+        self.read(cx).can_toggle_read_only(cx)
+    }
+
+    fn set_read_only(&self, read_only: bool, window: &mut Window, cx: &mut App) {
+        self.update(cx, |item, cx| item.set_read_only(read_only, window, cx))
+    }
+
     fn act_as_type<'a>(&'a self, type_id: TypeId, cx: &'a App) -> Option<AnyView> {
         self.read(cx).act_as_type(type_id, self, cx)
     }
@@ -955,6 +1069,14 @@ impl<T: Item> ItemHandle for Entity<T> {
         cx.observe_release(self, move |_, cx| callback(cx))
     }
 
+    fn on_notify(
+        &self,
+        cx: &mut App,
+        mut callback: Box<dyn FnMut(&mut App)>,
+    ) -> gpui::Subscription {
+        cx.observe(self, move |_, cx| callback(cx))
+    }
+
     fn to_searchable_item_handle(&self, cx: &App) -> Option<Box<dyn SearchableItemHandle>> {
         self.read(cx).as_searchable(self)
     }
@@ -967,10 +1089,18 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.read(cx).breadcrumbs(theme, cx)
     }
 
+    fn activate_breadcrumb(&self, ix: usize, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| this.activate_breadcrumb(ix, window, cx))
+    }
+
     fn show_toolbar(&self, cx: &App) -> bool {
         self.read(cx).show_toolbar()
     }
 
+    fn toolbar_items(&self, window: &Window, cx: &App) -> Vec<(AnyView, ToolbarItemLocation)> {
+        self.read(cx).toolbar_items(window, cx)
+    }
+
     fn pixel_position_of_cursor(&self, cx: &App) -> Option<Point<Pixels>> {
         self.read(cx).pixel_position_of_cursor(cx)
     }
@@ -1206,13 +1336,15 @@ impl<T: FollowableItem> WeakFollowableItemHandle for WeakEntity<T> {
 #[cfg(any(test, feature = "test-support"))]
 pub mod test {
     use super::{Item, ItemEvent, SerializableItem, TabContentParams};
-    use crate::{ItemId, ItemNavHistory, Workspace, WorkspaceId};
+    use crate::{ItemId, ItemNavHistory, ToolbarItemLocation, Workspace, WorkspaceId};
+    use fs::MTime;
     use gpui::{
-        AnyElement, App, AppContext as _, Context, Entity, EntityId, EventEmitter, Focusable,
-        InteractiveElement, IntoElement, Render, SharedString, Task, WeakEntity, Window,
+        AnyElement, AnyView, App, AppContext as _, Context, Entity, EntityId, EventEmitter,
+        Focusable, InteractiveElement, IntoElement, Render, SharedString, Task, WeakEntity, Window,
     };
     use project::{Project, ProjectEntryId, ProjectPath, WorktreeId};
     use std::{any::Any, cell::Cell, path::Path};
+    use ui::{Icon, IconName};
 
     pub struct TestProjectItem {
         pub entry_id: Option<ProjectEntryId>,
@@ -1230,10 +1362,17 @@ pub mod test {
         pub is_dirty: bool,
         pub is_singleton: bool,
         pub has_conflict: bool,
+        pub can_autosave: bool,
+        pub reload_on_external_change: bool,
+        pub saved_mtime: Option<MTime>,
+        /// Whether the item is read-only, or `None` if it doesn't support read-only mode.
+        pub read_only: Option<bool>,
+        pub toolbar_items: Vec<(AnyView, ToolbarItemLocation)>,
         pub project_items: Vec<Entity<TestProjectItem>>,
         pub nav_history: Option<ItemNavHistory>,
         pub tab_descriptions: Option<Vec<&'static str>>,
         pub tab_detail: Cell<Option<usize>>,
+        pub tab_icon: Option<IconName>,
         serialize: Option<Box<dyn Fn() -> Option<Task<anyhow::Result<()>>>>>,
         focus_handle: gpui::FocusHandle,
     }
@@ -1309,11 +1448,17 @@ pub mod test {
                 reload_count: 0,
                 is_dirty: false,
                 has_conflict: false,
+                can_autosave: true,
+                reload_on_external_change: false,
+                saved_mtime: None,
+                read_only: None,
+                toolbar_items: Vec::new(),
                 project_items: Vec::new(),
                 is_singleton: true,
                 nav_history: None,
                 tab_descriptions: None,
                 tab_detail: Default::default(),
+                tab_icon: None,
                 workspace_id: Default::default(),
                 focus_handle: cx.focus_handle(),
                 serialize: None,
@@ -1346,6 +1491,26 @@ pub mod test {
             self
         }
 
+        pub fn with_autosave(mut self, can_autosave: bool) -> Self {
+            self.can_autosave = can_autosave;
+            self
+        }
+
+        pub fn with_reload_on_external_change(mut self, reload: bool) -> Self {
+            self.reload_on_external_change = reload;
+            self
+        }
+
+        pub fn with_read_only(mut self, read_only: bool) -> Self {
+            self.read_only = Some(read_only);
+            self
+        }
+
+        pub fn with_tab_icon(mut self, icon: IconName) -> Self {
+            self.tab_icon = Some(icon);
+            self
+        }
+
         pub fn with_project_items(mut self, items: &[Entity<TestProjectItem>]) -> Self {
             self.project_items.clear();
             self.project_items.extend(items.iter().cloned());
@@ -1409,6 +1574,14 @@ pub mod test {
             gpui::div().into_any_element()
         }
 
+        fn tab_content_text(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+            Some(self.label.clone().into())
+        }
+
+        fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+            self.tab_icon.map(Icon::new)
+        }
+
         fn for_each_project_item(
             &self,
             cx: &App,
@@ -1469,10 +1642,16 @@ pub mod test {
                 is_dirty: self.is_dirty,
                 is_singleton: self.is_singleton,
                 has_conflict: self.has_conflict,
+                can_autosave: self.can_autosave,
+                reload_on_external_change: self.reload_on_external_change,
+                saved_mtime: self.saved_mtime,
+                read_only: self.read_only,
+                toolbar_items: self.toolbar_items.clone(),
                 project_items: self.project_items.clone(),
                 nav_history: None,
                 tab_descriptions: None,
                 tab_detail: Default::default(),
+                tab_icon: self.tab_icon,
                 workspace_id: self.workspace_id,
                 focus_handle: cx.focus_handle(),
                 serialize: None,
@@ -1487,6 +1666,39 @@ pub mod test {
             self.has_conflict
         }
 
+        fn can_autosave(&self, _: &App) -> bool {
+            self.can_autosave
+        }
+
+        fn reload_on_external_change(&self, _: &App) -> bool {
+            self.reload_on_external_change
+        }
+
+        fn saved_mtime(&self, _: &App) -> Option<MTime> {
+            self.saved_mtime
+        }
+
+        fn is_read_only(&self, _: &App) -> bool {
+            self.read_only == Some(true)
+        }
+
+        fn toolbar_items(
+            &self,
+            _window: &Window,
+            _cx: &App,
+        ) -> Vec<(AnyView, ToolbarItemLocation)> {
+            self.toolbar_items.clone()
+        }
+
+        fn can_toggle_read_only(&self, _: &App) -> bool {
+            self.read_only.is_some()
+        }
+
+        fn set_read_only(&mut self, read_only: bool, _: &mut Window, cx: &mut Context<Self>) {
+            self.read_only = Some(read_only);
+            cx.notify();
+        }
+
         fn can_save(&self, cx: &App) -> bool {
             !self.project_items.is_empty()
                 && self
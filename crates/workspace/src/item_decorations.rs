@@ -0,0 +1,101 @@
+//! Colors and badges contributed to the tabs of items by other crates, such as the git status
+//! of a file or the diagnostics in it.
+//!
+//! Providers are registered with [`ItemDecorations`], and tell it when the decorations of some
+//! files changed. Only the panes showing one of those files are re-rendered.
+
+use crate::{item::TabDecoration, ItemHandle};
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Global};
+use project::ProjectPath;
+use ui::Color;
+
+/// How a provider decorates an item's tab.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ItemDecoration {
+    /// The color of the tab's label.
+    pub label_color: Option<Color>,
+    /// The marker at the start of the tab, shown unless the item has unsaved changes or
+    /// conflicts.
+    pub badge: Option<TabDecoration>,
+}
+
+pub trait ItemDecorationProvider: 'static {
+    fn decoration(&self, item: &dyn ItemHandle, cx: &App) -> Option<ItemDecoration>;
+}
+
+/// Emitted when the decorations of some items may have changed.
+#[derive(Clone, Debug)]
+pub struct ItemDecorationsChanged {
+    /// The files whose decorations changed, or `None` if any item's may have.
+    pub project_paths: Option<Vec<ProjectPath>>,
+}
+
+impl ItemDecorationsChanged {
+    /// Whether the decoration of the given item may have changed.
+    pub fn affects(&self, item: &dyn ItemHandle, cx: &App) -> bool {
+        match &self.project_paths {
+            Some(project_paths) => item
+                .project_path(cx)
+                .map_or(false, |project_path| project_paths.contains(&project_path)),
+            None => true,
+        }
+    }
+}
+
+/// The registered item decoration providers.
+#[derive(Default)]
+pub struct ItemDecorations {
+    providers: Vec<Box<dyn ItemDecorationProvider>>,
+}
+
+struct GlobalItemDecorations(Entity<ItemDecorations>);
+
+impl Global for GlobalItemDecorations {}
+
+impl EventEmitter<ItemDecorationsChanged> for ItemDecorations {}
+
+impl ItemDecorations {
+    pub fn global(cx: &mut App) -> Entity<Self> {
+        if let Some(global) = cx.try_global::<GlobalItemDecorations>() {
+            return global.0.clone();
+        }
+        let item_decorations = cx.new(|_| Self::default());
+        cx.set_global(GlobalItemDecorations(item_decorations.clone()));
+        item_decorations
+    }
+
+    pub fn try_global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalItemDecorations>()
+            .map(|global| global.0.clone())
+    }
+
+    pub fn register(&mut self, provider: impl ItemDecorationProvider, cx: &mut Context<Self>) {
+        self.providers.push(Box::new(provider));
+        cx.emit(ItemDecorationsChanged {
+            project_paths: None,
+        });
+    }
+
+    /// Re-renders the tabs of the given files, or of all items if `None`.
+    pub fn decorations_changed(
+        &mut self,
+        project_paths: Option<Vec<ProjectPath>>,
+        cx: &mut Context<Self>,
+    ) {
+        cx.emit(ItemDecorationsChanged { project_paths });
+    }
+
+    /// Combines the decorations of all providers. Where several providers set the same part of
+    /// the decoration, the one registered first wins.
+    pub fn decoration(&self, item: &dyn ItemHandle, cx: &App) -> ItemDecoration {
+        let mut decoration = ItemDecoration::default();
+        for provider in &self.providers {
+            let Some(provided) = provider.decoration(item, cx) else {
+                continue;
+            };
+            decoration.label_color = decoration.label_color.or(provided.label_color);
+            decoration.badge = decoration.badge.or(provided.badge);
+        }
+        decoration
+    }
+}
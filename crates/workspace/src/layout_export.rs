@@ -0,0 +1,405 @@
+//! Exporting the arrangement of a workspace to a JSON file and importing it again, so that a
+//! layout can be shared between machines and people.
+//!
+//! A layout file describes the docks, the tree of center panes, and the files open in each
+//! pane. Files are stored relative to their worktree, so the file can be imported into a
+//! checkout of the same project at a different location. Paths that would lead outside of
+//! the worktree are ignored when importing.
+
+use crate::{
+    notifications::DetachAndPromptErr as _,
+    pane_group::Member,
+    persistence::model::{DockData, DockStructure, SerializedLayout},
+    ExportLayout, ImportLayout, Pane, SplitDirection, Workspace,
+};
+use anyhow::{Context as _, Result};
+use gpui::{App, Axis, Context, Entity, Focusable as _, PathPromptOptions, Task, Window};
+use project::ProjectPath;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// The contents of a layout file. Unknown fields are ignored and missing ones are defaulted,
+/// so files written by other versions of Zed can still be imported.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportedLayout {
+    #[serde(default)]
+    pub docks: ExportedDocks,
+    #[serde(default)]
+    pub centered_layout: bool,
+    #[serde(default)]
+    pub center: ExportedMember,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportedDocks {
+    #[serde(default)]
+    pub left: ExportedDock,
+    #[serde(default)]
+    pub right: ExportedDock,
+    #[serde(default)]
+    pub bottom: ExportedDock,
+    #[serde(default)]
+    pub bottom_right: ExportedDock,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportedDock {
+    #[serde(default)]
+    pub visible: bool,
+    /// The persistent name of the dock's active panel.
+    #[serde(default)]
+    pub active_panel: Option<String>,
+    #[serde(default)]
+    pub zoom: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportedMember {
+    Group {
+        axis: Axis,
+        /// The relative sizes of the members, or `None` to size them equally.
+        #[serde(default)]
+        flexes: Option<Vec<f32>>,
+        members: Vec<ExportedMember>,
+    },
+    Pane(ExportedPane),
+}
+
+impl Default for ExportedMember {
+    fn default() -> Self {
+        Self::Pane(ExportedPane::default())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportedPane {
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub items: Vec<ExportedItem>,
+    /// The index of the active item in `items`.
+    #[serde(default)]
+    pub active_item: Option<usize>,
+}
+
+/// A file open in a pane. Items that aren't backed by a file, such as terminals, aren't
+/// exported.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportedItem {
+    /// The root name of the file's worktree. When importing, the file is looked up in the
+    /// worktree with this name, or in the first worktree if there is none.
+    #[serde(default)]
+    pub worktree: Option<String>,
+    pub path: PathBuf,
+}
+
+impl From<&DockData> for ExportedDock {
+    fn from(dock: &DockData) -> Self {
+        Self {
+            visible: dock.visible,
+            active_panel: dock.active_panel.clone(),
+            zoom: dock.zoom,
+        }
+    }
+}
+
+impl From<&ExportedDock> for DockData {
+    fn from(dock: &ExportedDock) -> Self {
+        Self {
+            visible: dock.visible,
+            active_panel: dock.active_panel.clone(),
+            zoom: dock.zoom,
+        }
+    }
+}
+
+impl Workspace {
+    /// Describes the docks, center panes and open files of this workspace.
+    pub fn export_layout(&self, window: &mut Window, cx: &mut App) -> ExportedLayout {
+        let docks = self.build_serialized_docks(window, cx);
+        ExportedLayout {
+            docks: ExportedDocks {
+                left: (&docks.left).into(),
+                right: (&docks.right).into(),
+                bottom: (&docks.bottom).into(),
+                bottom_right: (&docks.bottom_right).into(),
+            },
+            centered_layout: self.centered_layout,
+            center: self.export_member(&self.center.root, cx),
+        }
+    }
+
+    fn export_member(&self, member: &Member, cx: &App) -> ExportedMember {
+        match member {
+            Member::Axis(axis) => ExportedMember::Group {
+                axis: axis.axis,
+                flexes: Some(axis.flexes.lock().clone()),
+                members: axis
+                    .members
+                    .iter()
+                    .map(|member| self.export_member(member, cx))
+                    .collect(),
+            },
+            Member::Pane(pane_handle) => {
+                let pane = pane_handle.read(cx);
+                let active_item_id = pane.active_item().map(|item| item.item_id());
+                let mut items = Vec::new();
+                let mut active_item = None;
+                for item in pane.items() {
+                    let Some(project_path) = item.project_path(cx) else {
+                        continue;
+                    };
+                    if Some(item.item_id()) == active_item_id {
+                        active_item = Some(items.len());
+                    }
+                    let worktree = self
+                        .project
+                        .read(cx)
+                        .worktree_for_id(project_path.worktree_id, cx)
+                        .map(|worktree| worktree.read(cx).root_name().to_string());
+                    items.push(ExportedItem {
+                        worktree,
+                        path: project_path.path.to_path_buf(),
+                    });
+                }
+                ExportedMember::Pane(ExportedPane {
+                    active: *pane_handle == self.active_pane,
+                    items,
+                    active_item,
+                })
+            }
+        }
+    }
+
+    /// Rearranges this workspace into the given layout and opens its files. The items that
+    /// are already open are kept in the first pane.
+    pub fn import_layout(
+        &mut self,
+        layout: ExportedLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.apply_layout(
+            &SerializedLayout {
+                name: String::new(),
+                docks: DockStructure {
+                    left: (&layout.docks.left).into(),
+                    right: (&layout.docks.right).into(),
+                    bottom: (&layout.docks.bottom).into(),
+                    bottom_right: (&layout.docks.bottom_right).into(),
+                    bottom_right_panels: Vec::new(),
+                    removed_panels: Vec::new(),
+                    bottom_dock_split: None,
+                },
+                centered_layout: layout.centered_layout,
+                is_default: false,
+            },
+            window,
+            cx,
+        );
+        self.join_all_panes(window, cx);
+
+        // The joined panes are removed once their events are handled, so build the new
+        // layout afterwards.
+        cx.spawn_in(window, async move |this, cx| {
+            let panes = this.update_in(cx, |this, window, cx| {
+                let main_pane = this.active_pane.clone();
+                let panes = this.split_into_member(main_pane, &layout.center, window, cx);
+                apply_flexes(&this.center.root, &layout.center);
+                panes
+            })?;
+
+            let mut active_pane = None;
+            for (pane, exported_pane) in panes {
+                if exported_pane.active {
+                    active_pane = Some(pane.clone());
+                }
+                let mut active_item = None;
+                for (ix, exported_item) in exported_pane.items.iter().enumerate() {
+                    let open_task = this.update_in(cx, |this, window, cx| {
+                        let project_path = this.resolve_exported_item(exported_item, cx)?;
+                        Some(this.open_path(
+                            project_path,
+                            Some(pane.downgrade()),
+                            false,
+                            window,
+                            cx,
+                        ))
+                    })?;
+                    let Some(open_task) = open_task else {
+                        continue;
+                    };
+                    match open_task.await {
+                        Ok(item) => {
+                            if exported_pane.active_item == Some(ix) {
+                                active_item = Some(item);
+                            }
+                        }
+                        Err(error) => {
+                            log::error!(
+                                "failed to open {:?} from layout: {error:#}",
+                                exported_item.path
+                            );
+                        }
+                    }
+                }
+                if let Some(active_item) = active_item {
+                    pane.update_in(cx, |pane, window, cx| {
+                        if let Some(index) = pane.index_for_item(active_item.as_ref()) {
+                            pane.activate_item(index, false, false, window, cx);
+                        }
+                    })?;
+                }
+            }
+
+            this.update_in(cx, |this, window, cx| {
+                if let Some(active_pane) = active_pane {
+                    window.focus(&active_pane.focus_handle(cx));
+                }
+                this.serialize_workspace(window, cx);
+                cx.notify();
+            })
+        })
+    }
+
+    /// Splits the given pane into the panes of the given member, returning them along with the
+    /// exported panes they correspond to.
+    fn split_into_member(
+        &mut self,
+        pane: Entity<Pane>,
+        member: &ExportedMember,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<(Entity<Pane>, ExportedPane)> {
+        match member {
+            ExportedMember::Pane(exported_pane) => vec![(pane, exported_pane.clone())],
+            ExportedMember::Group { axis, members, .. } => {
+                let direction = match axis {
+                    Axis::Horizontal => SplitDirection::Right,
+                    Axis::Vertical => SplitDirection::Down,
+                };
+                let mut member_panes = vec![pane];
+                for _ in 1..members.len() {
+                    let last_pane = member_panes[member_panes.len() - 1].clone();
+                    member_panes.push(self.split_pane(last_pane, direction, window, cx));
+                }
+                member_panes
+                    .into_iter()
+                    .zip(members)
+                    .flat_map(|(pane, member)| self.split_into_member(pane, member, window, cx))
+                    .collect()
+            }
+        }
+    }
+
+    fn resolve_exported_item(&self, item: &ExportedItem, cx: &App) -> Option<ProjectPath> {
+        // Layout files may come from someone else, so they can't point outside the worktree.
+        let is_within_worktree = item.path.components().next().is_some()
+            && item
+                .path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if !is_within_worktree {
+            log::warn!(
+                "ignoring {:?} from layout, it's not within a worktree",
+                item.path
+            );
+            return None;
+        }
+
+        let project = self.project.read(cx);
+        let worktree = item
+            .worktree
+            .as_ref()
+            .and_then(|name| {
+                project
+                    .visible_worktrees(cx)
+                    .find(|worktree| worktree.read(cx).root_name() == name)
+            })
+            .or_else(|| project.visible_worktrees(cx).next())?;
+        Some(ProjectPath {
+            worktree_id: worktree.read(cx).id(),
+            path: Path::new(&item.path).into(),
+        })
+    }
+
+    pub(crate) fn export_layout_to_file(
+        &mut self,
+        _: &ExportLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let layout = self.export_layout(window, cx);
+        let fs = self.app_state.fs.clone();
+        let directory = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .filter_map(|worktree| Some(worktree.read(cx).as_local()?.abs_path().to_path_buf()))
+            .next()
+            .unwrap_or_default();
+        let abs_path = cx.prompt_for_new_path(&directory);
+        cx.spawn_in(window, async move |_, _| {
+            let Some(abs_path) = abs_path.await?? else {
+                return Ok(());
+            };
+            let json = serde_json::to_string_pretty(&layout)?;
+            fs.atomic_write(abs_path, json).await
+        })
+        .detach_and_prompt_err("Failed to export layout", window, cx, |_, _, _| None);
+    }
+
+    pub(crate) fn import_layout_from_file(
+        &mut self,
+        _: &ImportLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let fs = self.app_state.fs.clone();
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+        });
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(path) = paths.await??.and_then(|paths| paths.into_iter().next()) else {
+                return Ok(());
+            };
+            let json = fs.load(&path).await?;
+            let layout: ExportedLayout = serde_json::from_str(&json)
+                .with_context(|| format!("parsing layout file {path:?}"))?;
+            this.update_in(cx, |this, window, cx| {
+                this.import_layout(layout, window, cx)
+            })?
+            .await
+        })
+        .detach_and_prompt_err("Failed to import layout", window, cx, |_, _, _| None);
+    }
+}
+
+/// Sets the sizes of the center pane axes from the layout, where its structure still matches
+/// the panes that were created for it.
+fn apply_flexes(member: &Member, exported: &ExportedMember) {
+    let (
+        Member::Axis(axis),
+        ExportedMember::Group {
+            flexes, members, ..
+        },
+    ) = (member, exported)
+    else {
+        return;
+    };
+    if axis.members.len() != members.len() {
+        return;
+    }
+    if let Some(flexes) = flexes
+        .as_ref()
+        .filter(|flexes| flexes.len() == members.len())
+    {
+        *axis.flexes.lock() = flexes.clone();
+    }
+    for (member, exported) in axis.members.iter().zip(members) {
+        apply_flexes(member, exported);
+    }
+}
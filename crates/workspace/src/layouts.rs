@@ -0,0 +1,253 @@
+use crate::{
+    dock::BottomDockSlot,
+    move_item,
+    persistence::{model::SerializedLayout, DB},
+    Pane, PaneLayout, SplitDirection, Workspace, WorkspaceSettings,
+};
+use anyhow::Result;
+use gpui::{App, AppContext as _, Context, Entity, Focusable, Task, Window};
+use settings::Settings;
+
+/// A panel group that was opened with [`Workspace::toggle_panel_group`], along with the
+/// layout to restore when it's closed.
+pub(crate) struct OpenPanelGroup {
+    name: String,
+    previous_layout: SerializedLayout,
+}
+
+impl Workspace {
+    /// Captures the current dock layout of this workspace under the given name.
+    pub fn current_layout(
+        &self,
+        name: impl Into<String>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> SerializedLayout {
+        SerializedLayout {
+            name: name.into(),
+            docks: self.build_serialized_docks(window, cx),
+            centered_layout: self.centered_layout,
+            is_default: false,
+        }
+    }
+
+    /// Saves the current dock layout under the given name, replacing any saved layout
+    /// with the same name.
+    pub fn save_layout(&self, name: String, window: &mut Window, cx: &mut App) -> Task<Result<()>> {
+        let layout = self.current_layout(name, window, cx);
+        cx.background_spawn(async move {
+            DB.save_named_layout(layout.name, layout.docks, layout.centered_layout)
+                .await
+        })
+    }
+
+    /// Applies a saved layout to this workspace, opening, activating and zooming
+    /// the dock panels it describes.
+    pub fn apply_layout(
+        &mut self,
+        layout: &SerializedLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Put the bottom panels back into the bottom dock they were in when the layout was saved.
+        let bottom_right_panels = &layout.docks.bottom_right_panels;
+        let moved_panels = self
+            .bottom_right_dock
+            .read(cx)
+            .panels()
+            .filter(|panel| {
+                !bottom_right_panels
+                    .iter()
+                    .any(|name| name == panel.persistent_name())
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        for panel in moved_panels {
+            self.move_panel_to_bottom_slot(panel, BottomDockSlot::Left, window, cx);
+        }
+        self.bottom_right_panels.clear();
+        self.restore_bottom_right_panels(bottom_right_panels, window, cx);
+        if let Some(split) = layout.docks.bottom_dock_split {
+            self.bottom_dock_split = split;
+        }
+
+        for (dock, serialized_dock) in [
+            (&self.left_dock, &layout.docks.left),
+            (&self.right_dock, &layout.docks.right),
+            (&self.bottom_dock, &layout.docks.bottom),
+            (&self.bottom_right_dock, &layout.docks.bottom_right),
+        ] {
+            dock.update(cx, |dock, cx| {
+                dock.serialized_dock = Some(serialized_dock.clone());
+                dock.restore_state(window, cx);
+                if !serialized_dock.zoom {
+                    if let Some(panel) = dock.active_panel() {
+                        panel.set_zoomed(false, window, cx);
+                    }
+                }
+            });
+        }
+        self.centered_layout = layout.centered_layout;
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    /// Opens the panels of the group with the given name from the `panel_groups` setting,
+    /// or closes them again if that group is already open. Closing a group restores the
+    /// dock layout from before it was opened.
+    pub fn toggle_panel_group(&mut self, name: &str, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(open_group) = self.open_panel_group.take() {
+            self.close_panel_group(&open_group, window, cx);
+            if open_group.name == name {
+                return;
+            }
+        }
+
+        let Some(panel_names) = WorkspaceSettings::get_global(cx)
+            .panel_groups
+            .get(name)
+            .cloned()
+        else {
+            log::warn!("no panel group named {name:?}");
+            return;
+        };
+
+        let previous_layout = self.current_layout(name, window, cx);
+        let mut opened_docks = Vec::new();
+        for panel_name in &panel_names {
+            for dock in self.all_docks() {
+                let Some(index) = dock
+                    .read(cx)
+                    .panel_index_for_persistent_name(panel_name, cx)
+                else {
+                    continue;
+                };
+                // Each dock only shows one panel, so the first one listed for it wins.
+                if !opened_docks.contains(&dock.entity_id()) {
+                    dock.update(cx, |dock, cx| {
+                        dock.activate_panel(index, window, cx);
+                        dock.set_open(true, window, cx);
+                    });
+                    opened_docks.push(dock.entity_id());
+                }
+                break;
+            }
+        }
+        if opened_docks.is_empty() {
+            return;
+        }
+
+        self.open_panel_group = Some(OpenPanelGroup {
+            name: name.to_string(),
+            previous_layout,
+        });
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    /// The name of the panel group that's currently open, if any.
+    pub fn open_panel_group(&self) -> Option<&str> {
+        self.open_panel_group
+            .as_ref()
+            .map(|group| group.name.as_str())
+    }
+
+    fn close_panel_group(
+        &mut self,
+        group: &OpenPanelGroup,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.apply_layout(&group.previous_layout, window, cx);
+    }
+
+    /// Rearranges the center panes into the given layout and spreads the open items over
+    /// them. The active item stays in the top-left pane, and the other items are dealt out
+    /// over the panes in reading order.
+    pub fn apply_pane_layout(
+        &mut self,
+        layout: PaneLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.join_all_panes(window, cx);
+        // The joined panes are removed once their events are handled, so build the new
+        // layout afterwards.
+        cx.defer_in(window, move |this, window, cx| {
+            let main_pane = this.active_pane.clone();
+            let panes = this.split_into_layout(&main_pane, layout, window, cx);
+
+            let main = main_pane.read(cx);
+            let active_item = main.active_item();
+            let active_item_id = active_item.as_ref().map(|item| item.item_id());
+            let other_item_ids = main
+                .items()
+                .map(|item| item.item_id())
+                .filter(|item_id| Some(*item_id) != active_item_id)
+                .collect::<Vec<_>>();
+            for (ix, item_id) in other_item_ids.into_iter().enumerate() {
+                let destination = &panes[(ix + 1) % panes.len()];
+                if *destination != main_pane {
+                    let destination_index = destination.read(cx).items_len();
+                    move_item(
+                        &main_pane,
+                        destination,
+                        item_id,
+                        destination_index,
+                        window,
+                        cx,
+                    );
+                }
+            }
+
+            if let Some(active_item) = active_item {
+                main_pane.update(cx, |pane, cx| {
+                    if let Some(index) = pane.index_for_item(active_item.as_ref()) {
+                        pane.activate_item(index, true, true, window, cx);
+                    }
+                });
+            }
+            window.focus(&main_pane.focus_handle(cx));
+            this.serialize_workspace(window, cx);
+            cx.notify();
+        });
+    }
+
+    /// Splits the given pane into the given layout, returning its panes in reading order.
+    fn split_into_layout(
+        &mut self,
+        main_pane: &Entity<Pane>,
+        layout: PaneLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<Entity<Pane>> {
+        match layout {
+            PaneLayout::Grid2x2 => {
+                let top_right =
+                    self.split_pane(main_pane.clone(), SplitDirection::Right, window, cx);
+                let bottom_left =
+                    self.split_pane(main_pane.clone(), SplitDirection::Down, window, cx);
+                let bottom_right =
+                    self.split_pane(top_right.clone(), SplitDirection::Down, window, cx);
+                vec![main_pane.clone(), top_right, bottom_left, bottom_right]
+            }
+            PaneLayout::ThreeColumns => {
+                let middle = self.split_pane(main_pane.clone(), SplitDirection::Right, window, cx);
+                let right = self.split_pane(middle.clone(), SplitDirection::Right, window, cx);
+                vec![main_pane.clone(), middle, right]
+            }
+            PaneLayout::MainAndStack => {
+                let stack_top = self.split_pane_with_ratio(
+                    main_pane.clone(),
+                    SplitDirection::Right,
+                    0.4,
+                    window,
+                    cx,
+                );
+                let stack_bottom =
+                    self.split_pane(stack_top.clone(), SplitDirection::Down, window, cx);
+                vec![main_pane.clone(), stack_top, stack_bottom]
+            }
+        }
+    }
+}
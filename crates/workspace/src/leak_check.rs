@@ -0,0 +1,65 @@
+//! Reports docks and panels that outlive their workspace.
+//!
+//! Docks are owned by their workspace and panels by their dock, so all of them should be
+//! released along with the workspace. One that's still alive afterwards is usually kept
+//! around by a long-lived closure that captured a strong handle instead of a weak one. In
+//! debug builds, the workspace checks for such leaks when it's released and logs an error
+//! for each dock or panel that is still alive after [`LEAK_CHECK_DELAY`].
+
+use crate::Workspace;
+use gpui::{AnyWeakView, App};
+use std::time::Duration;
+
+/// How long docks and panels get to be released after their workspace was.
+pub(crate) const LEAK_CHECK_DELAY: Duration = Duration::from_secs(1);
+
+/// Weak handles to the docks and panels of a workspace, labeled for reporting.
+pub(crate) struct LeakCheck {
+    entities: Vec<(String, AnyWeakView)>,
+}
+
+impl LeakCheck {
+    pub(crate) fn new(workspace: &Workspace, cx: &App) -> Self {
+        let mut entities = Vec::new();
+        for dock in workspace.all_docks() {
+            let dock_state = dock.read(cx);
+            entities.push((
+                format!("{} dock", dock_state.label()),
+                dock.downgrade().into(),
+            ));
+            for panel in dock_state.panels() {
+                entities.push((
+                    format!("{} panel", panel.persistent_name()),
+                    panel.to_any().downgrade(),
+                ));
+            }
+        }
+        for panel in workspace.removed_panels() {
+            entities.push((
+                format!("{} panel", panel.persistent_name()),
+                panel.to_any().downgrade(),
+            ));
+        }
+        Self { entities }
+    }
+
+    /// Returns the labels of the docks and panels that are still alive.
+    pub(crate) fn leaked(&self) -> Vec<&str> {
+        self.entities
+            .iter()
+            .filter(|(_, entity)| entity.upgrade().is_some())
+            .map(|(label, _)| label.as_str())
+            .collect()
+    }
+
+    /// Logs an error for every dock and panel that is still alive after [`LEAK_CHECK_DELAY`].
+    pub(crate) fn report_after_delay(self, cx: &mut App) {
+        cx.spawn(async move |cx| {
+            cx.background_executor().timer(LEAK_CHECK_DELAY).await;
+            for label in self.leaked() {
+                log::error!("{label} is still alive after its workspace was released");
+            }
+        })
+        .detach();
+    }
+}
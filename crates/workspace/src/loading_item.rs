@@ -0,0 +1,108 @@
+//! The placeholder tab shown while a file that is slow to load is being opened.
+//!
+//! [`Workspace::open_path_preview`](crate::Workspace::open_path_preview) adds a
+//! [`LoadingItem`] to the pane when loading takes longer than [`LOADING_PLACEHOLDER_DELAY`],
+//! and replaces it with the real item once it's loaded. Closing the placeholder cancels the
+//! load.
+
+use crate::item::{Item, TabContentParams};
+use futures::channel::oneshot;
+use gpui::{
+    percentage, Animation, AnimationExt as _, AnyElement, EventEmitter, FocusHandle, Focusable,
+    SharedString, Transformation,
+};
+use project::ProjectPath;
+use std::time::Duration;
+use ui::{prelude::*, Icon, IconName};
+
+/// How long loading a file may take before a placeholder tab is shown for it.
+pub const LOADING_PLACEHOLDER_DELAY: Duration = Duration::from_millis(100);
+
+pub struct LoadingItem {
+    project_path: ProjectPath,
+    file_name: SharedString,
+    focus_handle: FocusHandle,
+    /// Dropped along with the placeholder, which tells the loading task to stop.
+    _closed_tx: oneshot::Sender<()>,
+}
+
+impl LoadingItem {
+    /// Creates a placeholder for the given file, and a receiver that resolves once the
+    /// placeholder is dropped.
+    pub fn new(project_path: ProjectPath, cx: &mut Context<Self>) -> (Self, oneshot::Receiver<()>) {
+        let (closed_tx, closed_rx) = oneshot::channel();
+        let file_name = project_path
+            .path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_else(|| project_path.path.to_string_lossy().to_string());
+        let loading_item = Self {
+            project_path,
+            file_name: file_name.into(),
+            focus_handle: cx.focus_handle(),
+            _closed_tx: closed_tx,
+        };
+        (loading_item, closed_rx)
+    }
+
+    pub fn project_path(&self) -> &ProjectPath {
+        &self.project_path
+    }
+
+    fn spinner(&self) -> impl IntoElement {
+        Icon::new(IconName::ArrowCircle)
+            .size(IconSize::Small)
+            .color(Color::Muted)
+            .with_animation(
+                "loading-item-spinner",
+                Animation::new(Duration::from_secs(2)).repeat(),
+                |icon, delta| icon.transform(Transformation::rotate(percentage(delta))),
+            )
+    }
+}
+
+impl EventEmitter<()> for LoadingItem {}
+
+impl Focusable for LoadingItem {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for LoadingItem {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .track_focus(&self.focus_handle)
+            .key_context("LoadingItem")
+            .size_full()
+            .justify_center()
+            .gap_2()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.spinner())
+            .child(Label::new(format!("Loading {}…", self.file_name)).color(Color::Muted))
+    }
+}
+
+impl Item for LoadingItem {
+    type Event = ();
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _cx: &App) -> AnyElement {
+        h_flex()
+            .gap_1()
+            .child(self.spinner())
+            .child(Label::new(self.file_name.clone()).color(params.text_color()))
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some(self.file_name.clone())
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(self.project_path.path.to_string_lossy().to_string().into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
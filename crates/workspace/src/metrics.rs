@@ -0,0 +1,93 @@
+use crate::dock::DockPosition;
+use gpui::{App, Global};
+use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
+
+/// How long metrics are held back before they're delivered, so that a burst of layout changes,
+/// e.g. from restoring a workspace or applying a layout, is reported once.
+pub const METRICS_DELIVERY_DELAY: Duration = Duration::from_secs(1);
+
+/// A structured measurement of how the workspace layout is being used.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WorkspaceMetric {
+    /// A panel became the active panel of its dock.
+    PanelActivated {
+        panel: &'static str,
+        position: DockPosition,
+    },
+    /// A dock was closed after having been open for `open_duration`.
+    DockClosed {
+        position: DockPosition,
+        open_duration: Duration,
+    },
+    /// A center pane was added or removed.
+    PaneCountChanged { pane_count: usize },
+}
+
+impl WorkspaceMetric {
+    /// Whether this metric makes an earlier one that wasn't delivered yet irrelevant.
+    fn supersedes(&self, earlier: &WorkspaceMetric) -> bool {
+        match (self, earlier) {
+            (
+                WorkspaceMetric::PanelActivated { position, .. },
+                WorkspaceMetric::PanelActivated {
+                    position: earlier_position,
+                    ..
+                },
+            ) => position == earlier_position,
+            (
+                WorkspaceMetric::PaneCountChanged { .. },
+                WorkspaceMetric::PaneCountChanged { .. },
+            ) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Receives [`WorkspaceMetric`]s reported by workspaces.
+///
+/// The workspace doesn't record these metrics anywhere on its own; the application can
+/// install a sink with [`set_metrics_sink`] to forward them to telemetry. Metrics are
+/// delivered [`METRICS_DELIVERY_DELAY`] after the first one of a burst, keeping only the
+/// last pane count and the last panel activated in each dock.
+pub trait WorkspaceMetricsSink: 'static {
+    fn record(&self, metric: WorkspaceMetric);
+}
+
+struct GlobalWorkspaceMetricsSink {
+    sink: Arc<dyn WorkspaceMetricsSink>,
+    pending: Rc<RefCell<Vec<WorkspaceMetric>>>,
+}
+
+impl Global for GlobalWorkspaceMetricsSink {}
+
+/// Installs the sink that receives the metrics of all workspaces, replacing any previous one.
+pub fn set_metrics_sink(sink: Arc<dyn WorkspaceMetricsSink>, cx: &mut App) {
+    cx.set_global(GlobalWorkspaceMetricsSink {
+        sink,
+        pending: Default::default(),
+    });
+}
+
+pub(crate) fn record_metric(metric: WorkspaceMetric, cx: &App) {
+    let Some(global) = cx.try_global::<GlobalWorkspaceMetricsSink>() else {
+        return;
+    };
+    let mut pending = global.pending.borrow_mut();
+    let deliver = pending.is_empty();
+    pending.retain(|earlier| !metric.supersedes(earlier));
+    pending.push(metric);
+    if deliver {
+        let sink = global.sink.clone();
+        let pending = global.pending.clone();
+        let timer = cx.background_executor().timer(METRICS_DELIVERY_DELAY);
+        cx.foreground_executor()
+            .spawn(async move {
+                timer.await;
+                let metrics = pending.take();
+                for metric in metrics {
+                    sink.record(metric);
+                }
+            })
+            .detach();
+    }
+}
@@ -1,6 +1,6 @@
 use gpui::{
-    AnyView, DismissEvent, Entity, FocusHandle, Focusable as _, ManagedView, MouseButton,
-    Subscription,
+    AnyView, DismissEvent, Entity, EntityId, EventEmitter, FocusHandle, Focusable as _,
+    ManagedView, MouseButton, Subscription,
 };
 use ui::prelude::*;
 
@@ -51,8 +51,23 @@ pub struct ActiveModal {
     focus_handle: FocusHandle,
 }
 
+impl ActiveModal {
+    fn entity_id(&self) -> EntityId {
+        self.modal.view().entity_id()
+    }
+}
+
+#[derive(Debug)]
+pub enum ModalEvent {
+    /// A modal was removed from the layer, either because it dismissed itself or because
+    /// the user dismissed it.
+    Dismissed,
+}
+
+/// Shows modals on top of the workspace. Modals are kept in a stack: only the topmost one is
+/// interactive, and dismissing it returns to the one below.
 pub struct ModalLayer {
-    active_modal: Option<ActiveModal>,
+    modals: Vec<ActiveModal>,
     dismiss_on_focus_lost: bool,
 }
 
@@ -65,17 +80,19 @@ impl Default for ModalLayer {
 impl ModalLayer {
     pub fn new() -> Self {
         Self {
-            active_modal: None,
+            modals: Vec::new(),
             dismiss_on_focus_lost: false,
         }
     }
 
+    /// Shows the modal built by `build_view` in place of the topmost modal, or dismisses the
+    /// topmost modal if it's already a `V`.
     pub fn toggle_modal<V, B>(&mut self, window: &mut Window, cx: &mut Context<Self>, build_view: B)
     where
         V: ModalView,
         B: FnOnce(&mut Window, &mut Context<V>) -> V,
     {
-        if let Some(active_modal) = &self.active_modal {
+        if let Some(active_modal) = self.modals.last() {
             let is_close = active_modal.modal.view().downcast::<V>().is_ok();
             let did_close = self.hide_modal(window, cx);
             if is_close || !did_close {
@@ -86,24 +103,43 @@ impl ModalLayer {
         self.show_modal(new_modal, window, cx);
     }
 
+    /// Shows the modal built by `build_view` on top of the current modals, which are shown
+    /// again once it's dismissed.
+    pub fn push_modal<V, B>(&mut self, window: &mut Window, cx: &mut Context<Self>, build_view: B)
+    where
+        V: ModalView,
+        B: FnOnce(&mut Window, &mut Context<V>) -> V,
+    {
+        let new_modal = cx.new(|cx| build_view(window, cx));
+        self.show_modal(new_modal, window, cx);
+    }
+
     fn show_modal<V>(&mut self, new_modal: Entity<V>, window: &mut Window, cx: &mut Context<Self>)
     where
         V: ModalView,
     {
         let focus_handle = cx.focus_handle();
-        self.active_modal = Some(ActiveModal {
+        self.dismiss_on_focus_lost = false;
+        self.modals.push(ActiveModal {
             modal: Box::new(new_modal.clone()),
             _subscriptions: [
                 cx.subscribe_in(
                     &new_modal,
                     window,
-                    |this, _, _: &DismissEvent, window, cx| {
-                        this.hide_modal(window, cx);
+                    |this, modal, _: &DismissEvent, window, cx| {
+                        this.remove_modal(modal.entity_id(), window, cx);
                     },
                 ),
-                cx.on_focus_out(&focus_handle, window, |this, _event, window, cx| {
-                    if this.dismiss_on_focus_lost {
-                        this.hide_modal(window, cx);
+                cx.on_focus_out(&focus_handle, window, {
+                    let focus_handle = focus_handle.clone();
+                    move |this, _event, window, cx| {
+                        let is_topmost = this
+                            .modals
+                            .last()
+                            .is_some_and(|modal| modal.focus_handle == focus_handle);
+                        if is_topmost && this.dismiss_on_focus_lost {
+                            this.hide_modal(window, cx);
+                        }
                     }
                 }),
             ],
@@ -116,8 +152,10 @@ impl ModalLayer {
         cx.notify();
     }
 
+    /// Dismisses the topmost modal, unless it decides to stay open. Returns whether it was
+    /// dismissed.
     pub fn hide_modal(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
-        let Some(active_modal) = self.active_modal.as_mut() else {
+        let Some(active_modal) = self.modals.last_mut() else {
             self.dismiss_on_focus_lost = false;
             return false;
         };
@@ -135,36 +173,76 @@ impl ModalLayer {
             }
         }
 
-        if let Some(active_modal) = self.active_modal.take() {
-            if let Some(previous_focus) = active_modal.previous_focus_handle {
-                if active_modal.focus_handle.contains_focused(window, cx) {
-                    previous_focus.focus(window);
-                }
-            }
-            cx.notify();
+        if let Some(active_modal) = self.modals.pop() {
+            self.modal_removed(active_modal, window, cx);
         }
         true
     }
 
+    /// Removes the modal that dismissed itself, along with any modals shown on top of it.
+    fn remove_modal(&mut self, entity_id: EntityId, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self
+            .modals
+            .iter()
+            .position(|modal| modal.entity_id() == entity_id)
+        else {
+            return;
+        };
+        if ix + 1 == self.modals.len() {
+            self.hide_modal(window, cx);
+            return;
+        }
+        for active_modal in self.modals.drain(ix..).rev().collect::<Vec<_>>() {
+            self.modal_removed(active_modal, window, cx);
+        }
+    }
+
+    fn modal_removed(&mut self, modal: ActiveModal, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(previous_focus) = modal.previous_focus_handle {
+            if modal.focus_handle.contains_focused(window, cx) {
+                previous_focus.focus(window);
+            }
+        }
+        cx.emit(ModalEvent::Dismissed);
+        cx.notify();
+    }
+
+    /// Returns the topmost modal if it's a `V`.
     pub fn active_modal<V>(&self) -> Option<Entity<V>>
     where
         V: 'static,
     {
-        let active_modal = self.active_modal.as_ref()?;
+        let active_modal = self.modals.last()?;
         active_modal.modal.view().downcast::<V>().ok()
     }
 
     pub fn has_active_modal(&self) -> bool {
-        self.active_modal.is_some()
+        !self.modals.is_empty()
+    }
+
+    pub fn modal_count(&self) -> usize {
+        self.modals.len()
     }
 }
 
+impl EventEmitter<ModalEvent> for ModalLayer {}
+
 impl Render for ModalLayer {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let Some(active_modal) = &self.active_modal else {
+        if self.modals.is_empty() {
             return div();
-        };
+        }
 
+        div().absolute().size_full().top_0().left_0().children(
+            self.modals
+                .iter()
+                .map(|active_modal| Self::render_modal(active_modal, cx)),
+        )
+    }
+}
+
+impl ModalLayer {
+    fn render_modal(active_modal: &ActiveModal, cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .occlude()
             .absolute()
@@ -190,6 +268,10 @@ impl Render for ModalLayer {
                     .flex_col()
                     .items_center()
                     .track_focus(&active_modal.focus_handle)
+                    // Modals that don't handle escape themselves are dismissed by it.
+                    .on_action(cx.listener(|this, _: &menu::Cancel, window, cx| {
+                        this.hide_modal(window, cx);
+                    }))
                     .child(
                         h_flex()
                             .occlude()
@@ -0,0 +1,61 @@
+use crate::{persistence::model::SerializedNamedSession, persistence::DB, CloseIntent, Workspace};
+use anyhow::{anyhow, Result};
+use gpui::{App, Context, Task, Window};
+
+impl Workspace {
+    /// Saves the project roots, layout and open files of this workspace under the given
+    /// name, replacing any session with the same name. Unlike the automatic restore of the
+    /// last session, named sessions are only reopened on request.
+    pub fn save_named_session(
+        &self,
+        name: String,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        let Some(paths) = self.local_paths(cx) else {
+            return Task::ready(Err(anyhow!("only local projects can be saved as sessions")));
+        };
+        let layout = self.export_layout(window, cx);
+        cx.background_spawn(async move {
+            let paths = serde_json::to_string(&paths)?;
+            let layout = serde_json::to_string(&layout)?;
+            DB.save_named_session(name, paths, layout).await
+        })
+    }
+
+    /// Replaces this workspace with the given session, after asking to save its changes.
+    pub fn open_named_session(
+        &mut self,
+        session: SerializedNamedSession,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let app_state = self.app_state.clone();
+        let window_handle = window.window_handle().downcast::<Self>();
+        let prepare_to_close = self.prepare_to_close(CloseIntent::ReplaceWindow, window, cx);
+        cx.spawn(async move |_, cx| {
+            if !prepare_to_close.await? {
+                return Ok(());
+            }
+            // The session is laid out from scratch, rather than on top of the state last
+            // serialized for its paths, or in another window that has them open.
+            let (session_window, _) = cx
+                .update(|cx| {
+                    Workspace::new_local_internal(
+                        session.paths,
+                        app_state,
+                        window_handle,
+                        None,
+                        false,
+                        cx,
+                    )
+                })?
+                .await?;
+            session_window
+                .update(cx, |workspace, window, cx| {
+                    workspace.import_layout(session.layout, window, cx)
+                })?
+                .await
+        })
+    }
+}
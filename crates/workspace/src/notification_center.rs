@@ -0,0 +1,351 @@
+use crate::{
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::{NotificationLogEntry, NotificationSeverity},
+    workspace_settings::{NotificationCenterDockPosition, NotificationCenterSettings},
+    Event, Workspace,
+};
+use fs::Fs;
+use gpui::{
+    actions, App, Context, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription,
+    Task, WeakEntity, Window,
+};
+use settings::Settings;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use ui::{prelude::*, Tab, Tooltip};
+
+actions!(notification_center, [ToggleFocus, ClearAll]);
+
+/// How often the relative timestamps of the notifications are updated while the panel is shown.
+const TIMESTAMP_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<NotificationCenter>(window, cx);
+        });
+    })
+    .detach();
+}
+
+/// A panel listing the notifications that were shown in the workspace, including
+/// the ones that were already dismissed.
+pub struct NotificationCenter {
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    active: bool,
+    severity_filter: Option<NotificationSeverity>,
+    /// Notifications logged after this time are counted as unseen in the panel button.
+    last_seen: SystemTime,
+    /// Re-renders the panel while it's shown, so that its relative timestamps stay current.
+    _refresh_timestamps: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl NotificationCenter {
+    pub fn new(
+        workspace: &mut Workspace,
+        _window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let workspace_handle = cx.entity();
+        cx.new(|cx| {
+            let subscription = cx.subscribe(&workspace_handle, |this, _, event, cx| {
+                if let Event::NotificationLogChanged = event {
+                    if this.active {
+                        this.last_seen = SystemTime::now();
+                    }
+                    cx.notify();
+                }
+            });
+            Self {
+                workspace: workspace_handle.downgrade(),
+                fs,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                active: false,
+                severity_filter: None,
+                last_seen: SystemTime::now(),
+                _refresh_timestamps: None,
+                _subscription: subscription,
+            }
+        })
+    }
+
+    pub fn set_severity_filter(
+        &mut self,
+        severity: Option<NotificationSeverity>,
+        cx: &mut Context<Self>,
+    ) {
+        self.severity_filter = severity;
+        cx.notify();
+    }
+
+    /// Returns the logged notifications that pass the severity filter, newest first.
+    pub fn visible_entries(&self, cx: &App) -> Vec<NotificationLogEntry> {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return Vec::new();
+        };
+        workspace
+            .read(cx)
+            .notification_log()
+            .rev()
+            .filter(|entry| {
+                self.severity_filter
+                    .is_none_or(|severity| entry.severity == severity)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn clear_all(&mut self, _: &ClearAll, _: &mut Window, cx: &mut Context<Self>) {
+        self.workspace
+            .update(cx, |workspace, cx| workspace.clear_notification_log(cx))
+            .ok();
+    }
+
+    fn unseen_count(&self, cx: &App) -> usize {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return 0;
+        };
+        workspace
+            .read(cx)
+            .notification_log()
+            .filter(|entry| entry.timestamp > self.last_seen)
+            .count()
+    }
+
+    fn render_filter_button(
+        &self,
+        label: &'static str,
+        severity: Option<NotificationSeverity>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        Button::new(label, label)
+            .label_size(LabelSize::Small)
+            .toggle_state(self.severity_filter == severity)
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.set_severity_filter(severity, cx);
+            }))
+    }
+
+    fn render_entry(&self, ix: usize, entry: &NotificationLogEntry, cx: &App) -> impl IntoElement {
+        let (icon, color) = match entry.severity {
+            NotificationSeverity::Info => (IconName::Info, Color::Muted),
+            NotificationSeverity::Warning => (IconName::Warning, Color::Warning),
+            NotificationSeverity::Error => (IconName::XCircle, Color::Error),
+        };
+
+        h_flex()
+            .id(("notification-log-entry", ix))
+            .w_full()
+            .items_start()
+            .gap_2()
+            .px_2()
+            .py_1p5()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Icon::new(icon).size(IconSize::Small).color(color))
+            .child(
+                v_flex()
+                    .min_w_0()
+                    .flex_1()
+                    .child(Label::new(entry.message.clone()).size(LabelSize::Small))
+                    .child(
+                        Label::new(format_timestamp(entry.timestamp, SystemTime::now()))
+                            .size(LabelSize::XSmall)
+                            .color(Color::Muted),
+                    ),
+            )
+    }
+}
+
+fn format_timestamp(timestamp: SystemTime, now: SystemTime) -> String {
+    let seconds = now.duration_since(timestamp).unwrap_or_default().as_secs();
+    if seconds < 60 {
+        "just now".to_string()
+    } else if seconds < 60 * 60 {
+        format!("{}m ago", seconds / 60)
+    } else if seconds < 24 * 60 * 60 {
+        format!("{}h ago", seconds / (60 * 60))
+    } else {
+        format!("{}d ago", seconds / (24 * 60 * 60))
+    }
+}
+
+impl Render for NotificationCenter {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = self.visible_entries(cx);
+
+        v_flex()
+            .key_context("NotificationCenter")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::clear_all))
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        h_flex()
+                            .gap_0p5()
+                            .child(self.render_filter_button("All", None, cx))
+                            .child(self.render_filter_button(
+                                "Info",
+                                Some(NotificationSeverity::Info),
+                                cx,
+                            ))
+                            .child(self.render_filter_button(
+                                "Warnings",
+                                Some(NotificationSeverity::Warning),
+                                cx,
+                            ))
+                            .child(self.render_filter_button(
+                                "Errors",
+                                Some(NotificationSeverity::Error),
+                                cx,
+                            )),
+                    )
+                    .child(
+                        IconButton::new("clear-all-notifications", IconName::Trash)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::for_action_title_in(
+                                "Clear All",
+                                &ClearAll,
+                                &self.focus_handle,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.clear_all(&ClearAll, window, cx)
+                            })),
+                    ),
+            )
+            .map(|this| {
+                if entries.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("No notifications.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        v_flex()
+                            .id("notification-log")
+                            .flex_1()
+                            .overflow_y_scroll()
+                            .children(
+                                entries
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(ix, entry)| self.render_entry(ix, entry, cx)),
+                            ),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for NotificationCenter {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for NotificationCenter {}
+
+impl Panel for NotificationCenter {
+    fn persistent_name() -> &'static str {
+        "NotificationCenter"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match NotificationCenterSettings::get_global(cx).dock {
+            NotificationCenterDockPosition::Left => DockPosition::Left,
+            NotificationCenterDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<NotificationCenterSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| {
+                let dock = match position {
+                    DockPosition::Left | DockPosition::Bottom => {
+                        NotificationCenterDockPosition::Left
+                    }
+                    DockPosition::Right => NotificationCenterDockPosition::Right,
+                };
+                settings.dock = Some(dock);
+            },
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| NotificationCenterSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.active = active;
+        self._refresh_timestamps = active.then(|| {
+            cx.spawn(async move |this, cx| loop {
+                cx.background_executor()
+                    .timer(TIMESTAMP_REFRESH_INTERVAL)
+                    .await;
+                if this.update(cx, |_, cx| cx.notify()).is_err() {
+                    break;
+                }
+            })
+        });
+        if active {
+            self.last_seen = SystemTime::now();
+            cx.notify();
+        }
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        if !NotificationCenterSettings::get_global(cx).button {
+            return None;
+        }
+        Some(IconName::Info)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Notification Center")
+    }
+
+    fn icon_label(&self, _: &Window, cx: &App) -> Option<String> {
+        match self.unseen_count(cx) {
+            0 => None,
+            count => Some(count.to_string()),
+        }
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Toast, Workspace};
+use crate::{feedback::give_feedback, Toast, Workspace};
 use gpui::{
     svg, AnyView, App, AppContext as _, AsyncWindowContext, ClipboardItem, Context, DismissEvent,
     Entity, EventEmitter, FocusHandle, Focusable, PromptLevel, Render, ScrollHandle, Task,
@@ -6,7 +6,11 @@ use gpui::{
 use parking_lot::Mutex;
 use std::ops::Deref;
 use std::sync::{Arc, LazyLock};
-use std::{any::TypeId, time::Duration};
+use std::{
+    any::TypeId,
+    mem,
+    time::{Duration, SystemTime},
+};
 use ui::{prelude::*, Tooltip};
 use util::ResultExt;
 
@@ -54,7 +58,46 @@ impl NotificationId {
     }
 }
 
-pub trait Notification: EventEmitter<DismissEvent> + Focusable + Render {}
+pub trait Notification: EventEmitter<DismissEvent> + Focusable + Render {
+    /// The severity and the text recorded in the workspace's notification log when the
+    /// notification is shown. Notifications without a text summary return `None` and aren't
+    /// logged.
+    fn log_entry(&self, _cx: &App) -> Option<(NotificationSeverity, SharedString)> {
+        None
+    }
+}
+
+/// The maximum number of entries kept in a workspace's notification log.
+const NOTIFICATION_LOG_LIMIT: usize = 500;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NotificationSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl From<PromptLevel> for NotificationSeverity {
+    fn from(level: PromptLevel) -> Self {
+        match level {
+            PromptLevel::Info => Self::Info,
+            PromptLevel::Warning => Self::Warning,
+            PromptLevel::Critical => Self::Error,
+        }
+    }
+}
+
+/// The view of a notification along with its [`Notification::log_entry`].
+type BuiltNotification = (AnyView, Option<(NotificationSeverity, SharedString)>);
+
+/// A notification that was shown in the workspace, kept so it can be reviewed after it's dismissed.
+#[derive(Clone, Debug)]
+pub struct NotificationLogEntry {
+    pub id: NotificationId,
+    pub severity: NotificationSeverity,
+    pub message: SharedString,
+    pub timestamp: SystemTime,
+}
 
 impl Workspace {
     #[cfg(any(test, feature = "test-support"))]
@@ -81,24 +124,31 @@ impl Workspace {
                 }
             })
             .detach();
-            notification.into()
+            let log_entry = notification.read(cx).log_entry(cx);
+            (notification.into(), log_entry)
         });
     }
 
-    /// Shows a notification in this workspace's window. Caller must handle dismiss.
+    /// Shows a notification in this workspace's window and records it in the notification log.
+    /// Caller must handle dismiss.
     ///
     /// This exists so that the `build_notification` closures stored for app notifications can
     /// return `AnyView`. Subscribing to events from an `AnyView` is not supported, so instead that
-    /// responsibility is pushed to the caller where the `V` type is known.
+    /// responsibility is pushed to the caller where the `V` type is known. For the same reason,
+    /// the caller provides the notification's [`Notification::log_entry`].
     pub(crate) fn show_notification_without_handling_dismiss_events(
         &mut self,
         id: &NotificationId,
         cx: &mut Context<Self>,
-        build_notification: impl FnOnce(&mut Context<Self>) -> AnyView,
+        build_notification: impl FnOnce(&mut Context<Self>) -> BuiltNotification,
     ) {
-        self.dismiss_notification(id, cx);
-        self.notifications
-            .push((id.clone(), build_notification(cx)));
+        self.remove_notification(id, cx);
+        let (notification, log_entry) = build_notification(cx);
+        if let Some((severity, message)) = log_entry {
+            self.log_notification(id.clone(), severity, message, cx);
+        }
+        self.notifications.push((id.clone(), notification));
+        give_feedback(cx, |feedback, cx| feedback.notification_shown(id, cx));
         cx.notify();
     }
 
@@ -106,8 +156,9 @@ impl Workspace {
     where
         E: std::fmt::Debug + std::fmt::Display,
     {
+        let message = format!("Error: {err}");
         self.show_notification(workspace_error_notification_id(), cx, |cx| {
-            cx.new(|cx| ErrorMessagePrompt::new(format!("Error: {err}"), cx))
+            cx.new(|cx| ErrorMessagePrompt::new(message, cx))
         });
     }
 
@@ -124,19 +175,50 @@ impl Workspace {
         });
     }
 
-    pub fn dismiss_notification(&mut self, id: &NotificationId, cx: &mut Context<Self>) {
-        self.notifications.retain(|(existing_id, _)| {
-            if existing_id == id {
-                cx.notify();
-                false
-            } else {
-                true
+    /// Dismisses the notification with the given id once the duration has passed, unless it's
+    /// dismissed or replaced by a notification with the same id before then.
+    pub fn dismiss_notification_after(
+        &mut self,
+        id: NotificationId,
+        duration: Duration,
+        cx: &mut Context<Self>,
+    ) {
+        let timer = cx.spawn({
+            let id = id.clone();
+            async move |workspace, cx| {
+                cx.background_executor().timer(duration).await;
+                workspace
+                    .update(cx, |workspace, cx| workspace.dismiss_notification(&id, cx))
+                    .ok();
             }
         });
+        self.notification_dismiss_timers
+            .retain(|(existing_id, _)| *existing_id != id);
+        self.notification_dismiss_timers.push((id, timer));
+    }
+
+    pub fn dismiss_notification(&mut self, id: &NotificationId, cx: &mut Context<Self>) {
+        if self.remove_notification(id, cx) {
+            give_feedback(cx, |feedback, cx| feedback.notification_dismissed(id, cx));
+        }
+    }
+
+    /// Removes the notification with the given id, returning whether there was one.
+    fn remove_notification(&mut self, id: &NotificationId, cx: &mut Context<Self>) -> bool {
+        self.notification_dismiss_timers
+            .retain(|(existing_id, _)| existing_id != id);
+        let notification_count = self.notifications.len();
+        self.notifications
+            .retain(|(existing_id, _)| existing_id != id);
+        if self.notifications.len() == notification_count {
+            return false;
+        }
+        cx.notify();
+        true
     }
 
     pub fn show_toast(&mut self, toast: Toast, cx: &mut Context<Self>) {
-        self.dismiss_notification(&toast.id, cx);
+        self.remove_notification(&toast.id, cx);
         self.show_notification(toast.id.clone(), cx, |cx| {
             cx.new(|cx| match toast.on_click.as_ref() {
                 Some((click_msg, on_click)) => {
@@ -150,16 +232,8 @@ impl Workspace {
                 }
             })
         });
-        if toast.autohide {
-            cx.spawn(async move |workspace, cx| {
-                cx.background_executor()
-                    .timer(Duration::from_millis(5000))
-                    .await;
-                workspace
-                    .update(cx, |workspace, cx| workspace.dismiss_toast(&toast.id, cx))
-                    .ok();
-            })
-            .detach();
+        if let Some(duration) = toast.autohide {
+            self.dismiss_notification_after(toast.id, duration, cx);
         }
     }
 
@@ -168,10 +242,44 @@ impl Workspace {
     }
 
     pub fn clear_all_notifications(&mut self, cx: &mut Context<Self>) {
-        self.notifications.clear();
+        self.notification_dismiss_timers.clear();
+        for (id, _) in mem::take(&mut self.notifications) {
+            give_feedback(cx, |feedback, cx| feedback.notification_dismissed(&id, cx));
+        }
         cx.notify();
     }
 
+    /// Records a notification in the workspace's notification log, which outlives the
+    /// notification itself.
+    pub fn log_notification(
+        &mut self,
+        id: NotificationId,
+        severity: NotificationSeverity,
+        message: impl Into<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.notification_log.len() == NOTIFICATION_LOG_LIMIT {
+            self.notification_log.pop_front();
+        }
+        self.notification_log.push_back(NotificationLogEntry {
+            id,
+            severity,
+            message: message.into(),
+            timestamp: SystemTime::now(),
+        });
+        cx.emit(crate::Event::NotificationLogChanged);
+    }
+
+    /// Returns the logged notifications, oldest first.
+    pub fn notification_log(&self) -> impl DoubleEndedIterator<Item = &NotificationLogEntry> {
+        self.notification_log.iter()
+    }
+
+    pub fn clear_notification_log(&mut self, cx: &mut Context<Self>) {
+        self.notification_log.clear();
+        cx.emit(crate::Event::NotificationLogChanged);
+    }
+
     pub fn show_initial_notifications(&mut self, cx: &mut Context<Self>) {
         // Allow absence of the global so that tests don't need to initialize it.
         let app_notifications = GLOBAL_APP_NOTIFICATIONS
@@ -200,7 +308,15 @@ impl Focusable for LanguageServerPrompt {
     }
 }
 
-impl Notification for LanguageServerPrompt {}
+impl Notification for LanguageServerPrompt {
+    fn log_entry(&self, _cx: &App) -> Option<(NotificationSeverity, SharedString)> {
+        let request = self.request.as_ref()?;
+        Some((
+            request.level.into(),
+            format!("{}: {}", request.lsp_name, request.message).into(),
+        ))
+    }
+}
 
 impl LanguageServerPrompt {
     pub fn new(request: project::LanguageServerPromptRequest, cx: &mut App) -> Self {
@@ -402,7 +518,11 @@ impl Focusable for ErrorMessagePrompt {
 
 impl EventEmitter<DismissEvent> for ErrorMessagePrompt {}
 
-impl Notification for ErrorMessagePrompt {}
+impl Notification for ErrorMessagePrompt {
+    fn log_entry(&self, _cx: &App) -> Option<(NotificationSeverity, SharedString)> {
+        Some((NotificationSeverity::Error, self.message.clone()))
+    }
+}
 
 pub mod simple_message_notification {
     use std::sync::Arc;
@@ -413,10 +533,11 @@ pub mod simple_message_notification {
     };
     use ui::prelude::*;
 
-    use super::Notification;
+    use super::{Notification, NotificationSeverity};
 
     pub struct MessageNotification {
         focus_handle: FocusHandle,
+        message: Option<SharedString>,
         build_content: Box<dyn Fn(&mut Window, &mut Context<Self>) -> AnyElement>,
         primary_message: Option<SharedString>,
         primary_icon: Option<IconName>,
@@ -440,7 +561,12 @@ pub mod simple_message_notification {
 
     impl EventEmitter<DismissEvent> for MessageNotification {}
 
-    impl Notification for MessageNotification {}
+    impl Notification for MessageNotification {
+        fn log_entry(&self, _cx: &App) -> Option<(NotificationSeverity, SharedString)> {
+            let message = self.message.clone().or_else(|| self.title.clone())?;
+            Some((NotificationSeverity::Info, message))
+        }
+    }
 
     impl MessageNotification {
         pub fn new<S>(message: S, cx: &mut App) -> MessageNotification
@@ -448,9 +574,12 @@ pub mod simple_message_notification {
             S: Into<SharedString>,
         {
             let message = message.into();
-            Self::new_from_builder(cx, move |_, _| {
-                Label::new(message.clone()).into_any_element()
-            })
+            Self {
+                message: Some(message.clone()),
+                ..Self::new_from_builder(cx, move |_, _| {
+                    Label::new(message.clone()).into_any_element()
+                })
+            }
         }
 
         pub fn new_from_builder<F>(cx: &mut App, content: F) -> MessageNotification
@@ -458,6 +587,7 @@ pub mod simple_message_notification {
             F: 'static + Fn(&mut Window, &mut Context<Self>) -> AnyElement,
         {
             Self {
+                message: None,
                 build_content: Box::new(content),
                 primary_message: None,
                 primary_icon: None,
@@ -676,20 +806,16 @@ static GLOBAL_APP_NOTIFICATIONS: LazyLock<Mutex<AppNotifications>> = LazyLock::n
     })
 });
 
+/// Builds an app notification in a workspace.
+type BuildAppNotification = dyn Fn(&mut Context<Workspace>) -> BuiltNotification + Send + Sync;
+
 /// Stores app notifications so that they can be shown in new workspaces.
 struct AppNotifications {
-    app_notifications: Vec<(
-        NotificationId,
-        Arc<dyn Fn(&mut Context<Workspace>) -> AnyView + Send + Sync>,
-    )>,
+    app_notifications: Vec<(NotificationId, Arc<BuildAppNotification>)>,
 }
 
 impl AppNotifications {
-    pub fn insert(
-        &mut self,
-        id: NotificationId,
-        build_notification: Arc<dyn Fn(&mut Context<Workspace>) -> AnyView + Send + Sync>,
-    ) {
+    pub fn insert(&mut self, id: NotificationId, build_notification: Arc<BuildAppNotification>) {
         self.remove(&id);
         self.app_notifications.push((id, build_notification))
     }
@@ -711,21 +837,21 @@ pub fn show_app_notification<V: Notification + 'static>(
     // Defer notification creation so that windows on the stack can be returned to GPUI
     cx.defer(move |cx| {
         // Handle dismiss events by removing the notification from all workspaces.
-        let build_notification: Arc<dyn Fn(&mut Context<Workspace>) -> AnyView + Send + Sync> =
-            Arc::new({
-                let id = id.clone();
-                move |cx| {
-                    let notification = build_notification(cx);
-                    cx.subscribe(&notification, {
-                        let id = id.clone();
-                        move |_, _, _: &DismissEvent, cx| {
-                            dismiss_app_notification(&id, cx);
-                        }
-                    })
-                    .detach();
-                    notification.into()
-                }
-            });
+        let build_notification: Arc<BuildAppNotification> = Arc::new({
+            let id = id.clone();
+            move |cx| {
+                let notification = build_notification(cx);
+                cx.subscribe(&notification, {
+                    let id = id.clone();
+                    move |_, _, _: &DismissEvent, cx| {
+                        dismiss_app_notification(&id, cx);
+                    }
+                })
+                .detach();
+                let log_entry = notification.read(cx).log_entry(cx);
+                (notification.into(), log_entry)
+            }
+        });
 
         // Store the notification so that new workspaces also receive it.
         GLOBAL_APP_NOTIFICATIONS
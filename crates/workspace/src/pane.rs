@@ -1,36 +1,42 @@
 use crate::{
     item::{
         ActivateOnClose, ClosePosition, Item, ItemHandle, ItemSettings, PreviewTabsSettings,
-        ShowCloseButton, ShowDiagnostics, TabContentParams, TabTooltipContent, WeakItemHandle,
+        ShowCloseButton, ShowDiagnostics, TabContentParams, TabDecoration, TabTooltipContent,
+        WeakItemHandle,
     },
+    item_decorations::{ItemDecorations, ItemDecorationsChanged},
     move_item,
     notifications::NotifyResultExt,
+    prompt_dialog::PromptOptions,
     toolbar::Toolbar,
-    workspace_settings::{AutosaveSetting, TabBarSettings, WorkspaceSettings},
+    workspace_settings::{AutosaveSetting, TabBarPlacement, TabBarSettings, WorkspaceSettings},
     CloseWindow, NewFile, NewTerminal, OpenInTerminal, OpenOptions, OpenTerminal, OpenVisible,
     SplitDirection, ToggleFileFinder, ToggleProjectSymbols, ToggleZoom, Workspace,
 };
 use anyhow::Result;
-use collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use fs::MTime;
 use futures::{stream::FuturesUnordered, StreamExt};
 use gpui::{
-    actions, anchored, deferred, impl_actions, prelude::*, Action, AnyElement, App,
-    AsyncWindowContext, ClickEvent, ClipboardItem, Context, Corner, Div, DragMoveEvent, Entity,
-    EntityId, EventEmitter, ExternalPaths, FocusHandle, FocusOutEvent, Focusable, KeyContext,
-    MouseButton, MouseDownEvent, NavigationDirection, Pixels, Point, PromptLevel, Render,
-    ScrollHandle, Subscription, Task, WeakEntity, WeakFocusHandle, Window,
+    actions, anchored, canvas, deferred, impl_actions, prelude::*, Action, Along, AnyElement, App,
+    AsyncWindowContext, Axis, Bounds, ClickEvent, ClipboardItem, Context, Corner, Div,
+    DragMoveEvent, Entity, EntityId, EventEmitter, ExternalPaths, FocusHandle, FocusOutEvent,
+    Focusable, KeyContext, MouseButton, MouseDownEvent, NavigationDirection, Pixels, Point,
+    PromptLevel, Render, ScrollHandle, Stateful, Subscription, Task, WeakEntity, WeakFocusHandle,
+    Window,
 };
 use itertools::Itertools;
 use language::DiagnosticSeverity;
 use parking_lot::Mutex;
 use project::{Project, ProjectEntryId, ProjectPath, WorktreeId};
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use settings::{Settings, SettingsStore};
 use std::{
     any::Any,
+    cell::{Cell, RefCell},
     cmp, fmt, mem,
-    ops::ControlFlow,
+    ops::{ControlFlow, Range},
     path::PathBuf,
     rc::Rc,
     sync::{
@@ -191,6 +197,7 @@ actions!(
         SwapItemRight,
         TogglePreviewTab,
         TogglePinTab,
+        ToggleReadOnly,
     ]
 );
 
@@ -204,6 +211,24 @@ impl DeploySearch {
 
 const MAX_NAVIGATION_HISTORY_LEN: usize = 1024;
 
+/// The width of the tab bar when it's shown beside the pane's content.
+const VERTICAL_TAB_BAR_WIDTH: Pixels = px(240.);
+
+/// Panes with more unpinned tabs than this only render the unpinned tabs in view.
+const VIRTUALIZED_TAB_THRESHOLD: usize = 100;
+/// How many tabs are rendered on each side of the visible ones in a virtualized tab bar.
+const TAB_OVERSCAN: usize = 8;
+/// The size assumed for tabs along the tab bar until tabs have been laid out.
+const ESTIMATED_TAB_EXTENT: Pixels = px(120.);
+
+/// The unpinned tabs rendered in a virtualized tab bar. The tabs before and after them are
+/// replaced by spacers sized by the average size of the rendered tabs.
+struct VirtualizedTabs {
+    range: Range<usize>,
+    axis: Axis,
+    tab_extent: Pixels,
+}
+
 pub enum Event {
     AddItem {
         item: Box<dyn ItemHandle>,
@@ -270,6 +295,22 @@ impl fmt::Debug for Event {
     }
 }
 
+/// A value that a pane holds for the items created in it, see [`Pane::context`].
+/// Pane contexts are serialized with the workspace, and copied to new panes split off a pane.
+pub trait PaneContext: Serialize + DeserializeOwned + 'static {
+    /// Identifies the value among the pane's context, including in the serialized workspace.
+    const KEY: &'static str;
+}
+
+/// The directory containing the file that was last active in a pane, which new terminals
+/// opened in the pane start in.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PaneWorkingDirectory(pub PathBuf);
+
+impl PaneContext for PaneWorkingDirectory {
+    const KEY: &'static str = "working_directory";
+}
+
 /// A container for 0 to many items that are open in the workspace.
 /// Treats all items uniformly via the [`ItemHandle`] trait, whether it's an editor, search results multibuffer, terminal or something else,
 /// responsible for managing item tabs, focus and zoom states and drag and drop features.
@@ -310,6 +351,17 @@ pub struct Pane {
     show_tab_bar_buttons: bool,
     _subscriptions: Vec<Subscription>,
     tab_bar_scroll_handle: ScrollHandle,
+    virtualized_tabs: Option<VirtualizedTabs>,
+    /// The level of detail of every tab's description, along with the items it was computed
+    /// for. Recomputed when the items change or one of their tabs is updated.
+    tab_details: Option<(Vec<EntityId>, Rc<[usize]>)>,
+    /// Where the pane was last painted.
+    bounds: Rc<Cell<Bounds<Pixels>>>,
+    /// Whether the tab bar was shown the last time the pane was rendered.
+    tab_bar_displayed: bool,
+    /// Where the pinned tabs were last painted. The unpinned tabs' bounds are tracked by
+    /// the tab bar's scroll handle.
+    pinned_tab_bounds: Rc<RefCell<Vec<Bounds<Pixels>>>>,
     /// Is None if navigation buttons are permanently turned off (and should not react to setting changes).
     /// Otherwise, when `display_nav_history_buttons` is Some, it determines whether nav buttons should be displayed.
     display_nav_history_buttons: Option<bool>,
@@ -319,8 +371,15 @@ pub struct Pane {
     pub new_item_context_menu_handle: PopoverMenuHandle<ContextMenu>,
     pub split_item_context_menu_handle: PopoverMenuHandle<ContextMenu>,
     pinned_tab_count: usize,
+    /// Serialized [`PaneContext`] values by their key.
+    context: BTreeMap<String, String>,
     diagnostics: HashMap<ProjectPath, DiagnosticSeverity>,
+    /// Items whose file changed on disk while they had unsaved changes, which show a
+    /// conflict banner.
+    externally_changed_items: HashSet<EntityId>,
     zoom_out_on_close: bool,
+    /// Overrides the `tab_bar.placement` setting for this pane.
+    tab_bar_placement: Option<TabBarPlacement>,
 }
 
 pub struct ActivationHistoryEntry {
@@ -345,6 +404,9 @@ struct NavHistoryState {
     paths_by_item: HashMap<EntityId, (ProjectPath, Option<PathBuf>)>,
     pane: WeakEntity<Pane>,
     next_timestamp: Arc<AtomicUsize>,
+    /// When an entry was last pushed by navigating to a new location, which invalidates
+    /// the forward entries of every pane that are older than it.
+    last_navigation_timestamp: Option<usize>,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -393,12 +455,25 @@ impl Pane {
     ) -> Self {
         let focus_handle = cx.focus_handle();
 
+        let item_decorations = ItemDecorations::global(cx);
         let subscriptions = vec![
             cx.on_focus(&focus_handle, window, Pane::focus_in),
             cx.on_focus_in(&focus_handle, window, Pane::focus_in),
             cx.on_focus_out(&focus_handle, window, Pane::focus_out),
             cx.observe_global::<SettingsStore>(Self::settings_changed),
             cx.subscribe(&project, Self::project_events),
+            cx.subscribe(
+                &item_decorations,
+                |pane, _, event: &ItemDecorationsChanged, cx| {
+                    if pane
+                        .items
+                        .iter()
+                        .any(|item| event.affects(item.as_ref(), cx))
+                    {
+                        cx.notify();
+                    }
+                },
+            ),
         ];
 
         let handle = cx.entity().downgrade();
@@ -421,9 +496,15 @@ impl Pane {
                 paths_by_item: Default::default(),
                 pane: handle.clone(),
                 next_timestamp,
+                last_navigation_timestamp: None,
             }))),
             toolbar: cx.new(|_| Toolbar::new()),
             tab_bar_scroll_handle: ScrollHandle::new(),
+            virtualized_tabs: None,
+            tab_details: None,
+            bounds: Rc::default(),
+            tab_bar_displayed: false,
+            pinned_tab_bounds: Rc::default(),
             drag_split_direction: None,
             workspace,
             project: project.downgrade(),
@@ -524,8 +605,11 @@ impl Pane {
             split_item_context_menu_handle: Default::default(),
             new_item_context_menu_handle: Default::default(),
             pinned_tab_count: 0,
+            context: BTreeMap::default(),
             diagnostics: Default::default(),
             zoom_out_on_close: true,
+            tab_bar_placement: None,
+            externally_changed_items: HashSet::default(),
         }
     }
 
@@ -699,6 +783,23 @@ impl Pane {
         self.should_display_tab_bar = Rc::new(should_display_tab_bar);
     }
 
+    /// Where this pane's tabs are shown, following the `tab_bar.placement` setting
+    /// unless it was overridden with [`Self::set_tab_bar_placement`].
+    pub fn tab_bar_placement(&self, cx: &App) -> TabBarPlacement {
+        self.tab_bar_placement
+            .unwrap_or_else(|| TabBarSettings::get_global(cx).placement)
+    }
+
+    /// Overrides the `tab_bar.placement` setting for this pane, or follows it again when `None`.
+    pub fn set_tab_bar_placement(
+        &mut self,
+        placement: Option<TabBarPlacement>,
+        cx: &mut Context<Self>,
+    ) {
+        self.tab_bar_placement = placement;
+        cx.notify();
+    }
+
     pub fn set_can_split(
         &mut self,
         can_split_predicate: Option<
@@ -774,12 +875,26 @@ impl Pane {
         !self.nav_history.0.lock().forward_stack.is_empty()
     }
 
+    /// Center panes navigate through the history of all center panes, other panes only
+    /// through their own.
+    fn navigates_across_panes(&self, cx: &Context<Self>) -> bool {
+        self.workspace.upgrade().map_or(false, |workspace| {
+            workspace.read(cx).panes().contains(&cx.entity())
+        })
+    }
+
     fn navigate_backward(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(workspace) = self.workspace.upgrade() {
+            let across_panes = self.navigates_across_panes(cx);
             let pane = cx.entity().downgrade();
             window.defer(cx, move |window, cx| {
                 workspace.update(cx, |workspace, cx| {
-                    workspace.go_back(pane, window, cx).detach_and_log_err(cx)
+                    let task = if across_panes {
+                        workspace.go_back_across_panes(window, cx)
+                    } else {
+                        workspace.go_back(pane, window, cx)
+                    };
+                    task.detach_and_log_err(cx)
                 })
             })
         }
@@ -787,12 +902,16 @@ impl Pane {
 
     fn navigate_forward(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(workspace) = self.workspace.upgrade() {
+            let across_panes = self.navigates_across_panes(cx);
             let pane = cx.entity().downgrade();
             window.defer(cx, move |window, cx| {
                 workspace.update(cx, |workspace, cx| {
-                    workspace
-                        .go_forward(pane, window, cx)
-                        .detach_and_log_err(cx)
+                    let task = if across_panes {
+                        workspace.go_forward_across_panes(window, cx)
+                    } else {
+                        workspace.go_forward(pane, window, cx)
+                    };
+                    task.detach_and_log_err(cx)
                 })
             })
         }
@@ -800,6 +919,44 @@ impl Pane {
 
     fn history_updated(&mut self, cx: &mut Context<Self>) {
         self.toolbar.update(cx, |_, cx| cx.notify());
+        // The navigation buttons of every pane depend on the history of all of them.
+        if let Some(workspace) = self.workspace.upgrade() {
+            for pane in workspace.read(cx).panes().to_vec() {
+                if pane != cx.entity() {
+                    pane.update(cx, |_, cx| cx.notify());
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn render_history_menu(
+        workspace: WeakEntity<Workspace>,
+        mode: NavigationMode,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<ContextMenu> {
+        ContextMenu::build(window, cx, |mut menu, _, cx| {
+            let Some(entries) = workspace
+                .upgrade()
+                .map(|workspace| workspace.read(cx).navigation_history(mode, cx))
+            else {
+                return menu;
+            };
+            for entry in entries {
+                let workspace = workspace.clone();
+                menu = menu.entry(entry.label, None, move |window, cx| {
+                    workspace
+                        .update(cx, |workspace, cx| {
+                            workspace
+                                .navigate_to_history_entry(mode, entry.timestamp, window, cx)
+                                .detach_and_log_err(cx)
+                        })
+                        .ok();
+                });
+            }
+            menu
+        })
     }
 
     pub fn preview_item_id(&self) -> Option<EntityId> {
@@ -842,6 +999,39 @@ impl Pane {
         self.pinned_tab_count
     }
 
+    /// Returns the value of the given context type that was set in this pane.
+    pub fn context<T: PaneContext>(&self) -> Option<T> {
+        let value = self.context.get(T::KEY)?;
+        serde_json::from_str(value).log_err()
+    }
+
+    pub fn set_context<T: PaneContext>(&mut self, value: &T) {
+        if let Some(value) = serde_json::to_string(value).log_err() {
+            self.context.insert(T::KEY.to_string(), value);
+        }
+    }
+
+    pub(crate) fn serialized_context(&self) -> &BTreeMap<String, String> {
+        &self.context
+    }
+
+    pub(crate) fn set_serialized_context(&mut self, context: BTreeMap<String, String>) {
+        self.context = context;
+    }
+
+    fn update_working_directory(&mut self, item: &dyn ItemHandle, cx: &App) {
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        let directory = item
+            .project_path(cx)
+            .and_then(|path| project.read(cx).absolute_path(&path, cx))
+            .and_then(|path| Some(path.parent()?.to_path_buf()));
+        if let Some(directory) = directory {
+            self.set_context(&PaneWorkingDirectory(directory));
+        }
+    }
+
     pub fn handle_item_edit(&mut self, item_id: EntityId, cx: &App) {
         if let Some(preview_item) = self.preview_item() {
             if preview_item.item_id() == item_id && !preview_item.preserve_preview(cx) {
@@ -1161,7 +1351,7 @@ impl Pane {
                     prev_item.deactivated(window, cx);
                 }
             }
-            if let Some(newly_active_item) = self.items.get(index) {
+            if let Some(newly_active_item) = self.items.get(index).cloned() {
                 self.activation_history
                     .retain(|entry| entry.entity_id != newly_active_item.item_id());
                 self.activation_history.push(ActivationHistoryEntry {
@@ -1170,6 +1360,7 @@ impl Pane {
                         .next_activation_timestamp
                         .fetch_add(1, Ordering::SeqCst),
                 });
+                self.update_working_directory(newly_active_item.as_ref(), cx);
             }
 
             self.update_toolbar(window, cx);
@@ -1185,8 +1376,7 @@ impl Pane {
             });
 
             if !self.is_tab_pinned(index) {
-                self.tab_bar_scroll_handle
-                    .scroll_to_item(index - self.pinned_tab_count);
+                self.scroll_to_tab(index - self.pinned_tab_count);
             }
 
             cx.notify();
@@ -1451,6 +1641,41 @@ impl Pane {
         ))
     }
 
+    /// Returns the number of items in this pane that have unsaved changes.
+    pub fn dirty_item_count(&self, cx: &App) -> usize {
+        self.items.iter().filter(|item| item.is_dirty(cx)).count()
+    }
+
+    /// Saves every item in this pane that has unsaved changes, stopping at the first one
+    /// whose save is cancelled.
+    pub fn save_all_items(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(project) = self.project.upgrade() else {
+            return Task::ready(Ok(()));
+        };
+        let dirty_items = self
+            .items
+            .iter()
+            .filter(|item| {
+                item.is_dirty(cx)
+                    && (item.is_singleton(cx) || !item.project_entry_ids(cx).is_empty())
+            })
+            .map(|item| item.boxed_clone())
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |pane, cx| {
+            for item in dirty_items {
+                if !Self::save_item(project.clone(), &pane, &*item, SaveIntent::SaveAll, cx).await?
+                {
+                    break;
+                }
+            }
+            Ok(())
+        })
+    }
+
     pub fn close_items_over_max_tabs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let Some(max_tabs) = WorkspaceSettings::get_global(cx).max_tabs.map(|i| i.get()) else {
             return;
@@ -1555,7 +1780,7 @@ impl Pane {
         &mut self,
         window: &mut Window,
         cx: &mut Context<Pane>,
-        mut save_intent: SaveIntent,
+        save_intent: SaveIntent,
         should_close: impl Fn(EntityId) -> bool,
     ) -> Task<Result<()>> {
         // Find the items to close.
@@ -1594,26 +1819,48 @@ impl Pane {
                     .collect::<Vec<_>>()
             })?;
 
+            // With several dirty items, ask about all of them at once instead of one by one.
+            let mut items_to_save = None;
             if save_intent == SaveIntent::Close && dirty_items.len() > 1 {
-                let answer = pane.update_in(cx, |_, window, cx| {
-                    let detail = Self::file_names_for_prompt(&mut dirty_items.iter(), cx);
-                    window.prompt(
-                        PromptLevel::Warning,
+                let answer = workspace.update_in(cx, |workspace, window, cx| {
+                    let file_names = dirty_items
+                        .iter()
+                        .map(|item| dirty_file_names(item.as_ref(), cx))
+                        .collect::<Vec<_>>();
+                    workspace.prompt_with_checklist(
                         "Do you want to save changes to the following files?",
-                        Some(&detail),
-                        &["Save all", "Discard all", "Cancel"],
+                        PromptOptions::new(["Save all", "Discard all", "Cancel"])
+                            .level(PromptLevel::Warning)
+                            .detail("Unchecked files are closed without saving.")
+                            .checklist(file_names),
+                        window,
                         cx,
                     )
                 })?;
                 match answer.await {
-                    Ok(0) => save_intent = SaveIntent::SaveAll,
-                    Ok(1) => save_intent = SaveIntent::Skip,
-                    Ok(2) => return Ok(()),
-                    _ => {}
+                    Some((0, checked)) => {
+                        items_to_save = Some(
+                            dirty_items
+                                .iter()
+                                .zip(checked)
+                                .filter(|(_, checked)| *checked)
+                                .map(|(item, _)| item.item_id())
+                                .collect::<Vec<_>>(),
+                        )
+                    }
+                    Some((1, _)) => items_to_save = Some(Vec::new()),
+                    _ => return Ok(()),
                 }
             }
 
             for item_to_close in items_to_close {
+                let save_intent = match &items_to_save {
+                    Some(item_ids) if item_ids.contains(&item_to_close.item_id()) => {
+                        SaveIntent::SaveAll
+                    }
+                    Some(_) => SaveIntent::Skip,
+                    None => save_intent,
+                };
                 let mut should_save = true;
                 if save_intent == SaveIntent::Close {
                     workspace.update(cx, |workspace, cx| {
@@ -1701,6 +1948,8 @@ impl Pane {
         let activate_on_close = &ItemSettings::get_global(cx).activate_on_close;
         self.activation_history
             .retain(|entry| entry.entity_id != self.items[item_index].item_id());
+        self.externally_changed_items
+            .remove(&self.items[item_index].item_id());
 
         if self.is_tab_pinned(item_index) {
             self.pinned_tab_count -= 1;
@@ -1882,7 +2131,8 @@ impl Pane {
                     }
                     Ok(1) => {
                         pane.update_in(cx, |pane, window, cx| {
-                            pane.remove_item(item.item_id(), false, true, window, cx)
+                            let close_pane_if_empty = pane.close_pane_if_empty;
+                            pane.remove_item(item.item_id(), false, close_pane_if_empty, window, cx)
                         })?;
                     }
                     _ => return Ok(false),
@@ -1927,13 +2177,16 @@ impl Pane {
                         if pane.save_modals_spawned.insert(item_id) {
                             pane.activate_item(item_ix, true, true, window, cx);
                             let prompt = dirty_message_for(item.project_path(cx));
-                            Some(window.prompt(
-                                PromptLevel::Warning,
-                                &prompt,
-                                None,
-                                &["Save", "Don't Save", "Cancel"],
-                                cx,
-                            ))
+                            let answer = pane.workspace.update(cx, |workspace, cx| {
+                                workspace.prompt(
+                                    prompt,
+                                    PromptOptions::new(["Save", "Don't Save", "Cancel"])
+                                        .level(PromptLevel::Warning),
+                                    window,
+                                    cx,
+                                )
+                            });
+                            Some(answer.unwrap_or_else(|_| Task::ready(None)))
                         } else {
                             None
                         }
@@ -1948,8 +2201,8 @@ impl Pane {
                             }
                         })?;
                         match answer {
-                            Ok(0) => {}
-                            Ok(1) => {
+                            Some(0) => {}
+                            Some(1) => {
                                 // Don't save this file
                                 pane.update_in(cx, |pane, window, cx| {
                                     if pane.is_tab_pinned(item_ix) && !item.can_save(cx) {
@@ -2009,7 +2262,11 @@ impl Pane {
 
     fn can_autosave_item(item: &dyn ItemHandle, cx: &App) -> bool {
         let is_deleted = item.project_entry_ids(cx).is_empty();
-        item.is_dirty(cx) && !item.has_conflict(cx) && item.can_save(cx) && !is_deleted
+        item.is_dirty(cx)
+            && !item.has_conflict(cx)
+            && item.can_save(cx)
+            && item.can_autosave(cx)
+            && !is_deleted
     }
 
     pub fn autosave_item(
@@ -2058,12 +2315,160 @@ impl Pane {
             }
         })?;
 
-        self.remove_item(item_id, false, true, window, cx);
+        self.remove_item(item_id, false, self.close_pane_if_empty, window, cx);
         self.nav_history.remove_item(item_id);
 
         Some(())
     }
 
+    /// Reloads the items that opted into [`Item::reload_on_external_change`] when their file
+    /// changed on disk, given with its new modification time. Items with unsaved changes
+    /// aren't reloaded, they show a conflict banner instead.
+    pub(crate) fn handle_externally_changed_paths(
+        &mut self,
+        changes: &[(ProjectPath, Option<MTime>)],
+        window: &mut Window,
+        cx: &mut Context<Pane>,
+    ) {
+        let changed_items = self
+            .items
+            .iter()
+            .filter(|item| {
+                if !item.reload_on_external_change(cx) {
+                    return false;
+                }
+                let Some(project_path) = item.project_path(cx) else {
+                    return false;
+                };
+                changes.iter().any(|(path, mtime)| {
+                    // A change with the modification time of the item's own save isn't
+                    // external.
+                    *path == project_path && (mtime.is_none() || *mtime != item.saved_mtime(cx))
+                })
+            })
+            .map(|item| item.boxed_clone())
+            .collect::<Vec<_>>();
+        for item in changed_items {
+            if item.is_dirty(cx) {
+                self.externally_changed_items.insert(item.item_id());
+                cx.notify();
+            } else {
+                self.reload_item(item.item_id(), window, cx);
+            }
+        }
+    }
+
+    fn reload_item(&mut self, item_id: EntityId, window: &mut Window, cx: &mut Context<Pane>) {
+        self.externally_changed_items.remove(&item_id);
+        cx.notify();
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        if let Some(ix) = self.index_for_item_id(item_id) {
+            let item = self.items[ix].boxed_clone();
+            item.reload(project, window, cx).detach_and_log_err(cx);
+        }
+    }
+
+    fn render_read_only_banner(
+        &self,
+        item: &dyn ItemHandle,
+        cx: &Context<Pane>,
+    ) -> Option<AnyElement> {
+        if !item.is_read_only(cx) {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .w_full()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .bg(cx.theme().colors().toolbar_background)
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    Icon::new(IconName::FileLock)
+                        .size(IconSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(
+                    div().flex_1().child(
+                        Label::new("This file is read-only.")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+                )
+                .when(item.can_toggle_read_only(cx), |this| {
+                    this.child(
+                        Button::new("make-editable", "Make Editable")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(|pane, _, window, cx| {
+                                pane.toggle_read_only(&ToggleReadOnly, window, cx)
+                            })),
+                    )
+                })
+                .into_any_element(),
+        )
+    }
+
+    /// The banner shown above the given item when its file changed on disk while it had
+    /// unsaved changes, either the one the item contributes or a default one.
+    fn render_conflict_banner(
+        &self,
+        item: &dyn ItemHandle,
+        window: &Window,
+        cx: &Context<Pane>,
+    ) -> Option<AnyElement> {
+        let item_id = item.item_id();
+        if !self.externally_changed_items.contains(&item_id) || !item.is_dirty(cx) {
+            return None;
+        }
+        if let Some(banner) = item.conflict_banner(window, cx) {
+            return Some(banner);
+        }
+
+        Some(
+            h_flex()
+                .w_full()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .bg(cx.theme().status().warning_background)
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    Icon::new(IconName::Warning)
+                        .size(IconSize::Small)
+                        .color(Color::Warning),
+                )
+                .child(
+                    div().flex_1().child(
+                        Label::new("This file has changed on disk since you started editing it.")
+                            .size(LabelSize::Small),
+                    ),
+                )
+                .child(
+                    Button::new("reload-externally-changed", "Reload")
+                        .label_size(LabelSize::Small)
+                        .tooltip(Tooltip::text("Discard your changes and reload the file"))
+                        .on_click(cx.listener(move |pane, _, window, cx| {
+                            pane.reload_item(item_id, window, cx);
+                        })),
+                )
+                .child(
+                    Button::new("keep-externally-changed", "Keep Changes")
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(move |pane, _, _, cx| {
+                            pane.externally_changed_items.remove(&item_id);
+                            cx.notify();
+                        })),
+                )
+                .into_any_element(),
+        )
+    }
+
     fn update_toolbar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let active_item = self
             .items
@@ -2126,6 +2531,22 @@ impl Pane {
         }
     }
 
+    /// Switches the active item in or out of read-only mode, if it supports it.
+    fn toggle_read_only(
+        &mut self,
+        _: &ToggleReadOnly,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(item) = self.active_item() else {
+            return;
+        };
+        if item.can_toggle_read_only(cx) {
+            item.set_read_only(!item.is_read_only(cx), window, cx);
+            cx.notify();
+        }
+    }
+
     fn pin_tab_at(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
         maybe!({
             let pane = cx.entity().clone();
@@ -2215,6 +2636,8 @@ impl Pane {
                 detail: Some(detail),
                 selected: is_active,
                 preview: is_preview,
+                label_color: ItemDecorations::try_global(cx)
+                    .and_then(|decorations| decorations.read(cx).decoration(item, cx).label_color),
             },
             window,
             cx,
@@ -2277,6 +2700,8 @@ impl Pane {
         let is_first_item = ix == 0;
         let is_last_item = ix == self.items.len() - 1;
         let is_pinned = self.is_tab_pinned(ix);
+        // Pinned tabs only show their icon, unless they don't have one.
+        let is_compact = is_pinned && (icon.is_some() || decorated_icon.is_some());
         let position_relative_to_active_item = ix.cmp(&self.active_item_index);
 
         let tab = Tab::new(ix)
@@ -2292,9 +2717,16 @@ impl Pane {
                 ClosePosition::Right => ui::TabCloseSide::End,
             })
             .toggle_state(is_active)
-            .on_click(cx.listener(move |pane: &mut Self, _, window, cx| {
-                pane.activate_item(ix, true, true, window, cx)
-            }))
+            .on_click(
+                cx.listener(move |pane: &mut Self, event: &ClickEvent, window, cx| {
+                    pane.activate_item(ix, true, true, window, cx);
+                    if event.up.click_count == 2
+                        && TabBarSettings::get_global(cx).double_click_zooms
+                    {
+                        pane.toggle_zoom(&ToggleZoom, window, cx);
+                    }
+                }),
+            )
             // TODO: This should be a click listener with the middle mouse button instead of a mouse down listener.
             .on_mouse_down(
                 MouseButton::Middle,
@@ -2356,6 +2788,10 @@ impl Pane {
             })
             .start_slot::<Indicator>(indicator)
             .map(|this| {
+                if is_compact {
+                    return this;
+                }
+
                 let end_slot_action: &'static dyn Action;
                 let end_slot_tooltip_text: &'static str;
                 let end_slot = if is_pinned {
@@ -2423,7 +2859,7 @@ impl Pane {
                         })
                         .flatten(),
                     )
-                    .child(label),
+                    .when(!is_compact, |this| this.child(label)),
             );
 
         let single_entry_to_resolve = self.items[ix]
@@ -2665,8 +3101,18 @@ impl Pane {
         })
     }
 
-    fn render_tab_bar(&mut self, window: &mut Window, cx: &mut Context<Pane>) -> impl IntoElement {
+    fn render_nav_history_buttons(&self, cx: &mut Context<Pane>) -> [AnyElement; 2] {
         let focus_handle = self.focus_handle.clone();
+        let (can_navigate_backward, can_navigate_forward) = match self.workspace.upgrade() {
+            Some(workspace) if self.navigates_across_panes(cx) => {
+                let workspace = workspace.read(cx);
+                (
+                    workspace.can_navigate_across_panes(NavigationMode::GoingBack, cx),
+                    workspace.can_navigate_across_panes(NavigationMode::GoingForward, cx),
+                )
+            }
+            _ => (self.can_navigate_backward(), self.can_navigate_forward()),
+        };
         let navigate_backward = IconButton::new("navigate_backward", IconName::ArrowLeft)
             .icon_size(IconSize::Small)
             .on_click({
@@ -2675,7 +3121,7 @@ impl Pane {
                     entity.update(cx, |pane, cx| pane.navigate_backward(window, cx))
                 }
             })
-            .disabled(!self.can_navigate_backward())
+            .disabled(!can_navigate_backward)
             .tooltip({
                 let focus_handle = focus_handle.clone();
                 move |window, cx| {
@@ -2689,7 +3135,7 @@ impl Pane {
                 let entity = cx.entity().clone();
                 move |_, window, cx| entity.update(cx, |pane, cx| pane.navigate_forward(window, cx))
             })
-            .disabled(!self.can_navigate_forward())
+            .disabled(!can_navigate_forward)
             .tooltip({
                 let focus_handle = focus_handle.clone();
                 move |window, cx| {
@@ -2697,26 +3143,223 @@ impl Pane {
                 }
             });
 
-        let mut tab_items = self
+        let workspace = self.workspace.clone();
+        // Right-clicking a navigation button lists the entries it would go through.
+        [
+            right_click_menu("navigate_backward_history")
+                .trigger(navigate_backward)
+                .menu({
+                    let workspace = workspace.clone();
+                    move |window, cx| {
+                        Self::render_history_menu(
+                            workspace.clone(),
+                            NavigationMode::GoingBack,
+                            window,
+                            cx,
+                        )
+                    }
+                })
+                .into_any_element(),
+            right_click_menu("navigate_forward_history")
+                .trigger(navigate_forward)
+                .menu(move |window, cx| {
+                    Self::render_history_menu(
+                        workspace.clone(),
+                        NavigationMode::GoingForward,
+                        window,
+                        cx,
+                    )
+                })
+                .into_any_element(),
+        ]
+    }
+
+    /// Renders the pinned tabs and the unpinned tabs in view. In a virtualized tab bar, the
+    /// unpinned tabs are preceded and followed by spacers standing in for the tabs out of view.
+    fn render_tabs(
+        &mut self,
+        axis: Axis,
+        window: &mut Window,
+        cx: &mut Context<Pane>,
+    ) -> (Vec<AnyElement>, Vec<AnyElement>) {
+        let unpinned_range = self.unpinned_tab_range(axis);
+        let pinned_tab_count = self.pinned_tab_count;
+        let rendered_range =
+            pinned_tab_count + unpinned_range.start..pinned_tab_count + unpinned_range.end;
+        let focus_handle = self.focus_handle.clone();
+        let tab_details = self.tab_details(cx);
+        let mut tab_items = (0..pinned_tab_count)
+            .chain(rendered_range)
+            .map(|ix| {
+                self.render_tab(
+                    ix,
+                    &*self.items[ix],
+                    tab_details[ix],
+                    &focus_handle,
+                    window,
+                    cx,
+                )
+                .into_any_element()
+            })
+            .collect::<Vec<_>>();
+        let mut unpinned_tabs = tab_items.split_off(pinned_tab_count);
+
+        if let Some(virtualized_tabs) = &self.virtualized_tabs {
+            let unpinned_tab_count = self.items.len() - pinned_tab_count;
+            let spacer = |tab_count: usize| {
+                let extent = virtualized_tabs.tab_extent * tab_count as f32;
+                match axis {
+                    Axis::Horizontal => div().flex_none().w(extent).h_full(),
+                    Axis::Vertical => div().flex_none().h(extent).w_full(),
+                }
+                .into_any_element()
+            };
+            unpinned_tabs.insert(0, spacer(unpinned_range.start));
+            unpinned_tabs.push(spacer(unpinned_tab_count - unpinned_range.end));
+        }
+        (tab_items, unpinned_tabs)
+    }
+
+    /// The level of detail of each tab's description, so that tabs with the same description
+    /// can be told apart. Tabs out of view are included, as telling them apart depends on all of
+    /// the pane's items, but the result is cached until the items change.
+    fn tab_details(&mut self, cx: &App) -> Rc<[usize]> {
+        let item_ids = self
             .items
             .iter()
-            .enumerate()
-            .zip(tab_details(&self.items, cx))
-            .map(|((ix, item), detail)| {
-                self.render_tab(ix, &**item, detail, &focus_handle, window, cx)
-            })
+            .map(|item| item.item_id())
             .collect::<Vec<_>>();
-        let tab_count = tab_items.len();
-        let unpinned_tabs = tab_items.split_off(self.pinned_tab_count);
-        let pinned_tabs = tab_items;
+        match &self.tab_details {
+            Some((cached_ids, details)) if *cached_ids == item_ids => details.clone(),
+            _ => {
+                let details: Rc<[usize]> = tab_details(&self.items, cx).into();
+                self.tab_details = Some((item_ids, details.clone()));
+                details
+            }
+        }
+    }
+
+    pub(crate) fn invalidate_tab_details(&mut self) {
+        self.tab_details = None;
+    }
+
+    /// The unpinned tabs to render, by their index among the unpinned tabs. When the pane has
+    /// many tabs, only the ones scrolled into view are rendered, plus a few on each side.
+    fn unpinned_tab_range(&mut self, axis: Axis) -> Range<usize> {
+        let unpinned_tab_count = self.items.len() - self.pinned_tab_count;
+        let previous = self.virtualized_tabs.take();
+        if unpinned_tab_count <= VIRTUALIZED_TAB_THRESHOLD {
+            return 0..unpinned_tab_count;
+        }
+
+        let scroll_handle = &self.tab_bar_scroll_handle;
+        let mut tab_extent = ESTIMATED_TAB_EXTENT;
+        if let Some(previous) = previous.filter(|previous| previous.axis == axis) {
+            tab_extent = previous.tab_extent;
+            // The scroll handle still has the bounds of the last frame's children: a spacer,
+            // the rendered tabs, another spacer and the drop target.
+            let rendered_count = previous.range.len();
+            if rendered_count > 0 && scroll_handle.children_count() == rendered_count + 3 {
+                let rendered_extent = (1..=rendered_count)
+                    .filter_map(|ix| scroll_handle.bounds_for_item(ix))
+                    .fold(px(0.), |extent, bounds| extent + bounds.size.along(axis));
+                if rendered_extent > px(0.) {
+                    tab_extent = rendered_extent / rendered_count as f32;
+                }
+            }
+        }
+
+        let scroll_offset = -scroll_handle.offset().along(axis);
+        let viewport_extent = scroll_handle.bounds().size.along(axis);
+        let first_visible = (scroll_offset.max(px(0.)) / tab_extent).floor() as usize;
+        let visible_count = (viewport_extent / tab_extent).ceil() as usize + 1;
+        let start = first_visible
+            .saturating_sub(TAB_OVERSCAN)
+            .min(unpinned_tab_count);
+        let end = (first_visible + visible_count + TAB_OVERSCAN).min(unpinned_tab_count);
+        self.virtualized_tabs = Some(VirtualizedTabs {
+            range: start..end,
+            axis,
+            tab_extent,
+        });
+        start..end
+    }
+
+    /// Scrolls the tab bar to reveal the unpinned tab at the given index among the unpinned tabs.
+    fn scroll_to_tab(&self, unpinned_ix: usize) {
+        let Some(virtualized_tabs) = &self.virtualized_tabs else {
+            self.tab_bar_scroll_handle.scroll_to_item(unpinned_ix);
+            return;
+        };
+        if virtualized_tabs.range.contains(&unpinned_ix) {
+            // Skip the spacer before the rendered tabs.
+            self.tab_bar_scroll_handle
+                .scroll_to_item(unpinned_ix - virtualized_tabs.range.start + 1);
+        } else {
+            let offset = -(virtualized_tabs.tab_extent * unpinned_ix as f32);
+            self.tab_bar_scroll_handle
+                .set_offset(Point::default().apply_along(virtualized_tabs.axis, |_| offset));
+        }
+    }
+
+    /// Where the pane was last painted. Panes that are no longer shown keep their last bounds.
+    pub fn bounds(&self) -> Bounds<Pixels> {
+        self.bounds.get()
+    }
+
+    /// Records the bounds of the pinned tabs, given the bounds of their container's children.
+    fn record_pinned_tab_bounds(
+        &self,
+    ) -> impl Fn(Vec<Bounds<Pixels>>, &mut Window, &mut App) + 'static {
+        let pinned_tab_bounds = self.pinned_tab_bounds.clone();
+        move |bounds, _, _| *pinned_tab_bounds.borrow_mut() = bounds
+    }
+
+    /// The item whose tab was painted at the given position, if any.
+    pub fn item_for_tab_at(&self, position: Point<Pixels>) -> Option<&dyn ItemHandle> {
+        if !self.tab_bar_displayed {
+            return None;
+        }
+        let pinned_ix = self
+            .pinned_tab_bounds
+            .borrow()
+            .iter()
+            .position(|bounds| bounds.contains(&position));
+        let ix = if let Some(pinned_ix) = pinned_ix {
+            pinned_ix
+        } else {
+            let scroll_handle = &self.tab_bar_scroll_handle;
+            if !scroll_handle.bounds().contains(&position) {
+                return None;
+            }
+            // The children's bounds are where they'd be without scrolling.
+            let unscrolled_position = position - scroll_handle.offset();
+            let child_ix = (0..scroll_handle.children_count()).find(|&ix| {
+                scroll_handle
+                    .bounds_for_item(ix)
+                    .map_or(false, |bounds| bounds.contains(&unscrolled_position))
+            })?;
+            let unpinned_ix = match &self.virtualized_tabs {
+                // Skip the spacer before the rendered tabs, and ignore the one after them.
+                Some(virtualized_tabs) => {
+                    Some(virtualized_tabs.range.start + child_ix.checked_sub(1)?)
+                        .filter(|ix| virtualized_tabs.range.contains(ix))?
+                }
+                None => child_ix,
+            };
+            self.pinned_tab_count + unpinned_ix
+        };
+        // The drop target after the last tab doesn't belong to an item.
+        self.items.get(ix).map(|item| item.as_ref())
+    }
+
+    fn render_tab_bar(&mut self, window: &mut Window, cx: &mut Context<Pane>) -> impl IntoElement {
+        let tab_count = self.items.len();
+        let (pinned_tabs, unpinned_tabs) = self.render_tabs(Axis::Horizontal, window, cx);
         TabBar::new("tab_bar")
             .when(
                 self.display_nav_history_buttons.unwrap_or_default(),
-                |tab_bar| {
-                    tab_bar
-                        .start_child(navigate_backward)
-                        .start_child(navigate_forward)
-                },
+                |tab_bar| tab_bar.start_children(self.render_nav_history_buttons(cx)),
             )
             .map(|tab_bar| {
                 if self.show_tab_bar_buttons {
@@ -2729,8 +3372,13 @@ impl Pane {
                     tab_bar
                 }
             })
+            .when(
+                TabBarSettings::get_global(cx).show_dirty_item_count && !self.items.is_empty(),
+                |tab_bar| tab_bar.end_child(self.render_dirty_items_menu(cx)),
+            )
             .children(pinned_tabs.len().ne(&0).then(|| {
                 h_flex()
+                    .on_children_prepainted(self.record_pinned_tab_bounds())
                     .children(pinned_tabs)
                     .border_r_2()
                     .border_color(cx.theme().colors().border)
@@ -2743,53 +3391,191 @@ impl Pane {
                     .track_scroll(&self.tab_bar_scroll_handle)
                     .children(unpinned_tabs)
                     .child(
-                        div()
-                            .id("tab_bar_drop_target")
+                        self.render_tab_bar_drop_target(tab_count, cx)
                             .min_w_6()
-                            // HACK: This empty child is currently necessary to force the drop target to appear
-                            // despite us setting a min width above.
-                            .child("")
-                            .h_full()
-                            .flex_grow()
-                            .drag_over::<DraggedTab>(|bar, _, _, cx| {
-                                bar.bg(cx.theme().colors().drop_target_background)
-                            })
-                            .drag_over::<DraggedSelection>(|bar, _, _, cx| {
-                                bar.bg(cx.theme().colors().drop_target_background)
-                            })
-                            .on_drop(cx.listener(
-                                move |this, dragged_tab: &DraggedTab, window, cx| {
-                                    this.drag_split_direction = None;
-                                    this.handle_tab_drop(dragged_tab, this.items.len(), window, cx)
-                                },
-                            ))
-                            .on_drop(cx.listener(
-                                move |this, selection: &DraggedSelection, window, cx| {
-                                    this.drag_split_direction = None;
-                                    this.handle_project_entry_drop(
-                                        &selection.active_selection.entry_id,
-                                        Some(tab_count),
-                                        window,
-                                        cx,
-                                    )
-                                },
-                            ))
-                            .on_drop(cx.listener(move |this, paths, window, cx| {
-                                this.drag_split_direction = None;
-                                this.handle_external_paths_drop(paths, window, cx)
-                            }))
-                            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
-                                if event.up.click_count == 2 {
-                                    window.dispatch_action(
-                                        this.double_click_dispatch_action.boxed_clone(),
-                                        cx,
-                                    );
-                                }
-                            })),
+                            .h_full(),
+                    ),
+            )
+    }
+
+    /// Renders the tabs in a column beside the pane's content, for `tab_bar.placement`
+    /// set to `left` or `right`.
+    fn render_vertical_tab_bar(
+        &mut self,
+        placement: TabBarPlacement,
+        window: &mut Window,
+        cx: &mut Context<Pane>,
+    ) -> impl IntoElement {
+        let tab_count = self.items.len();
+        let (pinned_tabs, unpinned_tabs) = self.render_tabs(Axis::Vertical, window, cx);
+        let (left_buttons, right_buttons) = if self.show_tab_bar_buttons {
+            let render_tab_buttons = self.render_tab_bar_buttons.clone();
+            render_tab_buttons(self, window, cx)
+        } else {
+            (None, None)
+        };
+        let show_nav_history_buttons = self.display_nav_history_buttons.unwrap_or_default();
+        let show_dirty_items_menu =
+            TabBarSettings::get_global(cx).show_dirty_item_count && !self.items.is_empty();
+        let has_header = show_nav_history_buttons
+            || left_buttons.is_some()
+            || right_buttons.is_some()
+            || show_dirty_items_menu;
+
+        v_flex()
+            .id("vertical_tab_bar")
+            .debug_selector(|| "TAB_BAR-vertical_tab_bar".into())
+            .flex_none()
+            .w(VERTICAL_TAB_BAR_WIDTH)
+            .h_full()
+            .overflow_hidden()
+            .bg(cx.theme().colors().tab_bar_background)
+            .border_color(cx.theme().colors().border)
+            .map(|this| match placement {
+                TabBarPlacement::Right => this.border_l_1(),
+                _ => this.border_r_1(),
+            })
+            .when(has_header, |this| {
+                this.child(
+                    h_flex()
+                        .flex_none()
+                        .h(Tab::container_height(cx))
+                        .px(DynamicSpacing::Base06.rems(cx))
+                        .gap(DynamicSpacing::Base04.rems(cx))
+                        .border_b_1()
+                        .border_color(cx.theme().colors().border)
+                        .when(show_nav_history_buttons, |this| {
+                            this.children(self.render_nav_history_buttons(cx))
+                        })
+                        .children(left_buttons)
+                        .child(div().flex_1())
+                        .when(show_dirty_items_menu, |this| {
+                            this.child(self.render_dirty_items_menu(cx))
+                        })
+                        .children(right_buttons),
+                )
+            })
+            .children(pinned_tabs.len().ne(&0).then(|| {
+                v_flex()
+                    .flex_none()
+                    .on_children_prepainted(self.record_pinned_tab_bounds())
+                    .children(pinned_tabs)
+                    .border_b_2()
+                    .border_color(cx.theme().colors().border)
+            }))
+            .child(
+                v_flex()
+                    .id("unpinned tabs")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .track_scroll(&self.tab_bar_scroll_handle)
+                    .children(unpinned_tabs)
+                    .child(
+                        self.render_tab_bar_drop_target(tab_count, cx)
+                            .min_h(Tab::container_height(cx))
+                            .w_full(),
                     ),
             )
     }
 
+    /// The space after the last tab, which tabs, project entries and external paths can be
+    /// dropped on to open them at the end of the pane.
+    fn render_tab_bar_drop_target(
+        &self,
+        tab_count: usize,
+        cx: &mut Context<Pane>,
+    ) -> Stateful<Div> {
+        div()
+            .id("tab_bar_drop_target")
+            // HACK: This empty child is currently necessary to force the drop target to appear
+            // despite the tab bars setting a min size on it.
+            .child("")
+            .flex_grow()
+            .drag_over::<DraggedTab>(|bar, _, _, cx| {
+                bar.bg(cx.theme().colors().drop_target_background)
+            })
+            .drag_over::<DraggedSelection>(|bar, _, _, cx| {
+                bar.bg(cx.theme().colors().drop_target_background)
+            })
+            .on_drop(
+                cx.listener(move |this, dragged_tab: &DraggedTab, window, cx| {
+                    this.drag_split_direction = None;
+                    this.handle_tab_drop(dragged_tab, this.items.len(), window, cx)
+                }),
+            )
+            .on_drop(
+                cx.listener(move |this, selection: &DraggedSelection, window, cx| {
+                    this.drag_split_direction = None;
+                    this.handle_project_entry_drop(
+                        &selection.active_selection.entry_id,
+                        Some(tab_count),
+                        window,
+                        cx,
+                    )
+                }),
+            )
+            .on_drop(cx.listener(move |this, paths, window, cx| {
+                this.drag_split_direction = None;
+                this.handle_external_paths_drop(paths, window, cx)
+            }))
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                if event.up.click_count != 2 {
+                    return;
+                }
+                if TabBarSettings::get_global(cx).double_click_zooms {
+                    this.toggle_zoom(&ToggleZoom, window, cx);
+                } else {
+                    window.dispatch_action(this.double_click_dispatch_action.boxed_clone(), cx);
+                }
+            }))
+    }
+
+    /// Renders the number of unsaved items in this pane, with a menu to save or close them all.
+    fn render_dirty_items_menu(&self, cx: &mut Context<Pane>) -> impl IntoElement {
+        let dirty_item_count = self.dirty_item_count(cx);
+        let tooltip = match dirty_item_count {
+            1 => "1 Unsaved Item".to_string(),
+            count => format!("{count} Unsaved Items"),
+        };
+        let pane = cx.entity().downgrade();
+        PopoverMenu::new("pane-dirty-items-menu")
+            .trigger_with_tooltip(
+                Button::new("dirty_items", dirty_item_count.to_string())
+                    .label_size(LabelSize::Small)
+                    .color(if dirty_item_count > 0 {
+                        Color::Modified
+                    } else {
+                        Color::Muted
+                    }),
+                Tooltip::text(tooltip),
+            )
+            .anchor(Corner::TopRight)
+            .menu(move |window, cx| {
+                let pane = pane.clone();
+                Some(ContextMenu::build(window, cx, |menu, _, _| {
+                    menu.entry("Save All in Pane", None, {
+                        let pane = pane.clone();
+                        move |window, cx| {
+                            pane.update(cx, |pane, cx| {
+                                pane.save_all_items(window, cx).detach_and_log_err(cx);
+                            })
+                            .ok();
+                        }
+                    })
+                    .entry("Close All in Pane", None, move |window, cx| {
+                        pane.update(cx, |pane, cx| {
+                            if let Some(task) =
+                                pane.close_all_items(&CloseAllItems::default(), window, cx)
+                            {
+                                task.detach_and_log_err(cx);
+                            }
+                        })
+                        .ok();
+                    })
+                }))
+            })
+    }
+
     pub fn render_menu_overlay(menu: &Entity<ContextMenu>) -> Div {
         div().absolute().bottom_0().right_0().size_0().child(
             deferred(anchored().anchor(Corner::TopRight).child(menu.clone())).with_priority(1),
@@ -2861,7 +3647,7 @@ impl Pane {
         }
     }
 
-    fn handle_tab_drop(
+    pub(crate) fn handle_tab_drop(
         &mut self,
         dragged_tab: &DraggedTab,
         ix: usize,
@@ -3112,7 +3898,7 @@ impl Pane {
         self.display_nav_history_buttons = display;
     }
 
-    fn get_non_closeable_item_ids(&self, close_pinned: bool) -> Vec<EntityId> {
+    pub(crate) fn get_non_closeable_item_ids(&self, close_pinned: bool) -> Vec<EntityId> {
         if close_pinned {
             return vec![];
         }
@@ -3148,8 +3934,21 @@ impl Render for Pane {
             key_context.add("EmptyPane");
         }
 
+        // Zen mode hides the chrome of the center panes.
+        let zen_mode = self.workspace.upgrade().map_or(false, |workspace| {
+            let workspace = workspace.read(cx);
+            workspace.is_zen_mode()
+                && workspace
+                    .center
+                    .panes()
+                    .iter()
+                    .any(|pane| pane.entity_id() == cx.entity_id())
+        });
         let should_display_tab_bar = self.should_display_tab_bar.clone();
-        let display_tab_bar = should_display_tab_bar(window, cx);
+        let display_tab_bar = !zen_mode && should_display_tab_bar(window, cx);
+        self.tab_bar_displayed = display_tab_bar && self.active_item().is_some();
+        self.pinned_tab_bounds.borrow_mut().clear();
+        let tab_bar_placement = self.tab_bar_placement(cx);
         let Some(project) = self.project.upgrade() else {
             return div().track_focus(&self.focus_handle(cx));
         };
@@ -3222,6 +4021,7 @@ impl Render for Pane {
             .on_action(cx.listener(|pane, action, window, cx| {
                 pane.toggle_pin_tab(action, window, cx);
             }))
+            .on_action(cx.listener(Self::toggle_read_only))
             .when(PreviewTabsSettings::get_global(cx).enabled, |this| {
                 this.on_action(cx.listener(|pane: &mut Pane, _: &TogglePreviewTab, _, cx| {
                     if let Some(active_item_id) = pane.active_item().map(|i| i.item_id()) {
@@ -3297,8 +4097,27 @@ impl Render for Pane {
                     }
                 }),
             )
+            .child({
+                let bounds = self.bounds.clone();
+                canvas(
+                    move |pane_bounds, _, _| bounds.set(pane_bounds),
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full()
+            })
+            // A vertical tab bar is laid out beside the content, on its left or right side.
+            .map(|pane| match tab_bar_placement {
+                TabBarPlacement::Top => pane,
+                TabBarPlacement::Left => pane.flex_row(),
+                TabBarPlacement::Right => pane.flex_row_reverse(),
+            })
             .when(self.active_item().is_some() && display_tab_bar, |pane| {
-                pane.child(self.render_tab_bar(window, cx))
+                if tab_bar_placement.is_vertical() {
+                    pane.child(self.render_vertical_tab_bar(tab_bar_placement, window, cx))
+                } else {
+                    pane.child(self.render_tab_bar(window, cx))
+                }
             })
             .child({
                 let has_worktrees = project.read(cx).visible_worktrees(cx).next().is_some();
@@ -3318,7 +4137,9 @@ impl Render for Pane {
                             div.v_flex()
                                 .size_full()
                                 .overflow_hidden()
-                                .child(self.toolbar.clone())
+                                .when(!zen_mode, |this| this.child(self.toolbar.clone()))
+                                .children(self.render_read_only_banner(item.as_ref(), cx))
+                                .children(self.render_conflict_banner(item.as_ref(), window, cx))
                                 .child(item.to_any())
                         } else {
                             let placeholder = div.h_flex().size_full().justify_center();
@@ -3384,31 +4205,11 @@ impl Render for Pane {
             })
             .on_mouse_down(
                 MouseButton::Navigate(NavigationDirection::Back),
-                cx.listener(|pane, _, window, cx| {
-                    if let Some(workspace) = pane.workspace.upgrade() {
-                        let pane = cx.entity().downgrade();
-                        window.defer(cx, move |window, cx| {
-                            workspace.update(cx, |workspace, cx| {
-                                workspace.go_back(pane, window, cx).detach_and_log_err(cx)
-                            })
-                        })
-                    }
-                }),
+                cx.listener(|pane, _, window, cx| pane.navigate_backward(window, cx)),
             )
             .on_mouse_down(
                 MouseButton::Navigate(NavigationDirection::Forward),
-                cx.listener(|pane, _, window, cx| {
-                    if let Some(workspace) = pane.workspace.upgrade() {
-                        let pane = cx.entity().downgrade();
-                        window.defer(cx, move |window, cx| {
-                            workspace.update(cx, |workspace, cx| {
-                                workspace
-                                    .go_forward(pane, window, cx)
-                                    .detach_and_log_err(cx)
-                            })
-                        })
-                    }
-                }),
+                cx.listener(|pane, _, window, cx| pane.navigate_forward(window, cx)),
             )
     }
 }
@@ -3506,13 +4307,15 @@ impl NavHistory {
                 if state.backward_stack.len() >= MAX_NAVIGATION_HISTORY_LEN {
                     state.backward_stack.pop_front();
                 }
+                let timestamp = state.next_timestamp.fetch_add(1, Ordering::SeqCst);
                 state.backward_stack.push_back(NavigationEntry {
                     item,
                     data: data.map(|data| Box::new(data) as Box<dyn Any + Send>),
-                    timestamp: state.next_timestamp.fetch_add(1, Ordering::SeqCst),
+                    timestamp,
                     is_preview,
                 });
                 state.forward_stack.clear();
+                state.last_navigation_timestamp = Some(timestamp);
             }
             NavigationMode::GoingBack => {
                 if state.forward_stack.len() >= MAX_NAVIGATION_HISTORY_LEN {
@@ -3568,9 +4371,84 @@ impl NavHistory {
     pub fn path_for_item(&self, item_id: EntityId) -> Option<(ProjectPath, Option<PathBuf>)> {
         self.0.lock().paths_by_item.get(&item_id).cloned()
     }
+
+    /// When the most recently closed item of this history was closed, if it has any.
+    pub fn last_closed_timestamp(&self) -> Option<usize> {
+        self.last_timestamp(NavigationMode::ReopeningClosedItem)
+    }
+
+    /// The timestamp of the entry that navigating in `mode` would pop next, if there is one.
+    pub fn last_timestamp(&self, mode: NavigationMode) -> Option<usize> {
+        let state = self.0.lock();
+        state.stack(mode)?.back().map(|entry| entry.timestamp)
+    }
+
+    /// When this history last recorded a navigation to a new location, if it did.
+    pub fn last_navigation_timestamp(&self) -> Option<usize> {
+        self.0.lock().last_navigation_timestamp
+    }
+
+    /// Calls `f` with the entries that navigating in `mode` would pop, most recent first,
+    /// along with the path of their item, if it's known.
+    pub fn for_each_entry_in(
+        &self,
+        mode: NavigationMode,
+        cx: &App,
+        mut f: impl FnMut(&NavigationEntry, Option<ProjectPath>),
+    ) {
+        let state = self.0.lock();
+        let Some(stack) = state.stack(mode) else {
+            return;
+        };
+        for entry in stack.iter().rev() {
+            let path = state
+                .paths_by_item
+                .get(&entry.item.id())
+                .map(|(project_path, _)| project_path.clone())
+                .or_else(|| entry.item.upgrade()?.project_path(cx));
+            f(entry, path);
+        }
+    }
+
+    /// Moves the entries of `other` into this history, ordered by their timestamps, so that
+    /// they can still be navigated to and reopened after `other`'s pane is gone.
+    pub fn take_history(&mut self, other: &NavHistory) {
+        if Arc::ptr_eq(&self.0, &other.0) {
+            return;
+        }
+
+        let other = &mut *other.0.lock();
+        let state = &mut *self.0.lock();
+        for (id, path) in other.paths_by_item.drain() {
+            state.paths_by_item.entry(id).or_insert(path);
+        }
+        state.last_navigation_timestamp = state
+            .last_navigation_timestamp
+            .max(other.last_navigation_timestamp);
+        for (stack, other_stack) in [
+            (&mut state.backward_stack, &mut other.backward_stack),
+            (&mut state.forward_stack, &mut other.forward_stack),
+            (&mut state.closed_stack, &mut other.closed_stack),
+        ] {
+            stack.extend(other_stack.drain(..));
+            stack.make_contiguous().sort_by_key(|entry| entry.timestamp);
+            while stack.len() > MAX_NAVIGATION_HISTORY_LEN {
+                stack.pop_front();
+            }
+        }
+    }
 }
 
 impl NavHistoryState {
+    fn stack(&self, mode: NavigationMode) -> Option<&VecDeque<NavigationEntry>> {
+        match mode {
+            NavigationMode::GoingBack => Some(&self.backward_stack),
+            NavigationMode::GoingForward => Some(&self.forward_stack),
+            NavigationMode::ReopeningClosedItem => Some(&self.closed_stack),
+            NavigationMode::Normal | NavigationMode::Disabled | NavigationMode::ClosingItem => None,
+        }
+    }
+
     pub fn did_update(&self, cx: &mut App) {
         if let Some(pane) = self.pane.upgrade() {
             cx.defer(move |cx| {
@@ -3580,6 +4458,27 @@ impl NavHistoryState {
     }
 }
 
+/// The names of the item's dirty files, for telling items apart when asking which to save.
+fn dirty_file_names(item: &dyn ItemHandle, cx: &App) -> String {
+    let mut file_names = Vec::new();
+    item.for_each_project_item(cx, &mut |_, project_item| {
+        if !project_item.is_dirty() {
+            return;
+        }
+        let file_name = project_item.project_path(cx).and_then(|path| {
+            path.path
+                .file_name()
+                .and_then(|name| name.to_str().map(ToOwned::to_owned))
+        });
+        file_names.push(file_name.unwrap_or_else(|| "untitled".to_string()));
+    });
+    if file_names.is_empty() {
+        "untitled".to_string()
+    } else {
+        file_names.into_iter().join(", ")
+    }
+}
+
 fn dirty_message_for(buffer_path: Option<ProjectPath>) -> String {
     let path = buffer_path
         .as_ref()
@@ -3634,13 +4533,29 @@ pub fn render_item_indicator(item: Box<dyn ItemHandle>, cx: &App) -> Option<Indi
         let indicator_color = match (item.has_conflict(cx), item.is_dirty(cx)) {
             (true, _) => Color::Warning,
             (_, true) => Color::Accent,
-            (false, false) => return None,
+            (false, false) => {
+                if let Some(decoration) = item.tab_decoration(cx) {
+                    return Some(decoration_indicator(decoration));
+                }
+                let decorations = ItemDecorations::try_global(cx)?;
+                let badge = decorations.read(cx).decoration(item.as_ref(), cx).badge?;
+                return Some(decoration_indicator(badge));
+            }
         };
 
         Some(Indicator::dot().color(indicator_color))
     })
 }
 
+fn decoration_indicator(decoration: TabDecoration) -> Indicator {
+    match decoration {
+        TabDecoration::Dot(color) => Indicator::dot().color(color),
+        TabDecoration::Icon(icon, color) => {
+            Indicator::icon(Icon::new(icon).size(IconSize::XSmall)).color(color)
+        }
+    }
+}
+
 impl Render for DraggedTab {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let ui_font = ThemeSettings::get_global(cx).ui_font.clone();
@@ -3649,6 +4564,7 @@ impl Render for DraggedTab {
                 detail: Some(self.detail),
                 selected: false,
                 preview: false,
+                label_color: None,
             },
             window,
             cx,
@@ -3667,7 +4583,9 @@ mod tests {
 
     use super::*;
     use crate::item::test::{TestItem, TestProjectItem};
-    use gpui::{TestAppContext, VisualTestContext};
+    use crate::test::TestWorkspace;
+    use crate::ToolbarItemLocation;
+    use gpui::{AnyView, TestAppContext, VisualTestContext};
     use project::FakeFs;
     use settings::SettingsStore;
     use theme::LoadThemes;
@@ -4480,7 +5398,15 @@ mod tests {
             .unwrap();
 
         cx.executor().run_until_parked();
-        cx.simulate_prompt_answer("Save all");
+        let prompt = workspace
+            .read_with(cx, |workspace, cx| workspace.pending_prompt(cx))
+            .unwrap();
+        prompt.read_with(cx, |prompt, _| {
+            assert_eq!(prompt.checklist(), ["A.txt", "B.txt", "C.txt"]);
+        });
+        workspace.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Save all", cx)
+        });
         save.await.unwrap();
         assert_item_labels(&pane, [], cx);
 
@@ -4502,11 +5428,54 @@ mod tests {
             .unwrap();
 
         cx.executor().run_until_parked();
-        cx.simulate_prompt_answer("Discard all");
+        workspace.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Discard all", cx)
+        });
         save.await.unwrap();
         assert_item_labels(&pane, [], cx);
     }
 
+    #[gpui::test]
+    async fn test_close_items_saves_only_checked_items(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let item_a = add_labeled_item(&pane, "A", true, cx);
+        item_a.update(cx, |item, cx| {
+            item.project_items
+                .push(TestProjectItem::new_dirty(1, "A.txt", cx))
+        });
+        let item_b = add_labeled_item(&pane, "B", true, cx);
+        item_b.update(cx, |item, cx| {
+            item.project_items
+                .push(TestProjectItem::new_dirty(2, "B.txt", cx))
+        });
+
+        let close = pane
+            .update_in(cx, |pane, window, cx| {
+                pane.close_all_items(&CloseAllItems::default(), window, cx)
+            })
+            .unwrap();
+        cx.executor().run_until_parked();
+        workspace
+            .read_with(cx, |workspace, cx| workspace.pending_prompt(cx))
+            .unwrap()
+            .update(cx, |prompt, cx| prompt.set_checked(1, false, cx));
+        workspace.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Save all", cx)
+        });
+        close.await.unwrap();
+
+        assert_item_labels(&pane, [], cx);
+        assert_eq!(item_a.read_with(cx, |item, _| item.save_count), 1);
+        assert_eq!(item_b.read_with(cx, |item, _| item.save_count), 0);
+    }
+
     #[gpui::test]
     async fn test_close_with_save_intent(cx: &mut TestAppContext) {
         init_test(cx);
@@ -4551,6 +5520,199 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_save_all_items(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let a = add_labeled_item(&pane, "A", true, cx);
+        a.update(cx, |item, cx| {
+            item.project_items
+                .push(TestProjectItem::new_dirty(1, "A.txt", cx))
+        });
+        let b = add_labeled_item(&pane, "B", false, cx);
+        let c = add_labeled_item(&pane, "C", true, cx);
+        c.update(cx, |item, cx| {
+            item.project_items
+                .push(TestProjectItem::new_dirty(2, "C.txt", cx))
+        });
+        assert_item_labels(&pane, ["A^", "B", "C*^"], cx);
+        pane.update(cx, |pane, cx| assert_eq!(pane.dirty_item_count(cx), 2));
+
+        let other_pane = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_pane(pane.clone(), SplitDirection::Right, window, cx)
+        });
+        let d = add_labeled_item(&other_pane, "D", true, cx);
+        d.update(cx, |item, cx| {
+            item.project_items
+                .push(TestProjectItem::new_dirty(3, "D.txt", cx))
+        });
+
+        pane.update_in(cx, |pane, window, cx| pane.save_all_items(window, cx))
+            .await
+            .unwrap();
+
+        assert_item_labels(&pane, ["A", "B", "C*"], cx);
+        pane.update(cx, |pane, cx| assert_eq!(pane.dirty_item_count(cx), 0));
+        cx.update(|_, cx| {
+            assert_eq!(a.read(cx).save_count, 1);
+            assert_eq!(b.read(cx).save_count, 0);
+            assert_eq!(c.read(cx).save_count, 1);
+            assert_eq!(d.read(cx).save_count, 0);
+        });
+        other_pane.update(cx, |pane, cx| assert_eq!(pane.dirty_item_count(cx), 1));
+    }
+
+    #[gpui::test]
+    async fn test_preview_tabs(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        add_labeled_item(&pane, "A", false, cx);
+        let b = open_labeled_item(&pane, 2, "B", true, cx);
+        assert_item_labels(&pane, ["A", "B*"], cx);
+        pane.update(cx, |pane, _| {
+            assert_eq!(pane.preview_item_id(), Some(b.item_id()))
+        });
+
+        // Opening another item as preview replaces the preview tab in place.
+        let c = open_labeled_item(&pane, 3, "C", true, cx);
+        assert_item_labels(&pane, ["A", "C*"], cx);
+        pane.update(cx, |pane, _| {
+            assert_eq!(pane.preview_item_id(), Some(c.item_id()))
+        });
+
+        // Opening the preview item permanently promotes its tab.
+        open_labeled_item(&pane, 3, "C", false, cx);
+        assert_item_labels(&pane, ["A", "C*"], cx);
+        pane.update(cx, |pane, _| assert_eq!(pane.preview_item_id(), None));
+
+        let d = open_labeled_item(&pane, 4, "D", true, cx);
+        assert_item_labels(&pane, ["A", "C", "D*"], cx);
+
+        // Editing the preview item promotes its tab as well.
+        pane.update(cx, |pane, cx| {
+            assert_eq!(pane.preview_item_id(), Some(d.item_id()));
+            pane.handle_item_edit(d.item_id(), cx);
+            assert_eq!(pane.preview_item_id(), None);
+        });
+
+        // With preview tabs disabled, every opened item gets its own tab.
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<PreviewTabsSettings>(cx, |settings| {
+                settings.enabled = Some(false)
+            });
+        });
+        open_labeled_item(&pane, 5, "E", true, cx);
+        open_labeled_item(&pane, 6, "F", true, cx);
+        assert_item_labels(&pane, ["A", "C", "D", "E", "F*"], cx);
+        pane.update(cx, |pane, _| assert_eq!(pane.preview_item_id(), None));
+    }
+
+    #[gpui::test]
+    async fn test_vertical_tab_bar(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        add_labeled_item(&pane, "A", false, cx);
+        add_labeled_item(&pane, "B", false, cx);
+
+        let tab_bars = |cx: &mut VisualTestContext| {
+            crate::test::draw(cx);
+            (
+                cx.debug_bounds("TAB_BAR-tab_bar"),
+                cx.debug_bounds("TAB_BAR-vertical_tab_bar"),
+            )
+        };
+        let (horizontal, vertical) = tab_bars(cx);
+        assert!(horizontal.is_some());
+        assert!(vertical.is_none());
+
+        // Placed on the left, the tabs are listed in a column beside the content.
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<TabBarSettings>(cx, |settings| {
+                settings.placement = Some(TabBarPlacement::Left)
+            });
+        });
+        let (horizontal, vertical) = tab_bars(cx);
+        assert!(horizontal.is_none());
+        let vertical = vertical.unwrap();
+        let center = cx.debug_bounds("CENTER").unwrap();
+        assert_eq!(vertical.origin.x, center.origin.x);
+        assert_eq!(vertical.size.height, center.size.height);
+        let tab_a = cx.debug_bounds("TAB-0").unwrap();
+        let tab_b = cx.debug_bounds("TAB-1").unwrap();
+        assert_eq!(tab_a.origin.x, tab_b.origin.x);
+        assert!(tab_a.origin.y < tab_b.origin.y);
+
+        // A pane can override the setting, here to show its tabs on the right.
+        pane.update(cx, |pane, cx| {
+            pane.set_tab_bar_placement(Some(TabBarPlacement::Right), cx)
+        });
+        let vertical = tab_bars(cx).1.unwrap();
+        let center = cx.debug_bounds("CENTER").unwrap();
+        assert_eq!(vertical.right(), center.right());
+
+        pane.update(cx, |pane, cx| {
+            pane.set_tab_bar_placement(Some(TabBarPlacement::Top), cx)
+        });
+        let (horizontal, vertical) = tab_bars(cx);
+        assert!(horizontal.is_some());
+        assert!(vertical.is_none());
+    }
+
+    #[gpui::test]
+    async fn test_pinned_tabs_render_compactly(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        pane.update_in(cx, |pane, window, cx| {
+            let item = cx.new(|cx| {
+                TestItem::new(cx)
+                    .with_label("A")
+                    .with_tab_icon(IconName::File)
+            });
+            pane.add_item(Box::new(item), false, false, None, window, cx);
+            let item = cx.new(|cx| TestItem::new(cx).with_label("B"));
+            pane.add_item(Box::new(item), false, false, None, window, cx);
+        });
+
+        // A pinned tab with an icon only shows that icon, without its unpin button.
+        pane.update_in(cx, |pane, window, cx| pane.pin_tab_at(0, window, cx));
+        crate::test::draw(cx);
+        assert_item_labels(&pane, ["A", "B*"], cx);
+        pane.update(cx, |pane, _| assert_eq!(pane.pinned_count(), 1));
+        assert!(cx.debug_bounds("ICON-Pin").is_none());
+
+        // Without an icon, the tab would be empty, so it renders in full.
+        pane.update_in(cx, |pane, window, cx| pane.pin_tab_at(1, window, cx));
+        crate::test::draw(cx);
+        assert_item_labels(&pane, ["A", "B*"], cx);
+        pane.update(cx, |pane, _| assert_eq!(pane.pinned_count(), 2));
+        let unpin_button = cx.debug_bounds("ICON-Pin").unwrap();
+        let tab_b = cx.debug_bounds("TAB-1").unwrap();
+        assert!(tab_b.contains(&unpin_button.center()));
+    }
+
     #[gpui::test]
     async fn test_close_all_items_including_pinned(cx: &mut TestAppContext) {
         init_test(cx);
@@ -4649,6 +5811,527 @@ mod tests {
         assert_item_labels(&pane2, ["B*"], cx);
     }
 
+    #[gpui::test]
+    async fn test_drag_tab_between_panes(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let pane1 = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let pane2 = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_pane(pane1.clone(), SplitDirection::Right, window, cx)
+        });
+        let item_a = add_labeled_item(&pane1, "A", false, cx);
+        let item_b = add_labeled_item(&pane1, "B", false, cx);
+        add_labeled_item(&pane2, "C", false, cx);
+        assert_item_labels(&pane1, ["A", "B*"], cx);
+        assert_item_labels(&pane2, ["C*"], cx);
+
+        // Dropping a tab onto another pane's tab bar moves the item into that pane.
+        pane2.update_in(cx, |pane, window, cx| {
+            let dragged_tab = DraggedTab {
+                pane: pane1.clone(),
+                item: item_b.boxed_clone(),
+                ix: 1,
+                detail: 0,
+                is_active: true,
+            };
+            pane.handle_tab_drop(&dragged_tab, 0, window, cx);
+        });
+        cx.run_until_parked();
+        assert_item_labels(&pane1, ["A*"], cx);
+        assert_item_labels(&pane2, ["B*", "C"], cx);
+
+        // Dropping a tab onto a pane's edge splits the pane and moves the item into the new one.
+        pane2.update_in(cx, |pane, window, cx| {
+            let dragged_tab = DraggedTab {
+                pane: pane1.clone(),
+                item: item_a.boxed_clone(),
+                ix: 0,
+                detail: 0,
+                is_active: true,
+            };
+            pane.drag_split_direction = Some(SplitDirection::Down);
+            pane.handle_tab_drop(&dragged_tab, 0, window, cx);
+        });
+        cx.run_until_parked();
+        assert_item_labels(&pane2, ["B*", "C"], cx);
+        workspace.update(cx, |workspace, cx| {
+            let new_pane = workspace.active_pane().clone();
+            assert_ne!(new_pane, pane1);
+            assert_ne!(new_pane, pane2);
+            assert_eq!(
+                new_pane.read(cx).active_item().unwrap().item_id(),
+                item_a.item_id()
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_virtualized_tab_bar(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let items = (0..500)
+            .map(|ix| add_labeled_item(&pane, &format!("{ix}"), false, cx))
+            .collect::<Vec<_>>();
+        let take_rendered_tabs = |cx: &mut VisualTestContext| {
+            cx.update(|window, _| window.refresh());
+            cx.run_until_parked();
+            items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| {
+                    item.read_with(cx, |item, _| item.tab_detail.take())
+                        .is_some()
+                })
+                .map(|(ix, _)| ix)
+                .collect::<Vec<_>>()
+        };
+
+        // Only the tabs in view and a few beyond them are rendered.
+        let rendered = take_rendered_tabs(cx);
+        assert!(rendered.contains(&0));
+        assert!(rendered.len() < 100, "rendered {} tabs", rendered.len());
+
+        // Activating a tab out of view scrolls to it.
+        pane.update_in(cx, |pane, window, cx| {
+            pane.activate_item(250, false, false, window, cx);
+        });
+        let rendered = take_rendered_tabs(cx);
+        assert!(rendered.contains(&250));
+        assert!(!rendered.contains(&0));
+        assert!(rendered.len() < 100, "rendered {} tabs", rendered.len());
+    }
+
+    #[gpui::test]
+    async fn test_item_decorations(cx: &mut TestAppContext) {
+        use crate::item_decorations::{ItemDecoration, ItemDecorationProvider};
+
+        struct LabelColorProvider;
+
+        impl ItemDecorationProvider for LabelColorProvider {
+            fn decoration(&self, item: &dyn ItemHandle, cx: &App) -> Option<ItemDecoration> {
+                item.project_path(cx).map(|_| ItemDecoration {
+                    label_color: Some(Color::Created),
+                    badge: None,
+                })
+            }
+        }
+
+        struct BadgeProvider;
+
+        impl ItemDecorationProvider for BadgeProvider {
+            fn decoration(&self, _: &dyn ItemHandle, _: &App) -> Option<ItemDecoration> {
+                Some(ItemDecoration {
+                    label_color: Some(Color::Error),
+                    badge: Some(TabDecoration::Dot(Color::Modified)),
+                })
+            }
+        }
+
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let file_item = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(1, "a.txt", cx)])
+        });
+        let untitled_item = cx.new(TestItem::new);
+        pane.update_in(cx, |pane, window, cx| {
+            pane.add_item(Box::new(file_item.clone()), true, true, None, window, cx);
+            pane.add_item(
+                Box::new(untitled_item.clone()),
+                true,
+                true,
+                None,
+                window,
+                cx,
+            );
+        });
+
+        // The provider registered first wins where several decorate the same part of a tab.
+        let decorations = cx.update(ItemDecorations::global);
+        decorations.update(cx, |decorations, cx| {
+            decorations.register(LabelColorProvider, cx);
+            decorations.register(BadgeProvider, cx);
+        });
+        cx.update(|_, cx| {
+            let decorations = decorations.read(cx);
+            assert_eq!(
+                decorations.decoration(&file_item, cx),
+                ItemDecoration {
+                    label_color: Some(Color::Created),
+                    badge: Some(TabDecoration::Dot(Color::Modified)),
+                }
+            );
+            assert_eq!(
+                decorations.decoration(&untitled_item, cx),
+                ItemDecoration {
+                    label_color: Some(Color::Error),
+                    badge: Some(TabDecoration::Dot(Color::Modified)),
+                }
+            );
+        });
+
+        // Only panes showing a changed file are re-rendered.
+        let notifications = Rc::new(std::cell::Cell::new(0));
+        let _subscription = cx.update(|_, cx| {
+            let notifications = notifications.clone();
+            cx.observe(&pane, move |_, _| {
+                notifications.set(notifications.get() + 1)
+            })
+        });
+        let file_path = file_item.read_with(cx, |item, cx| {
+            item.project_items[0].read(cx).project_path.clone().unwrap()
+        });
+        let other_path = ProjectPath {
+            worktree_id: file_path.worktree_id,
+            path: std::path::Path::new("b.txt").into(),
+        };
+        decorations.update(cx, |decorations, cx| {
+            decorations.decorations_changed(Some(vec![other_path]), cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(notifications.get(), 0);
+        decorations.update(cx, |decorations, cx| {
+            decorations.decorations_changed(Some(vec![file_path]), cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(notifications.get(), 1);
+    }
+
+    #[gpui::test]
+    async fn test_reload_on_external_change(cx: &mut TestAppContext) {
+        let mut test = TestWorkspace::new(cx)
+            .files(serde_json::json!({ "a.txt": "" }))
+            .build()
+            .await;
+        let project = test.project.clone();
+        let fs = test.fs.clone();
+        let cx = &mut test.cx;
+        let pane = test
+            .workspace
+            .read_with(cx, |workspace, _| workspace.active_pane().clone());
+
+        let path = project.read_with(cx, |project, cx| ProjectPath {
+            worktree_id: project.worktrees(cx).next().unwrap().read(cx).id(),
+            path: std::path::Path::new("a.txt").into(),
+        });
+        let project_item = cx.update(|_, cx| {
+            let project_item = TestProjectItem::new(1, "a.txt", cx);
+            project_item.update(cx, |project_item, _| {
+                project_item.project_path = Some(path.clone())
+            });
+            project_item
+        });
+        let opted_in_item = cx.new(|cx| {
+            TestItem::new(cx)
+                .with_project_items(&[project_item.clone()])
+                .with_reload_on_external_change(true)
+        });
+        let other_item = cx.new(|cx| TestItem::new(cx).with_project_items(&[project_item]));
+        pane.update_in(cx, |pane, window, cx| {
+            pane.add_item(Box::new(other_item.clone()), true, true, None, window, cx);
+            pane.add_item(
+                Box::new(opted_in_item.clone()),
+                true,
+                true,
+                None,
+                window,
+                cx,
+            );
+        });
+        let reload_counts = |cx: &mut VisualTestContext| {
+            (
+                opted_in_item.read_with(cx, |item, _| item.reload_count),
+                other_item.read_with(cx, |item, _| item.reload_count),
+            )
+        };
+
+        // Clean items that opted in are reloaded.
+        fs.insert_file("/root/a.txt", b"one".to_vec()).await;
+        cx.run_until_parked();
+        assert_eq!(reload_counts(cx), (1, 0));
+
+        // Unless the change was made by the item's own save.
+        opted_in_item.update(cx, |item, _| {
+            item.saved_mtime = Some(MTime::from_seconds_and_nanos(1000, 0))
+        });
+        fs.set_next_mtime(std::time::UNIX_EPOCH + std::time::Duration::from_secs(1000));
+        fs.insert_file("/root/a.txt", b"two".to_vec()).await;
+        cx.run_until_parked();
+        assert_eq!(reload_counts(cx), (1, 0));
+
+        // Dirty ones show a conflict banner instead, until the user reloads them.
+        opted_in_item.update(cx, |item, _| item.is_dirty = true);
+        fs.insert_file("/root/a.txt", b"three".to_vec()).await;
+        cx.run_until_parked();
+        assert_eq!(reload_counts(cx), (1, 0));
+        pane.read_with(cx, |pane, _| {
+            assert!(pane
+                .externally_changed_items
+                .contains(&opted_in_item.entity_id()))
+        });
+        pane.update_in(cx, |pane, window, cx| {
+            pane.reload_item(opted_in_item.entity_id(), window, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(reload_counts(cx), (2, 0));
+        pane.read_with(cx, |pane, _| {
+            assert!(pane.externally_changed_items.is_empty())
+        });
+    }
+
+    #[gpui::test]
+    async fn test_toggle_read_only(cx: &mut TestAppContext) {
+        let mut test = TestWorkspace::new(cx).build().await;
+        let cx = &mut test.cx;
+        let pane = test
+            .workspace
+            .read_with(cx, |workspace, _| workspace.active_pane().clone());
+
+        let read_only_item = cx.new(|cx| TestItem::new(cx).with_read_only(true));
+        let other_item = cx.new(TestItem::new);
+        let toggle_item = |item: &Entity<TestItem>, cx: &mut VisualTestContext| {
+            pane.update_in(cx, |pane, window, cx| {
+                let ix = pane.index_for_item(item).unwrap();
+                pane.activate_item(ix, true, true, window, cx);
+                pane.toggle_read_only(&ToggleReadOnly, window, cx);
+            });
+            item.read_with(cx, |item, cx| item.is_read_only(cx))
+        };
+        pane.update_in(cx, |pane, window, cx| {
+            pane.add_item(
+                Box::new(read_only_item.clone()),
+                true,
+                true,
+                None,
+                window,
+                cx,
+            );
+            pane.add_item(Box::new(other_item.clone()), true, true, None, window, cx);
+        });
+
+        let decoration = |item: &Entity<TestItem>, cx: &mut VisualTestContext| {
+            item.read_with(cx, |item, cx| item.tab_decoration(cx))
+        };
+        assert_eq!(
+            decoration(&read_only_item, cx),
+            Some(TabDecoration::Icon(IconName::FileLock, Color::Muted))
+        );
+        assert!(!toggle_item(&read_only_item, cx));
+        assert_eq!(decoration(&read_only_item, cx), None);
+        assert!(toggle_item(&read_only_item, cx));
+        // Items that don't support read-only mode are left alone.
+        assert!(!toggle_item(&other_item, cx));
+    }
+
+    #[gpui::test]
+    async fn test_item_indicator_precedence(cx: &mut TestAppContext) {
+        let mut test = TestWorkspace::new(cx).build().await;
+        let cx = &mut test.cx;
+
+        let indicator_color = |build: fn(TestItem) -> TestItem, cx: &mut VisualTestContext| {
+            let item = cx.new(|cx| build(TestItem::new(cx)));
+            cx.update(|_, cx| render_item_indicator(Box::new(item), cx).map(|i| i.color))
+        };
+
+        // A clean item shows its own decoration...
+        assert_eq!(
+            indicator_color(|item| item.with_read_only(true), cx),
+            Some(Color::Muted)
+        );
+        // ...but unsaved changes and conflicts take precedence over it.
+        assert_eq!(
+            indicator_color(|item| item.with_read_only(true).with_dirty(true), cx),
+            Some(Color::Accent)
+        );
+        assert_eq!(
+            indicator_color(
+                |item| item
+                    .with_read_only(true)
+                    .with_dirty(true)
+                    .with_conflict(true),
+                cx
+            ),
+            Some(Color::Warning)
+        );
+        assert_eq!(indicator_color(|item| item, cx), None);
+    }
+
+    #[gpui::test]
+    async fn test_item_toolbar_items(cx: &mut TestAppContext) {
+        let mut test = TestWorkspace::new(cx).build().await;
+        let cx = &mut test.cx;
+        let pane = test
+            .workspace
+            .read_with(cx, |workspace, _| workspace.active_pane().clone());
+
+        let zoom_buttons: AnyView = cx.new(TestItem::new).into();
+        let image_item = cx.new(|cx| {
+            let mut item = TestItem::new(cx);
+            item.toolbar_items = vec![(zoom_buttons.clone(), ToolbarItemLocation::PrimaryRight)];
+            item
+        });
+        let other_item = cx.new(TestItem::new);
+        pane.update_in(cx, |pane, window, cx| {
+            pane.add_item(Box::new(image_item.clone()), true, true, None, window, cx);
+            pane.add_item(Box::new(other_item.clone()), true, true, None, window, cx);
+        });
+        let right_toolbar_items = |cx: &mut VisualTestContext| {
+            cx.update(|window, cx| {
+                pane.read(cx)
+                    .toolbar()
+                    .read(cx)
+                    .visible_items(window, cx)
+                    .remove(&ToolbarItemLocation::PrimaryRight)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|view| view.entity_id())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // The contributed items are only shown while their item is active.
+        assert_eq!(right_toolbar_items(cx), []);
+        pane.update_in(cx, |pane, window, cx| {
+            pane.activate_item(0, true, true, window, cx)
+        });
+        assert_eq!(right_toolbar_items(cx), [zoom_buttons.entity_id()]);
+
+        // The toolbar is re-rendered when the active item changes what it contributes.
+        let toolbar = pane.read_with(cx, |pane, _| pane.toolbar().clone());
+        let toolbar_notified = Rc::new(Cell::new(false));
+        let _subscription = cx.update(|_, cx| {
+            let toolbar_notified = toolbar_notified.clone();
+            cx.observe(&toolbar, move |_, _| toolbar_notified.set(true))
+        });
+        cx.run_until_parked();
+        image_item.update(cx, |item, cx| {
+            item.toolbar_items.clear();
+            cx.notify();
+        });
+        assert!(toolbar_notified.get());
+        assert_eq!(right_toolbar_items(cx), []);
+    }
+
+    #[gpui::test]
+    async fn test_toolbar_flex_items_fill_the_space_between(cx: &mut TestAppContext) {
+        struct ToolbarTestItem {
+            selector: &'static str,
+            width: Option<Pixels>,
+        }
+
+        impl Render for ToolbarTestItem {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let selector = self.selector;
+                div()
+                    .debug_selector(move || selector.into())
+                    .h(px(16.))
+                    .map(|el| match self.width {
+                        Some(width) => el.w(width),
+                        None => el.w_full(),
+                    })
+            }
+        }
+
+        let mut test = TestWorkspace::new(cx).build().await;
+        let cx = &mut test.cx;
+        let pane = test
+            .workspace
+            .read_with(cx, |workspace, _| workspace.active_pane().clone());
+
+        let toolbar_item = |selector, width, cx: &mut VisualTestContext| -> AnyView {
+            cx.new(|_| ToolbarTestItem { selector, width }).into()
+        };
+        let left = toolbar_item("LEFT", Some(px(50.)), cx);
+        let flex = toolbar_item("FLEX", None, cx);
+        let right = toolbar_item("RIGHT", Some(px(50.)), cx);
+        let item = cx.new(|cx| {
+            let mut item = TestItem::new(cx);
+            item.toolbar_items = vec![
+                (right, ToolbarItemLocation::PrimaryRight),
+                (flex, ToolbarItemLocation::PrimaryFlex),
+                (left, ToolbarItemLocation::PrimaryLeft),
+            ];
+            item
+        });
+        pane.update_in(cx, |pane, window, cx| {
+            pane.add_item(Box::new(item), true, true, None, window, cx);
+        });
+        crate::test::draw(cx);
+
+        // The flex items sit between the left and the right items, whatever the order they're
+        // contributed in, and take up all the width those leave.
+        let left = cx.debug_bounds("LEFT").unwrap();
+        let flex = cx.debug_bounds("FLEX").unwrap();
+        let right = cx.debug_bounds("RIGHT").unwrap();
+        assert_eq!(left.size.width, px(50.));
+        assert_eq!(right.size.width, px(50.));
+        assert!(left.right() < flex.left());
+        assert!(flex.right() < right.left());
+        assert_eq!(flex.left() - left.right(), right.left() - flex.right());
+        assert!(flex.left() - left.right() < px(20.));
+    }
+
+    #[gpui::test]
+    async fn test_double_click_tab_to_zoom(cx: &mut TestAppContext) {
+        let mut test = TestWorkspace::new(cx).build().await;
+        let cx = &mut test.cx;
+        let pane = test
+            .workspace
+            .read_with(cx, |workspace, _| workspace.active_pane().clone());
+        add_labeled_item(&pane, "A", false, cx);
+        add_labeled_item(&pane, "B", false, cx);
+
+        let double_click_tab = |ix: usize, cx: &mut VisualTestContext| {
+            crate::test::draw(cx);
+            let position = cx.debug_bounds(&format!("TAB-{ix}")).unwrap().center();
+            for click_count in 1..=2 {
+                cx.simulate_event(MouseDownEvent {
+                    position,
+                    modifiers: gpui::Modifiers::none(),
+                    button: MouseButton::Left,
+                    click_count,
+                    first_mouse: false,
+                });
+                cx.simulate_event(gpui::MouseUpEvent {
+                    position,
+                    modifiers: gpui::Modifiers::none(),
+                    button: MouseButton::Left,
+                    click_count,
+                });
+            }
+            cx.run_until_parked();
+        };
+        let is_zoomed = |cx: &mut VisualTestContext| pane.read_with(cx, |pane, _| pane.is_zoomed());
+
+        double_click_tab(0, cx);
+        assert!(!is_zoomed(cx));
+        assert_item_labels(&pane, ["A*", "B"], cx);
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<TabBarSettings>(cx, |settings| {
+                settings.double_click_zooms = Some(true)
+            });
+        });
+        double_click_tab(1, cx);
+        assert!(is_zoomed(cx));
+        assert_item_labels(&pane, ["A", "B*"], cx);
+        double_click_tab(1, cx);
+        assert!(!is_zoomed(cx));
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
@@ -4681,6 +6364,38 @@ mod tests {
         })
     }
 
+    fn open_labeled_item(
+        pane: &Entity<Pane>,
+        id: u64,
+        label: &str,
+        allow_preview: bool,
+        cx: &mut VisualTestContext,
+    ) -> Box<dyn ItemHandle> {
+        pane.update_in(cx, |pane, window, cx| {
+            pane.open_item(
+                Some(ProjectEntryId::from_proto(id)),
+                true,
+                allow_preview,
+                true,
+                None,
+                window,
+                cx,
+                |_, cx| {
+                    Box::new(cx.new(|cx| {
+                        TestItem::new(cx)
+                            .with_singleton(true)
+                            .with_label(label)
+                            .with_project_items(&[TestProjectItem::new(
+                                id,
+                                &format!("{label}.txt"),
+                                cx,
+                            )])
+                    }))
+                },
+            )
+        })
+    }
+
     fn set_labeled_items<const COUNT: usize>(
         pane: &Entity<Pane>,
         labels: [&str; COUNT],
@@ -9,19 +9,20 @@ use client::proto::PeerId;
 use collections::HashMap;
 use gpui::{
     point, size, Along, AnyView, AnyWeakView, Axis, Bounds, Context, Entity, IntoElement,
-    MouseButton, Pixels, Point, StyleRefinement, Window,
+    MouseButton, Pixels, Point, StyleRefinement, WeakEntity, Window,
 };
 use parking_lot::Mutex;
 use project::Project;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use settings::Settings;
-use std::sync::Arc;
+use std::{fmt::Write as _, sync::Arc};
 use ui::prelude::*;
 
 pub const HANDLE_HITBOX_SIZE: f32 = 4.0;
 const HORIZONTAL_MIN_SIZE: f32 = 80.;
 const VERTICAL_MIN_SIZE: f32 = 100.;
+const MIN_SPLIT_RATIO: f32 = 0.1;
 
 /// One or many panes, arranged in a horizontal or vertical axis due to a split.
 /// Panes have all their tabs and capabilities preserved, and can be split again or resized.
@@ -57,7 +58,68 @@ impl PaneGroup {
                     Err(anyhow!("Pane not found"))
                 }
             }
-            Member::Axis(axis) => axis.split(old_pane, new_pane, direction),
+            Member::Axis(axis) => axis.split(old_pane, new_pane, direction, None),
+        }
+    }
+
+    /// Adds `new_pane` along an edge of the whole group, spanning its full width or height.
+    pub fn split_edge(&mut self, new_pane: &Entity<Pane>, direction: SplitDirection) {
+        let axis = direction.axis();
+        let root = std::mem::replace(&mut self.root, Member::Pane(new_pane.clone()));
+        let (mut members, mut flexes) = match root {
+            Member::Axis(root) if root.axis == axis => {
+                let flexes = root.flexes.lock().clone();
+                (root.members, flexes)
+            }
+            root => (vec![root], vec![1.]),
+        };
+        if direction.increasing() {
+            members.push(Member::Pane(new_pane.clone()));
+            flexes.push(1.);
+        } else {
+            members.insert(0, Member::Pane(new_pane.clone()));
+            flexes.insert(0, 1.);
+        }
+        self.root = Member::Axis(PaneAxis::load(axis, members, Some(flexes)));
+    }
+
+    /// The fraction of the group's width or height a pane added by [`Self::split_edge`] would
+    /// take up.
+    pub fn edge_split_fraction(&self, direction: SplitDirection) -> f32 {
+        match &self.root {
+            Member::Axis(root) if root.axis == direction.axis() => {
+                let flexes = root.flexes.lock();
+                1. / (flexes.iter().sum::<f32>() + 1.)
+            }
+            _ => 0.5,
+        }
+    }
+
+    /// Splits `old_pane`, giving `ratio` of its space to `new_pane` and the rest to `old_pane`.
+    /// Other panes keep their sizes, unlike [`Self::split`] which evens out the flexes.
+    pub fn split_with_ratio(
+        &mut self,
+        old_pane: &Entity<Pane>,
+        new_pane: &Entity<Pane>,
+        direction: SplitDirection,
+        ratio: f32,
+    ) -> Result<()> {
+        let ratio = ratio.clamp(MIN_SPLIT_RATIO, 1. - MIN_SPLIT_RATIO);
+        match &mut self.root {
+            Member::Pane(pane) => {
+                if pane == old_pane {
+                    self.root = Member::new_axis_with_ratio(
+                        old_pane.clone(),
+                        new_pane.clone(),
+                        direction,
+                        ratio,
+                    );
+                    Ok(())
+                } else {
+                    Err(anyhow!("Pane not found"))
+                }
+            }
+            Member::Axis(axis) => axis.split(old_pane, new_pane, direction, Some(ratio)),
         }
     }
 
@@ -75,6 +137,14 @@ impl PaneGroup {
         }
     }
 
+    /// The axis of the split whose resize handle is at the given position, if any.
+    pub fn divider_at_pixel_position(&self, coordinate: Point<Pixels>) -> Option<Axis> {
+        match &self.root {
+            Member::Pane(_) => None,
+            Member::Axis(axis) => axis.divider_at_pixel_position(coordinate),
+        }
+    }
+
     /// Returns:
     /// - Ok(true) if it found and removed a pane
     /// - Ok(false) if it found but did not remove the pane
@@ -115,6 +185,17 @@ impl PaneGroup {
         };
     }
 
+    /// Equalizes the sizes of the members of the axis that directly contains the given pane,
+    /// leaving the sizes within any nested axes as they are.
+    pub fn reset_axis_sizes(&mut self, pane: &Entity<Pane>) {
+        match &mut self.root {
+            Member::Pane(_) => {}
+            Member::Axis(axis) => {
+                axis.reset_axis_sizes(pane);
+            }
+        };
+    }
+
     pub fn swap(&mut self, from: &Entity<Pane>, to: &Entity<Pane>) {
         match &mut self.root {
             Member::Pane(_) => {}
@@ -126,6 +207,7 @@ impl PaneGroup {
         &self,
         project: &Entity<Project>,
         follower_states: &HashMap<PeerId, FollowerState>,
+        collaborator_panes: &HashMap<PeerId, WeakEntity<Pane>>,
         active_call: Option<&Entity<ActiveCall>>,
         active_pane: &Entity<Pane>,
         zoomed: Option<&AnyWeakView>,
@@ -137,6 +219,7 @@ impl PaneGroup {
             project,
             0,
             follower_states,
+            collaborator_panes,
             active_call,
             active_pane,
             zoomed,
@@ -213,6 +296,24 @@ impl Member {
         Member::Axis(PaneAxis::new(axis, members))
     }
 
+    fn new_axis_with_ratio(
+        old_pane: Entity<Pane>,
+        new_pane: Entity<Pane>,
+        direction: SplitDirection,
+        ratio: f32,
+    ) -> Self {
+        let Member::Axis(axis) = Self::new_axis(old_pane, new_pane, direction) else {
+            unreachable!()
+        };
+        let (old_flex, new_flex) = (2. * (1. - ratio), 2. * ratio);
+        *axis.flexes.lock() = if direction.increasing() {
+            vec![old_flex, new_flex]
+        } else {
+            vec![new_flex, old_flex]
+        };
+        Member::Axis(axis)
+    }
+
     fn first_pane(&self) -> Entity<Pane> {
         match self {
             Member::Axis(axis) => axis.members[0].first_pane(),
@@ -225,6 +326,7 @@ impl Member {
         project: &Entity<Project>,
         basis: usize,
         follower_states: &HashMap<PeerId, FollowerState>,
+        collaborator_panes: &HashMap<PeerId, WeakEntity<Pane>>,
         active_call: Option<&Entity<ActiveCall>>,
         active_pane: &Entity<Pane>,
         zoomed: Option<&AnyWeakView>,
@@ -307,12 +409,47 @@ impl Member {
                             leader.user.github_login
                         ))),
                     };
+                } else {
+                    let pane = pane.downgrade();
+                    leader_border = collaborator_panes
+                        .iter()
+                        .filter(|(_, collaborator_pane)| **collaborator_pane == pane)
+                        .filter_map(|(peer_id, _)| {
+                            let room = active_call?.read(cx).room()?.read(cx);
+                            room.remote_participant_for_peer_id(*peer_id)
+                        })
+                        .map(|collaborator| collaborator.participant_index.0)
+                        .min()
+                        .map(|participant_index| {
+                            let mut color = cx
+                                .theme()
+                                .players()
+                                .color_for_participant(participant_index)
+                                .cursor;
+                            color.fade_out(0.6);
+                            color
+                        });
                 }
 
                 div()
+                    .id(("center-pane", pane.entity_id()))
                     .relative()
                     .flex_1()
                     .size_full()
+                    .on_mouse_move(cx.listener({
+                        let pane = pane.clone();
+                        move |workspace, event, window, cx| {
+                            workspace.center_pane_hovered(&pane, event, window, cx)
+                        }
+                    }))
+                    .on_hover(cx.listener({
+                        let pane = pane.clone();
+                        move |workspace, hovered, _, _| {
+                            if !hovered {
+                                workspace.hover_focus_target_left(pane.entity_id());
+                            }
+                        }
+                    }))
                     .child(
                         AnyView::from(pane.clone())
                             .cached(StyleRefinement::default().v_flex().size_full()),
@@ -364,6 +501,7 @@ impl Member {
                     project,
                     basis + 1,
                     follower_states,
+                    collaborator_panes,
                     active_call,
                     active_pane,
                     zoomed,
@@ -385,6 +523,53 @@ impl Member {
             Member::Pane(pane) => panes.push(pane),
         }
     }
+
+    /// Writes the tree of panes to `output`, one line per pane or axis, indented by `depth`.
+    /// See [`Workspace::layout_debug_string`] for the format.
+    pub(crate) fn write_debug_string(
+        &self,
+        active_pane: &Entity<Pane>,
+        depth: usize,
+        output: &mut String,
+        window: &Window,
+        cx: &App,
+    ) {
+        let indent = "  ".repeat(depth);
+        match self {
+            Member::Axis(axis) => {
+                let axis_name = match axis.axis {
+                    Axis::Horizontal => "horizontal",
+                    Axis::Vertical => "vertical",
+                };
+                writeln!(output, "{indent}{axis_name}").unwrap();
+                for member in &axis.members {
+                    member.write_debug_string(active_pane, depth + 1, output, window, cx);
+                }
+            }
+            Member::Pane(pane_handle) => {
+                let pane = pane_handle.read(cx);
+                let active_item_id = pane.active_item().map(|item| item.item_id());
+                let items = pane
+                    .items()
+                    .map(|item| {
+                        let name = item
+                            .tab_content_text(window, cx)
+                            .or_else(|| item.tab_description(0, cx))
+                            .unwrap_or_else(|| "untitled".into());
+                        if Some(item.item_id()) == active_item_id {
+                            format!("*{name}")
+                        } else {
+                            name.to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let active = if pane_handle == active_pane { "*" } else { "" };
+                let zoomed = if pane.is_zoomed() { " zoomed" } else { "" };
+                writeln!(output, "{indent}pane{active} [{items}]{zoomed}").unwrap();
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -426,23 +611,42 @@ impl PaneAxis {
         old_pane: &Entity<Pane>,
         new_pane: &Entity<Pane>,
         direction: SplitDirection,
+        ratio: Option<f32>,
     ) -> Result<()> {
         for (mut idx, member) in self.members.iter_mut().enumerate() {
             match member {
                 Member::Axis(axis) => {
-                    if axis.split(old_pane, new_pane, direction).is_ok() {
+                    if axis.split(old_pane, new_pane, direction, ratio).is_ok() {
                         return Ok(());
                     }
                 }
                 Member::Pane(pane) => {
                     if pane == old_pane {
                         if direction.axis() == self.axis {
+                            let old_idx = idx;
                             if direction.increasing() {
                                 idx += 1;
                             }
 
                             self.members.insert(idx, Member::Pane(new_pane.clone()));
-                            *self.flexes.lock() = vec![1.; self.members.len()];
+                            let mut flexes = self.flexes.lock();
+                            if let Some(ratio) = ratio {
+                                let old_flex = flexes[old_idx];
+                                flexes[old_idx] = old_flex * (1. - ratio);
+                                flexes.insert(idx, old_flex * ratio);
+                                // Flexes must add up to the number of members.
+                                let scale = flexes.len() as f32 / (flexes.len() - 1) as f32;
+                                flexes.iter_mut().for_each(|flex| *flex *= scale);
+                            } else {
+                                *flexes = vec![1.; self.members.len()];
+                            }
+                        } else if let Some(ratio) = ratio {
+                            *member = Member::new_axis_with_ratio(
+                                old_pane.clone(),
+                                new_pane.clone(),
+                                direction,
+                                ratio,
+                            );
                         } else {
                             *member =
                                 Member::new_axis(old_pane.clone(), new_pane.clone(), direction);
@@ -506,6 +710,21 @@ impl PaneAxis {
         }
     }
 
+    fn reset_axis_sizes(&self, pane: &Entity<Pane>) -> bool {
+        let found_pane = self
+            .members
+            .iter()
+            .any(|member| matches!(member, Member::Pane(p) if p == pane));
+        if found_pane {
+            *self.flexes.lock() = vec![1.; self.members.len()];
+            return true;
+        }
+        self.members.iter().any(|member| match member {
+            Member::Axis(axis) => axis.reset_axis_sizes(pane),
+            Member::Pane(_) => false,
+        })
+    }
+
     fn resize(
         &mut self,
         pane: &Entity<Pane>,
@@ -669,11 +888,46 @@ impl PaneAxis {
         None
     }
 
+    fn divider_at_pixel_position(&self, coordinate: Point<Pixels>) -> Option<Axis> {
+        debug_assert!(self.members.len() == self.bounding_boxes.lock().len());
+
+        let bounding_boxes = self.bounding_boxes.lock();
+        let last_ix = self.members.len().saturating_sub(1);
+        for (idx, member) in self.members.iter().enumerate() {
+            let Some(bounds) = bounding_boxes[idx] else {
+                continue;
+            };
+            // Each member but the last is followed by a handle straddling its trailing edge.
+            if idx < last_ix {
+                let handle_bounds = Bounds {
+                    origin: bounds.origin.apply_along(self.axis, |origin| {
+                        origin + bounds.size.along(self.axis) - px(HANDLE_HITBOX_SIZE / 2.)
+                    }),
+                    size: bounds
+                        .size
+                        .apply_along(self.axis, |_| px(HANDLE_HITBOX_SIZE)),
+                };
+                if handle_bounds.contains(&coordinate) {
+                    return Some(self.axis);
+                }
+            }
+            if let Member::Axis(axis) = member {
+                if bounds.contains(&coordinate) {
+                    if let Some(axis) = axis.divider_at_pixel_position(coordinate) {
+                        return Some(axis);
+                    }
+                }
+            }
+        }
+        None
+    }
+
     fn render(
         &self,
         project: &Entity<Project>,
         basis: usize,
         follower_states: &HashMap<PeerId, FollowerState>,
+        collaborator_panes: &HashMap<PeerId, WeakEntity<Pane>>,
         active_call: Option<&Entity<ActiveCall>>,
         active_pane: &Entity<Pane>,
         zoomed: Option<&AnyWeakView>,
@@ -700,6 +954,7 @@ impl PaneAxis {
                     project,
                     (basis + ix) * 10,
                     follower_states,
+                    collaborator_panes,
                     active_call,
                     active_pane,
                     zoomed,
@@ -783,6 +1038,15 @@ impl SplitDirection {
         }
     }
 
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
     pub fn axis(&self) -> Axis {
         match self {
             Self::Up | Self::Down => Axis::Vertical,
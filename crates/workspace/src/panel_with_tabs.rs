@@ -0,0 +1,313 @@
+//! A strip of tabs for dock panels that show several views of the same kind, such as the
+//! terminals of a terminal panel.
+//!
+//! [`PanelWithTabs`] owns the tabs and renders them with the active one below, and handles
+//! adding, closing, activating and reordering them. The panel embedding it creates the tabs,
+//! including when the user asks for a new one with [`NewTab`].
+
+use collections::HashMap;
+use gpui::{
+    actions, App, Context, Entity, EntityId, EventEmitter, FocusHandle, Focusable, MouseButton,
+    Render, SharedString, Subscription, Window,
+};
+use ui::{prelude::*, IconButton, IconButtonShape, Tab, TabBar, TabPosition, Tooltip};
+
+actions!(
+    panel_with_tabs,
+    [
+        NewTab,
+        CloseTab,
+        ActivateNextTab,
+        ActivatePreviousTab,
+        MoveTabLeft,
+        MoveTabRight
+    ]
+);
+
+/// A view shown in a tab of a [`PanelWithTabs`].
+pub trait PanelTabView: Render + Focusable {
+    fn tab_label(&self, cx: &App) -> SharedString;
+
+    fn tab_icon(&self, _cx: &App) -> Option<IconName> {
+        None
+    }
+}
+
+pub enum PanelWithTabsEvent {
+    /// The user asked for a new tab. The panel should create it and add it with
+    /// [`PanelWithTabs::add_tab`].
+    NewTabRequested,
+    ActiveTabChanged,
+    /// A tab was closed, which the panel may want to hide itself for when it was the last.
+    TabClosed(EntityId),
+}
+
+/// A tab being dragged to another position in its tab strip.
+#[derive(Clone)]
+pub struct DraggedPanelTab {
+    tabs_id: EntityId,
+    ix: usize,
+    label: SharedString,
+}
+
+impl Render for DraggedPanelTab {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .bg(cx.theme().colors().tab_active_background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(self.label.clone()))
+    }
+}
+
+pub struct PanelWithTabs<T: PanelTabView> {
+    tabs: Vec<Entity<T>>,
+    active_tab_ix: usize,
+    focus_handle: FocusHandle,
+    tab_observations: HashMap<EntityId, Subscription>,
+}
+
+impl<T: PanelTabView> EventEmitter<PanelWithTabsEvent> for PanelWithTabs<T> {}
+
+impl<T: PanelTabView> Focusable for PanelWithTabs<T> {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match self.active_tab() {
+            Some(tab) => tab.focus_handle(cx),
+            None => self.focus_handle.clone(),
+        }
+    }
+}
+
+impl<T: PanelTabView> PanelWithTabs<T> {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Self {
+            tabs: Vec::new(),
+            active_tab_ix: 0,
+            focus_handle: cx.focus_handle(),
+            tab_observations: HashMap::default(),
+        }
+    }
+
+    pub fn tabs(&self) -> &[Entity<T>] {
+        &self.tabs
+    }
+
+    pub fn active_tab(&self) -> Option<&Entity<T>> {
+        self.tabs.get(self.active_tab_ix)
+    }
+
+    pub fn active_tab_index(&self) -> usize {
+        self.active_tab_ix
+    }
+
+    /// Adds a tab after the active one, and activates it.
+    pub fn add_tab(&mut self, tab: Entity<T>, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = if self.tabs.is_empty() {
+            0
+        } else {
+            self.active_tab_ix + 1
+        };
+        self.tab_observations
+            .insert(tab.entity_id(), cx.observe(&tab, |_, _, cx| cx.notify()));
+        self.tabs.insert(ix, tab);
+        self.activate_tab(ix, window, cx);
+    }
+
+    pub fn activate_tab(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab) = self.tabs.get(ix) else {
+            return;
+        };
+        window.focus(&tab.focus_handle(cx));
+        if ix != self.active_tab_ix {
+            self.active_tab_ix = ix;
+            cx.emit(PanelWithTabsEvent::ActiveTabChanged);
+        }
+        cx.notify();
+    }
+
+    pub fn close_tab(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if ix >= self.tabs.len() {
+            return;
+        }
+        let tab = self.tabs.remove(ix);
+        self.tab_observations.remove(&tab.entity_id());
+        cx.emit(PanelWithTabsEvent::TabClosed(tab.entity_id()));
+
+        if self.tabs.is_empty() {
+            self.active_tab_ix = 0;
+            window.focus(&self.focus_handle);
+            cx.emit(PanelWithTabsEvent::ActiveTabChanged);
+        } else if ix < self.active_tab_ix {
+            self.active_tab_ix -= 1;
+        } else if ix == self.active_tab_ix {
+            // Activate the tab to the right of the closed one, or the last if there's none.
+            self.active_tab_ix = ix.min(self.tabs.len() - 1);
+            window.focus(&self.tabs[self.active_tab_ix].focus_handle(cx));
+            cx.emit(PanelWithTabsEvent::ActiveTabChanged);
+        }
+        cx.notify();
+    }
+
+    /// Moves the tab at `from` to `to`, keeping the same tab active.
+    pub fn move_tab(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from >= self.tabs.len() || from == to {
+            return;
+        }
+        let to = to.min(self.tabs.len() - 1);
+        let active_tab_id = self.active_tab().map(|tab| tab.entity_id());
+        let tab = self.tabs.remove(from);
+        self.tabs.insert(to, tab);
+        if let Some(active_tab_id) = active_tab_id {
+            self.active_tab_ix = self
+                .tabs
+                .iter()
+                .position(|tab| tab.entity_id() == active_tab_id)
+                .unwrap_or(0);
+        }
+        cx.notify();
+    }
+
+    fn new_tab(&mut self, _: &NewTab, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(PanelWithTabsEvent::NewTabRequested);
+    }
+
+    fn close_active_tab(&mut self, _: &CloseTab, window: &mut Window, cx: &mut Context<Self>) {
+        self.close_tab(self.active_tab_ix, window, cx);
+    }
+
+    fn activate_next_tab(
+        &mut self,
+        _: &ActivateNextTab,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.tabs.is_empty() {
+            let ix = (self.active_tab_ix + 1) % self.tabs.len();
+            self.activate_tab(ix, window, cx);
+        }
+    }
+
+    fn activate_previous_tab(
+        &mut self,
+        _: &ActivatePreviousTab,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.tabs.is_empty() {
+            let ix = (self.active_tab_ix + self.tabs.len() - 1) % self.tabs.len();
+            self.activate_tab(ix, window, cx);
+        }
+    }
+
+    fn move_tab_left(&mut self, _: &MoveTabLeft, _: &mut Window, cx: &mut Context<Self>) {
+        if self.active_tab_ix > 0 {
+            self.move_tab(self.active_tab_ix, self.active_tab_ix - 1, cx);
+        }
+    }
+
+    fn move_tab_right(&mut self, _: &MoveTabRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_tab(self.active_tab_ix, self.active_tab_ix + 1, cx);
+    }
+
+    fn render_tab(&self, ix: usize, tab: &Entity<T>, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_active = ix == self.active_tab_ix;
+        let label = tab.read(cx).tab_label(cx);
+        let icon = tab.read(cx).tab_icon(cx);
+        let tabs_id = cx.entity_id();
+
+        Tab::new(ix)
+            .position(if ix == 0 {
+                TabPosition::First
+            } else if ix == self.tabs.len() - 1 {
+                TabPosition::Last
+            } else {
+                TabPosition::Middle(ix.cmp(&self.active_tab_ix))
+            })
+            .toggle_state(is_active)
+            .on_click(cx.listener(move |this, _, window, cx| this.activate_tab(ix, window, cx)))
+            .on_mouse_down(
+                MouseButton::Middle,
+                cx.listener(move |this, _, window, cx| this.close_tab(ix, window, cx)),
+            )
+            .on_drag(
+                DraggedPanelTab {
+                    tabs_id,
+                    ix,
+                    label: label.clone(),
+                },
+                |tab, _, _, cx| cx.new(|_| tab.clone()),
+            )
+            .drag_over::<DraggedPanelTab>(|tab, _, _, cx| {
+                tab.bg(cx.theme().colors().drop_target_background)
+            })
+            .on_drop(cx.listener(move |this, dragged: &DraggedPanelTab, _, cx| {
+                if dragged.tabs_id == tabs_id {
+                    this.move_tab(dragged.ix, ix, cx);
+                }
+            }))
+            .end_slot(
+                IconButton::new(("close-panel-tab", ix), IconName::Close)
+                    .shape(IconButtonShape::Square)
+                    .icon_color(Color::Muted)
+                    .size(ButtonSize::None)
+                    .icon_size(IconSize::XSmall)
+                    .visible_on_hover("")
+                    .tooltip(Tooltip::text("Close Tab"))
+                    .on_click(
+                        cx.listener(move |this, _, window, cx| this.close_tab(ix, window, cx)),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .children(
+                        icon.map(|icon| Icon::new(icon).size(IconSize::Small).color(Color::Muted)),
+                    )
+                    .child(Label::new(label).color(if is_active {
+                        Color::Default
+                    } else {
+                        Color::Muted
+                    })),
+            )
+    }
+}
+
+impl<T: PanelTabView> Render for PanelWithTabs<T> {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let tabs = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(ix, tab)| self.render_tab(ix, tab, cx).into_any_element())
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("PanelWithTabs")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .on_action(cx.listener(Self::new_tab))
+            .on_action(cx.listener(Self::close_active_tab))
+            .on_action(cx.listener(Self::activate_next_tab))
+            .on_action(cx.listener(Self::activate_previous_tab))
+            .on_action(cx.listener(Self::move_tab_left))
+            .on_action(cx.listener(Self::move_tab_right))
+            .child(
+                TabBar::new("panel-tabs").children(tabs).end_child(
+                    IconButton::new("new-panel-tab", IconName::Plus)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("New Tab"))
+                        .on_click(
+                            cx.listener(|_, _, _, cx| cx.emit(PanelWithTabsEvent::NewTabRequested)),
+                        ),
+                ),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_h_0()
+                    .children(self.active_tab().cloned()),
+            )
+    }
+}
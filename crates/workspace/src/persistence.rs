@@ -29,8 +29,8 @@ use uuid::Uuid;
 use crate::WorkspaceId;
 
 use model::{
-    GroupId, LocalPaths, PaneId, SerializedItem, SerializedPane, SerializedPaneGroup,
-    SerializedSshProject, SerializedWorkspace,
+    GroupId, LocalPaths, PaneId, SerializedItem, SerializedLayout, SerializedNamedSession,
+    SerializedPane, SerializedPaneGroup, SerializedSshProject, SerializedWorkspace,
 };
 
 use self::model::{DockStructure, LocalPathsOrder, SerializedWorkspaceLocation};
@@ -136,7 +136,12 @@ impl Column for SerializedWindowBounds {
             "Windowed" | "Fixed" => SerializedWindowBounds(WindowBounds::Windowed(bounds)),
             "Maximized" => SerializedWindowBounds(WindowBounds::Maximized(bounds)),
             "FullScreen" => SerializedWindowBounds(WindowBounds::Fullscreen(bounds)),
-            _ => bail!("Window State did not have a valid string"),
+            // A newer version of Zed may have stored a state this version doesn't know about.
+            // Keep the window's bounds rather than failing to restore the whole workspace.
+            window_state => {
+                log::warn!("Unknown window state {window_state:?}, restoring as windowed");
+                SerializedWindowBounds(WindowBounds::Windowed(bounds))
+            }
         };
 
         Ok((status, next_index + 4))
@@ -262,6 +267,13 @@ impl Column for Breakpoints {
     }
 }
 
+/// The center pane group of a workspace without a stored one, or whose stored one can't be
+/// read, e.g. because it was written by a newer version of Zed. The workspace is still
+/// restored with its docks and window bounds.
+fn empty_center_group() -> SerializedPaneGroup {
+    SerializedPaneGroup::Pane(SerializedPane::new(Vec::new(), true, 0))
+}
+
 #[derive(Clone, Debug, PartialEq)]
 struct SerializedPixels(gpui::Pixels);
 impl sqlez::bindable::StaticColumnCount for SerializedPixels {}
@@ -339,6 +351,40 @@ define_connection! {
     //      kind: int, // The kind of breakpoint (standard, log)
     //      log_message: String, // log message for log breakpoints, otherwise it's Null
     // )
+    //
+    // CREATE TABLE named_layouts(
+    //      name: String, // Primary key, the name the user gave to the layout
+    //      left_dock_visible: Option<bool>,
+    //      left_dock_active_panel: Option<String>,
+    //      left_dock_zoom: Option<bool>,
+    //      right_dock_visible: Option<bool>,
+    //      right_dock_active_panel: Option<String>,
+    //      right_dock_zoom: Option<bool>,
+    //      bottom_dock_visible: Option<bool>,
+    //      bottom_dock_active_panel: Option<String>,
+    //      bottom_dock_zoom: Option<bool>,
+    //      bottom_right_dock_visible: Option<bool>,
+    //      bottom_right_dock_active_panel: Option<String>,
+    //      bottom_right_dock_zoom: Option<bool>,
+    //      bottom_right_panels: Option<String>, // A JSON array of the panels moved to the bottom right dock
+    //      removed_panels: Option<String>, // A JSON array of the panels removed from their docks
+    //      bottom_dock_split: Option<f32>, // The share of the bottom docks' width taken by the left one
+    //      centered_layout: Option<bool>,
+    //      is_default: bool, // Whether new workspaces start with this layout
+    // )
+    //
+    // CREATE TABLE named_sessions(
+    //      name: String, // Primary key, the name the user gave to the session
+    //      paths: String, // A JSON array of the project's root paths, in the project's order
+    //      layout: String, // The session's ExportedLayout as JSON
+    //      timestamp: String, // UTC YYYY-MM-DD HH:MM:SS, when the session was last saved
+    // )
+    //
+    // CREATE TABLE window_tabs(
+    //      workspace_id: usize, // Primary key for workspaces, the workspace of the tab
+    //      window_workspace_id: usize, // The workspace of the window's active tab
+    //      tab_ix: usize, // The position of the tab in the window
+    // )
     pub static ref DB: WorkspaceDb<()> =
     &[
         sql!(
@@ -529,6 +575,60 @@ define_connection! {
                 ON UPDATE CASCADE
             );
         ),
+    sql!(
+        CREATE TABLE named_layouts (
+            name TEXT PRIMARY KEY NOT NULL,
+            left_dock_visible INTEGER, //bool
+            left_dock_active_panel TEXT,
+            left_dock_zoom INTEGER, //bool
+            right_dock_visible INTEGER, //bool
+            right_dock_active_panel TEXT,
+            right_dock_zoom INTEGER, //bool
+            bottom_dock_visible INTEGER, //bool
+            bottom_dock_active_panel TEXT,
+            bottom_dock_zoom INTEGER, //bool
+            centered_layout INTEGER, //bool
+            is_default INTEGER NOT NULL DEFAULT 0 //bool
+        ) STRICT;
+    ),
+    sql!(
+        ALTER TABLE panes ADD COLUMN context TEXT DEFAULT NULL;
+    ),
+    sql!(
+        CREATE TABLE named_sessions (
+            name TEXT PRIMARY KEY NOT NULL,
+            paths TEXT NOT NULL,
+            layout TEXT NOT NULL,
+            timestamp TEXT DEFAULT CURRENT_TIMESTAMP NOT NULL
+        ) STRICT;
+    ),
+    sql!(
+        CREATE TABLE window_tabs (
+            workspace_id INTEGER PRIMARY KEY NOT NULL,
+            window_workspace_id INTEGER NOT NULL,
+            tab_ix INTEGER NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+        ) STRICT;
+    ),
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN bottom_right_dock_visible INTEGER; //bool
+        ALTER TABLE workspaces ADD COLUMN bottom_right_dock_active_panel TEXT;
+        ALTER TABLE workspaces ADD COLUMN bottom_right_dock_zoom INTEGER; //bool
+        ALTER TABLE workspaces ADD COLUMN bottom_right_panels TEXT;
+        ALTER TABLE named_layouts ADD COLUMN bottom_right_dock_visible INTEGER; //bool
+        ALTER TABLE named_layouts ADD COLUMN bottom_right_dock_active_panel TEXT;
+        ALTER TABLE named_layouts ADD COLUMN bottom_right_dock_zoom INTEGER; //bool
+        ALTER TABLE named_layouts ADD COLUMN bottom_right_panels TEXT;
+    ),
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN removed_panels TEXT;
+        ALTER TABLE named_layouts ADD COLUMN removed_panels TEXT;
+    ),
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_split REAL;
+        ALTER TABLE named_layouts ADD COLUMN bottom_dock_split REAL;
+    ),
     ];
 }
 
@@ -586,6 +686,12 @@ impl WorkspaceDb {
                     bottom_dock_visible,
                     bottom_dock_active_panel,
                     bottom_dock_zoom,
+                    bottom_right_dock_visible,
+                    bottom_right_dock_active_panel,
+                    bottom_right_dock_zoom,
+                    bottom_right_panels,
+                    removed_panels,
+                    bottom_dock_split,
                     window_id
                 FROM workspaces
                 WHERE local_paths = ?
@@ -604,13 +710,15 @@ impl WorkspaceDb {
             }
         };
 
+        let center_group = self
+            .get_center_pane_group(workspace_id)
+            .context("Getting center group")
+            .log_err();
+        let center_group_unreadable = center_group.is_none();
         Some(SerializedWorkspace {
             id: workspace_id,
             location,
-            center_group: self
-                .get_center_pane_group(workspace_id)
-                .context("Getting center group")
-                .log_err()?,
+            center_group: center_group.unwrap_or_else(empty_center_group),
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
             display,
@@ -618,6 +726,7 @@ impl WorkspaceDb {
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
             window_id,
+            center_group_unreadable,
         })
     }
 
@@ -652,6 +761,12 @@ impl WorkspaceDb {
                     bottom_dock_visible,
                     bottom_dock_active_panel,
                     bottom_dock_zoom,
+                    bottom_right_dock_visible,
+                    bottom_right_dock_active_panel,
+                    bottom_right_dock_zoom,
+                    bottom_right_panels,
+                    removed_panels,
+                    bottom_dock_split,
                     window_id
                 FROM workspaces
                 WHERE ssh_project_id = ?
@@ -661,13 +776,15 @@ impl WorkspaceDb {
             .warn_on_err()
             .flatten()?;
 
+        let center_group = self
+            .get_center_pane_group(workspace_id)
+            .context("Getting center group")
+            .log_err();
+        let center_group_unreadable = center_group.is_none();
         Some(SerializedWorkspace {
             id: workspace_id,
             location: SerializedWorkspaceLocation::Ssh(ssh_project.clone()),
-            center_group: self
-                .get_center_pane_group(workspace_id)
-                .context("Getting center group")
-                .log_err()?,
+            center_group: center_group.unwrap_or_else(empty_center_group),
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
             breakpoints: self.breakpoints(workspace_id),
@@ -675,6 +792,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             window_id,
+            center_group_unreadable,
         })
     }
 
@@ -721,13 +839,19 @@ impl WorkspaceDb {
     /// Saves a workspace using the worktree roots. Will garbage collect any workspaces
     /// that used this workspace previously
     pub(crate) async fn save_workspace(&self, workspace: SerializedWorkspace) {
+        // A center group that couldn't be read, e.g. because it was written by a newer version
+        // of Zed, was restored as an empty pane. Keep the stored one rather than overwriting it
+        // with that placeholder, so that the newer version can still restore it.
+        let keep_stored_center_group = workspace.center_group_unreadable;
         self.write(move |conn| {
             conn.with_savepoint("update_worktrees", || {
                 // Clear out panes and pane_groups
-                conn.exec_bound(sql!(
-                    DELETE FROM pane_groups WHERE workspace_id = ?1;
-                    DELETE FROM panes WHERE workspace_id = ?1;))?(workspace.id)
-                .context("Clearing old panes")?;
+                if !keep_stored_center_group {
+                    conn.exec_bound(sql!(
+                        DELETE FROM pane_groups WHERE workspace_id = ?1;
+                        DELETE FROM panes WHERE workspace_id = ?1;))?(workspace.id)
+                    .context("Clearing old panes")?;
+                }
                 for (path, breakpoints) in workspace.breakpoints {
                     conn.exec_bound(sql!(DELETE FROM breakpoints WHERE workspace_id = ?1 AND path = ?2))?((workspace.id, path.as_ref()))
                     .context("Clearing old breakpoints")?;
@@ -777,11 +901,17 @@ impl WorkspaceDb {
                                 bottom_dock_visible,
                                 bottom_dock_active_panel,
                                 bottom_dock_zoom,
+                                bottom_right_dock_visible,
+                                bottom_right_dock_active_panel,
+                                bottom_right_dock_zoom,
+                                bottom_right_panels,
+                                removed_panels,
+                                bottom_dock_split,
                                 session_id,
                                 window_id,
                                 timestamp
                             )
-                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, CURRENT_TIMESTAMP)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, CURRENT_TIMESTAMP)
                             ON CONFLICT DO
                             UPDATE SET
                                 local_paths = ?2,
@@ -795,8 +925,14 @@ impl WorkspaceDb {
                                 bottom_dock_visible = ?10,
                                 bottom_dock_active_panel = ?11,
                                 bottom_dock_zoom = ?12,
-                                session_id = ?13,
-                                window_id = ?14,
+                                bottom_right_dock_visible = ?13,
+                                bottom_right_dock_active_panel = ?14,
+                                bottom_right_dock_zoom = ?15,
+                                bottom_right_panels = ?16,
+                                removed_panels = ?17,
+                                bottom_dock_split = ?18,
+                                session_id = ?19,
+                                window_id = ?20,
                                 timestamp = CURRENT_TIMESTAMP
                         );
                         let mut prepared_query = conn.exec_bound(query)?;
@@ -825,11 +961,17 @@ impl WorkspaceDb {
                                 bottom_dock_visible,
                                 bottom_dock_active_panel,
                                 bottom_dock_zoom,
+                                bottom_right_dock_visible,
+                                bottom_right_dock_active_panel,
+                                bottom_right_dock_zoom,
+                                bottom_right_panels,
+                                removed_panels,
+                                bottom_dock_split,
                                 session_id,
                                 window_id,
                                 timestamp
                             )
-                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, CURRENT_TIMESTAMP)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, CURRENT_TIMESTAMP)
                             ON CONFLICT DO
                             UPDATE SET
                                 ssh_project_id = ?2,
@@ -842,8 +984,14 @@ impl WorkspaceDb {
                                 bottom_dock_visible = ?9,
                                 bottom_dock_active_panel = ?10,
                                 bottom_dock_zoom = ?11,
-                                session_id = ?12,
-                                window_id = ?13,
+                                bottom_right_dock_visible = ?12,
+                                bottom_right_dock_active_panel = ?13,
+                                bottom_right_dock_zoom = ?14,
+                                bottom_right_panels = ?15,
+                                removed_panels = ?16,
+                                bottom_dock_split = ?17,
+                                session_id = ?18,
+                                window_id = ?19,
                                 timestamp = CURRENT_TIMESTAMP
                         ))?((
                             workspace.id,
@@ -857,8 +1005,15 @@ impl WorkspaceDb {
                 }
 
                 // Save center pane group
-                Self::save_pane_group(conn, workspace.id, &workspace.center_group, None)
-                    .context("save pane group in save workspace")?;
+                if keep_stored_center_group {
+                    log::warn!(
+                        "Not saving the center pane group of workspace {:?}, the stored one can't be read",
+                        workspace.id
+                    );
+                } else {
+                    Self::save_pane_group(conn, workspace.id, &workspace.center_group, None)
+                        .context("save pane group in save workspace")?;
+                }
 
                 Ok(())
             })
@@ -929,6 +1084,9 @@ impl WorkspaceDb {
             SELECT local_paths, local_paths_order, window_id, ssh_project_id
             FROM workspaces
             WHERE session_id = ?1 AND dev_server_project_id IS NULL
+            AND workspace_id NOT IN (
+                SELECT workspace_id FROM window_tabs WHERE workspace_id != window_workspace_id
+            )
             ORDER BY timestamp DESC
         }
     }
@@ -948,6 +1106,269 @@ impl WorkspaceDb {
         }
     }
 
+    query! {
+        pub async fn named_layouts() -> Result<Vec<SerializedLayout>> {
+            SELECT
+                name,
+                left_dock_visible,
+                left_dock_active_panel,
+                left_dock_zoom,
+                right_dock_visible,
+                right_dock_active_panel,
+                right_dock_zoom,
+                bottom_dock_visible,
+                bottom_dock_active_panel,
+                bottom_dock_zoom,
+                bottom_right_dock_visible,
+                bottom_right_dock_active_panel,
+                bottom_right_dock_zoom,
+                bottom_right_panels,
+                removed_panels,
+                bottom_dock_split,
+                centered_layout,
+                is_default
+            FROM named_layouts
+            ORDER BY name
+        }
+    }
+
+    query! {
+        pub(crate) async fn default_named_layout() -> Result<Option<SerializedLayout>> {
+            SELECT
+                name,
+                left_dock_visible,
+                left_dock_active_panel,
+                left_dock_zoom,
+                right_dock_visible,
+                right_dock_active_panel,
+                right_dock_zoom,
+                bottom_dock_visible,
+                bottom_dock_active_panel,
+                bottom_dock_zoom,
+                bottom_right_dock_visible,
+                bottom_right_dock_active_panel,
+                bottom_right_dock_zoom,
+                bottom_right_panels,
+                removed_panels,
+                bottom_dock_split,
+                centered_layout,
+                is_default
+            FROM named_layouts
+            WHERE is_default = 1
+            LIMIT 1
+        }
+    }
+
+    query! {
+        pub(crate) async fn save_named_layout(name: String, docks: DockStructure, centered_layout: bool) -> Result<()> {
+            INSERT INTO named_layouts(
+                name,
+                left_dock_visible,
+                left_dock_active_panel,
+                left_dock_zoom,
+                right_dock_visible,
+                right_dock_active_panel,
+                right_dock_zoom,
+                bottom_dock_visible,
+                bottom_dock_active_panel,
+                bottom_dock_zoom,
+                bottom_right_dock_visible,
+                bottom_right_dock_active_panel,
+                bottom_right_dock_zoom,
+                bottom_right_panels,
+                removed_panels,
+                bottom_dock_split,
+                centered_layout
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+            ON CONFLICT DO
+            UPDATE SET
+                left_dock_visible = ?2,
+                left_dock_active_panel = ?3,
+                left_dock_zoom = ?4,
+                right_dock_visible = ?5,
+                right_dock_active_panel = ?6,
+                right_dock_zoom = ?7,
+                bottom_dock_visible = ?8,
+                bottom_dock_active_panel = ?9,
+                bottom_dock_zoom = ?10,
+                bottom_right_dock_visible = ?11,
+                bottom_right_dock_active_panel = ?12,
+                bottom_right_dock_zoom = ?13,
+                bottom_right_panels = ?14,
+                removed_panels = ?15,
+                bottom_dock_split = ?16,
+                centered_layout = ?17
+        }
+    }
+
+    query! {
+        pub async fn rename_named_layout(old_name: String, new_name: String) -> Result<()> {
+            UPDATE named_layouts
+            SET name = ?2
+            WHERE name = ?1
+        }
+    }
+
+    query! {
+        pub async fn duplicate_named_layout(name: String, new_name: String) -> Result<()> {
+            INSERT INTO named_layouts(
+                name,
+                left_dock_visible,
+                left_dock_active_panel,
+                left_dock_zoom,
+                right_dock_visible,
+                right_dock_active_panel,
+                right_dock_zoom,
+                bottom_dock_visible,
+                bottom_dock_active_panel,
+                bottom_dock_zoom,
+                bottom_right_dock_visible,
+                bottom_right_dock_active_panel,
+                bottom_right_dock_zoom,
+                bottom_right_panels,
+                removed_panels,
+                bottom_dock_split,
+                centered_layout
+            )
+            SELECT
+                ?2,
+                left_dock_visible,
+                left_dock_active_panel,
+                left_dock_zoom,
+                right_dock_visible,
+                right_dock_active_panel,
+                right_dock_zoom,
+                bottom_dock_visible,
+                bottom_dock_active_panel,
+                bottom_dock_zoom,
+                bottom_right_dock_visible,
+                bottom_right_dock_active_panel,
+                bottom_right_dock_zoom,
+                bottom_right_panels,
+                removed_panels,
+                bottom_dock_split,
+                centered_layout
+            FROM named_layouts
+            WHERE name = ?1
+        }
+    }
+
+    query! {
+        pub async fn delete_named_layout(name: String) -> Result<()> {
+            DELETE FROM named_layouts
+            WHERE name = ?
+        }
+    }
+
+    // Marks the given layout as the one new workspaces start with, or clears
+    // the default when no layout has the given name.
+    query! {
+        pub async fn set_default_named_layout(name: String) -> Result<()> {
+            UPDATE named_layouts
+            SET is_default = (name = ?1)
+        }
+    }
+
+    query! {
+        async fn named_session_rows() -> Result<Vec<(String, String, String)>> {
+            SELECT name, paths, layout
+            FROM named_sessions
+            ORDER BY timestamp DESC
+        }
+    }
+
+    /// The named sessions, most recently saved first. Sessions that can't be parsed, e.g.
+    /// because a newer version of Zed saved them, are skipped.
+    pub async fn named_sessions(&self) -> Result<Vec<SerializedNamedSession>> {
+        Ok(self
+            .named_session_rows()
+            .await?
+            .into_iter()
+            .filter_map(|(name, paths, layout)| {
+                let context = format!("parsing named session {name:?}");
+                SerializedNamedSession::parse(name, &paths, &layout)
+                    .context(context)
+                    .log_err()
+            })
+            .collect())
+    }
+
+    query! {
+        pub(crate) async fn save_named_session(name: String, paths: String, layout: String) -> Result<()> {
+            INSERT INTO named_sessions(name, paths, layout)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT DO
+            UPDATE SET
+                paths = ?2,
+                layout = ?3,
+                timestamp = CURRENT_TIMESTAMP
+        }
+    }
+
+    query! {
+        pub async fn delete_named_session(name: String) -> Result<()> {
+            DELETE FROM named_sessions
+            WHERE name = ?
+        }
+    }
+
+    /// Records the workspaces of a window's tabs, replacing what was recorded for any of them.
+    /// A window with a single tab has nothing to record.
+    pub(crate) async fn set_window_tabs(
+        &self,
+        workspace_ids: Vec<WorkspaceId>,
+        active_ix: usize,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.with_savepoint("window_tabs", || {
+                for workspace_id in &workspace_ids {
+                    conn.exec_bound(sql!(
+                        DELETE FROM window_tabs
+                        WHERE workspace_id = ?1 OR window_workspace_id = ?1
+                    ))?(*workspace_id)?;
+                }
+                if workspace_ids.len() > 1 {
+                    let window_workspace_id = workspace_ids[active_ix];
+                    for (tab_ix, workspace_id) in workspace_ids.iter().enumerate() {
+                        conn.exec_bound(sql!(
+                            INSERT INTO window_tabs(workspace_id, window_workspace_id, tab_ix)
+                            VALUES (?1, ?2, ?3)
+                        ))?((*workspace_id, window_workspace_id, tab_ix))?;
+                    }
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+
+    query! {
+        pub(crate) async fn delete_window_tab(workspace_id: WorkspaceId) -> Result<()> {
+            DELETE FROM window_tabs
+            WHERE workspace_id = ?
+        }
+    }
+
+    query! {
+        pub(crate) async fn delete_background_window_tab(workspace_id: WorkspaceId) -> Result<()> {
+            DELETE FROM window_tabs
+            WHERE workspace_id = ?1 AND window_workspace_id != ?1
+        }
+    }
+
+    // Returns the workspaces of the tabs of the window whose active tab is the given workspace,
+    // in tab order.
+    query! {
+        pub(crate) async fn window_tabs(window_workspace_id: WorkspaceId) -> Result<Vec<(WorkspaceId, LocalPaths)>> {
+            SELECT workspaces.workspace_id, workspaces.local_paths
+            FROM window_tabs
+            JOIN workspaces ON workspaces.workspace_id = window_tabs.workspace_id
+            WHERE window_tabs.window_workspace_id = ? AND workspaces.local_paths IS NOT NULL
+            ORDER BY window_tabs.tab_ix
+        }
+    }
+
     query! {
         fn ssh_projects() -> Result<Vec<SerializedSshProject>> {
             SELECT id, host, port, paths, user
@@ -1091,13 +1512,7 @@ impl WorkspaceDb {
             .get_pane_group(workspace_id, None)?
             .into_iter()
             .next()
-            .unwrap_or_else(|| {
-                SerializedPaneGroup::Pane(SerializedPane {
-                    active: true,
-                    children: vec![],
-                    pinned_count: 0,
-                })
-            }))
+            .unwrap_or_else(empty_center_group))
     }
 
     fn get_pane_group(
@@ -1113,9 +1528,10 @@ impl WorkspaceDb {
             Option<bool>,
             Option<usize>,
             Option<String>,
+            Option<String>,
         );
         self.select_bound::<GroupKey, GroupOrPane>(sql!(
-            SELECT group_id, axis, pane_id, active, pinned_count, flexes
+            SELECT group_id, axis, pane_id, active, pinned_count, flexes, context
                 FROM (SELECT
                         group_id,
                         axis,
@@ -1125,7 +1541,8 @@ impl WorkspaceDb {
                         position,
                         parent_group_id,
                         workspace_id,
-                        flexes
+                        flexes,
+                        NULL as context
                       FROM pane_groups
                     UNION
                       SELECT
@@ -1137,35 +1554,41 @@ impl WorkspaceDb {
                         position,
                         parent_group_id,
                         panes.workspace_id as workspace_id,
-                        NULL
+                        NULL,
+                        panes.context as context
                       FROM center_panes
                       JOIN panes ON center_panes.pane_id = panes.pane_id)
                 WHERE parent_group_id IS ? AND workspace_id = ?
                 ORDER BY position
         ))?((group_id, workspace_id))?
         .into_iter()
-        .map(|(group_id, axis, pane_id, active, pinned_count, flexes)| {
-            let maybe_pane = maybe!({ Some((pane_id?, active?, pinned_count?)) });
-            if let Some((group_id, axis)) = group_id.zip(axis) {
-                let flexes = flexes
-                    .map(|flexes: String| serde_json::from_str::<Vec<f32>>(&flexes))
-                    .transpose()?;
-
-                Ok(SerializedPaneGroup::Group {
-                    axis,
-                    children: self.get_pane_group(workspace_id, Some(group_id))?,
-                    flexes,
-                })
-            } else if let Some((pane_id, active, pinned_count)) = maybe_pane {
-                Ok(SerializedPaneGroup::Pane(SerializedPane::new(
-                    self.get_items(pane_id)?,
-                    active,
-                    pinned_count,
-                )))
-            } else {
-                bail!("Pane Group Child was neither a pane group or a pane");
-            }
-        })
+        .map(
+            |(group_id, axis, pane_id, active, pinned_count, flexes, context)| {
+                let maybe_pane = maybe!({ Some((pane_id?, active?, pinned_count?)) });
+                if let Some((group_id, axis)) = group_id.zip(axis) {
+                    let flexes = flexes
+                        .map(|flexes: String| serde_json::from_str::<Vec<f32>>(&flexes))
+                        .transpose()?;
+
+                    Ok(SerializedPaneGroup::Group {
+                        axis,
+                        children: self.get_pane_group(workspace_id, Some(group_id))?,
+                        flexes,
+                    })
+                } else if let Some((pane_id, active, pinned_count)) = maybe_pane {
+                    let context = context
+                        .map(|context| serde_json::from_str(&context))
+                        .transpose()?
+                        .unwrap_or_default();
+                    Ok(SerializedPaneGroup::Pane(
+                        SerializedPane::new(self.get_items(pane_id)?, active, pinned_count)
+                            .with_context(context),
+                    ))
+                } else {
+                    bail!("Pane Group Child was neither a pane group or a pane");
+                }
+            },
+        )
         // Filter out panes and pane groups which don't have any children or items
         .filter(|pane_group| match pane_group {
             Ok(SerializedPaneGroup::Group { children, .. }) => !children.is_empty(),
@@ -1231,11 +1654,14 @@ impl WorkspaceDb {
         pane: &SerializedPane,
         parent: Option<(GroupId, usize)>,
     ) -> Result<PaneId> {
+        let context = (!pane.context.is_empty())
+            .then(|| serde_json::to_string(&pane.context))
+            .transpose()?;
         let pane_id = conn.select_row_bound::<_, i64>(sql!(
-            INSERT INTO panes(workspace_id, active, pinned_count)
-            VALUES (?, ?, ?)
+            INSERT INTO panes(workspace_id, active, pinned_count, context)
+            VALUES (?, ?, ?, ?)
             RETURNING pane_id
-        ))?((workspace_id, pane.active, pane.pinned_count))?
+        ))?((workspace_id, pane.active, pane.pinned_count, context))?
         .ok_or_else(|| anyhow!("Could not retrieve inserted pane_id"))?;
 
         let (parent_id, order) = parent.unzip();
@@ -1388,7 +1814,7 @@ mod tests {
     use std::time::Duration;
 
     use super::*;
-    use crate::persistence::model::SerializedWorkspace;
+    use crate::persistence::model::{DockData, SerializedWorkspace};
     use crate::persistence::model::{SerializedItem, SerializedPane, SerializedPaneGroup};
     use db::open_test_db;
     use gpui;
@@ -1441,6 +1867,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            center_group_unreadable: false,
         };
 
         db.save_workspace(workspace.clone()).await;
@@ -1538,6 +1965,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            center_group_unreadable: false,
         };
 
         let workspace_2 = SerializedWorkspace {
@@ -1551,6 +1979,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            center_group_unreadable: false,
         };
 
         db.save_workspace(workspace_1.clone()).await;
@@ -1659,6 +2088,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(999),
+            center_group_unreadable: false,
         };
 
         db.save_workspace(workspace.clone()).await;
@@ -1694,6 +2124,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(1),
+            center_group_unreadable: false,
         };
 
         let mut workspace_2 = SerializedWorkspace {
@@ -1707,6 +2138,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
+            center_group_unreadable: false,
         };
 
         db.save_workspace(workspace_1.clone()).await;
@@ -1750,6 +2182,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(3),
+            center_group_unreadable: false,
         };
 
         db.save_workspace(workspace_3.clone()).await;
@@ -1787,6 +2220,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
+            center_group_unreadable: false,
         };
 
         let workspace_2 = SerializedWorkspace {
@@ -1800,6 +2234,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
+            center_group_unreadable: false,
         };
 
         let workspace_3 = SerializedWorkspace {
@@ -1813,6 +2248,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
+            center_group_unreadable: false,
         };
 
         let workspace_4 = SerializedWorkspace {
@@ -1826,6 +2262,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            center_group_unreadable: false,
         };
 
         let ssh_project = db
@@ -1844,6 +2281,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
+            center_group_unreadable: false,
         };
 
         let workspace_6 = SerializedWorkspace {
@@ -1860,6 +2298,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("session-id-3".to_owned()),
             window_id: Some(60),
+            center_group_unreadable: false,
         };
 
         db.save_workspace(workspace_1.clone()).await;
@@ -1916,6 +2355,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: None,
+            center_group_unreadable: false,
         }
     }
 
@@ -1962,6 +2402,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            center_group_unreadable: false,
         })
         .collect::<Vec<_>>();
 
@@ -2054,6 +2495,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            center_group_unreadable: false,
         })
         .collect::<Vec<_>>();
 
@@ -2206,6 +2648,216 @@ mod tests {
         assert_eq!(stored_projects.len(), projects.len());
     }
 
+    #[gpui::test]
+    async fn test_named_sessions() {
+        use crate::layout_export::{ExportedItem, ExportedLayout, ExportedMember, ExportedPane};
+
+        let db = WorkspaceDb(open_test_db("test_named_sessions").await);
+
+        let layout = ExportedLayout {
+            center: ExportedMember::Pane(ExportedPane {
+                active: true,
+                items: vec![ExportedItem {
+                    worktree: Some("zed".to_string()),
+                    path: "src/main.rs".into(),
+                }],
+                active_item: Some(0),
+            }),
+            ..Default::default()
+        };
+        db.save_named_session(
+            "Release".to_string(),
+            serde_json::to_string(&["/code/zed", "/code/docs"]).unwrap(),
+            serde_json::to_string(&layout).unwrap(),
+        )
+        .await
+        .unwrap();
+        db.save_named_session(
+            "Release".to_string(),
+            serde_json::to_string(&["/code/zed"]).unwrap(),
+            serde_json::to_string(&layout).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let sessions = db.named_sessions().await.unwrap();
+        assert_eq!(
+            sessions,
+            vec![SerializedNamedSession {
+                name: "Release".to_string(),
+                paths: vec!["/code/zed".into()],
+                layout,
+            }]
+        );
+
+        // Sessions that can't be parsed are skipped.
+        db.save_named_session(
+            "Corrupt".to_string(),
+            serde_json::to_string(&["/code/zed"]).unwrap(),
+            "{".to_string(),
+        )
+        .await
+        .unwrap();
+        let sessions = db.named_sessions().await.unwrap();
+        assert_eq!(
+            sessions
+                .iter()
+                .map(|session| session.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Release"]
+        );
+
+        db.delete_named_session("Release".to_string())
+            .await
+            .unwrap();
+        db.delete_named_session("Corrupt".to_string())
+            .await
+            .unwrap();
+        assert!(db.named_sessions().await.unwrap().is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_window_tabs() {
+        let db = WorkspaceDb(open_test_db("test_window_tabs").await);
+
+        for (id, path) in [(1, "/tmp1"), (2, "/tmp2"), (3, "/tmp3")] {
+            db.save_workspace(SerializedWorkspace {
+                id: WorkspaceId(id),
+                session_id: Some("session-id-1".to_owned()),
+                window_id: Some(10),
+                ..default_workspace(&[path], &Default::default())
+            })
+            .await;
+        }
+        let tab_ids = |tabs: Vec<(WorkspaceId, LocalPaths)>| {
+            tabs.into_iter().map(|(id, _)| id.0).collect::<Vec<_>>()
+        };
+
+        db.set_window_tabs(vec![WorkspaceId(1), WorkspaceId(2), WorkspaceId(3)], 1)
+            .await
+            .unwrap();
+        assert_eq!(
+            tab_ids(db.window_tabs(WorkspaceId(2)).await.unwrap()),
+            [1, 2, 3]
+        );
+        assert!(db.window_tabs(WorkspaceId(1)).await.unwrap().is_empty());
+
+        // Only the active tab is restored as a window of the session.
+        let session_workspaces = db.session_workspaces("session-id-1".to_owned()).unwrap();
+        assert_eq!(session_workspaces.len(), 1);
+        assert_eq!(session_workspaces[0].0, LocalPaths::new(["/tmp2"]));
+
+        // Closing the active tab activates the one that takes its place.
+        db.set_window_tabs(vec![WorkspaceId(1), WorkspaceId(3)], 1)
+            .await
+            .unwrap();
+        db.delete_window_tab(WorkspaceId(2)).await.unwrap();
+        assert_eq!(
+            tab_ids(db.window_tabs(WorkspaceId(3)).await.unwrap()),
+            [1, 3]
+        );
+        assert!(db.window_tabs(WorkspaceId(2)).await.unwrap().is_empty());
+
+        // A tab opened in its own window no longer belongs to the other window.
+        db.delete_background_window_tab(WorkspaceId(1))
+            .await
+            .unwrap();
+        db.delete_background_window_tab(WorkspaceId(3))
+            .await
+            .unwrap();
+        assert_eq!(tab_ids(db.window_tabs(WorkspaceId(3)).await.unwrap()), [3]);
+    }
+
+    #[gpui::test]
+    async fn test_named_layouts() {
+        let db = WorkspaceDb(open_test_db("test_named_layouts").await);
+
+        let docks = DockStructure {
+            left: DockData {
+                visible: true,
+                active_panel: Some("ProjectPanel".to_string()),
+                zoom: false,
+            },
+            right: DockData::default(),
+            bottom: DockData {
+                visible: true,
+                active_panel: Some("TerminalPanel".to_string()),
+                zoom: true,
+            },
+            bottom_right: DockData {
+                visible: true,
+                active_panel: Some("DebugPanel".to_string()),
+                zoom: false,
+            },
+            bottom_right_panels: vec!["DebugPanel".to_string()],
+            removed_panels: vec!["OutlinePanel".to_string()],
+            bottom_dock_split: Some(0.3),
+        };
+        db.save_named_layout("Coding".to_string(), docks.clone(), false)
+            .await
+            .unwrap();
+        db.save_named_layout("Review".to_string(), DockStructure::default(), true)
+            .await
+            .unwrap();
+        assert_eq!(db.default_named_layout().await.unwrap(), None);
+
+        db.duplicate_named_layout("Coding".to_string(), "Debugging".to_string())
+            .await
+            .unwrap();
+        db.rename_named_layout("Review".to_string(), "Reading".to_string())
+            .await
+            .unwrap();
+        db.set_default_named_layout("Debugging".to_string())
+            .await
+            .unwrap();
+
+        let layouts = db.named_layouts().await.unwrap();
+        assert_eq!(
+            layouts,
+            vec![
+                SerializedLayout {
+                    name: "Coding".to_string(),
+                    docks: docks.clone(),
+                    centered_layout: false,
+                    is_default: false,
+                },
+                SerializedLayout {
+                    name: "Debugging".to_string(),
+                    docks: docks.clone(),
+                    centered_layout: false,
+                    is_default: true,
+                },
+                SerializedLayout {
+                    name: "Reading".to_string(),
+                    docks: DockStructure::default(),
+                    centered_layout: true,
+                    is_default: false,
+                },
+            ]
+        );
+        assert_eq!(
+            db.default_named_layout()
+                .await
+                .unwrap()
+                .map(|layout| layout.name),
+            Some("Debugging".to_string())
+        );
+
+        // Saving over an existing layout keeps its default status.
+        db.save_named_layout("Debugging".to_string(), DockStructure::default(), false)
+            .await
+            .unwrap();
+        db.delete_named_layout("Coding".to_string()).await.unwrap();
+        let layouts = db.named_layouts().await.unwrap();
+        assert_eq!(
+            layouts
+                .iter()
+                .map(|layout| (layout.name.as_str(), layout.is_default))
+                .collect::<Vec<_>>(),
+            vec![("Debugging", true), ("Reading", false)]
+        );
+    }
+
     #[gpui::test]
     async fn test_simple_split() {
         env_logger::try_init().ok();
@@ -2261,6 +2913,129 @@ mod tests {
         assert_eq!(workspace.center_group, new_workspace.center_group);
     }
 
+    #[gpui::test]
+    async fn test_pane_context() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_pane_context").await);
+
+        let center_pane = group(
+            Axis::Horizontal,
+            vec![
+                SerializedPaneGroup::Pane(
+                    SerializedPane::new(
+                        vec![SerializedItem::new("Terminal", 1, true, false)],
+                        true,
+                        0,
+                    )
+                    .with_context(BTreeMap::from_iter([(
+                        "working_directory".to_string(),
+                        "\"/tmp/project\"".to_string(),
+                    )])),
+                ),
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 2, true, false)],
+                    false,
+                    0,
+                )),
+            ],
+        );
+
+        let workspace = default_workspace(&["/tmp"], &center_pane);
+
+        db.save_workspace(workspace.clone()).await;
+
+        let new_workspace = db.workspace_for_roots(&["/tmp"]).unwrap();
+
+        assert_eq!(workspace.center_group, new_workspace.center_group);
+    }
+
+    #[gpui::test]
+    async fn test_restoring_unknown_layout_values() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_restoring_unknown_layout_values").await);
+
+        let center_pane = group(
+            Axis::Horizontal,
+            vec![
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 1, true, false)],
+                    true,
+                    0,
+                )),
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 2, true, false)],
+                    false,
+                    0,
+                )),
+            ],
+        );
+        let mut workspace = default_workspace(&["/tmp"], &center_pane);
+        workspace.docks.left.visible = true;
+        workspace.docks.bottom_right = DockData {
+            visible: true,
+            active_panel: Some("TerminalPanel".to_string()),
+            zoom: false,
+        };
+        workspace.docks.bottom_right_panels = vec!["TerminalPanel".to_string()];
+        workspace.docks.bottom_dock_split = Some(0.3);
+        db.save_workspace(workspace.clone()).await;
+
+        // Simulate values written by a newer version of Zed.
+        db.write(|conn| {
+            conn.exec("UPDATE pane_groups SET axis = 'Diagonal'")
+                .unwrap()()
+            .unwrap();
+            conn.exec(
+                "UPDATE workspaces SET window_state = 'Tiled', window_x = 10, window_y = 20, \
+                 window_width = 800, window_height = 600",
+            )
+            .unwrap()()
+            .unwrap();
+        })
+        .await;
+
+        let restored = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(restored.center_group, empty_center_group());
+        assert!(restored.center_group_unreadable);
+        assert_eq!(restored.docks, workspace.docks);
+
+        // Saving the restored workspace keeps the stored layout for the newer version.
+        db.save_workspace(restored.clone()).await;
+        let axis: Vec<String> = db
+            .select_bound(sql!(SELECT axis FROM pane_groups WHERE workspace_id = ?))
+            .unwrap()(restored.id)
+        .unwrap();
+        assert_eq!(axis, vec!["Diagonal".to_string()]);
+        let pane_count: Vec<i64> = db
+            .select_bound(sql!(SELECT COUNT(*) FROM panes WHERE workspace_id = ?))
+            .unwrap()(restored.id)
+        .unwrap();
+        assert_eq!(pane_count, vec![2]);
+        assert_eq!(
+            restored.window_bounds,
+            Some(SerializedWindowBounds(WindowBounds::Windowed(Bounds {
+                origin: point(px(10.), px(20.)),
+                size: size(px(800.), px(600.)),
+            })))
+        );
+
+        // Once the user changes the center layout, it replaces the stored one.
+        let changed_center_pane = SerializedPaneGroup::Pane(SerializedPane::new(
+            vec![SerializedItem::new("Terminal", 3, true, false)],
+            true,
+            0,
+        ));
+        let mut changed = restored.clone();
+        changed.center_group = changed_center_pane.clone();
+        changed.center_group_unreadable = false;
+        db.save_workspace(changed).await;
+        let restored = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(restored.center_group, changed_center_pane);
+        assert!(!restored.center_group_unreadable);
+    }
+
     #[gpui::test]
     async fn test_cleanup_panes() {
         env_logger::try_init().ok();
@@ -1,6 +1,7 @@
 use super::{SerializedAxis, SerializedWindowBounds};
 use crate::{
-    item::ItemHandle, Member, Pane, PaneAxis, SerializableItemRegistry, Workspace, WorkspaceId,
+    item::ItemHandle, layout_export::ExportedLayout, Member, Pane, PaneAxis,
+    SerializableItemRegistry, Workspace, WorkspaceId,
 };
 use anyhow::{Context as _, Result};
 use async_recursion::async_recursion;
@@ -266,6 +267,9 @@ pub(crate) struct SerializedWorkspace {
     pub(crate) session_id: Option<String>,
     pub(crate) breakpoints: BTreeMap<Arc<Path>, Vec<SerializedBreakpoint>>,
     pub(crate) window_id: Option<u64>,
+    /// Whether the stored center group couldn't be read when the workspace was loaded, in
+    /// which case `center_group` is an empty placeholder.
+    pub(crate) center_group_unreadable: bool,
 }
 
 #[derive(Debug, PartialEq, Clone, Default)]
@@ -273,6 +277,13 @@ pub struct DockStructure {
     pub(crate) left: DockData,
     pub(crate) right: DockData,
     pub(crate) bottom: DockData,
+    pub(crate) bottom_right: DockData,
+    /// The persistent names of the panels the user moved to the bottom right dock.
+    pub(crate) bottom_right_panels: Vec<String>,
+    /// The persistent names of the panels the user removed from their docks.
+    pub(crate) removed_panels: Vec<String>,
+    /// The share of the bottom docks' width taken by the left one while both are open.
+    pub(crate) bottom_dock_split: Option<f32>,
 }
 
 impl Column for DockStructure {
@@ -280,11 +291,25 @@ impl Column for DockStructure {
         let (left, next_index) = DockData::column(statement, start_index)?;
         let (right, next_index) = DockData::column(statement, next_index)?;
         let (bottom, next_index) = DockData::column(statement, next_index)?;
+        let (bottom_right, next_index) = DockData::column(statement, next_index)?;
+        let (bottom_right_panels, next_index) = Option::<String>::column(statement, next_index)?;
+        let bottom_right_panels = bottom_right_panels
+            .and_then(|panels| serde_json::from_str(&panels).log_err())
+            .unwrap_or_default();
+        let (removed_panels, next_index) = Option::<String>::column(statement, next_index)?;
+        let removed_panels = removed_panels
+            .and_then(|panels| serde_json::from_str(&panels).log_err())
+            .unwrap_or_default();
+        let (bottom_dock_split, next_index) = Option::<f32>::column(statement, next_index)?;
         Ok((
             DockStructure {
                 left,
                 right,
                 bottom,
+                bottom_right,
+                bottom_right_panels,
+                removed_panels,
+                bottom_dock_split,
             },
             next_index,
         ))
@@ -295,7 +320,15 @@ impl Bind for DockStructure {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
         let next_index = statement.bind(&self.left, start_index)?;
         let next_index = statement.bind(&self.right, next_index)?;
-        statement.bind(&self.bottom, next_index)
+        let next_index = statement.bind(&self.bottom, next_index)?;
+        let next_index = statement.bind(&self.bottom_right, next_index)?;
+        let next_index = statement.bind(
+            &serde_json::to_string(&self.bottom_right_panels)?,
+            next_index,
+        )?;
+        let next_index =
+            statement.bind(&serde_json::to_string(&self.removed_panels)?, next_index)?;
+        statement.bind(&self.bottom_dock_split, next_index)
     }
 }
 
@@ -330,6 +363,54 @@ impl Bind for DockData {
     }
 }
 
+/// A named snapshot of the dock layout that can be applied to any workspace window.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SerializedLayout {
+    pub name: String,
+    pub(crate) docks: DockStructure,
+    pub(crate) centered_layout: bool,
+    pub is_default: bool,
+}
+
+impl Column for SerializedLayout {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (name, next_index) = String::column(statement, start_index)?;
+        let (docks, next_index) = DockStructure::column(statement, next_index)?;
+        let (centered_layout, next_index) = Option::<bool>::column(statement, next_index)?;
+        let (is_default, next_index) = bool::column(statement, next_index)?;
+        Ok((
+            SerializedLayout {
+                name,
+                docks,
+                centered_layout: centered_layout.unwrap_or(false),
+                is_default,
+            },
+            next_index,
+        ))
+    }
+}
+
+/// A workspace saved under a name with [`Workspace::save_named_session`], which can be
+/// reopened later.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SerializedNamedSession {
+    pub name: String,
+    /// The root paths of the session's project, in the project's order.
+    pub paths: Vec<PathBuf>,
+    pub layout: ExportedLayout,
+}
+
+impl SerializedNamedSession {
+    /// Parses a session from the JSON its paths and layout are stored as.
+    pub(crate) fn parse(name: String, paths: &str, layout: &str) -> Result<Self> {
+        Ok(Self {
+            paths: serde_json::from_str(paths).context("parsing session paths")?,
+            layout: serde_json::from_str(layout).context("parsing session layout")?,
+            name,
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) enum SerializedPaneGroup {
     Group {
@@ -347,6 +428,7 @@ impl Default for SerializedPaneGroup {
             children: vec![SerializedItem::default()],
             active: false,
             pinned_count: 0,
+            context: BTreeMap::default(),
         })
     }
 }
@@ -436,6 +518,8 @@ pub struct SerializedPane {
     pub(crate) active: bool,
     pub(crate) children: Vec<SerializedItem>,
     pub(crate) pinned_count: usize,
+    /// The pane's serialized [`PaneContext`](crate::pane::PaneContext) values by their key.
+    pub(crate) context: BTreeMap<String, String>,
 }
 
 impl SerializedPane {
@@ -444,9 +528,15 @@ impl SerializedPane {
             children,
             active,
             pinned_count,
+            context: BTreeMap::default(),
         }
     }
 
+    pub fn with_context(mut self, context: BTreeMap<String, String>) -> Self {
+        self.context = context;
+        self
+    }
+
     pub async fn deserialize_to(
         &self,
         project: &Entity<Project>,
@@ -506,6 +596,8 @@ impl SerializedPane {
         }
         pane.update(cx, |pane, _| {
             pane.set_pinned_count(self.pinned_count.min(items.len()));
+            // Restore the context after activating the items, which may have updated it.
+            pane.set_serialized_context(self.context.clone());
         })?;
 
         anyhow::Ok(items)
@@ -0,0 +1,351 @@
+//! Progress of long-running operations, shown in the status bar.
+//!
+//! Operations are started with [`Workspace::start_progress`], which returns a
+//! [`ProgressHandle`] for reporting how far along the operation is. The operation ends when
+//! the handle is finished or dropped. All of a workspace's running operations are aggregated
+//! into a single status bar item.
+
+use crate::{notifications::NotificationId, ItemHandle, StatusItemView, Toast, Workspace};
+use futures::channel::oneshot;
+use gpui::{
+    percentage, Animation, AnimationExt as _, App, Context, Entity, EventEmitter, IntoElement,
+    Render, Task, Transformation, WeakEntity, Window,
+};
+use std::time::Duration;
+use ui::{prelude::*, ContextMenu, Tooltip};
+
+struct ProgressToast;
+
+struct ProgressOperation {
+    id: usize,
+    title: SharedString,
+    /// How much of the operation is done, between 0 and 1, or `None` if that isn't known.
+    fraction: Option<f32>,
+    on_cancel: Option<Box<dyn FnOnce(&mut App)>>,
+    /// Whether the operation is also shown as a toast.
+    shows_toast: bool,
+    /// Finishes the operation when its handle is dropped.
+    _finish_on_drop: Task<()>,
+}
+
+pub enum ProgressEvent {
+    ShowToast { id: usize },
+    Finished { id: usize },
+}
+
+/// The status bar item showing the progress of all running operations of a workspace.
+pub struct ProgressIndicator {
+    operations: Vec<ProgressOperation>,
+    next_id: usize,
+}
+
+impl ProgressIndicator {
+    pub fn new() -> Self {
+        Self {
+            operations: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn operation_count(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// The combined progress of all running operations, or `None` if the progress of
+    /// any of them isn't known.
+    pub fn aggregate_fraction(&self) -> Option<f32> {
+        if self.operations.is_empty() {
+            return None;
+        }
+        let mut sum = 0.;
+        for operation in &self.operations {
+            sum += operation.fraction?;
+        }
+        Some(sum / self.operations.len() as f32)
+    }
+
+    fn start(&mut self, title: SharedString, cx: &mut Context<Self>) -> ProgressHandle {
+        let id = self.next_id;
+        self.next_id += 1;
+        let (handle_dropped_tx, handle_dropped_rx) = oneshot::channel::<()>();
+        self.operations.push(ProgressOperation {
+            id,
+            title,
+            fraction: None,
+            on_cancel: None,
+            shows_toast: false,
+            _finish_on_drop: cx.spawn(async move |this, cx| {
+                handle_dropped_rx.await.ok();
+                this.update(cx, |this, cx| {
+                    this.finish(id, cx);
+                })
+                .ok();
+            }),
+        });
+        cx.notify();
+        ProgressHandle {
+            id,
+            indicator: cx.entity().downgrade(),
+            _handle_dropped: handle_dropped_tx,
+        }
+    }
+
+    fn operation_mut(&mut self, id: usize) -> Option<&mut ProgressOperation> {
+        self.operations
+            .iter_mut()
+            .find(|operation| operation.id == id)
+    }
+
+    fn finish(&mut self, id: usize, cx: &mut Context<Self>) -> Option<ProgressOperation> {
+        let ix = self
+            .operations
+            .iter()
+            .position(|operation| operation.id == id)?;
+        let operation = self.operations.remove(ix);
+        cx.emit(ProgressEvent::Finished { id });
+        cx.notify();
+        Some(operation)
+    }
+
+    /// Stops tracking the operation and runs its cancellation callback.
+    pub fn cancel(&mut self, id: usize, cx: &mut Context<Self>) {
+        if let Some(on_cancel) = self
+            .finish(id, cx)
+            .and_then(|operation| operation.on_cancel)
+        {
+            on_cancel(cx);
+        }
+    }
+
+    fn label(&self) -> SharedString {
+        let label: SharedString = match self.operations.as_slice() {
+            [operation] => operation.title.clone(),
+            operations => format!("{} operations", operations.len()).into(),
+        };
+        match self.aggregate_fraction() {
+            Some(fraction) => format!("{label} ({:.0}%)", fraction * 100.).into(),
+            None => label,
+        }
+    }
+
+    fn toast(&self, id: usize, indicator: WeakEntity<Self>) -> Option<Toast> {
+        let operation = self
+            .operations
+            .iter()
+            .find(|operation| operation.id == id)?;
+        let mut toast = Toast::new(
+            NotificationId::composite::<ProgressToast>(id),
+            format!("{}…", operation.title),
+        );
+        if operation.on_cancel.is_some() {
+            toast = toast.on_click("Cancel", move |_, cx| {
+                indicator
+                    .update(cx, |indicator, cx| indicator.cancel(id, cx))
+                    .ok();
+            });
+        }
+        Some(toast)
+    }
+}
+
+impl Default for ProgressIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventEmitter<ProgressEvent> for ProgressIndicator {}
+
+impl Render for ProgressIndicator {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        if self.operations.is_empty() {
+            return div().hidden().into_any_element();
+        }
+
+        let tooltip = self
+            .operations
+            .iter()
+            .map(|operation| operation.title.as_ref())
+            .collect::<Vec<_>>()
+            .join("\n");
+        h_flex()
+            .id("progress-indicator")
+            .gap_1()
+            .child(
+                Icon::new(IconName::ArrowCircle)
+                    .size(IconSize::Small)
+                    .color(Color::Muted)
+                    .with_animation(
+                        "progress-indicator-spinner",
+                        Animation::new(Duration::from_secs(2)).repeat(),
+                        |icon, delta| icon.transform(Transformation::rotate(percentage(delta))),
+                    ),
+            )
+            .child(Label::new(self.label()).size(LabelSize::Small))
+            .tooltip(Tooltip::text(tooltip))
+            .into_any_element()
+    }
+}
+
+impl StatusItemView for ProgressIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    fn is_visible(&self, _cx: &App) -> bool {
+        !self.operations.is_empty()
+    }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some("progress")
+    }
+
+    fn context_menu(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<ContextMenu>> {
+        let cancellable = self
+            .operations
+            .iter()
+            .filter(|operation| operation.on_cancel.is_some())
+            .map(|operation| (operation.id, operation.title.clone()))
+            .collect::<Vec<_>>();
+        if cancellable.is_empty() {
+            return None;
+        }
+
+        let indicator = cx.entity().downgrade();
+        Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+            for (id, title) in cancellable {
+                let indicator = indicator.clone();
+                menu = menu.entry(format!("Cancel {title}"), None, move |_, cx| {
+                    indicator
+                        .update(cx, |indicator, cx| indicator.cancel(id, cx))
+                        .ok();
+                });
+            }
+            menu
+        }))
+    }
+}
+
+/// Reports the progress of an operation started with [`Workspace::start_progress`].
+///
+/// The operation is shown until [`ProgressHandle::finish`] is called, the handle is dropped,
+/// or the operation is cancelled.
+pub struct ProgressHandle {
+    id: usize,
+    indicator: WeakEntity<ProgressIndicator>,
+    _handle_dropped: oneshot::Sender<()>,
+}
+
+impl ProgressHandle {
+    /// Identifies the operation within its [`ProgressIndicator`].
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Sets how much of the operation is done, between 0 and 1.
+    pub fn set_fraction(&self, fraction: f32, cx: &mut App) {
+        self.update(cx, |operation| {
+            operation.fraction = Some(fraction.clamp(0., 1.))
+        });
+    }
+
+    /// Shows the operation as indeterminate again.
+    pub fn clear_fraction(&self, cx: &mut App) {
+        self.update(cx, |operation| operation.fraction = None);
+    }
+
+    /// Lets the user cancel the operation, running the given callback if they do.
+    pub fn on_cancel(&self, on_cancel: impl FnOnce(&mut App) + 'static, cx: &mut App) {
+        let mut shows_toast = false;
+        self.update(cx, |operation| {
+            operation.on_cancel = Some(Box::new(on_cancel));
+            shows_toast = operation.shows_toast;
+        });
+        // Replace the toast, so that it gets a button to cancel the operation.
+        if shows_toast {
+            self.show_toast(cx);
+        }
+    }
+
+    /// Also shows the operation as a toast until it's finished.
+    pub fn show_toast(&self, cx: &mut App) {
+        let id = self.id;
+        self.update(cx, |operation| operation.shows_toast = true);
+        self.indicator
+            .update(cx, |_, cx| cx.emit(ProgressEvent::ShowToast { id }))
+            .ok();
+    }
+
+    /// Whether the operation was cancelled, or its workspace was closed.
+    pub fn is_cancelled(&self, cx: &App) -> bool {
+        self.indicator.upgrade().is_none_or(|indicator| {
+            !indicator
+                .read(cx)
+                .operations
+                .iter()
+                .any(|operation| operation.id == self.id)
+        })
+    }
+
+    pub fn finish(self, cx: &mut App) {
+        self.indicator
+            .update(cx, |indicator, cx| {
+                indicator.finish(self.id, cx);
+            })
+            .ok();
+    }
+
+    fn update(&self, cx: &mut App, f: impl FnOnce(&mut ProgressOperation)) {
+        self.indicator
+            .update(cx, |indicator, cx| {
+                if let Some(operation) = indicator.operation_mut(self.id) {
+                    f(operation);
+                    cx.notify();
+                }
+            })
+            .ok();
+    }
+}
+
+impl Workspace {
+    pub fn progress_indicator(&self) -> &Entity<ProgressIndicator> {
+        &self.progress_indicator
+    }
+
+    /// Shows an operation in the status bar until the returned handle is finished or dropped.
+    pub fn start_progress(
+        &mut self,
+        title: impl Into<SharedString>,
+        cx: &mut Context<Self>,
+    ) -> ProgressHandle {
+        let title = title.into();
+        self.progress_indicator
+            .update(cx, |indicator, cx| indicator.start(title, cx))
+    }
+
+    pub(crate) fn on_progress_event(
+        &mut self,
+        indicator: Entity<ProgressIndicator>,
+        event: &ProgressEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match *event {
+            ProgressEvent::ShowToast { id } => {
+                if let Some(toast) = indicator.read(cx).toast(id, indicator.downgrade()) {
+                    self.show_toast(toast, cx);
+                }
+            }
+            ProgressEvent::Finished { id } => {
+                self.dismiss_toast(&NotificationId::composite::<ProgressToast>(id), cx);
+            }
+        }
+    }
+}
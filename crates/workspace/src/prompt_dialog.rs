@@ -0,0 +1,365 @@
+//! Prompts rendered inside the workspace's window, as opposed to the OS's prompts shown by
+//! [`Window::prompt`].
+
+use crate::{ModalView, Workspace};
+use db::kvp::KEY_VALUE_STORE;
+use futures::channel::oneshot;
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, FontWeight,
+    PromptLevel, Render, Task, Window,
+};
+use ui::{prelude::*, Checkbox, ElevationIndex, TintColor, ToggleState};
+use util::ResultExt;
+
+/// Describes the answers a prompt offers and how it's shown.
+pub struct PromptOptions {
+    pub level: PromptLevel,
+    pub detail: Option<SharedString>,
+    pub answers: Vec<SharedString>,
+    /// When set, the prompt offers a "Don't ask again" checkbox. If the user checks it, their
+    /// answer is remembered under this key and returned without prompting from then on, until
+    /// the remembered answers are reset with [`reset_remembered_answers`].
+    pub dont_ask_again_key: Option<SharedString>,
+    /// Entries listed with a checkbox each, all checked at first. See
+    /// [`Workspace::prompt_with_checklist`].
+    pub checklist: Vec<SharedString>,
+}
+
+impl PromptOptions {
+    pub fn new(answers: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        Self {
+            level: PromptLevel::Info,
+            detail: None,
+            answers: answers.into_iter().map(Into::into).collect(),
+            dont_ask_again_key: None,
+            checklist: Vec::new(),
+        }
+    }
+
+    pub fn level(mut self, level: PromptLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn detail(mut self, detail: impl Into<SharedString>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn dont_ask_again(mut self, key: impl Into<SharedString>) -> Self {
+        self.dont_ask_again_key = Some(key.into());
+        self
+    }
+
+    pub fn checklist(mut self, entries: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        self.checklist = entries.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+const REMEMBERED_ANSWER_KEY_PREFIX: &str = "prompt-answer-";
+
+fn remembered_answer_key(key: &str) -> String {
+    format!("{REMEMBERED_ANSWER_KEY_PREFIX}{key}")
+}
+
+/// Forgets the answers of all prompts where "Don't ask again" was checked, so that they're
+/// asked again.
+pub fn reset_remembered_answers(cx: &mut App) {
+    db::write_and_log(cx, || {
+        KEY_VALUE_STORE.delete_kvps_with_prefix(REMEMBERED_ANSWER_KEY_PREFIX.to_string())
+    });
+}
+
+/// A modal asking the user to pick one of several answers.
+pub struct PromptDialog {
+    message: SharedString,
+    options: PromptOptions,
+    selected_ix: usize,
+    dont_ask_again: ToggleState,
+    checked: Vec<bool>,
+    answer_tx: Option<oneshot::Sender<Option<(usize, Vec<bool>)>>>,
+    focus_handle: FocusHandle,
+}
+
+impl PromptDialog {
+    fn new(
+        message: SharedString,
+        options: PromptOptions,
+        answer_tx: oneshot::Sender<Option<(usize, Vec<bool>)>>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            message,
+            checked: vec![true; options.checklist.len()],
+            options,
+            selected_ix: 0,
+            dont_ask_again: ToggleState::Unselected,
+            answer_tx: Some(answer_tx),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn answers(&self) -> Vec<&str> {
+        self.options.answers.iter().map(AsRef::as_ref).collect()
+    }
+
+    pub fn checklist(&self) -> Vec<&str> {
+        self.options.checklist.iter().map(AsRef::as_ref).collect()
+    }
+
+    pub fn selected_answer(&self) -> usize {
+        self.selected_ix
+    }
+
+    pub fn set_checked(&mut self, ix: usize, checked: bool, cx: &mut Context<Self>) {
+        if let Some(entry) = self.checked.get_mut(ix) {
+            *entry = checked;
+            cx.notify();
+        }
+    }
+
+    pub fn set_dont_ask_again(&mut self, dont_ask_again: bool, cx: &mut Context<Self>) {
+        self.dont_ask_again = dont_ask_again.into();
+        cx.notify();
+    }
+
+    pub fn answer(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if let Some(key) = &self.options.dont_ask_again_key {
+            if self.dont_ask_again.selected() {
+                let key = remembered_answer_key(key);
+                db::write_and_log(cx, move || KEY_VALUE_STORE.write_kvp(key, ix.to_string()));
+            }
+        }
+        if let Some(answer_tx) = self.answer_tx.take() {
+            answer_tx.send(Some((ix, self.checked.clone()))).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        self.answer(self.selected_ix, cx);
+    }
+
+    pub fn dismiss(&mut self, cx: &mut Context<Self>) {
+        if let Some(answer_tx) = self.answer_tx.take() {
+            answer_tx.send(None).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        self.dismiss(cx);
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.selected_ix = (self.selected_ix + 1) % self.options.answers.len();
+        cx.notify();
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected_ix = self
+            .selected_ix
+            .checked_sub(1)
+            .unwrap_or(self.options.answers.len() - 1);
+        cx.notify();
+    }
+
+    fn select_first(&mut self, _: &menu::SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.selected_ix = 0;
+        cx.notify();
+    }
+
+    fn select_last(&mut self, _: &menu::SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        self.selected_ix = self.options.answers.len() - 1;
+        cx.notify();
+    }
+}
+
+impl PromptDialog {
+    fn render_checklist(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = self
+            .options
+            .checklist
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| {
+                let checkbox = Checkbox::new(("prompt-checklist", ix), self.checked[ix].into())
+                    .on_click(cx.listener(move |this, state: &ToggleState, _, cx| {
+                        this.set_checked(ix, state.selected(), cx);
+                    }));
+                h_flex()
+                    .gap_2()
+                    .child(checkbox)
+                    .child(Label::new(entry.clone()).size(LabelSize::Small))
+            });
+        v_flex()
+            .id("prompt-checklist")
+            .max_h_64()
+            .overflow_y_scroll()
+            .gap_1()
+            .children(entries)
+    }
+}
+
+impl EventEmitter<DismissEvent> for PromptDialog {}
+
+impl Focusable for PromptDialog {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ModalView for PromptDialog {
+    fn fade_out_background(&self) -> bool {
+        true
+    }
+}
+
+impl Render for PromptDialog {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let icon = match self.options.level {
+            PromptLevel::Info => None,
+            PromptLevel::Warning => Some(Icon::new(IconName::Warning).color(Color::Warning)),
+            PromptLevel::Critical => Some(Icon::new(IconName::XCircle).color(Color::Error)),
+        };
+
+        v_flex()
+            .key_context("Prompt")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .elevation_3(cx)
+            .map(|this| {
+                if self.options.checklist.is_empty() {
+                    this.w_80()
+                } else {
+                    this.w_96()
+                }
+            })
+            .p_4()
+            .gap_4()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .children(icon)
+                    .child(Label::new(self.message.clone()).weight(FontWeight::BOLD)),
+            )
+            .children(self.options.detail.clone().map(|detail| {
+                Label::new(detail)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+            }))
+            .when(!self.options.checklist.is_empty(), |this| {
+                this.child(self.render_checklist(cx))
+            })
+            .when(self.options.dont_ask_again_key.is_some(), |this| {
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .child(
+                            Checkbox::new("dont-ask-again", self.dont_ask_again).on_click(
+                                cx.listener(|this, state: &ToggleState, _, cx| {
+                                    this.set_dont_ask_again(state.selected(), cx);
+                                }),
+                            ),
+                        )
+                        .child(Label::new("Don't ask again").size(LabelSize::Small)),
+                )
+            })
+            .child(h_flex().justify_end().gap_2().children(
+                self.options.answers.iter().enumerate().map(|(ix, answer)| {
+                    Button::new(("prompt-answer", ix), answer.clone())
+                        .style(ButtonStyle::Filled)
+                        .when(ix == self.selected_ix, |this| {
+                            this.style(ButtonStyle::Tinted(TintColor::Accent))
+                        })
+                        .layer(ElevationIndex::ModalSurface)
+                        .on_click(cx.listener(move |this, _, _, cx| this.answer(ix, cx)))
+                }),
+            ))
+    }
+}
+
+impl Workspace {
+    /// Asks the user to pick one of the given answers in a dialog inside the window, resolving
+    /// to the index of the answer, or `None` if the dialog was dismissed.
+    pub fn prompt(
+        &mut self,
+        message: impl Into<SharedString>,
+        options: PromptOptions,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<usize>> {
+        let answer = self.prompt_with_checklist(message, options, window, cx);
+        cx.background_spawn(async move { answer.await.map(|(answer, _)| answer) })
+    }
+
+    /// Like [`Workspace::prompt`], but also lists [`PromptOptions::checklist`] with a checkbox
+    /// each, resolving to the answer and to whether each of the entries was left checked.
+    pub fn prompt_with_checklist(
+        &mut self,
+        message: impl Into<SharedString>,
+        options: PromptOptions,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<(usize, Vec<bool>)>> {
+        if options.answers.is_empty() {
+            return Task::ready(None);
+        }
+        if let Some(key) = &options.dont_ask_again_key {
+            let remembered_answer = KEY_VALUE_STORE
+                .read_kvp(&remembered_answer_key(key))
+                .log_err()
+                .flatten()
+                .and_then(|answer| answer.parse::<usize>().ok())
+                .filter(|answer| *answer < options.answers.len());
+            if let Some(answer) = remembered_answer {
+                return Task::ready(Some((answer, vec![true; options.checklist.len()])));
+            }
+        }
+
+        let message = message.into();
+        let (answer_tx, answer_rx) = oneshot::channel();
+        self.push_modal(window, cx, move |_, cx| {
+            PromptDialog::new(message, options, answer_tx, cx)
+        });
+        cx.background_spawn(async move { answer_rx.await.ok().flatten() })
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl Workspace {
+    /// The prompt shown by [`Workspace::prompt`] that's waiting for an answer, if any.
+    pub fn pending_prompt(&self, cx: &App) -> Option<Entity<PromptDialog>> {
+        self.active_modal::<PromptDialog>(cx)
+    }
+
+    /// Picks the answer with the given label in the pending prompt.
+    pub fn simulate_prompt_answer(&mut self, answer: &str, cx: &mut Context<Self>) {
+        let prompt = self
+            .pending_prompt(cx)
+            .expect("no prompt is waiting for an answer");
+        prompt.update(cx, |prompt, cx| {
+            let ix = prompt
+                .answers()
+                .iter()
+                .position(|label| *label == answer)
+                .unwrap_or_else(|| panic!("the prompt has no answer {answer:?}"));
+            prompt.answer(ix, cx);
+        });
+    }
+}
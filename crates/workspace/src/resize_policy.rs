@@ -0,0 +1,167 @@
+use gpui::{px, Pixels, Size};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The smallest size the center panes are shrunk to before [`CenterFirst`] starts shrinking docks.
+pub const MIN_CENTER_SIZE: Size<Pixels> = Size {
+    width: px(320.),
+    height: px(160.),
+};
+
+/// The sizes of the docks that are currently open, along their resizable axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DockSizes {
+    pub left: Option<Pixels>,
+    pub right: Option<Pixels>,
+    pub bottom: Option<Pixels>,
+}
+
+/// Decides how the docks are resized when the workspace bounds change, e.g. when the OS window
+/// is resized by dragging one of its edges.
+pub trait ResizePolicy {
+    fn resize_docks(
+        &self,
+        previous_size: Size<Pixels>,
+        new_size: Size<Pixels>,
+        docks: DockSizes,
+    ) -> DockSizes;
+}
+
+/// How docks react to the workspace window being resized.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowResizePolicy {
+    /// Keep the docks at their pixel sizes, giving all of the change to the center panes.
+    #[default]
+    Fixed,
+    /// Scale the docks together with the window, keeping their share of the window constant.
+    Proportional,
+    /// Keep the docks at their pixel sizes, unless that would shrink the center panes
+    /// below a minimum size, in which case the docks shrink instead.
+    CenterFirst,
+}
+
+impl WindowResizePolicy {
+    pub fn policy(&self) -> &'static dyn ResizePolicy {
+        match self {
+            Self::Fixed => &Fixed,
+            Self::Proportional => &Proportional,
+            Self::CenterFirst => &CenterFirst,
+        }
+    }
+}
+
+pub struct Fixed;
+
+impl ResizePolicy for Fixed {
+    fn resize_docks(&self, _: Size<Pixels>, _: Size<Pixels>, docks: DockSizes) -> DockSizes {
+        docks
+    }
+}
+
+pub struct Proportional;
+
+impl ResizePolicy for Proportional {
+    fn resize_docks(
+        &self,
+        previous_size: Size<Pixels>,
+        new_size: Size<Pixels>,
+        docks: DockSizes,
+    ) -> DockSizes {
+        let scale = |size: Option<Pixels>, previous: Pixels, new: Pixels| {
+            if previous <= px(0.) {
+                return size;
+            }
+            size.map(|size| size * (new / previous))
+        };
+        DockSizes {
+            left: scale(docks.left, previous_size.width, new_size.width),
+            right: scale(docks.right, previous_size.width, new_size.width),
+            bottom: scale(docks.bottom, previous_size.height, new_size.height),
+        }
+    }
+}
+
+pub struct CenterFirst;
+
+impl ResizePolicy for CenterFirst {
+    fn resize_docks(&self, _: Size<Pixels>, new_size: Size<Pixels>, docks: DockSizes) -> DockSizes {
+        let horizontal = shrink_to_fit(
+            [docks.left, docks.right],
+            new_size.width - MIN_CENTER_SIZE.width,
+        );
+        let [bottom] = shrink_to_fit([docks.bottom], new_size.height - MIN_CENTER_SIZE.height);
+        DockSizes {
+            left: horizontal[0],
+            right: horizontal[1],
+            bottom,
+        }
+    }
+}
+
+/// Shrinks the given sizes proportionally so that together they fit into `available`.
+fn shrink_to_fit<const N: usize>(
+    sizes: [Option<Pixels>; N],
+    available: Pixels,
+) -> [Option<Pixels>; N] {
+    let total = sizes
+        .iter()
+        .flatten()
+        .fold(px(0.), |total, size| total + *size);
+    if total <= available || total <= px(0.) {
+        return sizes;
+    }
+    let scale = available.max(px(0.)) / total;
+    sizes.map(|size| size.map(|size| size * scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::size;
+
+    #[test]
+    fn test_proportional_policy() {
+        let docks = DockSizes {
+            left: Some(px(200.)),
+            right: None,
+            bottom: Some(px(300.)),
+        };
+        let resized =
+            Proportional.resize_docks(size(px(1000.), px(800.)), size(px(2000.), px(400.)), docks);
+        assert_eq!(
+            resized,
+            DockSizes {
+                left: Some(px(400.)),
+                right: None,
+                bottom: Some(px(150.)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_center_first_policy() {
+        let docks = DockSizes {
+            left: Some(px(300.)),
+            right: Some(px(100.)),
+            bottom: Some(px(200.)),
+        };
+
+        // There is enough room for the center, so nothing changes.
+        let resized =
+            CenterFirst.resize_docks(size(px(1000.), px(800.)), size(px(900.), px(600.)), docks);
+        assert_eq!(resized, docks);
+
+        // The docks shrink proportionally to leave the center at its minimum size.
+        let resized =
+            CenterFirst.resize_docks(size(px(1000.), px(800.)), size(px(520.), px(260.)), docks);
+        assert_eq!(
+            resized,
+            DockSizes {
+                left: Some(px(150.)),
+                right: Some(px(50.)),
+                bottom: Some(px(100.)),
+            }
+        );
+    }
+}
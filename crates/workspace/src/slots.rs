@@ -0,0 +1,78 @@
+//! Named places in the workspace chrome where other crates can mount their views.
+//!
+//! Instead of patching [`Workspace`]'s render function for every new piece of chrome, a
+//! feature registers its view for one of the [`WorkspaceSlot`]s with
+//! [`Workspace::register_slot_view`]. Views in the same slot are rendered in the order
+//! they were registered.
+
+use crate::Workspace;
+use collections::HashMap;
+use gpui::{AnyView, Context, EntityId};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WorkspaceSlot {
+    /// Spans the window's width between the title bar and the docks.
+    BelowTitleBar,
+    /// Spans the window's width right above the status bar.
+    AboveStatusBar,
+    /// Covers the center panes. Views are laid out over the panes, so they should position
+    /// themselves and leave the rest of the slot empty, where the panes keep getting the
+    /// mouse events.
+    OverCenter,
+    /// At the end of the status bar, for the people in the current call. See
+    /// [`CallControls::facepile`](crate::call_controls::CallControls::facepile).
+    Facepile,
+}
+
+/// The views mounted in each slot of a workspace.
+#[derive(Default)]
+pub(crate) struct SlotViews {
+    views: HashMap<WorkspaceSlot, Vec<AnyView>>,
+}
+
+impl SlotViews {
+    pub(crate) fn views(&self, slot: WorkspaceSlot) -> impl Iterator<Item = AnyView> + '_ {
+        self.views.get(&slot).into_iter().flatten().cloned()
+    }
+}
+
+impl Workspace {
+    /// Mounts the given view in a slot of the workspace chrome. Registering a view that is
+    /// already mounted in the slot does nothing.
+    pub fn register_slot_view(
+        &mut self,
+        slot: WorkspaceSlot,
+        view: AnyView,
+        cx: &mut Context<Self>,
+    ) {
+        let views = self.slot_views.views.entry(slot).or_default();
+        if views
+            .iter()
+            .all(|existing| existing.entity_id() != view.entity_id())
+        {
+            views.push(view);
+            cx.notify();
+        }
+    }
+
+    /// Removes the view with the given id from a slot of the workspace chrome.
+    pub fn unregister_slot_view(
+        &mut self,
+        slot: WorkspaceSlot,
+        view_id: EntityId,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(views) = self.slot_views.views.get_mut(&slot) {
+            let view_count = views.len();
+            views.retain(|view| view.entity_id() != view_id);
+            if views.len() != view_count {
+                cx.notify();
+            }
+        }
+    }
+
+    /// The views mounted in the given slot, in the order they're rendered.
+    pub fn slot_views(&self, slot: WorkspaceSlot) -> Vec<AnyView> {
+        self.slot_views.views(slot).collect()
+    }
+}
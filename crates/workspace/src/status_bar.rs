@@ -1,11 +1,15 @@
-use crate::{ItemHandle, Pane};
+use crate::{workspace_settings::StatusBarSettings, ItemHandle, Pane};
+use collections::{HashMap, HashSet};
+use fs::Fs;
 use gpui::{
-    AnyView, App, Context, Decorations, Entity, IntoElement, ParentElement, Render, Styled,
-    Subscription, Window,
+    anchored, canvas, deferred, Action, AnyView, App, Bounds, Context, Corner, Decorations,
+    DismissEvent, Entity, EntityId, Focusable, IntoElement, MouseButton, MouseDownEvent,
+    ParentElement, Pixels, Point, Render, Styled, Subscription, Window,
 };
-use std::any::TypeId;
+use settings::{update_settings_file, Settings, SettingsStore};
+use std::{any::TypeId, cmp, rc::Rc, sync::Arc};
 use theme::CLIENT_SIDE_DECORATION_ROUNDING;
-use ui::{h_flex, prelude::*};
+use ui::{h_flex, prelude::*, ContextMenu, IconButton, PopoverMenu, Tooltip};
 use util::ResultExt;
 
 pub trait StatusItemView: Render {
@@ -15,6 +19,56 @@ pub trait StatusItemView: Render {
         window: &mut Window,
         cx: &mut Context<Self>,
     );
+
+    /// Whether the item has anything to show right now. The secondary status bar is
+    /// hidden while none of its items are visible.
+    fn is_visible(&self, _cx: &App) -> bool {
+        true
+    }
+
+    /// Whether the item stays in the status bar in focus mode, which hides all other items.
+    fn is_essential(&self) -> bool {
+        false
+    }
+
+    /// The name under which the item can be hidden with the `status_bar.hidden_items`
+    /// setting. Items without a name are always shown.
+    fn status_item_name(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The action dispatched when the item is clicked. Items that render their own buttons
+    /// don't need one.
+    fn click_action(&self, _cx: &App) -> Option<Box<dyn Action>> {
+        None
+    }
+
+    /// The menu shown when the item is right-clicked.
+    fn context_menu(
+        &mut self,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<Entity<ContextMenu>> {
+        None
+    }
+}
+
+/// Hides the status items with the given [name](StatusItemView::status_item_name) by adding
+/// it to the `status_bar.hidden_items` setting.
+pub fn hide_status_item(name: &'static str, fs: Arc<dyn Fs>, cx: &mut App) {
+    update_settings_file::<StatusBarSettings>(fs, cx, move |settings, _| {
+        let hidden_items = settings.hidden_items.get_or_insert_with(Vec::new);
+        if !hidden_items.iter().any(|hidden| hidden == name) {
+            hidden_items.push(name.to_string());
+        }
+    });
+}
+
+/// The side of the status bar an item is shown on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StatusItemAlignment {
+    Left,
+    Right,
 }
 
 trait StatusItemViewHandle: Send {
@@ -26,18 +80,55 @@ trait StatusItemViewHandle: Send {
         cx: &mut App,
     );
     fn item_type(&self) -> TypeId;
+    fn is_visible(&self, cx: &App) -> bool;
+    fn is_essential(&self, cx: &App) -> bool;
+    fn name(&self, cx: &App) -> Option<&'static str>;
+    fn click_action(&self, cx: &App) -> Option<Box<dyn Action>>;
+    fn context_menu(&self, window: &mut Window, cx: &mut App) -> Option<Entity<ContextMenu>>;
+}
+
+struct StatusBarEntry {
+    item: Box<dyn StatusItemViewHandle>,
+    priority: i32,
 }
 
 pub struct StatusBar {
-    left_items: Vec<Box<dyn StatusItemViewHandle>>,
-    right_items: Vec<Box<dyn StatusItemViewHandle>>,
+    /// The items on each side, ordered from the edge of the status bar inwards.
+    left_items: Vec<StatusBarEntry>,
+    right_items: Vec<StatusBarEntry>,
     active_pane: Entity<Pane>,
+    /// Whether this is the thin strip between the center and the bottom dock,
+    /// rather than the status bar at the bottom of the window.
+    secondary: bool,
+    focus_mode: bool,
+    /// The width of the status bar and of its items, as of the last time they were laid out.
+    /// Items that don't fit are moved into the overflow menu, starting with those with the
+    /// lowest priority.
+    width: Pixels,
+    item_widths: HashMap<EntityId, Pixels>,
+    /// Where the items shown in the status bar were last painted, for hit-testing.
+    item_bounds: HashMap<EntityId, Bounds<Pixels>>,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     _observe_active_pane: Subscription,
+    _observe_settings: Subscription,
 }
 
+/// The width reserved for the button that opens the overflow menu.
+const OVERFLOW_BUTTON_WIDTH: Pixels = px(24.);
+
 impl Render for StatusBar {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let overflowed_items = self.overflowed_items(window, cx);
+        self.item_bounds.clear();
+        if self.secondary {
+            return self
+                .render_secondary(&overflowed_items, cx)
+                .into_any_element();
+        }
+
         h_flex()
+            .debug_selector(|| "STATUS_BAR".into())
+            .relative()
             .w_full()
             .justify_between()
             .gap(DynamicSpacing::Base08.rems(cx))
@@ -58,35 +149,321 @@ impl Render for StatusBar {
                     .border_b(px(1.0))
                     .border_color(cx.theme().colors().status_bar_background),
             })
-            .child(self.render_left_tools(cx))
-            .child(self.render_right_tools(cx))
+            .child(self.render_width_observer(cx))
+            .child(self.render_left_tools(&overflowed_items, cx))
+            .child(self.render_right_tools(&overflowed_items, cx))
+            .children(self.render_context_menu())
+            .into_any_element()
     }
 }
 
 impl StatusBar {
-    fn render_left_tools(&self, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_secondary(
+        &self,
+        overflowed_items: &HashSet<EntityId>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .debug_selector(|| "SECONDARY_STATUS_BAR".into())
+            .relative()
+            .w_full()
+            .justify_between()
+            .gap(DynamicSpacing::Base08.rems(cx))
+            .px(DynamicSpacing::Base08.rems(cx))
+            .bg(cx.theme().colors().status_bar_background)
+            .border_t_1()
+            .border_color(cx.theme().colors().border)
+            .when(!self.has_visible_items(cx), |this| this.hidden())
+            .child(self.render_width_observer(cx))
+            .child(self.render_left_tools(overflowed_items, cx))
+            .child(self.render_right_tools(overflowed_items, cx))
+            .children(self.render_context_menu())
+    }
+
+    fn render_left_tools(
+        &self,
+        overflowed_items: &HashSet<EntityId>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let items = self.render_items(&self.left_items, overflowed_items, cx);
         h_flex()
+            .debug_selector(|| "STATUS_BAR-left".into())
             .gap(DynamicSpacing::Base04.rems(cx))
             .overflow_x_hidden()
-            .children(self.left_items.iter().map(|item| item.to_any()))
+            .children(items)
     }
 
-    fn render_right_tools(&self, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_right_tools(
+        &self,
+        overflowed_items: &HashSet<EntityId>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let items = self.render_items(&self.right_items, overflowed_items, cx);
         h_flex()
+            .debug_selector(|| "STATUS_BAR-right".into())
             .gap(DynamicSpacing::Base04.rems(cx))
-            .children(self.right_items.iter().rev().map(|item| item.to_any()))
+            .when(!overflowed_items.is_empty(), |this| {
+                this.child(self.render_overflow_menu(overflowed_items, cx))
+            })
+            .children(items.into_iter().rev())
+    }
+
+    /// Renders the shown items of one side that aren't in the overflow menu, from the edge
+    /// of the status bar inwards.
+    fn render_items(
+        &self,
+        items: &[StatusBarEntry],
+        overflowed_items: &HashSet<EntityId>,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let items = self
+            .shown_items(items, cx)
+            .map(|item| (item.to_any(), item.is_visible(cx), item.click_action(cx)))
+            .filter(|(view, _, _)| !overflowed_items.contains(&view.entity_id()))
+            .collect::<Vec<_>>();
+        items
+            .into_iter()
+            .map(|(view, is_visible, click_action)| {
+                self.render_item(view, is_visible, click_action, cx)
+            })
+            .collect()
+    }
+
+    /// Renders an item along with a canvas that records its width, which is used to decide
+    /// whether it fits into the status bar.
+    fn render_item(
+        &self,
+        view: AnyView,
+        is_visible: bool,
+        click_action: Option<Box<dyn Action>>,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        // Items without anything to show hide themselves, and a wrapper would still take
+        // up a gap.
+        if !is_visible {
+            return view.into_any_element();
+        }
+
+        let entity_id = view.entity_id();
+        let this = cx.entity().downgrade();
+        div()
+            .id(("status-item", entity_id))
+            .relative()
+            .when_some(click_action, |el, action| {
+                el.cursor_pointer()
+                    .on_click(move |_, window, cx| window.dispatch_action(action.boxed_clone(), cx))
+            })
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_context_menu(entity_id, event.position, window, cx)
+                }),
+            )
+            .child(view)
+            .child(
+                canvas(
+                    move |bounds, _, cx| {
+                        this.update(cx, |this, cx| {
+                            this.item_bounds.insert(entity_id, bounds);
+                            let width = bounds.size.width;
+                            if this.item_widths.insert(entity_id, width) != Some(width) {
+                                cx.notify();
+                            }
+                        })
+                        .ok();
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .into_any_element()
+    }
+
+    fn render_context_menu(&self) -> Option<impl IntoElement> {
+        let (menu, position, _) = self.context_menu.as_ref()?;
+        Some(
+            deferred(
+                anchored()
+                    .position(*position)
+                    .anchor(Corner::BottomLeft)
+                    .child(menu.clone()),
+            )
+            .with_priority(1),
+        )
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        entity_id: EntityId,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(item) = self
+            .left_items
+            .iter()
+            .chain(&self.right_items)
+            .find(|entry| entry.item.to_any().entity_id() == entity_id)
+        else {
+            return;
+        };
+        let Some(context_menu) = item.item.context_menu(window, cx) else {
+            return;
+        };
+
+        window.focus(&context_menu.focus_handle(cx));
+        let subscription = cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
+            this.context_menu.take();
+            cx.notify();
+        });
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn render_width_observer(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let this = cx.entity().downgrade();
+        canvas(
+            move |bounds, _, cx| {
+                this.update(cx, |this, cx| {
+                    if this.width != bounds.size.width {
+                        this.width = bounds.size.width;
+                        cx.notify();
+                    }
+                })
+                .ok();
+            },
+            |_, _, _, _| {},
+        )
+        .absolute()
+        .size_full()
+    }
+
+    fn render_overflow_menu(
+        &self,
+        overflowed_items: &HashSet<EntityId>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        // List the overflowed items in the order they'd have in the status bar.
+        let items = self
+            .shown_items(&self.left_items, cx)
+            .chain(self.shown_items(&self.right_items, cx).rev())
+            .map(|item| (item.to_any(), item.click_action(cx)))
+            .filter(|(view, _)| overflowed_items.contains(&view.entity_id()))
+            .map(|(view, click_action)| (view, click_action.map(Rc::<dyn Action>::from)))
+            .collect::<Vec<_>>();
+        PopoverMenu::new("status-bar-overflow")
+            .menu(move |window, cx| {
+                let items = items.clone();
+                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for (view, click_action) in items {
+                        let render =
+                            move |_: &mut Window, _: &mut App| view.clone().into_any_element();
+                        // Like in the status bar, items without a click action handle clicks
+                        // on their own buttons.
+                        menu = match click_action {
+                            Some(action) => menu.custom_entry(render, move |window, cx| {
+                                window.dispatch_action(action.boxed_clone(), cx)
+                            }),
+                            None => menu.custom_row(render),
+                        };
+                    }
+                    menu
+                }))
+            })
+            .trigger(
+                IconButton::new("status-bar-overflow", IconName::Ellipsis)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("More Status Items")),
+            )
+            .attach(Corner::TopRight)
+    }
+
+    /// The items that don't fit into the status bar. The items with the lowest priority are
+    /// moved into the overflow menu first, and among those the ones farthest from the edges.
+    fn overflowed_items(&self, window: &Window, cx: &App) -> HashSet<EntityId> {
+        let mut overflowed_items = HashSet::default();
+        if self.width <= Pixels::ZERO {
+            return overflowed_items;
+        }
+
+        let rem_size = window.rem_size();
+        let item_gap = DynamicSpacing::Base04.rems(cx).to_pixels(rem_size);
+        let side_spacing = DynamicSpacing::Base08.rems(cx).to_pixels(rem_size);
+        // The padding on both ends, and the gap between the left and the right items.
+        let available_width = self.width - side_spacing * 3.;
+
+        let mut items = Vec::new();
+        for side in [&self.left_items, &self.right_items] {
+            for (ix, entry) in side.iter().enumerate() {
+                if !self.is_shown(entry.item.as_ref(), cx) || !entry.item.is_visible(cx) {
+                    continue;
+                }
+                let entity_id = entry.item.to_any().entity_id();
+                let width = self
+                    .item_widths
+                    .get(&entity_id)
+                    .copied()
+                    .unwrap_or_default();
+                items.push((entry.priority, ix, entity_id, width));
+            }
+        }
+        let total_width = |items: &[(i32, usize, EntityId, Pixels)]| {
+            items.iter().fold(Pixels::ZERO, |total, (_, _, _, width)| {
+                total + *width + item_gap
+            })
+        };
+        if total_width(&items) <= available_width {
+            return overflowed_items;
+        }
+
+        items.sort_by_key(|(priority, ix, _, _)| (cmp::Reverse(*priority), *ix));
+        while total_width(&items) + OVERFLOW_BUTTON_WIDTH + item_gap > available_width {
+            let Some((_, _, entity_id, _)) = items.pop() else {
+                break;
+            };
+            overflowed_items.insert(entity_id);
+        }
+        overflowed_items
     }
 }
 
 impl StatusBar {
     pub fn new(active_pane: &Entity<Pane>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self::build(active_pane, false, window, cx)
+    }
+
+    /// Creates the strip between the center and the bottom dock, which hosts items that only
+    /// matter for the active pane's context. It's hidden while none of its items are visible.
+    pub fn new_secondary(
+        active_pane: &Entity<Pane>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self::build(active_pane, true, window, cx)
+    }
+
+    fn build(
+        active_pane: &Entity<Pane>,
+        secondary: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
         let mut this = Self {
             left_items: Default::default(),
             right_items: Default::default(),
             active_pane: active_pane.clone(),
+            secondary,
+            focus_mode: false,
+            width: Pixels::ZERO,
+            item_widths: HashMap::default(),
+            item_bounds: HashMap::default(),
+            context_menu: None,
             _observe_active_pane: cx.observe_in(active_pane, window, |this, _, window, cx| {
                 this.update_active_pane_item(window, cx)
             }),
+            _observe_settings: cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
         };
         this.update_active_pane_item(window, cx);
         this
@@ -95,38 +472,90 @@ impl StatusBar {
     pub fn add_left_item<T>(&mut self, item: Entity<T>, window: &mut Window, cx: &mut Context<Self>)
     where
         T: 'static + StatusItemView,
+    {
+        self.add_item(item, StatusItemAlignment::Left, 0, window, cx);
+    }
+
+    pub fn add_right_item<T>(
+        &mut self,
+        item: Entity<T>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) where
+        T: 'static + StatusItemView,
+    {
+        self.add_item(item, StatusItemAlignment::Right, 0, window, cx);
+    }
+
+    /// Adds an item to the given side of the status bar. Items with a higher priority are
+    /// shown closer to the edge of the status bar, and items with the same priority in the
+    /// order they were added.
+    pub fn add_item<T>(
+        &mut self,
+        item: Entity<T>,
+        alignment: StatusItemAlignment,
+        priority: i32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) where
+        T: 'static + StatusItemView,
     {
         let active_pane_item = self.active_pane.read(cx).active_item();
         item.set_active_pane_item(active_pane_item.as_deref(), window, cx);
 
-        self.left_items.push(Box::new(item));
+        let items = match alignment {
+            StatusItemAlignment::Left => &mut self.left_items,
+            StatusItemAlignment::Right => &mut self.right_items,
+        };
+        let ix = items.partition_point(|entry| entry.priority >= priority);
+        items.insert(
+            ix,
+            StatusBarEntry {
+                item: Box::new(item),
+                priority,
+            },
+        );
         cx.notify();
     }
 
+    /// The item painted at the given position, if any.
+    pub fn item_at(&self, position: Point<Pixels>) -> Option<AnyView> {
+        let (&entity_id, _) = self
+            .item_bounds
+            .iter()
+            .find(|(_, bounds)| bounds.contains(&position))?;
+        self.left_items
+            .iter()
+            .chain(self.right_items.iter())
+            .map(|entry| entry.item.to_any())
+            .find(|view| view.entity_id() == entity_id)
+    }
+
     pub fn item_of_type<T: StatusItemView>(&self) -> Option<Entity<T>> {
         self.left_items
             .iter()
             .chain(self.right_items.iter())
-            .find_map(|item| item.to_any().clone().downcast().log_err())
+            .find_map(|entry| entry.item.to_any().clone().downcast().log_err())
     }
 
     pub fn position_of_item<T>(&self) -> Option<usize>
     where
         T: StatusItemView,
     {
-        for (index, item) in self.left_items.iter().enumerate() {
-            if item.item_type() == TypeId::of::<T>() {
+        for (index, entry) in self.left_items.iter().enumerate() {
+            if entry.item.item_type() == TypeId::of::<T>() {
                 return Some(index);
             }
         }
-        for (index, item) in self.right_items.iter().enumerate() {
-            if item.item_type() == TypeId::of::<T>() {
+        for (index, entry) in self.right_items.iter().enumerate() {
+            if entry.item.item_type() == TypeId::of::<T>() {
                 return Some(index + self.left_items.len());
             }
         }
         None
     }
 
+    /// Inserts an item right after the one at the given position, with the same priority.
     pub fn insert_item_after<T>(
         &mut self,
         position: usize,
@@ -139,12 +568,19 @@ impl StatusBar {
         let active_pane_item = self.active_pane.read(cx).active_item();
         item.set_active_pane_item(active_pane_item.as_deref(), window, cx);
 
-        if position < self.left_items.len() {
-            self.left_items.insert(position + 1, Box::new(item))
+        let (items, ix) = if position < self.left_items.len() {
+            (&mut self.left_items, position)
         } else {
-            self.right_items
-                .insert(position + 1 - self.left_items.len(), Box::new(item))
-        }
+            (&mut self.right_items, position - self.left_items.len())
+        };
+        let priority = items[ix].priority;
+        items.insert(
+            ix + 1,
+            StatusBarEntry {
+                item: Box::new(item),
+                priority,
+            },
+        );
         cx.notify()
     }
 
@@ -157,18 +593,15 @@ impl StatusBar {
         cx.notify();
     }
 
-    pub fn add_right_item<T>(
-        &mut self,
-        item: Entity<T>,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) where
+    /// Removes all items of type `T` from both sides of the status bar.
+    pub fn remove_items_of_type<T>(&mut self, cx: &mut Context<Self>)
+    where
         T: 'static + StatusItemView,
     {
-        let active_pane_item = self.active_pane.read(cx).active_item();
-        item.set_active_pane_item(active_pane_item.as_deref(), window, cx);
-
-        self.right_items.push(Box::new(item));
+        self.left_items
+            .retain(|entry| entry.item.item_type() != TypeId::of::<T>());
+        self.right_items
+            .retain(|entry| entry.item.item_type() != TypeId::of::<T>());
         cx.notify();
     }
 
@@ -185,10 +618,48 @@ impl StatusBar {
         self.update_active_pane_item(window, cx);
     }
 
+    pub fn has_visible_items(&self, cx: &App) -> bool {
+        self.shown_items(&self.left_items, cx)
+            .chain(self.shown_items(&self.right_items, cx))
+            .any(|item| item.is_visible(cx))
+    }
+
+    pub fn is_focus_mode(&self) -> bool {
+        self.focus_mode
+    }
+
+    /// Toggles focus mode, in which only the items that declare themselves essential are shown.
+    pub fn toggle_focus_mode(&mut self, cx: &mut Context<Self>) {
+        self.focus_mode = !self.focus_mode;
+        cx.notify();
+    }
+
+    fn shown_items<'a>(
+        &'a self,
+        items: &'a [StatusBarEntry],
+        cx: &'a App,
+    ) -> impl DoubleEndedIterator<Item = &'a Box<dyn StatusItemViewHandle>> {
+        items
+            .iter()
+            .map(|entry| &entry.item)
+            .filter(move |item| self.is_shown(item.as_ref(), cx))
+    }
+
+    /// Whether the item is shown, given focus mode and the items hidden by the settings.
+    fn is_shown(&self, item: &dyn StatusItemViewHandle, cx: &App) -> bool {
+        let hidden_items = &StatusBarSettings::get_global(cx).hidden_items;
+        (!self.focus_mode || item.is_essential(cx))
+            && item.name(cx).map_or(true, |name| {
+                !hidden_items.iter().any(|hidden| hidden == name)
+            })
+    }
+
     fn update_active_pane_item(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let active_pane_item = self.active_pane.read(cx).active_item();
-        for item in self.left_items.iter().chain(&self.right_items) {
-            item.set_active_pane_item(active_pane_item.as_deref(), window, cx);
+        for entry in self.left_items.iter().chain(&self.right_items) {
+            entry
+                .item
+                .set_active_pane_item(active_pane_item.as_deref(), window, cx);
         }
     }
 }
@@ -212,6 +683,26 @@ impl<T: StatusItemView> StatusItemViewHandle for Entity<T> {
     fn item_type(&self) -> TypeId {
         TypeId::of::<T>()
     }
+
+    fn is_visible(&self, cx: &App) -> bool {
+        self.read(cx).is_visible(cx)
+    }
+
+    fn is_essential(&self, cx: &App) -> bool {
+        self.read(cx).is_essential()
+    }
+
+    fn name(&self, cx: &App) -> Option<&'static str> {
+        self.read(cx).status_item_name()
+    }
+
+    fn click_action(&self, cx: &App) -> Option<Box<dyn Action>> {
+        self.read(cx).click_action(cx)
+    }
+
+    fn context_menu(&self, window: &mut Window, cx: &mut App) -> Option<Entity<ContextMenu>> {
+        self.update(cx, |this, cx| this.context_menu(window, cx))
+    }
 }
 
 impl From<&dyn StatusItemViewHandle> for AnyView {
@@ -0,0 +1,368 @@
+//! A harness for tests that build a workspace and rearrange its layout.
+//!
+//! ```ignore
+//! let mut test = TestWorkspace::new(cx).panel(DockPosition::Left).build().await;
+//! test.add_item("a");
+//! test.split(SplitDirection::Right);
+//! test.add_item("b");
+//! test.assert_center_layout("horizontal\n  pane [*a]\n  pane* [*b]");
+//! ```
+
+use crate::{
+    dock::{test::TestPanel, DockPosition},
+    item::test::TestItem,
+    pane::DraggedTab,
+    ItemHandle, Pane, SplitDirection, StatusItemView, Workspace,
+};
+use fs::FakeFs;
+use gpui::{
+    div, px, Action, App, AppContext as _, Context, Entity, EntityId, Focusable as _,
+    InteractiveElement as _, IntoElement, Pixels, Render, Styled as _, TestAppContext,
+    VisualTestContext, Window,
+};
+use project::Project;
+use settings::SettingsStore;
+use std::{path::Path, sync::Arc};
+use ui::ContextMenu;
+
+/// Lays out and paints the window again, so that the bounds of its elements returned by
+/// `debug_bounds` reflect the latest state.
+pub fn draw(cx: &mut VisualTestContext) {
+    cx.run_until_parked();
+    cx.update(|window, _| window.refresh());
+}
+
+/// Builds a [`TestWorkspace`], see [`TestWorkspace::new`].
+pub struct TestWorkspaceBuilder<'a> {
+    cx: &'a mut TestAppContext,
+    files: serde_json::Value,
+    panels: Vec<DockPosition>,
+}
+
+impl TestWorkspaceBuilder<'_> {
+    /// Adds files to the fake file system, and the directory `/root` containing them to the
+    /// project.
+    pub fn files(mut self, files: serde_json::Value) -> Self {
+        self.files = files;
+        self
+    }
+
+    /// Adds a [`TestPanel`] to the dock at the given position.
+    pub fn panel(mut self, position: DockPosition) -> Self {
+        self.panels.push(position);
+        self
+    }
+
+    pub async fn build(self) -> TestWorkspace {
+        let cx = self.cx;
+        if !cx.has_global::<SettingsStore>() {
+            cx.update(|cx| {
+                let settings_store = SettingsStore::test(cx);
+                cx.set_global(settings_store);
+                theme::init(theme::LoadThemes::JustBase, cx);
+                language::init(cx);
+                crate::init_settings(cx);
+                Project::init_settings(cx);
+            });
+        }
+
+        let fs = FakeFs::new(cx.executor());
+        let mut roots = Vec::new();
+        if !self.files.is_null() {
+            fs.insert_tree("/root", self.files).await;
+            roots.push(Path::new("/root"));
+        }
+        let project = Project::test(fs.clone(), roots, cx).await;
+
+        let window = cx.add_window({
+            let project = project.clone();
+            |window, cx| Workspace::test_new(project, window, cx)
+        });
+        let workspace = window.root(cx).unwrap();
+        let mut cx = VisualTestContext::from_window(*window, cx);
+
+        let panels = workspace.update_in(&mut cx, |workspace, window, cx| {
+            self.panels
+                .into_iter()
+                .map(|position| {
+                    let panel = cx.new(|cx| TestPanel::new(position, cx));
+                    workspace.add_panel(panel.clone(), window, cx);
+                    panel
+                })
+                .collect()
+        });
+        cx.run_until_parked();
+
+        TestWorkspace {
+            workspace,
+            project,
+            fs,
+            panels,
+            cx,
+        }
+    }
+}
+
+/// A workspace in a test window, with helpers to simulate what users do to its layout and to
+/// check the result.
+pub struct TestWorkspace {
+    pub workspace: Entity<Workspace>,
+    pub project: Entity<Project>,
+    /// The file system of the project, containing the files added by
+    /// [`TestWorkspaceBuilder::files`].
+    pub fs: Arc<FakeFs>,
+    /// The panels added by [`TestWorkspaceBuilder::panel`], in the order they were added.
+    pub panels: Vec<Entity<TestPanel>>,
+    pub cx: VisualTestContext,
+}
+
+impl TestWorkspace {
+    /// Starts building a workspace with an empty project and no panels. The settings needed
+    /// by the workspace are initialized, unless the test already did.
+    pub fn new(cx: &mut TestAppContext) -> TestWorkspaceBuilder<'_> {
+        TestWorkspaceBuilder {
+            cx,
+            files: serde_json::Value::Null,
+            panels: Vec::new(),
+        }
+    }
+
+    /// Opens a [`TestItem`] with the given label in the active pane.
+    pub fn add_item(&mut self, label: &str) -> Entity<TestItem> {
+        let item = self.cx.new(|cx| TestItem::new(cx).with_label(label));
+        self.workspace
+            .update_in(&mut self.cx, |workspace, window, cx| {
+                workspace.add_item_to_active_pane(Box::new(item.clone()), None, true, window, cx);
+            });
+        item
+    }
+
+    /// See [`draw`].
+    pub fn draw(&mut self) {
+        draw(&mut self.cx);
+    }
+
+    pub fn toggle_dock(&mut self, position: DockPosition) {
+        self.workspace
+            .update_in(&mut self.cx, |workspace, window, cx| {
+                workspace.toggle_dock(position, window, cx);
+            });
+        self.cx.run_until_parked();
+    }
+
+    /// Splits the active pane and focuses the new pane, which is returned.
+    pub fn split(&mut self, direction: SplitDirection) -> Entity<Pane> {
+        let pane = self
+            .workspace
+            .update_in(&mut self.cx, |workspace, window, cx| {
+                let active_pane = workspace.active_pane().clone();
+                workspace.split_pane(active_pane, direction, window, cx)
+            });
+        self.focus_pane(&pane);
+        pane
+    }
+
+    /// Drops the tab of the given item onto the tab bar of `destination` at index `ix`, as if
+    /// the user dragged it there.
+    pub fn drag_tab(&mut self, item: &Entity<TestItem>, destination: &Entity<Pane>, ix: usize) {
+        let source = self
+            .pane_for(item)
+            .expect("the dragged item isn't open in the center");
+        let dragged_tab = source.read_with(&self.cx, |pane, _| DraggedTab {
+            pane: source.clone(),
+            item: Box::new(item.clone()),
+            ix: pane.index_for_item(item).unwrap(),
+            detail: 0,
+            is_active: pane
+                .active_item()
+                .map_or(false, |active| active.item_id() == item.item_id()),
+        });
+        destination.update_in(&mut self.cx, |pane, window, cx| {
+            pane.handle_tab_drop(&dragged_tab, ix, window, cx);
+        });
+        self.cx.run_until_parked();
+    }
+
+    pub fn focus_pane(&mut self, pane: &Entity<Pane>) {
+        self.cx
+            .update(|window, cx| window.focus(&pane.focus_handle(cx)));
+        self.cx.run_until_parked();
+    }
+
+    /// The panes of the center, from left to right and top to bottom.
+    pub fn center_panes(&mut self) -> Vec<Entity<Pane>> {
+        self.workspace.read_with(&self.cx, |workspace, _| {
+            workspace.center.panes().into_iter().cloned().collect()
+        })
+    }
+
+    /// The center pane showing the given item.
+    pub fn pane_for(&mut self, item: &Entity<TestItem>) -> Option<Entity<Pane>> {
+        self.workspace.read_with(&self.cx, |workspace, cx| {
+            workspace
+                .center
+                .panes()
+                .into_iter()
+                .find(|pane| pane.read(cx).index_for_item(item).is_some())
+                .cloned()
+        })
+    }
+
+    /// Asserts the tree of center panes and the items open in them, written as in
+    /// [`Workspace::layout_debug_string`] but without indentation at the root.
+    #[track_caller]
+    pub fn assert_center_layout(&mut self, expected: &str) {
+        let workspace = self.workspace.clone();
+        let layout = self.cx.update(|window, cx| {
+            let workspace = workspace.read(cx);
+            let mut layout = String::new();
+            workspace.center.root.write_debug_string(
+                workspace.active_pane(),
+                0,
+                &mut layout,
+                window,
+                cx,
+            );
+            layout
+        });
+        assert_eq!(
+            layout.trim_end(),
+            expected.trim_end(),
+            "unexpected layout of the center"
+        );
+    }
+
+    /// Asserts the whole layout, as described by [`Workspace::layout_debug_string`].
+    #[track_caller]
+    pub fn assert_layout(&mut self, expected: &str) {
+        let workspace = self.workspace.clone();
+        let layout = self
+            .cx
+            .update(|window, cx| workspace.read(cx).layout_debug_string(window, cx));
+        assert_eq!(
+            layout.trim_end(),
+            expected.trim_end(),
+            "unexpected layout of the workspace"
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_active_pane(&mut self, pane: &Entity<Pane>) {
+        let active_pane = self
+            .workspace
+            .read_with(&self.cx, |workspace, _| workspace.active_pane().clone());
+        assert_eq!(&active_pane, pane, "a different pane is active");
+    }
+
+    #[track_caller]
+    pub fn assert_dock_open(&mut self, position: DockPosition, open: bool) {
+        let is_open = self.workspace.read_with(&self.cx, |workspace, cx| {
+            workspace.dock_at_position(position).read(cx).is_open()
+        });
+        assert_eq!(is_open, open, "unexpected state of the {position:?} dock");
+    }
+}
+
+/// Identifies the toasts shown by tests, see
+/// [`NotificationId::unique`](crate::notifications::NotificationId::unique).
+pub struct TestToast;
+
+/// A status bar item of a fixed size, which can be found in the rendered window by its name
+/// with `debug_bounds`.
+pub struct TestStatusItem {
+    pub name: &'static str,
+    pub width: Pixels,
+    pub visible: bool,
+    pub essential: bool,
+    pub click_action: Option<Box<dyn Action>>,
+    /// The item of the active pane, as last reported to the status item.
+    pub active_item_id: Option<EntityId>,
+    /// How often the item's right-click menu was built. Only items with a click action have
+    /// one, which offers that action.
+    pub context_menus_built: usize,
+}
+
+impl TestStatusItem {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            width: px(20.),
+            visible: true,
+            essential: false,
+            click_action: None,
+            active_item_id: None,
+            context_menus_built: 0,
+        }
+    }
+
+    pub fn with_width(mut self, width: Pixels) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    pub fn with_essential(mut self, essential: bool) -> Self {
+        self.essential = essential;
+        self
+    }
+
+    pub fn with_click_action(mut self, action: Box<dyn Action>) -> Self {
+        self.click_action = Some(action);
+        self
+    }
+}
+
+impl Render for TestStatusItem {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        let name = self.name;
+        div()
+            .debug_selector(move || name.into())
+            .w(self.width)
+            .h(px(10.))
+    }
+}
+
+impl StatusItemView for TestStatusItem {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+        self.active_item_id = active_pane_item.map(|item| item.item_id());
+    }
+
+    fn is_visible(&self, _: &App) -> bool {
+        self.visible
+    }
+
+    fn is_essential(&self) -> bool {
+        self.essential
+    }
+
+    fn status_item_name(&self) -> Option<&'static str> {
+        Some(self.name)
+    }
+
+    fn click_action(&self, _: &App) -> Option<Box<dyn Action>> {
+        self.click_action
+            .as_ref()
+            .map(|action| action.boxed_clone())
+    }
+
+    fn context_menu(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<ContextMenu>> {
+        let action = self.click_action.as_ref()?.boxed_clone();
+        self.context_menus_built += 1;
+        Some(ContextMenu::build(window, cx, |menu, _, _| {
+            menu.action("Click", action)
+        }))
+    }
+}
@@ -1,7 +1,8 @@
 use crate::ItemHandle;
+use collections::HashMap;
 use gpui::{
     AnyView, App, Context, Entity, EntityId, EventEmitter, ParentElement as _, Render, Styled,
-    Window,
+    Subscription, Window,
 };
 use ui::prelude::*;
 use ui::{h_flex, v_flex};
@@ -39,10 +40,12 @@ trait ToolbarItemViewHandle: Send {
     fn focus_changed(&mut self, pane_focused: bool, window: &mut Window, cx: &mut App);
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ToolbarItemLocation {
     Hidden,
     PrimaryLeft,
+    /// Between the left and the right items, taking up the width they leave.
+    PrimaryFlex,
     PrimaryRight,
     Secondary,
 }
@@ -52,67 +55,80 @@ pub struct Toolbar {
     hidden: bool,
     can_navigate: bool,
     items: Vec<(Box<dyn ToolbarItemViewHandle>, ToolbarItemLocation)>,
+    /// Re-renders the toolbar when the active item changes the toolbar items it contributes.
+    _observe_active_item: Option<Subscription>,
 }
 
 impl Toolbar {
-    fn has_any_visible_items(&self) -> bool {
-        self.items
+    /// The views to show at each location: the registered items, followed by the ones
+    /// contributed by the active item.
+    pub(crate) fn visible_items(
+        &self,
+        window: &Window,
+        cx: &App,
+    ) -> HashMap<ToolbarItemLocation, Vec<AnyView>> {
+        let mut visible_items = HashMap::<_, Vec<_>>::default();
+        // Secondary items stack from the bottom up, in the order they were registered.
+        let registered_items = self
+            .items
             .iter()
-            .any(|(_item, location)| *location != ToolbarItemLocation::Hidden)
-    }
-
-    fn left_items(&self) -> impl Iterator<Item = &dyn ToolbarItemViewHandle> {
-        self.items.iter().filter_map(|(item, location)| {
-            if *location == ToolbarItemLocation::PrimaryLeft {
-                Some(item.as_ref())
-            } else {
-                None
-            }
-        })
-    }
-
-    fn right_items(&self) -> impl Iterator<Item = &dyn ToolbarItemViewHandle> {
-        self.items.iter().filter_map(|(item, location)| {
-            if *location == ToolbarItemLocation::PrimaryRight {
-                Some(item.as_ref())
-            } else {
-                None
-            }
-        })
-    }
-
-    fn secondary_items(&self) -> impl Iterator<Item = &dyn ToolbarItemViewHandle> {
-        self.items.iter().rev().filter_map(|(item, location)| {
-            if *location == ToolbarItemLocation::Secondary {
-                Some(item.as_ref())
-            } else {
-                None
+            .filter(|(_, location)| *location != ToolbarItemLocation::Secondary)
+            .chain(
+                self.items
+                    .iter()
+                    .rev()
+                    .filter(|(_, location)| *location == ToolbarItemLocation::Secondary),
+            )
+            .map(|(item, location)| (item.to_any(), *location));
+        let contributed_items = self
+            .active_item
+            .as_ref()
+            .map(|item| item.toolbar_items(window, cx))
+            .unwrap_or_default();
+        for (view, location) in registered_items.chain(contributed_items) {
+            if location != ToolbarItemLocation::Hidden {
+                visible_items.entry(location).or_default().push(view);
             }
-        })
+        }
+        visible_items
     }
 }
 
 impl Render for Toolbar {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        if !self.has_any_visible_items() {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut visible_items = self.visible_items(window, cx);
+        if visible_items.is_empty() {
             return div();
         }
 
-        let secondary_items = self.secondary_items().map(|item| item.to_any());
+        let left_items = visible_items
+            .remove(&ToolbarItemLocation::PrimaryLeft)
+            .unwrap_or_default();
+        let flex_items = visible_items
+            .remove(&ToolbarItemLocation::PrimaryFlex)
+            .unwrap_or_default();
+        let right_items = visible_items
+            .remove(&ToolbarItemLocation::PrimaryRight)
+            .unwrap_or_default();
+        let secondary_items = visible_items
+            .remove(&ToolbarItemLocation::Secondary)
+            .unwrap_or_default();
 
-        let has_left_items = self.left_items().count() > 0;
-        let has_right_items = self.right_items().count() > 0;
+        let has_left_items = !left_items.is_empty();
+        let has_flex_items = !flex_items.is_empty();
+        let has_right_items = !right_items.is_empty();
+        let has_primary_items = has_left_items || has_flex_items || has_right_items;
 
         v_flex()
             .group("toolbar")
             .p(DynamicSpacing::Base08.rems(cx))
-            .when(has_left_items || has_right_items, |this| {
+            .when(has_primary_items, |this| {
                 this.gap(DynamicSpacing::Base08.rems(cx))
             })
             .border_b_1()
             .border_color(cx.theme().colors().border_variant)
             .bg(cx.theme().colors().toolbar_background)
-            .when(has_left_items || has_right_items, |this| {
+            .when(has_primary_items, |this| {
                 this.child(
                     h_flex()
                         .min_h(rems_from_px(24.))
@@ -121,17 +137,33 @@ impl Render for Toolbar {
                         .when(has_left_items, |this| {
                             this.child(
                                 h_flex()
-                                    .flex_auto()
+                                    .map(|el| {
+                                        if has_flex_items {
+                                            el.flex_none()
+                                        } else {
+                                            el.flex_auto()
+                                        }
+                                    })
                                     .justify_start()
                                     .overflow_x_hidden()
-                                    .children(self.left_items().map(|item| item.to_any())),
+                                    .children(left_items),
+                            )
+                        })
+                        .when(has_flex_items, |this| {
+                            this.child(
+                                h_flex()
+                                    .flex_1()
+                                    .min_w_0()
+                                    .gap(DynamicSpacing::Base08.rems(cx))
+                                    .overflow_x_hidden()
+                                    .children(flex_items),
                             )
                         })
                         .when(has_right_items, |this| {
                             this.child(
                                 h_flex()
                                     .map(|el| {
-                                        if has_left_items {
+                                        if has_left_items || has_flex_items {
                                             // We're using `flex_none` here to prevent some flickering that can occur when the
                                             // size of the left items container changes.
                                             el.flex_none()
@@ -140,7 +172,7 @@ impl Render for Toolbar {
                                         }
                                     })
                                     .justify_end()
-                                    .children(self.right_items().map(|item| item.to_any())),
+                                    .children(right_items),
                             )
                         }),
                 )
@@ -162,6 +194,7 @@ impl Toolbar {
             items: Default::default(),
             hidden: false,
             can_navigate: true,
+            _observe_active_item: None,
         }
     }
 
@@ -203,6 +236,16 @@ impl Toolbar {
         cx: &mut Context<Self>,
     ) {
         self.active_item = item.map(|item| item.boxed_clone());
+        let toolbar = cx.entity().downgrade();
+        self._observe_active_item = item.map(|item| {
+            item.on_notify(
+                cx,
+                Box::new(move |cx| {
+                    toolbar.update(cx, |_, cx| cx.notify()).ok();
+                }),
+            )
+        });
+        cx.notify();
         self.hidden = self
             .active_item
             .as_ref()
@@ -1,19 +1,47 @@
+pub mod call_controls;
+#[cfg(any(test, feature = "test-support"))]
+pub mod chrome_snapshot;
+pub mod default_layout;
 pub mod dock;
+pub mod dock_behavior;
+pub mod dock_switcher;
+pub mod extension_status_items;
+pub mod feedback;
+mod frame_scheduler;
+mod hit_test;
 pub mod item;
+pub mod item_decorations;
+pub mod layout_export;
+mod layouts;
+mod leak_check;
+pub mod loading_item;
+pub mod metrics;
 mod modal_layer;
+mod named_sessions;
+pub mod notification_center;
 pub mod notifications;
 pub mod pane;
 pub mod pane_group;
+pub mod panel_with_tabs;
 mod persistence;
+pub mod progress;
+pub mod prompt_dialog;
+pub mod resize_policy;
 pub mod searchable;
 pub mod shared_screen;
+pub mod slots;
 mod status_bar;
 pub mod tasks;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test;
 mod theme_preview;
 mod toast_layer;
 mod toolbar;
 mod workspace_settings;
+pub mod workspace_tabs;
 
+pub use frame_scheduler::DEFAULT_FRAME_BUDGET;
+pub use hit_test::WorkspaceRegion;
 pub use toast_layer::{RunAction, ToastAction, ToastLayer, ToastView};
 
 use anyhow::{anyhow, Context as _, Result};
@@ -23,9 +51,16 @@ use client::{
     ChannelId, Client, ErrorExt, Status, TypedEnvelope, UserStore,
 };
 use collections::{hash_map, HashMap, HashSet};
+use default_layout::DefaultLayout;
 use derive_more::{Deref, DerefMut};
-pub use dock::Panel;
-use dock::{Dock, DockPosition, PanelButtons, PanelHandle, RESIZE_HANDLE_SIZE};
+use dock::{
+    BottomDockSlot, Dock, DockEvent, DockPosition, PanelButtons, PanelHandle, RESIZE_HANDLE_SIZE,
+};
+pub use dock::{CloseReason, Panel, TemporarySizeDuration};
+use dock_behavior::DockButtonPlacement;
+use extension_status_items::ExtensionStatusItems;
+use feedback::give_feedback;
+use frame_scheduler::FrameScheduler;
 use futures::{
     channel::{
         mpsc::{self, UnboundedReceiver, UnboundedSender},
@@ -36,11 +71,11 @@ use futures::{
 };
 use gpui::{
     action_as, actions, canvas, impl_action_as, impl_actions, point, relative, size,
-    transparent_black, Action, AnyView, AnyWeakView, App, AsyncApp, AsyncWindowContext, Bounds,
-    Context, CursorStyle, Decorations, DragMoveEvent, Entity, EntityId, EventEmitter, FocusHandle,
-    Focusable, Global, Hsla, KeyContext, Keystroke, ManagedView, MouseButton, PathPromptOptions,
-    Point, PromptLevel, Render, ResizeEdge, Size, Stateful, Subscription, Task, Tiling, WeakEntity,
-    WindowBounds, WindowHandle, WindowId, WindowOptions,
+    transparent_black, Action, Along, AnyView, AnyWeakView, App, AsyncApp, AsyncWindowContext,
+    Bounds, Context, CursorStyle, Decorations, DragMoveEvent, Entity, EntityId, EventEmitter,
+    FocusHandle, Focusable, Global, Hsla, KeyContext, Keystroke, ManagedView, MouseButton,
+    MouseMoveEvent, PathPromptOptions, Point, PromptLevel, Render, ResizeEdge, Size, Stateful,
+    Subscription, Task, Tiling, WeakEntity, WindowBounds, WindowHandle, WindowId, WindowOptions,
 };
 pub use item::{
     FollowableItem, FollowableItemHandle, Item, ItemHandle, ItemSettings, PreviewTabsSettings,
@@ -48,15 +83,22 @@ pub use item::{
 };
 use itertools::Itertools;
 use language::{LanguageRegistry, Rope};
+use layouts::OpenPanelGroup;
+use leak_check::LeakCheck;
+use loading_item::{LoadingItem, LOADING_PLACEHOLDER_DELAY};
+use metrics::{record_metric, WorkspaceMetric};
 pub use modal_layer::*;
 use node_runtime::NodeRuntime;
 use notifications::{
-    simple_message_notification::MessageNotification, DetachAndPromptErr, Notifications,
+    simple_message_notification::MessageNotification, DetachAndPromptErr, NotificationLogEntry,
+    Notifications,
 };
 pub use pane::*;
 pub use pane_group::*;
 pub use persistence::{
-    model::{ItemId, LocalPaths, SerializedWorkspaceLocation},
+    model::{
+        ItemId, LocalPaths, SerializedLayout, SerializedNamedSession, SerializedWorkspaceLocation,
+    },
     WorkspaceDb, DB as WORKSPACE_DB,
 };
 use persistence::{
@@ -64,33 +106,39 @@ use persistence::{
     SerializedWindowBounds, DB,
 };
 use postage::stream::Stream;
+use progress::ProgressIndicator;
 use project::{
-    DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
+    DirectoryLister, PathChange, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree,
+    WorktreeId,
 };
 use remote::{ssh_session::ConnectionIdentifier, SshClientDelegate, SshConnectionOptions};
+use resize_policy::{DockSizes, ResizePolicy};
 use schemars::JsonSchema;
 use serde::Deserialize;
-use session::AppSession;
-use settings::Settings;
+use session::{AppSession, UnsavedChangesAnswer};
+use settings::{Settings, SettingsStore};
 use shared_screen::SharedScreen;
+use slots::{SlotViews, WorkspaceSlot};
 use sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
     statement::Statement,
 };
 use status_bar::StatusBar;
-pub use status_bar::StatusItemView;
+pub use status_bar::{hide_status_item, StatusItemAlignment, StatusItemView};
 use std::{
     any::TypeId,
     borrow::Cow,
     cell::RefCell,
     cmp,
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, VecDeque},
     env,
+    fmt::Write as _,
     hash::{Hash, Hasher},
+    mem,
     path::{Path, PathBuf},
     rc::Rc,
     sync::{atomic::AtomicUsize, Arc, LazyLock, Weak},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use task::SpawnInTerminal;
 use theme::{ActiveTheme, SystemAppearance, ThemeSettings};
@@ -100,8 +148,10 @@ use ui::prelude::*;
 use util::{paths::SanitizedPath, serde::default_true, ResultExt, TryFutureExt};
 use uuid::Uuid;
 pub use workspace_settings::{
-    AutosaveSetting, RestoreOnStartupBehavior, TabBarSettings, WorkspaceSettings,
+    AutosaveSetting, FocusFollowsMouse, NotificationCenterSettings, RestoreOnStartupBehavior,
+    StatusBarSettings, TabBarSettings, WorkspaceSettings,
 };
+use workspace_tabs::WorkspaceTabs;
 
 use crate::notifications::NotificationId;
 use crate::persistence::{
@@ -110,6 +160,11 @@ use crate::persistence::{
 };
 
 pub const SERIALIZATION_THROTTLE_TIME: Duration = Duration::from_millis(200);
+/// How long the workspace has to stop changing before a requested serialization is written.
+const SERIALIZATION_DEBOUNCE_TIME: Duration = Duration::from_millis(100);
+/// The longest a requested serialization is postponed while the workspace keeps changing,
+/// e.g. during a long drag.
+const MAX_SERIALIZATION_DELAY: Duration = Duration::from_secs(1);
 
 static ZED_WINDOW_SIZE: LazyLock<Option<Size<Pixels>>> = LazyLock::new(|| {
     env::var("ZED_WINDOW_SIZE")
@@ -155,9 +210,14 @@ actions!(
         ClearAllNotifications,
         CloseAllDocks,
         CloseWindow,
+        ExportLayout,
         Feedback,
+        FocusPanelButtons,
         FollowNextCollaborator,
+        ImportLayout,
+        MaximizeEditor,
         MoveFocusedPanelToNextPosition,
+        MoveItemToNewWindow,
         NewCenterTerminal,
         NewFile,
         NewFileSplitVertical,
@@ -170,14 +230,31 @@ actions!(
         OpenInTerminal,
         OpenComponentPreview,
         ReloadActiveItem,
+        RemovePanelFromDock,
+        ResetPromptAnswers,
+        RestoreDocks,
         SaveAs,
         SaveWithoutFormat,
         ShutdownDebugAdapters,
+        SplitBottomDock,
+        SplitDownOneThird,
+        SplitDownTwoThirds,
+        SplitLeftOneThird,
+        SplitLeftTwoThirds,
+        SplitRightOneThird,
+        SplitRightTwoThirds,
+        SplitUpOneThird,
+        SplitUpTwoThirds,
         ToggleBottomDock,
+        ToggleBottomLeftDock,
+        ToggleBottomRightDock,
         ToggleCenteredLayout,
         ToggleLeftDock,
         ToggleRightDock,
+        ToggleStatusBarFocusMode,
         ToggleZoom,
+        ToggleZenMode,
+        ToggleZoomPane,
         Unfollow,
         Welcome,
     ]
@@ -231,9 +308,47 @@ pub struct CloseInactiveTabsAndPanes {
     pub save_intent: Option<SaveIntent>,
 }
 
+#[derive(Clone, PartialEq, Debug, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CloseAllItemsInOtherPanes {
+    pub save_intent: Option<SaveIntent>,
+}
+
 #[derive(Clone, Deserialize, PartialEq, JsonSchema)]
 pub struct SendKeystrokes(pub String);
 
+/// Opens and focuses the panel with the given persistent name, such as `"ProjectPanel"`.
+/// When a dock is given, the panel is moved there first if it's docked elsewhere.
+#[derive(Clone, Debug, Deserialize, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ActivatePanel {
+    pub name: String,
+    #[serde(default)]
+    pub dock: Option<DockPosition>,
+}
+
+/// A preset arrangement of the center panes.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PaneLayout {
+    /// Two rows of two panes each.
+    #[serde(rename = "grid_2x2")]
+    Grid2x2,
+    /// Three panes side by side.
+    ThreeColumns,
+    /// A large pane on the left, with two panes stacked on its right.
+    MainAndStack,
+}
+
+/// Rearranges the center panes into a preset layout, spreading the open items over them.
+#[derive(Clone, Deserialize, PartialEq, JsonSchema)]
+pub struct ApplyPaneLayout(pub PaneLayout);
+
+/// Opens the panels of the named group from the `panel_groups` setting,
+/// or closes them if the group is already open.
+#[derive(Clone, Deserialize, PartialEq, JsonSchema)]
+pub struct TogglePanelGroup(pub String);
+
 #[derive(Clone, Deserialize, PartialEq, Default, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Reload {
@@ -254,7 +369,10 @@ impl_actions!(
     workspace,
     [
         ActivatePane,
+        ActivatePanel,
+        ApplyPaneLayout,
         CloseAllItemsAndPanes,
+        CloseAllItemsInOtherPanes,
         CloseInactiveTabsAndPanes,
         MoveItemToPane,
         MoveItemToPaneInDirection,
@@ -263,6 +381,7 @@ impl_actions!(
         Save,
         SaveAll,
         SendKeystrokes,
+        TogglePanelGroup,
     ]
 );
 
@@ -277,10 +396,15 @@ actions!(
         SwapPaneRight,
         SwapPaneUp,
         SwapPaneDown,
+        ResetPaneSizes,
+        ResizePaneLeft,
+        ResizePaneRight,
+        ResizePaneUp,
+        ResizePaneDown,
     ]
 );
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum CloseIntent {
     /// Quit the program entirely.
     Quit,
@@ -288,13 +412,17 @@ pub enum CloseIntent {
     CloseWindow,
     /// Replace the workspace in an existing window.
     ReplaceWindow,
+    /// Close one of the workspace tabs of a window.
+    CloseTab,
 }
 
+const DEFAULT_TOAST_AUTOHIDE_DURATION: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub struct Toast {
     id: NotificationId,
     msg: Cow<'static, str>,
-    autohide: bool,
+    autohide: Option<Duration>,
     on_click: Option<(Cow<'static, str>, Arc<dyn Fn(&mut Window, &mut App)>)>,
 }
 
@@ -304,7 +432,7 @@ impl Toast {
             id,
             msg: msg.into(),
             on_click: None,
-            autohide: false,
+            autohide: None,
         }
     }
 
@@ -318,7 +446,14 @@ impl Toast {
     }
 
     pub fn autohide(mut self) -> Self {
-        self.autohide = true;
+        self.autohide = Some(DEFAULT_TOAST_AUTOHIDE_DURATION);
+        self
+    }
+
+    /// Dismisses the toast after the given duration. Showing a toast with the same id again
+    /// restarts the timer.
+    pub fn autohide_after(mut self, duration: Duration) -> Self {
+        self.autohide = Some(duration);
         self
     }
 }
@@ -364,6 +499,8 @@ pub fn init_settings(cx: &mut App) {
     ItemSettings::register(cx);
     PreviewTabsSettings::register(cx);
     TabBarSettings::register(cx);
+    StatusBarSettings::register(cx);
+    NotificationCenterSettings::register(cx);
 }
 
 fn prompt_and_open_paths(app_state: Arc<AppState>, options: PathPromptOptions, cx: &mut App) {
@@ -403,8 +540,12 @@ pub fn init(app_state: Arc<AppState>, cx: &mut App) {
     component::init();
     theme_preview::init(cx);
     toast_layer::init(cx);
+    dock_switcher::init(cx);
+    notification_center::init(cx);
+    workspace_tabs::init(cx);
 
     cx.on_action(Workspace::close_global);
+    cx.on_action(|_: &ResetPromptAnswers, cx| prompt_dialog::reset_remembered_answers(cx));
     cx.on_action(reload);
 
     cx.on_action({
@@ -676,7 +817,6 @@ impl AppState {
     pub fn test(cx: &mut App) -> Arc<Self> {
         use node_runtime::NodeRuntime;
         use session::Session;
-        use settings::SettingsStore;
 
         if !cx.has_global::<SettingsStore>() {
             let settings_store = SettingsStore::test(cx);
@@ -764,12 +904,20 @@ impl DelayedDebouncedEditAction {
 
 pub enum Event {
     PaneAdded(Entity<Pane>),
-    PaneRemoved,
+    PaneRemoved(EntityId),
     ItemAdded {
         item: Box<dyn ItemHandle>,
     },
-    ItemRemoved,
+    ItemRemoved {
+        item_id: EntityId,
+    },
     ActiveItemChanged,
+    /// The active item of a pane changed. Unlike [`Event::ActiveItemChanged`], this is
+    /// emitted for all panes, not just the active one.
+    ItemActivated {
+        pane: Entity<Pane>,
+        item: Box<dyn ItemHandle>,
+    },
     UserSavedItem {
         pane: WeakEntity<Pane>,
         item: Box<dyn WeakItemHandle>,
@@ -786,6 +934,19 @@ pub enum Event {
         language: &'static str,
     },
     ZoomChanged,
+    /// A notification was added to or cleared from the notification log.
+    NotificationLogChanged,
+    /// The dock at the given position was opened or closed.
+    DockToggled {
+        position: DockPosition,
+        open: bool,
+    },
+    /// A panel moved to the dock at another position.
+    PanelMoved {
+        panel: Arc<dyn PanelHandle>,
+        from: DockPosition,
+        to: DockPosition,
+    },
 }
 
 #[derive(Debug)]
@@ -813,6 +974,35 @@ type PromptForOpenPath = Box<
     ) -> oneshot::Receiver<Option<Vec<PathBuf>>>,
 >;
 
+/// An entry of the navigation history across panes, see [`Workspace::navigation_history`].
+#[derive(Clone, Debug)]
+pub struct NavigationHistoryEntry {
+    pub label: SharedString,
+    pub timestamp: usize,
+}
+
+/// An entry of the log of recently used items, see [`Workspace::recent_items`].
+pub enum RecentItem {
+    /// An item that is open in `pane`.
+    Open {
+        pane: Entity<Pane>,
+        item: Box<dyn ItemHandle>,
+    },
+    /// The file of an item that was closed and isn't open anymore.
+    Closed(ProjectPath),
+}
+
+/// The items that [`Workspace::close_all_internal`] closes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CloseScope {
+    /// All items of all panes.
+    All,
+    /// All items but the active pane's active item.
+    AllButActiveItem,
+    /// All items of the panes other than the active one.
+    OtherPanes,
+}
+
 /// Collects everything project-related for a certain window opened.
 /// In some way, is a counterpart of a window, as the [`WindowHandle`] could be downcast into `Workspace`.
 ///
@@ -823,24 +1013,55 @@ pub struct Workspace {
     weak_self: WeakEntity<Self>,
     workspace_actions: Vec<Box<dyn Fn(Div, &mut Window, &mut Context<Self>) -> Div>>,
     zoomed: Option<AnyWeakView>,
+    /// Whether something was zoomed when the zoom last changed, to only give feedback once.
+    was_zoomed: bool,
     previous_dock_drag_coordinates: Option<Point<Pixels>>,
     zoomed_position: Option<DockPosition>,
     center: PaneGroup,
     left_dock: Entity<Dock>,
     bottom_dock: Entity<Dock>,
+    bottom_right_dock: Entity<Dock>,
     right_dock: Entity<Dock>,
+    /// The persistent names of the panels the user moved to the bottom right dock.
+    bottom_right_panels: HashSet<String>,
+    /// The share of the bottom docks' width taken by the left slot, while both are open.
+    bottom_dock_split: f32,
+    removed_panels: Vec<Arc<dyn PanelHandle>>,
+    /// The persistent names of the panels the user removed from their docks, including the
+    /// ones removed in a previous session that haven't been added yet.
+    removed_panel_names: HashSet<String>,
+    /// Notified when a panel with the given persistent name is added, see
+    /// [`Workspace::open_panel_async`].
+    panel_waiters: HashMap<&'static str, Vec<oneshot::Sender<()>>>,
+    /// Whether the panels the workspace starts with were loaded, after which panels that are
+    /// still missing aren't waited for anymore.
+    panels_initialized: bool,
     panes: Vec<Entity<Pane>>,
     panes_by_item: HashMap<EntityId, WeakEntity<Pane>>,
     active_pane: Entity<Pane>,
     last_active_center_pane: Option<WeakEntity<Pane>>,
+    pending_hover_focus: Option<(EntityId, Task<()>)>,
+    open_panel_group: Option<OpenPanelGroup>,
+    default_layout: Option<DefaultLayout>,
     last_active_view_id: Option<proto::ViewId>,
     status_bar: Entity<StatusBar>,
+    secondary_status_bar: Entity<StatusBar>,
+    extension_status_items: Entity<ExtensionStatusItems>,
+    progress_indicator: Entity<ProgressIndicator>,
+    panel_buttons: [Entity<PanelButtons>; 4],
+    panel_button_placement: DockButtonPlacement,
     modal_layer: Entity<ModalLayer>,
     toast_layer: Entity<ToastLayer>,
     titlebar_item: Option<AnyView>,
+    slot_views: SlotViews,
     notifications: Notifications,
+    /// Timers that dismiss notifications automatically, keyed by the notification's id.
+    notification_dismiss_timers: Vec<(NotificationId, Task<()>)>,
+    notification_log: VecDeque<NotificationLogEntry>,
     project: Entity<Project>,
     follower_states: HashMap<PeerId, FollowerState>,
+    /// The panes remote collaborators are active in, outlined in their color.
+    collaborator_panes: HashMap<PeerId, WeakEntity<Pane>>,
     last_leaders_by_pane: HashMap<WeakEntity<Pane>, PeerId>,
     window_edited: bool,
     dirty_items: HashMap<EntityId, Subscription>,
@@ -853,9 +1074,19 @@ pub struct Workspace {
     _apply_leader_updates: Task<Result<()>>,
     _observe_current_user: Task<Result<()>>,
     _schedule_serialize: Option<Task<()>>,
+    /// When the pending serialization was first requested.
+    serialization_requested_at: Option<Instant>,
     pane_history_timestamp: Arc<AtomicUsize>,
     bounds: Bounds<Pixels>,
     centered_layout: bool,
+    /// Whether the stored center group of this workspace couldn't be read, so that saving
+    /// leaves it alone instead of overwriting it. Cleared once the center layout changes.
+    center_group_unreadable: bool,
+    zen_mode: bool,
+    closed_docks: Option<Vec<ClosedDock>>,
+    /// The edge of the center a dragged tab would be dropped on to split the whole center.
+    center_drag_edge: Option<SplitDirection>,
+    maximized_pane: Option<WeakEntity<Pane>>,
     bounds_save_task_queued: Option<Task<()>>,
     on_prompt_for_new_path: Option<PromptForNewPath>,
     on_prompt_for_open_path: Option<PromptForOpenPath>,
@@ -863,6 +1094,9 @@ pub struct Workspace {
     serialized_ssh_project: Option<SerializedSshProject>,
     _items_serializer: Task<Result<()>>,
     session_id: Option<String>,
+    frame_scheduler: FrameScheduler,
+    resize_policy: Option<Box<dyn ResizePolicy>>,
+    window_tabs: WorkspaceTabs,
 }
 
 impl EventEmitter<Event> for Workspace {}
@@ -928,6 +1162,41 @@ impl Workspace {
                     window.remove_window();
                 }
 
+                project::Event::WorktreeUpdatedEntries(worktree_id, changes) => {
+                    let changes = changes
+                        .iter()
+                        .filter(|(_, _, change)| {
+                            matches!(change, PathChange::Updated | PathChange::AddedOrUpdated)
+                        })
+                        .map(|(path, _, _)| {
+                            let project_path = ProjectPath {
+                                worktree_id: *worktree_id,
+                                path: path.clone(),
+                            };
+                            let mtime = this
+                                .project
+                                .read(cx)
+                                .entry_for_path(&project_path, cx)
+                                .and_then(|entry| entry.mtime);
+                            (project_path, mtime)
+                        })
+                        .collect::<Vec<_>>();
+                    if !changes.is_empty() {
+                        // Items can be open in the panes of panels too.
+                        let dock_panes = this
+                            .all_docks()
+                            .into_iter()
+                            .flat_map(|dock| dock.read(cx).panels().cloned().collect::<Vec<_>>())
+                            .filter_map(|panel| panel.pane(cx))
+                            .collect::<Vec<_>>();
+                        for pane in this.panes.iter().chain(&dock_panes) {
+                            pane.update(cx, |pane, cx| {
+                                pane.handle_externally_changed_paths(&changes, window, cx)
+                            });
+                        }
+                    }
+                }
+
                 project::Event::DeletedEntry(_, entry_id) => {
                     for pane in this.panes.iter() {
                         pane.update(cx, |pane, cx| {
@@ -993,6 +1262,10 @@ impl Workspace {
                 cx,
             );
             center_pane.set_can_split(Some(Arc::new(|_, _, _, _| true)));
+            center_pane.set_close_pane_if_empty(
+                WorkspaceSettings::get_global(cx).auto_close_empty_panes,
+                cx,
+            );
             center_pane
         });
         cx.subscribe_in(&center_pane, window, Self::handle_pane_event)
@@ -1041,24 +1314,47 @@ impl Workspace {
 
         let left_dock = Dock::new(DockPosition::Left, modal_layer.clone(), window, cx);
         let bottom_dock = Dock::new(DockPosition::Bottom, modal_layer.clone(), window, cx);
+        let bottom_right_dock = Dock::new_in_slot(
+            DockPosition::Bottom,
+            BottomDockSlot::Right,
+            modal_layer.clone(),
+            window,
+            cx,
+        );
         let right_dock = Dock::new(DockPosition::Right, modal_layer.clone(), window, cx);
-        let left_dock_buttons = cx.new(|cx| PanelButtons::new(left_dock.clone(), cx));
-        let bottom_dock_buttons = cx.new(|cx| PanelButtons::new(bottom_dock.clone(), cx));
-        let right_dock_buttons = cx.new(|cx| PanelButtons::new(right_dock.clone(), cx));
+        // In the order of the docks. Where they're shown depends on the `button_placement`.
+        let panel_buttons = [&left_dock, &bottom_dock, &bottom_right_dock, &right_dock]
+            .map(|dock| cx.new(|cx| PanelButtons::new(dock.clone(), window, cx)));
+        let panel_button_placement = WorkspaceSettings::get_global(cx)
+            .effective_dock_options()
+            .button_placement;
+        let extension_status_items = cx.new(|_| ExtensionStatusItems::new());
+        let progress_indicator = cx.new(|_| ProgressIndicator::new());
         let status_bar = cx.new(|cx| {
             let mut status_bar = StatusBar::new(&center_pane.clone(), window, cx);
-            status_bar.add_left_item(left_dock_buttons, window, cx);
-            status_bar.add_right_item(right_dock_buttons, window, cx);
-            status_bar.add_right_item(bottom_dock_buttons, window, cx);
+            add_panel_buttons(
+                &mut status_bar,
+                &panel_buttons,
+                panel_button_placement,
+                window,
+                cx,
+            );
+            status_bar.add_right_item(extension_status_items.clone(), window, cx);
+            status_bar.add_left_item(progress_indicator.clone(), window, cx);
             status_bar
         });
+        let secondary_status_bar =
+            cx.new(|cx| StatusBar::new_secondary(&center_pane.clone(), window, cx));
 
         let session_id = app_state.session.read(cx).id().to_owned();
 
         let mut active_call = None;
         if let Some(call) = ActiveCall::try_global(cx) {
             let call = call.clone();
-            let subscriptions = vec![cx.subscribe_in(&call, window, Self::on_active_call_event)];
+            let subscriptions = vec![
+                cx.subscribe_in(&call, window, Self::on_active_call_event),
+                cx.observe(&call, |this, _, cx| this.forget_departed_collaborators(cx)),
+            ];
             active_call = Some((call, subscriptions));
         }
 
@@ -1068,8 +1364,9 @@ impl Workspace {
             Self::serialize_items(&this, serializable_items_rx, cx).await
         });
 
-        let subscriptions = vec![
+        let mut subscriptions = vec![
             cx.observe_window_activation(window, Self::on_window_activation_changed),
+            cx.subscribe(&progress_indicator, Self::on_progress_event),
             cx.observe_window_bounds(window, move |this, window, cx| {
                 if this.bounds_save_task_queued.is_some() {
                     return;
@@ -1107,20 +1404,52 @@ impl Workspace {
                 ThemeSettings::reload_current_theme(cx);
                 ThemeSettings::reload_current_icon_theme(cx);
             }),
+            cx.observe_global_in::<SettingsStore>(window, |this, window, cx| {
+                let settings = WorkspaceSettings::get_global(cx);
+                let auto_close_empty_panes = settings.auto_close_empty_panes;
+                let panel_button_placement = settings.effective_dock_options().button_placement;
+                for pane in &this.panes {
+                    pane.update(cx, |pane, cx| {
+                        pane.set_close_pane_if_empty(auto_close_empty_panes, cx)
+                    });
+                }
+                if this.panel_button_placement != panel_button_placement {
+                    this.panel_button_placement = panel_button_placement;
+                    let panel_buttons = this.panel_buttons.clone();
+                    this.status_bar.update(cx, |status_bar, cx| {
+                        add_panel_buttons(
+                            status_bar,
+                            &panel_buttons,
+                            panel_button_placement,
+                            window,
+                            cx,
+                        )
+                    });
+                }
+            }),
             cx.on_release(move |this, cx| {
                 this.app_state.workspace_store.update(cx, move |store, _| {
                     store.workspaces.remove(&window_handle.clone());
-                })
+                });
+                if cfg!(debug_assertions) {
+                    LeakCheck::new(this, cx).report_after_delay(cx);
+                }
             }),
         ];
+        subscriptions.extend(
+            [&left_dock, &bottom_dock, &bottom_right_dock, &right_dock]
+                .map(|dock| cx.subscribe_in(dock, window, Self::on_dock_event)),
+        );
 
         cx.defer_in(window, |this, window, cx| {
             this.update_window_title(window, cx);
             this.show_initial_notifications(cx);
+            this.add_call_controls(window, cx);
         });
         Workspace {
             weak_self: weak_handle.clone(),
             zoomed: None,
+            was_zoomed: false,
             zoomed_position: None,
             previous_dock_drag_coordinates: None,
             center: PaneGroup::new(center_pane.clone()),
@@ -1128,17 +1457,36 @@ impl Workspace {
             panes_by_item: Default::default(),
             active_pane: center_pane.clone(),
             last_active_center_pane: Some(center_pane.downgrade()),
+            pending_hover_focus: None,
+            open_panel_group: None,
+            default_layout: None,
             last_active_view_id: None,
             status_bar,
+            secondary_status_bar,
+            extension_status_items,
+            progress_indicator,
+            panel_buttons,
+            panel_button_placement,
             modal_layer,
             toast_layer,
             titlebar_item: None,
+            slot_views: SlotViews::default(),
             notifications: Default::default(),
+            notification_dismiss_timers: Vec::new(),
+            notification_log: VecDeque::new(),
             left_dock,
             bottom_dock,
+            bottom_right_dock,
             right_dock,
+            bottom_right_panels: Default::default(),
+            bottom_dock_split: 0.5,
+            removed_panels: Vec::new(),
+            removed_panel_names: HashSet::default(),
+            panel_waiters: HashMap::default(),
+            panels_initialized: false,
             project: project.clone(),
             follower_states: Default::default(),
+            collaborator_panes: Default::default(),
             last_leaders_by_pane: Default::default(),
             dispatching_keystrokes: Default::default(),
             window_edited: false,
@@ -1149,6 +1497,7 @@ impl Workspace {
             _observe_current_user,
             _apply_leader_updates,
             _schedule_serialize: None,
+            serialization_requested_at: None,
             leader_updates_tx,
             _subscriptions: subscriptions,
             pane_history_timestamp,
@@ -1156,6 +1505,11 @@ impl Workspace {
             // This data will be incorrect, but it will be overwritten by the time it needs to be used.
             bounds: Default::default(),
             centered_layout: false,
+            center_group_unreadable: false,
+            zen_mode: false,
+            closed_docks: None,
+            center_drag_edge: None,
+            maximized_pane: None,
             bounds_save_task_queued: None,
             on_prompt_for_new_path: None,
             on_prompt_for_open_path: None,
@@ -1163,6 +1517,9 @@ impl Workspace {
             _items_serializer,
             session_id: Some(session_id),
             serialized_ssh_project: None,
+            frame_scheduler: FrameScheduler::default(),
+            resize_policy: None,
+            window_tabs: WorkspaceTabs::default(),
         }
     }
 
@@ -1177,6 +1534,25 @@ impl Workspace {
             WindowHandle<Workspace>,
             Vec<Option<Result<Box<dyn ItemHandle>, anyhow::Error>>>,
         )>,
+    > {
+        Self::new_local_internal(abs_paths, app_state, requesting_window, env, true, cx)
+    }
+
+    /// Opens a workspace for the paths like [`Workspace::new_local`]. Unless `restore_state` is
+    /// set, the panes, items and docks last serialized for the paths aren't restored, and
+    /// neither is the default layout applied, leaving the caller to lay out the workspace.
+    pub(crate) fn new_local_internal(
+        abs_paths: Vec<PathBuf>,
+        app_state: Arc<AppState>,
+        requesting_window: Option<WindowHandle<Workspace>>,
+        env: Option<HashMap<String, String>>,
+        restore_state: bool,
+        cx: &mut App,
+    ) -> Task<
+        anyhow::Result<(
+            WindowHandle<Workspace>,
+            Vec<Option<Result<Box<dyn ItemHandle>, anyhow::Error>>>,
+        )>,
     > {
         let project_handle = Project::local(
             app_state.client.clone(),
@@ -1247,6 +1623,12 @@ impl Workspace {
                 DB.next_id().await.unwrap_or_else(|_| Default::default())
             };
 
+            let default_layout = if serialized_workspace.is_none() && restore_state {
+                DB.default_named_layout().await.log_err().flatten()
+            } else {
+                None
+            };
+
             let toolchains = DB.toolchains(workspace_id).await?;
             for (toolchain, worktree_id) in toolchains {
                 project_handle
@@ -1257,7 +1639,13 @@ impl Workspace {
             }
             let window = if let Some(window) = requesting_window {
                 cx.update_window(window.into(), |_, window, cx| {
-                    window.replace_root(cx, |window, cx| {
+                    // The new workspace takes over the replaced one's place among the tabs.
+                    let window_tabs = window
+                        .root::<Workspace>()
+                        .flatten()
+                        .map(|root| root.update(cx, |root, _| mem::take(&mut root.window_tabs)))
+                        .unwrap_or_default();
+                    let workspace = window.replace_root(cx, |window, cx| {
                         Workspace::new(
                             Some(workspace_id),
                             project_handle.clone(),
@@ -1266,9 +1654,17 @@ impl Workspace {
                             cx,
                         )
                     });
+                    workspace.update(cx, |workspace, cx| {
+                        workspace.window_tabs = window_tabs;
+                        workspace.serialize_workspace_tabs(cx);
+                    });
                 })?;
                 window
             } else {
+                // A workspace opened in its own window is no longer a tab of another one.
+                DB.delete_background_window_tab(workspace_id)
+                    .await
+                    .log_err();
                 let window_bounds_override = window_bounds_env_override();
 
                 let (window_bounds, display) = if let Some(bounds) = window_bounds_override {
@@ -1283,7 +1679,10 @@ impl Workspace {
                         });
 
                     if let Some((serialized_display, serialized_status)) = restorable_bounds {
-                        (Some(serialized_status.0), Some(serialized_display))
+                        let window_bounds = cx.update(|cx| {
+                            visible_window_bounds(serialized_status.0, serialized_display, cx)
+                        })?;
+                        (Some(window_bounds), Some(serialized_display))
                     } else {
                         (None, None)
                     }
@@ -1294,6 +1693,7 @@ impl Workspace {
                 options.window_bounds = window_bounds;
                 let centered_layout = serialized_workspace
                     .as_ref()
+                    .filter(|_| restore_state)
                     .map(|w| w.centered_layout)
                     .unwrap_or(false);
                 cx.open_window(options, {
@@ -1316,6 +1716,22 @@ impl Workspace {
             };
 
             notify_if_database_failed(window, cx);
+            if let Some(layout) = default_layout {
+                window
+                    .update(cx, |workspace, window, cx| {
+                        workspace.apply_layout(&layout, window, cx)
+                    })
+                    .log_err();
+            } else if serialized_workspace.is_none() && restore_state {
+                window
+                    .update(cx, |workspace, window, cx| {
+                        if let Some(layout) = cx.try_global::<DefaultLayout>().cloned() {
+                            workspace.apply_default_layout(layout, window, cx);
+                        }
+                    })
+                    .log_err();
+            }
+            let serialized_workspace = serialized_workspace.filter(|_| restore_state);
             let opened_items = window
                 .update(cx, |_workspace, window, cx| {
                     open_items(serialized_workspace, project_paths, window, cx)
@@ -1342,12 +1758,28 @@ impl Workspace {
         &self.bottom_dock
     }
 
+    pub fn bottom_right_dock(&self) -> &Entity<Dock> {
+        &self.bottom_right_dock
+    }
+
     pub fn right_dock(&self) -> &Entity<Dock> {
         &self.right_dock
     }
 
-    pub fn all_docks(&self) -> [&Entity<Dock>; 3] {
-        [&self.left_dock, &self.bottom_dock, &self.right_dock]
+    pub fn all_docks(&self) -> [&Entity<Dock>; 4] {
+        [
+            &self.left_dock,
+            &self.bottom_dock,
+            &self.bottom_right_dock,
+            &self.right_dock,
+        ]
+    }
+
+    /// Returns the panel with the given id, if it's docked in this workspace.
+    pub fn panel_for_id(&self, panel_id: EntityId, cx: &App) -> Option<Arc<dyn PanelHandle>> {
+        self.all_docks()
+            .into_iter()
+            .find_map(|dock| dock.read(cx).panel_for_id(panel_id).cloned())
     }
 
     pub fn dock_at_position(&self, position: DockPosition) -> &Entity<Dock> {
@@ -1358,6 +1790,192 @@ impl Workspace {
         }
     }
 
+    /// Returns the dock a panel with the given name should live in when placed at `position`,
+    /// taking into account which panels the user moved to the bottom right dock.
+    pub fn dock_for_panel(&self, persistent_name: &str, position: DockPosition) -> &Entity<Dock> {
+        if position == DockPosition::Bottom && self.bottom_right_panels.contains(persistent_name) {
+            &self.bottom_right_dock
+        } else {
+            self.dock_at_position(position)
+        }
+    }
+
+    /// Moves a panel living in one of the bottom docks into the given bottom dock slot.
+    pub fn move_panel_to_bottom_slot(
+        &mut self,
+        panel: Arc<dyn PanelHandle>,
+        slot: BottomDockSlot,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (from, to) = match slot {
+            BottomDockSlot::Left => (&self.bottom_right_dock, &self.bottom_dock),
+            BottomDockSlot::Right => (&self.bottom_dock, &self.bottom_right_dock),
+        };
+        if !from.read(cx).contains_panel(panel.panel_id()) {
+            return;
+        }
+        match slot {
+            BottomDockSlot::Left => {
+                self.bottom_right_panels.remove(panel.persistent_name());
+            }
+            BottomDockSlot::Right => {
+                self.bottom_right_panels
+                    .insert(panel.persistent_name().to_string());
+            }
+        }
+        panel.move_to_dock(from, to, window, cx);
+        cx.notify();
+    }
+
+    /// Puts the panels with the given names into the bottom right dock, both the ones that are
+    /// already docked and the ones added later.
+    pub(crate) fn restore_bottom_right_panels(
+        &mut self,
+        panel_names: &[String],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.bottom_right_panels.extend(panel_names.iter().cloned());
+        let docked_panels = self
+            .bottom_dock
+            .read(cx)
+            .panels()
+            .filter(|panel| self.bottom_right_panels.contains(panel.persistent_name()))
+            .cloned()
+            .collect::<Vec<_>>();
+        for panel in docked_panels {
+            self.move_panel_to_bottom_slot(panel, BottomDockSlot::Right, window, cx);
+        }
+    }
+
+    /// Panels the user removed from their docks, which can be restored with
+    /// [`Workspace::restore_removed_panel`].
+    pub fn removed_panels(&self) -> &[Arc<dyn PanelHandle>] {
+        &self.removed_panels
+    }
+
+    pub fn remove_panel_from_dock(
+        &mut self,
+        panel: Arc<dyn PanelHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !panel.can_close(window, cx) {
+            return;
+        }
+        if !panel.should_close(CloseReason::PanelRemoved, window, cx) {
+            self.show_panel_close_vetoed(panel.as_ref(), window, cx);
+            return;
+        }
+        let Some(dock) = self
+            .all_docks()
+            .into_iter()
+            .find(|dock| dock.read(cx).contains_panel(panel.panel_id()))
+            .cloned()
+        else {
+            return;
+        };
+
+        let was_focused = panel.panel_focus_handle(cx).contains_focused(window, cx);
+        panel.remove_from_dock(&dock, window, cx);
+        self.removed_panel_names
+            .insert(panel.persistent_name().to_string());
+        self.removed_panels.push(panel);
+        if was_focused {
+            self.active_pane
+                .update(cx, |pane, cx| window.focus(&pane.focus_handle(cx)));
+        }
+        self.notify_docks(cx);
+        self.serialize_workspace(window, cx);
+    }
+
+    pub fn restore_removed_panel(
+        &mut self,
+        panel_id: EntityId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self
+            .removed_panels
+            .iter()
+            .position(|panel| panel.panel_id() == panel_id)
+        else {
+            return;
+        };
+
+        let panel = self.removed_panels.remove(ix);
+        self.removed_panel_names.remove(panel.persistent_name());
+        let dock = self
+            .dock_for_panel(panel.persistent_name(), panel.position(window, cx))
+            .clone();
+        let index = panel.add_to_dock(&dock, window, cx);
+        dock.update(cx, |dock, cx| {
+            dock.set_open(true, window, cx);
+            dock.activate_panel(index, window, cx);
+        });
+        panel.panel_focus_handle(cx).focus(window);
+        self.notify_docks(cx);
+        self.serialize_workspace(window, cx);
+    }
+
+    fn remove_focused_panel_from_dock(
+        &mut self,
+        _: &RemovePanelFromDock,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let focused_panel = self.all_docks().into_iter().find_map(|dock| {
+            dock.read(cx)
+                .active_panel()
+                .filter(|panel| panel.panel_focus_handle(cx).contains_focused(window, cx))
+                .cloned()
+        });
+        if let Some(panel) = focused_panel {
+            self.remove_panel_from_dock(panel, window, cx);
+        }
+    }
+
+    /// Removes the panels with the given names from their docks, both the ones that are
+    /// already docked and the ones added later.
+    pub(crate) fn restore_removed_panels(
+        &mut self,
+        panel_names: &[String],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if panel_names.is_empty() {
+            return;
+        }
+        self.removed_panel_names.extend(panel_names.iter().cloned());
+        let docked_panels = self
+            .all_docks()
+            .into_iter()
+            .flat_map(|dock| {
+                dock.read(cx)
+                    .panels()
+                    .filter(|panel| self.removed_panel_names.contains(panel.persistent_name()))
+                    .map(|panel| (dock.clone(), panel.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for (dock, panel) in docked_panels {
+            panel.remove_from_dock(&dock, window, cx);
+            self.removed_panels.push(panel);
+        }
+        self.notify_docks(cx);
+    }
+
+    /// Tells the panel buttons of every dock that the set of removed panels changed.
+    fn notify_docks(&self, cx: &mut App) {
+        for dock in self.all_docks() {
+            dock.update(cx, |_, cx| {
+                cx.emit(DockEvent::PanelsChanged);
+                cx.notify();
+            });
+        }
+    }
+
     pub fn is_edited(&self) -> bool {
         self.window_edited
     }
@@ -1372,18 +1990,84 @@ impl Workspace {
         cx.on_focus_in(&focus_handle, window, Self::handle_panel_focused)
             .detach();
 
-        let dock_position = panel.position(window, cx);
-        let dock = self.dock_at_position(dock_position);
+        // The user removed this panel in a previous session.
+        if self.removed_panel_names.contains(T::persistent_name()) {
+            self.removed_panels.push(Arc::new(panel));
+            self.notify_docks(cx);
+            return;
+        }
+
+        let dock_position = self.valid_panel_position(&panel, window, cx);
+        let dock = self.dock_for_panel(T::persistent_name(), dock_position);
 
         dock.update(cx, |dock, cx| {
-            dock.add_panel(panel, self.weak_self.clone(), window, cx)
+            dock.add_panel(panel.clone(), self.weak_self.clone(), window, cx)
         });
+        self.apply_default_panel_size(&panel, window, cx);
+
+        for waiter in self
+            .panel_waiters
+            .remove(T::persistent_name())
+            .unwrap_or_default()
+        {
+            waiter.send(()).ok();
+        }
+    }
+
+    /// Returns the position the panel should be docked at. If the panel's position isn't
+    /// valid for it anymore, e.g. after a settings change, the panel is moved to its first
+    /// valid position and the user is notified.
+    pub(crate) fn valid_panel_position(
+        &mut self,
+        panel: &dyn PanelHandle,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> DockPosition {
+        let position = panel.position(window, cx);
+        if panel.position_is_valid(position, cx) {
+            return position;
+        }
+        let Some(new_position) = [
+            DockPosition::Left,
+            DockPosition::Bottom,
+            DockPosition::Right,
+        ]
+        .into_iter()
+        .find(|position| panel.position_is_valid(*position, cx)) else {
+            return position;
+        };
+
+        panel.set_position(new_position, window, cx);
+
+        struct PanelMigrated;
+        let name = panel
+            .icon_tooltip(window, cx)
+            .unwrap_or(panel.persistent_name());
+        self.show_toast(
+            Toast::new(
+                NotificationId::composite::<PanelMigrated>(panel.persistent_name()),
+                format!(
+                    "{name} can't be placed in the {} dock, so it was moved to the {} dock.",
+                    position.label(),
+                    new_position.label()
+                ),
+            )
+            .autohide(),
+            cx,
+        );
+        new_position
     }
 
     pub fn status_bar(&self) -> &Entity<StatusBar> {
         &self.status_bar
     }
 
+    /// The strip between the center and the bottom dock, for items that belong to the
+    /// active pane's context, such as the controls of a running debug session.
+    pub fn secondary_status_bar(&self) -> &Entity<StatusBar> {
+        &self.secondary_status_bar
+    }
+
     pub fn app_state(&self) -> &Arc<AppState> {
         &self.app_state
     }
@@ -1459,6 +2143,74 @@ impl Workspace {
             .collect()
     }
 
+    /// The items open in the center panes, most recently activated first, with the pane each
+    /// one is in. The panes share one activation clock, so their histories merge into a single
+    /// log across the workspace.
+    pub fn recently_activated_items(&self, cx: &App) -> Vec<(Entity<Pane>, Box<dyn ItemHandle>)> {
+        let mut items = self.activated_items_with_timestamps(cx);
+        items.sort_by_key(|(timestamp, _, _)| cmp::Reverse(*timestamp));
+        items
+            .into_iter()
+            .map(|(_, pane, item)| (pane, item))
+            .collect()
+    }
+
+    /// Like [`Self::recently_activated_items`], but also lists the files of closed items, by
+    /// when they were closed. A file is only listed as closed if it isn't open in any pane,
+    /// and only for the last time it was closed.
+    pub fn recent_items(&self, cx: &App) -> Vec<RecentItem> {
+        let items = self.activated_items_with_timestamps(cx);
+        let mut seen_paths = items
+            .iter()
+            .filter_map(|(_, _, item)| item.project_path(cx))
+            .collect::<HashSet<_>>();
+        let mut closed_items = Vec::new();
+        for pane in &self.panes {
+            pane.read(cx).nav_history().for_each_entry_in(
+                NavigationMode::ReopeningClosedItem,
+                cx,
+                |entry, project_path| {
+                    if let Some(project_path) = project_path {
+                        closed_items.push((entry.timestamp, project_path));
+                    }
+                },
+            );
+        }
+        closed_items.sort_by_key(|(timestamp, _)| cmp::Reverse(*timestamp));
+
+        let mut recent_items = items
+            .into_iter()
+            .map(|(timestamp, pane, item)| (timestamp, RecentItem::Open { pane, item }))
+            .chain(
+                closed_items
+                    .into_iter()
+                    .filter(|(_, project_path)| seen_paths.insert(project_path.clone()))
+                    .map(|(timestamp, project_path)| (timestamp, RecentItem::Closed(project_path))),
+            )
+            .collect::<Vec<_>>();
+        recent_items.sort_by_key(|(timestamp, _)| cmp::Reverse(*timestamp));
+        recent_items.into_iter().map(|(_, item)| item).collect()
+    }
+
+    fn activated_items_with_timestamps(
+        &self,
+        cx: &App,
+    ) -> Vec<(usize, Entity<Pane>, Box<dyn ItemHandle>)> {
+        let mut items = Vec::new();
+        for pane in &self.panes {
+            let pane_state = pane.read(cx);
+            for entry in pane_state.activation_history() {
+                if let Some(item) = pane_state
+                    .items()
+                    .find(|item| item.item_id() == entry.entity_id)
+                {
+                    items.push((entry.timestamp, pane.clone(), item.boxed_clone()));
+                }
+            }
+        }
+        items
+    }
+
     fn navigate_history(
         &mut self,
         pane: WeakEntity<Pane>,
@@ -1467,6 +2219,8 @@ impl Workspace {
         cx: &mut Context<Workspace>,
     ) -> Task<Result<()>> {
         let to_load = if let Some(pane) = pane.upgrade() {
+            // Moving to another pane is a navigation on its own, even if its item stays put.
+            let activates_pane = pane != self.active_pane;
             pane.update(cx, |pane, cx| {
                 window.focus(&pane.focus_handle(cx));
                 loop {
@@ -1484,7 +2238,8 @@ impl Workspace {
                         pane.activate_item(index, true, true, window, cx);
                         pane.nav_history_mut().set_mode(NavigationMode::Normal);
 
-                        let mut navigated = prev_active_item_index != pane.active_item_index();
+                        let mut navigated =
+                            activates_pane || prev_active_item_index != pane.active_item_index();
                         if let Some(data) = entry.data {
                             navigated |= pane.active_item()?.navigate(data, window, cx);
                         }
@@ -1604,21 +2359,145 @@ impl Workspace {
         self.navigate_history(pane, NavigationMode::GoingForward, window, cx)
     }
 
-    pub fn reopen_closed_item(
+    /// Navigates back through the history of all panes, to wherever the most recent
+    /// navigation happened, reopening the item navigated to if it was closed.
+    pub fn go_back_across_panes(
         &mut self,
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) -> Task<Result<()>> {
-        self.navigate_history(
-            self.active_pane().downgrade(),
-            NavigationMode::ReopeningClosedItem,
-            window,
-            cx,
-        )
+        self.navigate_history_across_panes(NavigationMode::GoingBack, window, cx)
     }
 
-    pub fn client(&self) -> &Arc<Client> {
-        &self.app_state.client
+    /// Navigates forward through the history of all panes, undoing [`Self::go_back_across_panes`].
+    pub fn go_forward_across_panes(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        self.navigate_history_across_panes(NavigationMode::GoingForward, window, cx)
+    }
+
+    pub fn can_navigate_across_panes(&self, mode: NavigationMode, cx: &App) -> bool {
+        self.pane_to_navigate(mode, cx).is_some()
+    }
+
+    /// Returns the entries that navigating back or forward across panes would go through,
+    /// most recent first.
+    pub fn navigation_history(
+        &self,
+        mode: NavigationMode,
+        cx: &App,
+    ) -> Vec<NavigationHistoryEntry> {
+        let forward_cutoff = self.forward_history_cutoff(cx);
+        let mut entries = Vec::new();
+        for pane in &self.panes {
+            pane.read(cx)
+                .nav_history()
+                .for_each_entry_in(mode, cx, |entry, path| {
+                    if matches!(mode, NavigationMode::GoingForward)
+                        && Some(entry.timestamp) <= forward_cutoff
+                    {
+                        return;
+                    }
+                    let label = path
+                        .and_then(|path| Some(path.path.file_name()?.to_string_lossy().into()))
+                        .or_else(|| entry.item.upgrade()?.tab_tooltip_text(cx));
+                    if let Some(label) = label {
+                        entries.push(NavigationHistoryEntry {
+                            label,
+                            timestamp: entry.timestamp,
+                        });
+                    }
+                });
+        }
+        entries.sort_by_key(|entry| cmp::Reverse(entry.timestamp));
+        entries
+    }
+
+    /// Navigates back or forward across panes until the given history entry was visited.
+    pub fn navigate_to_history_entry(
+        &mut self,
+        mode: NavigationMode,
+        timestamp: usize,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        cx.spawn_in(window, async move |workspace, cx| loop {
+            let step = workspace.update_in(cx, |workspace, window, cx| {
+                // Entries further back or forward are older, so once the next entry is older
+                // than the one to navigate to, that one was visited.
+                let (_, next_timestamp) = workspace.pane_to_navigate(mode, cx)?;
+                (next_timestamp >= timestamp)
+                    .then(|| workspace.navigate_history_across_panes(mode, window, cx))
+            })?;
+            let Some(step) = step else {
+                return Ok(());
+            };
+            step.await?;
+        })
+    }
+
+    fn navigate_history_across_panes(
+        &mut self,
+        mode: NavigationMode,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        let Some((pane, _)) = self.pane_to_navigate(mode, cx) else {
+            return Task::ready(Ok(()));
+        };
+        self.navigate_history(pane.downgrade(), mode, window, cx)
+    }
+
+    /// All panes share the timestamps of their history entries, so the next entry across panes
+    /// is the latest entry of any pane. Returns the pane it belongs to and its timestamp.
+    fn pane_to_navigate(&self, mode: NavigationMode, cx: &App) -> Option<(Entity<Pane>, usize)> {
+        let forward_cutoff = self.forward_history_cutoff(cx);
+        self.panes
+            .iter()
+            .filter_map(|pane| {
+                let timestamp = pane.read(cx).nav_history().last_timestamp(mode)?;
+                if matches!(mode, NavigationMode::GoingForward) && Some(timestamp) <= forward_cutoff
+                {
+                    return None;
+                }
+                Some((pane.clone(), timestamp))
+            })
+            .max_by_key(|(_, timestamp)| *timestamp)
+    }
+
+    /// Navigating to a new location in one pane clears its forward history, and makes the
+    /// forward entries of the other panes that are older than that navigation stale as well.
+    fn forward_history_cutoff(&self, cx: &App) -> Option<usize> {
+        self.panes
+            .iter()
+            .filter_map(|pane| pane.read(cx).nav_history().last_navigation_timestamp())
+            .max()
+    }
+
+    pub fn reopen_closed_item(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        // Reopen the most recently closed item of any pane, in the pane it was closed in.
+        let pane = self
+            .panes
+            .iter()
+            .filter_map(|pane| Some((pane.read(cx).nav_history().last_closed_timestamp()?, pane)))
+            .max_by_key(|(timestamp, _)| *timestamp)
+            .map_or_else(|| self.active_pane().clone(), |(_, pane)| pane.clone());
+        self.navigate_history(
+            pane.downgrade(),
+            NavigationMode::ReopeningClosedItem,
+            window,
+            cx,
+        )
+    }
+
+    pub fn client(&self) -> &Arc<Client> {
+        &self.app_state.client
     }
 
     pub fn set_titlebar_item(&mut self, item: AnyView, _: &mut Window, cx: &mut Context<Self>) {
@@ -1879,12 +2758,26 @@ impl Workspace {
         close_intent: CloseIntent,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) -> Task<Result<bool>> {
+        match close_intent {
+            CloseIntent::Quit | CloseIntent::CloseWindow if !self.window_tabs.is_empty() => {
+                self.prepare_workspace_tabs_to_close(close_intent, window, cx)
+            }
+            _ => self.prepare_workspace_to_close(close_intent, window, cx),
+        }
+    }
+
+    fn prepare_workspace_to_close(
+        &mut self,
+        close_intent: CloseIntent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Task<Result<bool>> {
         let active_call = self.active_call().cloned();
 
         // On Linux and Windows, closing the last window should restore the last workspace.
         let save_last_workspace = cfg!(not(target_os = "macos"))
-            && close_intent != CloseIntent::ReplaceWindow
+            && matches!(close_intent, CloseIntent::Quit | CloseIntent::CloseWindow)
             && cx.windows().len() == 1;
 
         cx.spawn_in(window, async move |this, cx| {
@@ -1896,8 +2789,10 @@ impl Workspace {
             })?;
 
             if let Some(active_call) = active_call {
-                if close_intent != CloseIntent::Quit
-                    && workspace_count == 1
+                if matches!(
+                    close_intent,
+                    CloseIntent::CloseWindow | CloseIntent::ReplaceWindow
+                ) && workspace_count == 1
                     && active_call.read_with(cx, |call, _| call.room().is_some())?
                 {
                     let answer = cx.update(|window, cx| {
@@ -1921,20 +2816,41 @@ impl Workspace {
                 }
             }
 
+            let panels_allow_close = this.update_in(cx, |this, window, cx| {
+                let panels = this
+                    .all_docks()
+                    .into_iter()
+                    .flat_map(|dock| dock.read(cx).panels().cloned().collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                let vetoing_panel = panels
+                    .into_iter()
+                    .find(|panel| !panel.should_close(CloseReason::WorkspaceShutdown, window, cx));
+                if let Some(panel) = &vetoing_panel {
+                    this.show_panel_close_vetoed(panel.as_ref(), window, cx);
+                }
+                vetoing_panel.is_none()
+            })?;
+            if !panels_allow_close {
+                return anyhow::Ok(false);
+            }
+
             let save_result = this
                 .update_in(cx, |this, window, cx| {
                     this.save_all_internal(SaveIntent::Close, window, cx)
                 })?
                 .await;
 
-            // If we're not quitting, but closing, we remove the workspace from
-            // the current session.
-            if close_intent != CloseIntent::Quit
-                && !save_last_workspace
-                && save_result.as_ref().map_or(false, |&res| res)
-            {
-                this.update_in(cx, |this, window, cx| this.remove_from_session(window, cx))?
-                    .await;
+            if save_result.as_ref().map_or(false, |&res| res) {
+                // If we're not quitting, but closing, we remove the workspace from
+                // the current session. Otherwise, write any pending changes before the
+                // window goes away.
+                if close_intent != CloseIntent::Quit && !save_last_workspace {
+                    this.update_in(cx, |this, window, cx| this.remove_from_session(window, cx))?
+                        .await;
+                } else {
+                    this.update_in(cx, |this, window, cx| this.flush_serialization(window, cx))?
+                        .await;
+                }
             }
 
             save_result
@@ -2048,24 +2964,28 @@ impl Workspace {
                 futures::future::try_join_all(serialize_tasks).await?;
 
                 if remaining_dirty_items.len() > 1 {
-                    let answer = workspace.update_in(cx, |_, window, cx| {
+                    let answer = workspace.update_in(cx, |workspace, window, cx| {
                         let detail = Pane::file_names_for_prompt(
                             &mut remaining_dirty_items.iter().map(|(_, handle)| handle),
                             cx,
                         );
-                        window.prompt(
-                            PromptLevel::Warning,
-                            &"Do you want to save all changes in the following files?",
-                            Some(&detail),
-                            &["Save all", "Discard all", "Cancel"],
+                        workspace.prompt(
+                            "Do you want to save all changes in the following files?",
+                            prompt_dialog::PromptOptions::new([
+                                "Save all",
+                                "Discard all",
+                                "Cancel",
+                            ])
+                            .level(PromptLevel::Warning)
+                            .detail(detail),
+                            window,
                             cx,
                         )
                     })?;
-                    match answer.await.log_err() {
+                    match answer.await {
                         Some(0) => save_intent = SaveIntent::SaveAll,
                         Some(1) => save_intent = SaveIntent::Skip,
-                        Some(2) => return Ok(false),
-                        _ => {}
+                        _ => return Ok(false),
                     }
                 }
 
@@ -2288,29 +3208,50 @@ impl Workspace {
         );
         cx.spawn_in(window, async move |this, cx| {
             if let Some(paths) = paths.await.log_err().flatten() {
-                let results = this
-                    .update_in(cx, |this, window, cx| {
-                        this.open_paths(
-                            paths,
-                            OpenOptions {
-                                visible: Some(OpenVisible::All),
-                                ..Default::default()
-                            },
-                            None,
-                            window,
-                            cx,
-                        )
-                    })?
-                    .await;
-                for result in results.into_iter().flatten() {
-                    result.log_err();
-                }
+                this.update_in(cx, |this, window, cx| {
+                    this.add_paths_to_project(paths, window, cx)
+                })?
+                .await;
             }
             anyhow::Ok(())
         })
         .detach_and_log_err(cx);
     }
 
+    /// Adds the paths to the project as visible worktrees, such as when they're dropped on the
+    /// title bar, and opens the ones that are files.
+    pub fn add_paths_to_project(
+        &mut self,
+        paths: Vec<PathBuf>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        if self.project.read(cx).is_via_collab() {
+            self.show_error(
+                &anyhow!("You cannot add folders to someone else's project"),
+                cx,
+            );
+            return Task::ready(());
+        }
+        let open_paths = self.open_paths(
+            paths,
+            OpenOptions {
+                visible: Some(OpenVisible::All),
+                ..Default::default()
+            },
+            None,
+            window,
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            for result in open_paths.await.into_iter().flatten() {
+                if let Err(error) = result {
+                    this.update(cx, |this, cx| this.show_error(&error, cx)).ok();
+                }
+            }
+        })
+    }
+
     pub fn project_path_for_path(
         project: Entity<Project>,
         abs_path: &Path,
@@ -2408,7 +3349,7 @@ impl Workspace {
         cx: &mut Context<Self>,
     ) {
         if let Some(task) = self.close_all_internal(
-            true,
+            CloseScope::AllButActiveItem,
             action.save_intent.unwrap_or(SaveIntent::Close),
             window,
             cx,
@@ -2424,7 +3365,23 @@ impl Workspace {
         cx: &mut Context<Self>,
     ) {
         if let Some(task) = self.close_all_internal(
-            false,
+            CloseScope::All,
+            action.save_intent.unwrap_or(SaveIntent::Close),
+            window,
+            cx,
+        ) {
+            task.detach_and_log_err(cx)
+        }
+    }
+
+    pub fn close_all_items_in_other_panes(
+        &mut self,
+        action: &CloseAllItemsInOtherPanes,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(task) = self.close_all_internal(
+            CloseScope::OtherPanes,
             action.save_intent.unwrap_or(SaveIntent::Close),
             window,
             cx,
@@ -2433,61 +3390,110 @@ impl Workspace {
         }
     }
 
+    /// Closes the unpinned items in the given scope. When several of them are dirty, asks once
+    /// whether to save all of them, rather than once per pane.
     fn close_all_internal(
         &mut self,
-        retain_active_pane: bool,
+        scope: CloseScope,
         save_intent: SaveIntent,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<Task<Result<()>>> {
-        let current_pane = self.active_pane();
-
-        let mut tasks = Vec::new();
+        let active_pane = self.active_pane().clone();
+        // The panes to close items in, each with the item to keep open, if any.
+        let panes = self
+            .panes()
+            .iter()
+            .filter_map(|pane| {
+                if *pane != active_pane {
+                    return Some((pane.clone(), None));
+                }
+                match scope {
+                    CloseScope::All => Some((pane.clone(), None)),
+                    CloseScope::AllButActiveItem => Some((
+                        pane.clone(),
+                        pane.read(cx).active_item().map(|item| item.item_id()),
+                    )),
+                    CloseScope::OtherPanes => None,
+                }
+            })
+            .collect::<Vec<_>>();
 
-        if retain_active_pane {
-            if let Some(current_pane_close) = current_pane.update(cx, |pane, cx| {
-                pane.close_inactive_items(
-                    &CloseInactiveItems {
-                        save_intent: None,
-                        close_pinned: false,
-                    },
-                    window,
-                    cx,
-                )
-            }) {
-                tasks.push(current_pane_close);
-            };
+        let mut items_to_close = Vec::new();
+        for (pane, retained_item_id) in &panes {
+            let pane = pane.read(cx);
+            let non_closeable_items = pane.get_non_closeable_item_ids(false);
+            items_to_close.extend(
+                pane.items()
+                    .filter(|item| {
+                        Some(item.item_id()) != *retained_item_id
+                            && !non_closeable_items.contains(&item.item_id())
+                    })
+                    .map(|item| item.boxed_clone()),
+            );
+        }
+        if items_to_close.is_empty() {
+            return None;
         }
+        let dirty_items = items_to_close
+            .into_iter()
+            .filter(|item| item.is_dirty(cx) && !Pane::skip_save_on_close(item.as_ref(), self, cx))
+            .collect::<Vec<_>>();
 
-        for pane in self.panes() {
-            if retain_active_pane && pane.entity_id() == current_pane.entity_id() {
-                continue;
+        Some(cx.spawn_in(window, async move |this, cx| {
+            let mut save_intent = save_intent;
+            if save_intent == SaveIntent::Close && dirty_items.len() > 1 {
+                let answer = this.update_in(cx, |this, window, cx| {
+                    let detail = Pane::file_names_for_prompt(&mut dirty_items.iter(), cx);
+                    this.prompt(
+                        "Do you want to save changes to the following files?",
+                        prompt_dialog::PromptOptions::new(["Save all", "Discard all", "Cancel"])
+                            .level(PromptLevel::Warning)
+                            .detail(detail),
+                        window,
+                        cx,
+                    )
+                })?;
+                match answer.await {
+                    Some(0) => save_intent = SaveIntent::SaveAll,
+                    Some(1) => save_intent = SaveIntent::Skip,
+                    _ => return Ok(()),
+                }
             }
 
-            if let Some(close_pane_items) = pane.update(cx, |pane: &mut Pane, cx| {
-                pane.close_all_items(
-                    &CloseAllItems {
-                        save_intent: Some(save_intent),
-                        close_pinned: false,
-                    },
-                    window,
-                    cx,
-                )
-            }) {
-                tasks.push(close_pane_items)
+            let tasks = this.update_in(cx, |_, window, cx| {
+                panes
+                    .into_iter()
+                    .filter_map(|(pane, retained_item_id)| {
+                        pane.update(cx, |pane, cx| {
+                            if retained_item_id.is_some() {
+                                pane.close_inactive_items(
+                                    &CloseInactiveItems {
+                                        save_intent: Some(save_intent),
+                                        close_pinned: false,
+                                    },
+                                    window,
+                                    cx,
+                                )
+                            } else {
+                                pane.close_all_items(
+                                    &CloseAllItems {
+                                        save_intent: Some(save_intent),
+                                        close_pinned: false,
+                                    },
+                                    window,
+                                    cx,
+                                )
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })?;
+            for task in tasks {
+                task.await?
             }
-        }
-
-        if tasks.is_empty() {
-            None
-        } else {
-            Some(cx.spawn_in(window, async move |_, _| {
-                for task in tasks {
-                    task.await?
-                }
-                Ok(())
-            }))
-        }
+            Ok(())
+        }))
     }
 
     pub fn is_dock_at_position_open(&self, position: DockPosition, cx: &mut Context<Self>) -> bool {
@@ -2500,57 +3506,218 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let dock = self.dock_at_position(dock_side);
-        let mut focus_center = false;
-        let mut reveal_dock = false;
-        dock.update(cx, |dock, cx| {
-            let other_is_zoomed = self.zoomed.is_some() && self.zoomed_position != Some(dock_side);
-            let was_visible = dock.is_open() && !other_is_zoomed;
-            dock.set_open(!was_visible, window, cx);
+        let dock = self.dock_at_position(dock_side).clone();
+        self.toggle_dock_entity(&dock, window, cx);
+    }
 
-            if dock.active_panel().is_none() && dock.panels_len() > 0 {
-                dock.activate_panel(0, window, cx);
-            }
+    pub fn toggle_bottom_dock_slot(
+        &mut self,
+        slot: BottomDockSlot,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let dock = match slot {
+            BottomDockSlot::Left => self.bottom_dock.clone(),
+            BottomDockSlot::Right => self.bottom_right_dock.clone(),
+        };
+        self.toggle_dock_entity(&dock, window, cx);
+    }
 
-            if let Some(active_panel) = dock.active_panel() {
-                if was_visible {
-                    if active_panel
-                        .panel_focus_handle(cx)
-                        .contains_focused(window, cx)
-                    {
-                        focus_center = true;
-                    }
-                } else {
-                    let focus_handle = &active_panel.panel_focus_handle(cx);
-                    window.focus(focus_handle);
-                    reveal_dock = true;
-                }
+    fn on_dock_event(
+        &mut self,
+        dock: &Entity<Dock>,
+        event: &DockEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            DockEvent::OpenChanged(open) => {
+                cx.emit(Event::DockToggled {
+                    position: dock.read(cx).position(),
+                    open: *open,
+                });
             }
-        });
-
-        if reveal_dock {
-            self.dismiss_zoomed_items_to_reveal(Some(dock_side), window, cx);
-        }
-
-        if focus_center {
-            self.active_pane
-                .update(cx, |pane, cx| window.focus(&pane.focus_handle(cx)))
+            DockEvent::CloseVetoed(panel) => {
+                self.show_panel_close_vetoed(panel.as_ref(), window, cx)
+            }
+            _ => {}
         }
+    }
 
-        cx.notify();
-        self.serialize_workspace(window, cx);
+    /// Tells the user that a panel they tried to close kept itself open.
+    fn show_panel_close_vetoed(
+        &mut self,
+        panel: &dyn PanelHandle,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        struct PanelCloseVetoed;
+        let name = panel
+            .icon_tooltip(window, cx)
+            .unwrap_or(panel.persistent_name());
+        self.show_toast(
+            Toast::new(
+                NotificationId::composite::<PanelCloseVetoed>(panel.persistent_name()),
+                format!("{name} can't be closed right now."),
+            )
+            .autohide(),
+            cx,
+        );
     }
 
-    pub fn close_all_docks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        for dock in self.all_docks() {
-            dock.update(cx, |dock, cx| {
-                dock.set_open(false, window, cx);
+    /// Shows the two bottom dock slots side by side, or hides the right one if both are
+    /// visible. If the right slot has no panels, a panel of the left slot other than its
+    /// active one is moved there.
+    pub fn split_bottom_dock(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let left_open = self.bottom_dock.read(cx).is_open();
+        if left_open && self.bottom_right_dock.read(cx).is_open() {
+            self.bottom_right_dock.update(cx, |dock, cx| {
+                dock.close(CloseReason::User, window, cx);
             });
+            self.serialize_workspace(window, cx);
+            cx.notify();
+            return;
         }
 
-        cx.focus_self(window);
-        cx.notify();
-        self.serialize_workspace(window, cx);
+        if self.bottom_right_dock.read(cx).panels_len() == 0 {
+            let bottom_dock = self.bottom_dock.read(cx);
+            let active_panel_id = bottom_dock.active_panel().map(|panel| panel.panel_id());
+            let Some(panel) = bottom_dock
+                .panels()
+                .find(|panel| Some(panel.panel_id()) != active_panel_id)
+                .cloned()
+            else {
+                return;
+            };
+            self.move_panel_to_bottom_slot(panel, BottomDockSlot::Right, window, cx);
+        }
+
+        for dock in [&self.bottom_dock, &self.bottom_right_dock] {
+            dock.update(cx, |dock, cx| {
+                if dock.panels_len() == 0 {
+                    return;
+                }
+                dock.set_open(true, window, cx);
+                if dock.active_panel().is_none() {
+                    dock.activate_panel(0, window, cx);
+                }
+            });
+        }
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    pub fn bottom_dock_split(&self) -> f32 {
+        self.bottom_dock_split
+    }
+
+    /// Sets the share of the bottom docks' width taken by the left slot.
+    pub fn set_bottom_dock_split(
+        &mut self,
+        split: f32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let split = split.clamp(MIN_BOTTOM_DOCK_SPLIT, 1. - MIN_BOTTOM_DOCK_SPLIT);
+        if split != self.bottom_dock_split {
+            self.bottom_dock_split = split;
+            self.serialize_workspace(window, cx);
+            cx.notify();
+        }
+    }
+
+    fn toggle_dock_entity(
+        &mut self,
+        dock: &Entity<Dock>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let dock_side = dock.read(cx).position();
+        let focus_on_toggle = WorkspaceSettings::get_global(cx)
+            .effective_dock_options()
+            .focus_on_toggle;
+        let mut focus_center = false;
+        let mut reveal_dock = false;
+        dock.update(cx, |dock, cx| {
+            let other_is_zoomed = self.zoomed.is_some() && self.zoomed_position != Some(dock_side);
+            let was_visible = dock.is_open() && !other_is_zoomed;
+            if was_visible {
+                if !dock.close(CloseReason::User, window, cx) {
+                    return;
+                }
+            } else {
+                dock.set_open(true, window, cx);
+            }
+
+            if dock.active_panel().is_none() && dock.panels_len() > 0 {
+                dock.activate_panel(0, window, cx);
+            }
+
+            if let Some(active_panel) = dock.active_panel() {
+                if was_visible {
+                    if active_panel
+                        .panel_focus_handle(cx)
+                        .contains_focused(window, cx)
+                    {
+                        focus_center = true;
+                    }
+                } else {
+                    if focus_on_toggle {
+                        window.focus(&active_panel.panel_focus_handle(cx));
+                    }
+                    reveal_dock = true;
+                }
+            }
+        });
+
+        if reveal_dock {
+            self.dismiss_zoomed_items_to_reveal(Some(dock_side), window, cx);
+        }
+
+        if focus_center {
+            self.active_pane
+                .update(cx, |pane, cx| window.focus(&pane.focus_handle(cx)))
+        }
+
+        cx.notify();
+        self.serialize_workspace(window, cx);
+    }
+
+    /// Moves keyboard focus to the next row of panel buttons in the status bar, from left to
+    /// right, wrapping around after the last one.
+    pub fn focus_panel_buttons(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let placement = WorkspaceSettings::get_global(cx)
+            .effective_dock_options()
+            .button_placement;
+        let (left_buttons, right_buttons) = placement.arrange(&self.panel_buttons);
+        // Both sides are ordered from the edge of the status bar inwards.
+        let panel_buttons = left_buttons
+            .into_iter()
+            .chain(right_buttons.into_iter().rev())
+            .collect::<Vec<_>>();
+        let focused_ix = panel_buttons
+            .iter()
+            .position(|buttons| buttons.focus_handle(cx).contains_focused(window, cx));
+        let start_ix = focused_ix.map_or(0, |ix| ix + 1);
+        let len = panel_buttons.len();
+        for offset in 0..len {
+            let buttons = panel_buttons[(start_ix + offset) % len].clone();
+            if buttons.update(cx, |buttons, cx| buttons.focus(window, cx)) {
+                break;
+            }
+        }
+    }
+
+    pub fn close_all_docks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for dock in self.all_docks() {
+            dock.update(cx, |dock, cx| {
+                dock.close(CloseReason::User, window, cx);
+            });
+        }
+
+        cx.focus_self(window);
+        cx.notify();
+        self.serialize_workspace(window, cx);
     }
 
     /// Transfer focus to the panel of the given type.
@@ -2563,6 +3730,48 @@ impl Workspace {
         panel.to_any().downcast().ok()
     }
 
+    /// Open and focus the panel of the given type if it isn't visible. If it is
+    /// visible, then close its dock, returning focus to the workspace center.
+    pub fn toggle_panel<T: Panel>(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<T>> {
+        let dock = self
+            .all_docks()
+            .into_iter()
+            .find(|dock| dock.read(cx).panel_index_for_type::<T>().is_some())?
+            .clone();
+        let panel = dock.read(cx).panel::<T>()?;
+        let is_visible = dock
+            .read(cx)
+            .visible_panel()
+            .map_or(false, |visible| visible.panel_id() == panel.entity_id());
+
+        if is_visible {
+            let was_focused = panel.focus_handle(cx).contains_focused(window, cx);
+            let closed = dock.update(cx, |dock, cx| dock.close(CloseReason::User, window, cx));
+            if closed && was_focused {
+                self.active_pane
+                    .update(cx, |pane, cx| window.focus(&pane.focus_handle(cx)));
+            }
+        } else {
+            dock.update(cx, |dock, cx| {
+                if let Some(panel_index) = dock.panel_index_for_type::<T>() {
+                    dock.activate_panel(panel_index, window, cx);
+                }
+                dock.set_open(true, window, cx);
+            });
+            window.focus(&panel.focus_handle(cx));
+            let position = dock.read(cx).position();
+            self.dismiss_zoomed_items_to_reveal(Some(position), window, cx);
+        }
+
+        cx.notify();
+        self.serialize_workspace(window, cx);
+        Some(panel)
+    }
+
     /// Focus the panel of the given type if it isn't already focused. If it is
     /// already focused, then transfer focus back to the workspace center.
     pub fn toggle_panel_focus<T: Panel>(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -2597,6 +3806,48 @@ impl Workspace {
         panel
     }
 
+    /// Opens and focuses the panel with the given persistent name, after moving it to the
+    /// given dock if it's docked elsewhere and can be placed there.
+    pub fn activate_panel_by_name(
+        &mut self,
+        name: &str,
+        position: Option<DockPosition>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Arc<dyn PanelHandle>> {
+        let Some((dock, panel_index)) = self.all_docks().into_iter().find_map(|dock| {
+            let index = dock.read(cx).panel_index_for_persistent_name(name, cx)?;
+            Some((dock.clone(), index))
+        }) else {
+            log::warn!("no panel named {name:?}");
+            return None;
+        };
+        let panel = dock.update(cx, |dock, cx| {
+            dock.activate_panel(panel_index, window, cx);
+            dock.set_open(true, window, cx);
+            dock.active_panel().cloned()
+        })?;
+
+        if let Some(position) = position {
+            if position != dock.read(cx).position() {
+                if panel.position_is_valid(position, cx) {
+                    // The open panel is moved along with its dock's visibility.
+                    panel.set_position(position, window, cx);
+                } else {
+                    log::warn!(
+                        "panel {name:?} can't be placed in the {} dock",
+                        position.label()
+                    );
+                }
+            }
+        }
+
+        panel.panel_focus_handle(cx).focus(window);
+        cx.notify();
+        self.serialize_workspace(window, cx);
+        Some(panel)
+    }
+
     /// Focus or unfocus the given panel type, depending on the given callback.
     fn focus_or_unfocus_panel<T: Panel>(
         &mut self,
@@ -2655,6 +3906,62 @@ impl Workspace {
         }
     }
 
+    /// Opens and focuses the panel of the given type, waiting for it to be added first if it
+    /// hasn't been yet. Panels are usually loaded asynchronously when the workspace opens,
+    /// so this lets commands that need a panel wait for it instead of racing its loading. Fails
+    /// if the panel wasn't added by the time [`Workspace::finish_panel_initialization`] is called.
+    pub fn open_panel_async<T: Panel>(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<T>>> {
+        // If the panel hasn't been added yet, it's docked when it is.
+        self.removed_panel_names.remove(T::persistent_name());
+        if let Some(removed_panel) = self
+            .removed_panels
+            .iter()
+            .find(|panel| panel.persistent_name() == T::persistent_name())
+        {
+            let panel_id = removed_panel.panel_id();
+            self.restore_removed_panel(panel_id, window, cx);
+        }
+
+        let panel_added = if self.panel::<T>(cx).is_some() {
+            None
+        } else if self.panels_initialized {
+            return Task::ready(Err(anyhow!(
+                "no {} panel was added to the workspace",
+                T::persistent_name()
+            )));
+        } else {
+            let (tx, rx) = oneshot::channel();
+            self.panel_waiters
+                .entry(T::persistent_name())
+                .or_default()
+                .push(tx);
+            Some(rx)
+        };
+
+        cx.spawn_in(window, async move |this, cx| {
+            if let Some(panel_added) = panel_added {
+                panel_added
+                    .await
+                    .context("panel wasn't added while the workspace loaded its panels")?;
+            }
+            this.update_in(cx, |this, window, cx| {
+                this.focus_panel::<T>(window, cx)
+                    .context("panel was removed before it could be opened")
+            })?
+        })
+    }
+
+    /// Records that the panels the workspace starts with were loaded, or failed to. Panels that
+    /// [`Workspace::open_panel_async`] is still waiting for won't be added anymore, so it fails.
+    pub fn finish_panel_initialization(&mut self) {
+        self.panels_initialized = true;
+        self.panel_waiters.clear();
+    }
+
     pub fn panel<T: Panel>(&self, cx: &App) -> Option<Entity<T>> {
         self.all_docks()
             .iter()
@@ -2698,7 +4005,7 @@ impl Workspace {
         if self.zoomed_position != dock_to_reveal {
             self.zoomed = None;
             self.zoomed_position = None;
-            cx.emit(Event::ZoomChanged);
+            self.zoom_changed(cx);
         }
 
         cx.notify();
@@ -2716,11 +4023,21 @@ impl Workspace {
                 cx,
             );
             pane.set_can_split(Some(Arc::new(|_, _, _, _| true)));
+            pane.set_close_pane_if_empty(
+                WorkspaceSettings::get_global(cx).auto_close_empty_panes,
+                cx,
+            );
             pane
         });
         cx.subscribe_in(&pane, window, Self::handle_pane_event)
             .detach();
         self.panes.push(pane.clone());
+        record_metric(
+            WorkspaceMetric::PaneCountChanged {
+                pane_count: self.panes.len(),
+            },
+            cx,
+        );
 
         window.focus(&pane.focus_handle(cx));
 
@@ -2804,6 +4121,60 @@ impl Workspace {
         self.add_item(new_pane, item, None, true, true, window, cx);
     }
 
+    /// Adds the item to the workspace at the given placement and focuses it.
+    pub fn open_item_with_placement(
+        &mut self,
+        item: Box<dyn ItemHandle>,
+        placement: Placement,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        match placement {
+            Placement::ActivePane => {
+                self.add_item_to_active_pane(item, None, true, window, cx);
+            }
+            Placement::SplitRight => {
+                self.split_item(SplitDirection::Right, item, window, cx);
+            }
+            Placement::PaneIndex(ix) => {
+                let pane = match self.center.panes().get(ix) {
+                    Some(pane) => (*pane).clone(),
+                    None => {
+                        self.split_pane(self.active_pane.clone(), SplitDirection::Right, window, cx)
+                    }
+                };
+                self.add_item(pane, item, None, true, true, window, cx);
+            }
+            Placement::NewWindow => {
+                let open_window = self.open_window_for_project(cx);
+                return cx.spawn(async move |_, cx| {
+                    let new_window = open_window.await?;
+                    new_window.update(cx, |workspace, window, cx| {
+                        workspace.add_item_to_active_pane(item, None, true, window, cx);
+                        window.activate_window();
+                    })?;
+                    Ok(())
+                });
+            }
+            Placement::Dock(position) => {
+                let dock = self.dock_at_position(position).clone();
+                let Some(pane) = dock
+                    .read(cx)
+                    .active_panel()
+                    .and_then(|panel| panel.pane(cx))
+                else {
+                    return Task::ready(Err(anyhow!(
+                        "the {} dock has no pane to open items in",
+                        position.label()
+                    )));
+                };
+                dock.update(cx, |dock, cx| dock.set_open(true, window, cx));
+                self.add_item(pane, item, None, true, true, window, cx);
+            }
+        }
+        Task::ready(Ok(()))
+    }
+
     pub fn open_abs_path(
         &mut self,
         abs_path: PathBuf,
@@ -2881,27 +4252,119 @@ impl Workspace {
             })
         });
 
-        let task = self.load_path(path.into(), window, cx);
+        let project_path = path.into();
+        let task = self.load_path(project_path.clone(), window, cx);
+        let workspace = self.weak_self.clone();
         window.spawn(cx, async move |cx| {
-            let (project_entry_id, build_item) = task.await?;
-            let result = pane.update_in(cx, |pane, window, cx| {
-                let result = pane.open_item(
+            let mut task = task.fuse();
+            let mut timer = cx
+                .background_executor()
+                .timer(LOADING_PLACEHOLDER_DELAY)
+                .fuse();
+            let loaded = futures::select_biased! {
+                loaded = task => Some(loaded),
+                _ = timer => None,
+            };
+
+            // Loading is slow, show a placeholder tab until it's done.
+            let mut loading_item = None;
+            let loaded = match loaded {
+                Some(loaded) => loaded,
+                None => {
+                    let placeholder = pane.update_in(cx, |pane, window, cx| {
+                        Self::add_loading_item(pane, project_path.clone(), activate, window, cx)
+                    })?;
+                    match placeholder {
+                        Some((item, mut closed)) => {
+                            loading_item = Some(item);
+                            futures::select_biased! {
+                                loaded = task => loaded,
+                                _ = closed => {
+                                    return Err(anyhow!(
+                                        "{:?} was closed before it finished loading",
+                                        project_path.path
+                                    ));
+                                }
+                            }
+                        }
+                        None => task.await,
+                    }
+                }
+            };
+
+            // Swap the placeholder for the item, in whichever pane it was moved to.
+            let mut pane = pane.upgrade();
+            let mut placeholder = None;
+            if let Some(loading_item) = loading_item.and_then(|item| item.upgrade()) {
+                let placeholder_pane = workspace.update(cx, |workspace, cx| {
+                    workspace
+                        .panes()
+                        .iter()
+                        .find(|pane| pane.read(cx).index_for_item(&loading_item).is_some())
+                        .cloned()
+                })?;
+                if let Some(placeholder_pane) = placeholder_pane {
+                    placeholder = placeholder_pane.update_in(cx, |pane, window, cx| {
+                        let ix = pane.index_for_item(&loading_item)?;
+                        let was_active = pane.active_item_index() == ix;
+                        pane.remove_item(loading_item.item_id(), false, false, window, cx);
+                        Some((ix, was_active))
+                    })?;
+                    pane = Some(placeholder_pane);
+                }
+            }
+
+            let (project_entry_id, build_item) = loaded?;
+            let pane = pane.context("pane was dropped")?;
+            pane.update_in(cx, |pane, window, cx| {
+                pane.open_item(
                     project_entry_id,
                     focus_item,
                     allow_preview,
-                    activate,
-                    None,
+                    activate || placeholder.map_or(false, |(_, was_active)| was_active),
+                    placeholder.map(|(ix, _)| ix),
                     window,
                     cx,
                     build_item,
-                );
-
-                result
-            });
-            result
+                )
+            })
         })
     }
 
+    /// Adds a [`LoadingItem`] for the given file to the pane, unless the file is already open
+    /// or loading in it.
+    fn add_loading_item(
+        pane: &mut Pane,
+        project_path: ProjectPath,
+        activate: bool,
+        window: &mut Window,
+        cx: &mut Context<Pane>,
+    ) -> Option<(WeakEntity<LoadingItem>, oneshot::Receiver<()>)> {
+        let is_loading = pane
+            .items_of_type::<LoadingItem>()
+            .any(|item| item.read(cx).project_path() == &project_path);
+        if is_loading || pane.item_for_path(project_path.clone(), cx).is_some() {
+            return None;
+        }
+
+        let mut closed = None;
+        let loading_item = cx.new(|cx| {
+            let (loading_item, closed_rx) = LoadingItem::new(project_path, cx);
+            closed = Some(closed_rx);
+            loading_item
+        });
+        pane.add_item_inner(
+            Box::new(loading_item.clone()),
+            false,
+            false,
+            activate,
+            None,
+            window,
+            cx,
+        );
+        Some((loading_item.downgrade(), closed?))
+    }
+
     pub fn split_path(
         &mut self,
         path: impl Into<ProjectPath>,
@@ -3162,6 +4625,7 @@ impl Workspace {
             LeftDock,
             RightDock,
             BottomDock,
+            BottomRightDock,
             Center,
         }
 
@@ -3169,6 +4633,7 @@ impl Workspace {
             (&self.left_dock, Origin::LeftDock),
             (&self.right_dock, Origin::RightDock),
             (&self.bottom_dock, Origin::BottomDock),
+            (&self.bottom_right_dock, Origin::BottomRightDock),
         ]
         .into_iter()
         .find_map(|(dock, origin)| {
@@ -3196,6 +4661,8 @@ impl Workspace {
 
         let try_dock =
             |dock: &Entity<Dock>| dock.read(cx).is_open().then(|| Target::Dock(dock.clone()));
+        let try_bottom_docks =
+            || try_dock(&self.bottom_dock).or_else(|| try_dock(&self.bottom_right_dock));
 
         let target = match (origin, direction) {
             // We're in the center, so we first try to go to a different pane,
@@ -3206,7 +4673,7 @@ impl Workspace {
                 } else {
                     match direction {
                         SplitDirection::Up => None,
-                        SplitDirection::Down => try_dock(&self.bottom_dock),
+                        SplitDirection::Down => try_bottom_docks(),
                         SplitDirection::Left => try_dock(&self.left_dock),
                         SplitDirection::Right => try_dock(&self.right_dock),
                     }
@@ -3217,27 +4684,67 @@ impl Workspace {
                 if let Some(last_active_pane) = get_last_active_pane() {
                     Some(Target::Pane(last_active_pane))
                 } else {
-                    try_dock(&self.bottom_dock).or_else(|| try_dock(&self.right_dock))
+                    try_bottom_docks().or_else(|| try_dock(&self.right_dock))
                 }
             }
 
-            (Origin::LeftDock, SplitDirection::Down)
-            | (Origin::RightDock, SplitDirection::Down) => try_dock(&self.bottom_dock),
+            (Origin::LeftDock, SplitDirection::Down) => try_bottom_docks(),
+            (Origin::RightDock, SplitDirection::Down) => {
+                try_dock(&self.bottom_right_dock).or_else(|| try_dock(&self.bottom_dock))
+            }
 
-            (Origin::BottomDock, SplitDirection::Up) => get_last_active_pane().map(Target::Pane),
+            (Origin::BottomDock | Origin::BottomRightDock, SplitDirection::Up) => {
+                get_last_active_pane().map(Target::Pane)
+            }
             (Origin::BottomDock, SplitDirection::Left) => try_dock(&self.left_dock),
-            (Origin::BottomDock, SplitDirection::Right) => try_dock(&self.right_dock),
+            (Origin::BottomDock, SplitDirection::Right) => {
+                try_dock(&self.bottom_right_dock).or_else(|| try_dock(&self.right_dock))
+            }
+            (Origin::BottomRightDock, SplitDirection::Left) => {
+                try_dock(&self.bottom_dock).or_else(|| try_dock(&self.left_dock))
+            }
+            (Origin::BottomRightDock, SplitDirection::Right) => try_dock(&self.right_dock),
 
             (Origin::RightDock, SplitDirection::Left) => {
                 if let Some(last_active_pane) = get_last_active_pane() {
                     Some(Target::Pane(last_active_pane))
                 } else {
-                    try_dock(&self.bottom_dock).or_else(|| try_dock(&self.left_dock))
+                    try_bottom_docks().or_else(|| try_dock(&self.left_dock))
                 }
             }
 
             _ => None,
         };
+        let target = target.or_else(|| {
+            if !WorkspaceSettings::get_global(cx).wrap_pane_navigation {
+                return None;
+            }
+            // Wrap around to whatever is farthest away on the opposite side.
+            let farthest_pane = || {
+                let mut pane = self
+                    .last_active_center_pane
+                    .as_ref()
+                    .and_then(|pane| pane.upgrade())
+                    .unwrap_or_else(|| self.active_pane.clone());
+                for _ in 0..self.panes.len() {
+                    match self
+                        .center
+                        .find_pane_in_direction(&pane, direction.opposite(), cx)
+                    {
+                        Some(next) => pane = next.clone(),
+                        None => break,
+                    }
+                }
+                Target::Pane(pane)
+            };
+            let target = match direction {
+                SplitDirection::Left => try_dock(&self.right_dock),
+                SplitDirection::Right => try_dock(&self.left_dock),
+                SplitDirection::Up => try_bottom_docks(),
+                SplitDirection::Down => None,
+            };
+            Some(target.unwrap_or_else(farthest_pane))
+        });
 
         match target {
             Some(ActivateInDirectionTarget::Pane(pane)) => {
@@ -3276,37 +4783,161 @@ impl Workspace {
         }
     }
 
-    pub fn bounding_box_for_pane(&self, pane: &Entity<Pane>) -> Option<Bounds<Pixels>> {
-        self.center.bounding_box_for_pane(pane)
-    }
-
-    pub fn find_pane_in_direction(
-        &mut self,
-        direction: SplitDirection,
-        cx: &App,
-    ) -> Option<Entity<Pane>> {
-        self.center
-            .find_pane_in_direction(&self.active_pane, direction, cx)
-            .cloned()
-    }
-
-    pub fn swap_pane_in_direction(&mut self, direction: SplitDirection, cx: &mut Context<Self>) {
-        if let Some(to) = self.find_pane_in_direction(direction, cx) {
-            self.center.swap(&self.active_pane, &to);
-            cx.notify();
-        }
-    }
-
-    pub fn resize_pane(
+    /// Moves the active item into a new window for the same project. Items that support
+    /// serialization carry their state over, and other items are reopened from their path.
+    pub fn move_active_item_to_new_window(
         &mut self,
-        axis: gpui::Axis,
-        amount: Pixels,
         window: &mut Window,
         cx: &mut Context<Self>,
-    ) {
-        let docks = self.all_docks();
-        let active_dock = docks
-            .into_iter()
+    ) -> Task<Result<WindowHandle<Workspace>>> {
+        let pane = self.active_pane.clone();
+        let Some(item) = pane.read(cx).active_item() else {
+            return Task::ready(Err(anyhow!("no active item to move")));
+        };
+        let item_id = item.item_id();
+        let open_in_new_window = self.open_item_in_new_window(item, window, cx);
+        cx.spawn_in(window, async move |_, cx| {
+            let new_window = open_in_new_window.await?;
+            pane.update_in(cx, |pane, window, cx| {
+                pane.remove_item(item_id, false, true, window, cx)
+            })?;
+            Ok(new_window)
+        })
+    }
+
+    /// Opens a new window with an empty workspace for the same project.
+    fn open_window_for_project(
+        &self,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<WindowHandle<Workspace>>> {
+        let project = self.project.clone();
+        let app_state = self.app_state.clone();
+        cx.spawn(async move |_, cx| {
+            let new_workspace_id = DB.next_id().await.log_err();
+            cx.update(|cx| {
+                let options = (app_state.build_window_options)(None, cx);
+                cx.open_window(options, |window, cx| {
+                    cx.new(|cx| Workspace::new(new_workspace_id, project, app_state, window, cx))
+                })
+            })?
+        })
+    }
+
+    /// Opens a copy of the item in a new window for the same project, leaving this workspace
+    /// as is.
+    fn open_item_in_new_window(
+        &mut self,
+        item: Box<dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<WindowHandle<Workspace>>> {
+        let serializable_item = item.to_serializable_item_handle(cx);
+        let project_path = item.project_path(cx);
+        if serializable_item.is_none() && (project_path.is_none() || item.is_dirty(cx)) {
+            return Task::ready(Err(anyhow!("the item can't be opened in a new window")));
+        }
+
+        let item_id = item.item_id();
+        cx.spawn_in(window, async move |this, cx| {
+            let new_window = this
+                .update(cx, |this, cx| this.open_window_for_project(cx))?
+                .await?;
+
+            new_window
+                .update(cx, |workspace, window, cx| {
+                    // The item is stored under the new workspace, which it is restored into.
+                    let serialized = workspace.database_id().zip(serializable_item).and_then(
+                        |(workspace_id, serializable_item)| {
+                            let task = serializable_item.serialize(workspace, false, window, cx)?;
+                            Some((workspace_id, serializable_item.serialized_item_kind(), task))
+                        },
+                    );
+                    match (serialized, project_path) {
+                        (Some((workspace_id, kind, serialize_task)), _) => {
+                            cx.spawn_in(window, async move |workspace, cx| {
+                                serialize_task.await?;
+                                let task = workspace.update_in(cx, |workspace, window, cx| {
+                                    let project = workspace.project.clone();
+                                    let workspace_handle = workspace.weak_handle();
+                                    workspace.active_pane.update(cx, |_, cx| {
+                                        SerializableItemRegistry::deserialize(
+                                            kind,
+                                            project,
+                                            workspace_handle,
+                                            workspace_id,
+                                            item_id.as_u64(),
+                                            window,
+                                            cx,
+                                        )
+                                    })
+                                })?;
+                                let item = task.await?;
+                                workspace.update_in(cx, |workspace, window, cx| {
+                                    workspace.add_item_to_active_pane(
+                                        item.boxed_clone(),
+                                        None,
+                                        true,
+                                        window,
+                                        cx,
+                                    );
+                                })?;
+                                anyhow::Ok(item)
+                            })
+                        }
+                        (None, Some(project_path)) => {
+                            workspace.open_path(project_path, None, true, window, cx)
+                        }
+                        (None, None) => {
+                            Task::ready(Err(anyhow!("the item can't be opened in a new window")))
+                        }
+                    }
+                })?
+                .await?;
+
+            new_window.update(cx, |_, window, _| window.activate_window())?;
+            Ok(new_window)
+        })
+    }
+
+    pub fn bounding_box_for_pane(&self, pane: &Entity<Pane>) -> Option<Bounds<Pixels>> {
+        self.center.bounding_box_for_pane(pane)
+    }
+
+    pub fn find_pane_in_direction(
+        &mut self,
+        direction: SplitDirection,
+        cx: &App,
+    ) -> Option<Entity<Pane>> {
+        self.center
+            .find_pane_in_direction(&self.active_pane, direction, cx)
+            .cloned()
+    }
+
+    /// Exchanges the positions of the active pane and its neighbor in the given direction.
+    /// The panes keep their items, so focus stays on the active item.
+    pub fn swap_pane_in_direction(
+        &mut self,
+        direction: SplitDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(to) = self.find_pane_in_direction(direction, cx) {
+            self.center.swap(&self.active_pane, &to);
+            self.serialize_workspace(window, cx);
+            cx.notify();
+        }
+    }
+
+    pub fn resize_pane(
+        &mut self,
+        axis: gpui::Axis,
+        amount: Pixels,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let docks = self.all_docks();
+        let active_dock = docks
+            .into_iter()
             .find(|dock| dock.focus_handle(cx).contains_focused(window, cx));
 
         if let Some(dock) = active_dock {
@@ -3325,11 +4956,55 @@ impl Workspace {
         cx.notify();
     }
 
+    /// Snaps the size a dock is being dragged to onto one of its panel's preferred sizes, or
+    /// onto a quarter, a third or half of the workspace, when one is close enough.
+    fn snap_dock_size(
+        &self,
+        position: DockPosition,
+        size: Pixels,
+        window: &Window,
+        cx: &App,
+    ) -> Pixels {
+        let (docks, available_size) = match position {
+            DockPosition::Left => (vec![&self.left_dock], self.bounds.size.width),
+            DockPosition::Right => (vec![&self.right_dock], self.bounds.size.width),
+            // Both bottom docks are resized together, so either one's panel can be snapped to.
+            DockPosition::Bottom => (
+                vec![&self.bottom_dock, &self.bottom_right_dock],
+                self.bounds.size.height,
+            ),
+        };
+        let preferred_sizes = docks
+            .into_iter()
+            .flat_map(|dock| dock.read(cx).preferred_sizes(window, cx))
+            .collect::<Vec<_>>();
+        dock::snap_size(size, available_size, &preferred_sizes)
+    }
+
     pub fn reset_pane_sizes(&mut self, cx: &mut Context<Self>) {
         self.center.reset_pane_sizes();
         cx.notify();
     }
 
+    /// Equalizes the sizes of the active pane and its siblings in the split that contains it.
+    pub fn reset_active_axis_sizes(&mut self, cx: &mut Context<Self>) {
+        self.center.reset_axis_sizes(&self.active_pane);
+        cx.notify();
+    }
+
+    /// Grows the active pane (or the focused dock) along the given axis by
+    /// `pane_resize_step` pixels, or shrinks it when `direction` is negative.
+    fn resize_pane_by_step(
+        &mut self,
+        axis: gpui::Axis,
+        direction: f32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let step = px(WorkspaceSettings::get_global(cx).pane_resize_step);
+        self.resize_pane(axis, step * direction, window, cx);
+    }
+
     fn handle_pane_focused(
         &mut self,
         pane: Entity<Pane>,
@@ -3341,6 +5016,9 @@ impl Workspace {
         self.status_bar.update(cx, |status_bar, cx| {
             status_bar.set_active_pane(&pane, window, cx);
         });
+        self.secondary_status_bar.update(cx, |status_bar, cx| {
+            status_bar.set_active_pane(&pane, window, cx);
+        });
         if self.active_pane != pane {
             self.set_active_pane(&pane, window, cx);
         }
@@ -3356,15 +5034,33 @@ impl Workspace {
             self.zoomed = None;
         }
         self.zoomed_position = None;
-        cx.emit(Event::ZoomChanged);
+        self.zoom_changed(cx);
         self.update_active_view_for_followers(window, cx);
         pane.update(cx, |pane, _| {
             pane.track_alternate_file_items();
         });
+        if self.panes.contains(&pane) {
+            self.auto_hide_docks(window, cx);
+        }
 
         cx.notify();
     }
 
+    /// Closes the open docks when they're configured to hide once a center pane is focused.
+    fn auto_hide_docks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !WorkspaceSettings::get_global(cx)
+            .effective_dock_options()
+            .auto_hide
+        {
+            return;
+        }
+        for dock in self.all_docks() {
+            dock.update(cx, |dock, cx| {
+                dock.close(CloseReason::Automatic, window, cx);
+            });
+        }
+    }
+
     fn set_active_pane(
         &mut self,
         pane: &Entity<Pane>,
@@ -3374,6 +5070,15 @@ impl Workspace {
         self.active_pane = pane.clone();
         self.active_item_path_changed(window, cx);
         self.last_active_center_pane = Some(pane.downgrade());
+        // Activating one of the hidden panes restores the split layout.
+        if self
+            .maximized_pane
+            .as_ref()
+            .is_some_and(|maximized| *maximized != pane.downgrade() && self.panes.contains(pane))
+        {
+            self.maximized_pane = None;
+            cx.notify();
+        }
     }
 
     fn handle_panel_focused(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -3388,6 +5093,19 @@ impl Workspace {
         cx: &mut Context<Self>,
     ) {
         let mut serialize_workspace = true;
+        if matches!(
+            event,
+            pane::Event::AddItem { .. }
+                | pane::Event::RemovedItem { .. }
+                | pane::Event::Split(_)
+                | pane::Event::JoinIntoNext
+                | pane::Event::JoinAll
+                | pane::Event::Remove { .. }
+        ) {
+            // The user changed the center layout, so it replaces the stored one that couldn't
+            // be read instead of being dropped on every save.
+            self.center_group_unreadable = false;
+        }
         match event {
             pane::Event::AddItem { item } => {
                 item.added_to_pane(self, pane.clone(), window, cx);
@@ -3421,6 +5139,12 @@ impl Workspace {
                 if *local {
                     self.unfollow_in_pane(&pane, window, cx);
                 }
+                if let Some(item) = pane.read(cx).active_item() {
+                    cx.emit(Event::ItemActivated {
+                        pane: pane.clone(),
+                        item,
+                    });
+                }
                 if pane == self.active_pane() {
                     self.active_item_path_changed(window, cx);
                     self.update_active_view_for_followers(window, cx);
@@ -3443,6 +5167,9 @@ impl Workspace {
             }
             pane::Event::RemoveItem { .. } => {}
             pane::Event::RemovedItem { item } => {
+                cx.emit(Event::ItemRemoved {
+                    item_id: item.item_id(),
+                });
                 cx.emit(Event::ActiveItemChanged);
                 self.update_window_edited(window, cx);
                 if let hash_map::Entry::Occupied(entry) = self.panes_by_item.entry(item.item_id()) {
@@ -3463,7 +5190,7 @@ impl Workspace {
                     if pane.read(cx).has_focus(window, cx) {
                         self.zoomed = Some(pane.downgrade().into());
                         self.zoomed_position = None;
-                        cx.emit(Event::ZoomChanged);
+                        self.zoom_changed(cx);
                     }
                     cx.notify();
                 }
@@ -3472,7 +5199,7 @@ impl Workspace {
                 pane.update(cx, |pane, cx| pane.set_zoomed(false, cx));
                 if self.zoomed_position.is_none() {
                     self.zoomed = None;
-                    cx.emit(Event::ZoomChanged);
+                    self.zoom_changed(cx);
                 }
                 cx.notify();
             }
@@ -3494,6 +5221,19 @@ impl Workspace {
         Some(leader_id)
     }
 
+    /// Adds a pane to be split off the given one, starting with a copy of its context.
+    fn add_split_pane(
+        &mut self,
+        pane_to_split: &Entity<Pane>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Pane> {
+        let context = pane_to_split.read(cx).serialized_context().clone();
+        let new_pane = self.add_pane(window, cx);
+        new_pane.update(cx, |pane, _| pane.set_serialized_context(context));
+        new_pane
+    }
+
     pub fn split_pane(
         &mut self,
         pane_to_split: Entity<Pane>,
@@ -3501,7 +5241,7 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Entity<Pane> {
-        let new_pane = self.add_pane(window, cx);
+        let new_pane = self.add_split_pane(&pane_to_split, window, cx);
         self.center
             .split(&pane_to_split, &new_pane, split_direction)
             .unwrap();
@@ -3509,6 +5249,134 @@ impl Workspace {
         new_pane
     }
 
+    /// Schedules focusing a center pane that the mouse moved over, if `focus_follows_mouse`
+    /// is enabled. The pane is focused once the mouse has rested over it for the debounce delay.
+    pub(crate) fn center_pane_hovered(
+        &mut self,
+        pane: &Entity<Pane>,
+        event: &MouseMoveEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let focus_handle = pane.focus_handle(cx);
+        self.schedule_hover_focus(pane.entity_id(), focus_handle, event, window, cx);
+    }
+
+    /// Like [`Self::center_pane_hovered`], for the active panel of a dock. Only applies when
+    /// `focus_follows_mouse.include_docks` is enabled too.
+    pub(crate) fn dock_hovered(
+        &mut self,
+        dock: &Entity<Dock>,
+        event: &MouseMoveEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let focus_handle = WorkspaceSettings::get_global(cx)
+            .focus_follows_mouse
+            .include_docks
+            .then(|| dock.read(cx).active_panel())
+            .flatten()
+            .map(|panel| panel.panel_focus_handle(cx));
+        match focus_handle {
+            Some(focus_handle) => {
+                self.schedule_hover_focus(dock.entity_id(), focus_handle, event, window, cx)
+            }
+            None => self.pending_hover_focus = None,
+        }
+    }
+
+    fn schedule_hover_focus(
+        &mut self,
+        hovered_id: EntityId,
+        focus_handle: FocusHandle,
+        event: &MouseMoveEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let settings = WorkspaceSettings::get_global(cx).focus_follows_mouse;
+        // Don't steal focus in the middle of a selection or a drag.
+        if !settings.enabled
+            || event.pressed_button.is_some()
+            || cx.has_active_drag()
+            || focus_handle.contains_focused(window, cx)
+        {
+            self.pending_hover_focus = None;
+            return;
+        }
+        if self
+            .pending_hover_focus
+            .as_ref()
+            .is_some_and(|(id, _)| *id == hovered_id)
+        {
+            return;
+        }
+
+        let debounce = Duration::from_millis(settings.debounce_ms);
+        let focus_handle = focus_handle.downgrade();
+        let task = cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(debounce).await;
+            this.update_in(cx, |this, window, cx| {
+                this.pending_hover_focus = None;
+                if this.modal_layer.read(cx).has_active_modal() {
+                    return;
+                }
+                if let Some(focus_handle) = focus_handle.upgrade() {
+                    window.focus(&focus_handle);
+                }
+            })
+            .ok();
+        });
+        self.pending_hover_focus = Some((hovered_id, task));
+    }
+
+    /// Cancels focusing the center pane or dock with the given id, which the mouse left.
+    pub(crate) fn hover_focus_target_left(&mut self, hovered_id: EntityId) {
+        if self
+            .pending_hover_focus
+            .as_ref()
+            .is_some_and(|(id, _)| *id == hovered_id)
+        {
+            self.pending_hover_focus = None;
+        }
+    }
+
+    /// Splits `pane_to_split`, giving `ratio` of its space to the new, empty pane.
+    pub fn split_pane_with_ratio(
+        &mut self,
+        pane_to_split: Entity<Pane>,
+        split_direction: SplitDirection,
+        ratio: f32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Pane> {
+        let new_pane = self.add_split_pane(&pane_to_split, window, cx);
+        self.center
+            .split_with_ratio(&pane_to_split, &new_pane, split_direction, ratio)
+            .unwrap();
+        cx.notify();
+        new_pane
+    }
+
+    fn split_active_pane_with_ratio(
+        &mut self,
+        direction: SplitDirection,
+        ratio: f32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let pane = self.active_pane.clone();
+        let clone = pane
+            .read(cx)
+            .active_item()
+            .and_then(|item| item.clone_on_split(self.database_id(), window, cx));
+        let new_pane = self.split_pane_with_ratio(pane, direction, ratio, window, cx);
+        if let Some(clone) = clone {
+            new_pane.update(cx, |pane, cx| {
+                pane.add_item(clone, true, true, None, window, cx)
+            });
+        }
+    }
+
     pub fn split_and_clone(
         &mut self,
         pane: Entity<Pane>,
@@ -3519,7 +5387,7 @@ impl Workspace {
         let item = pane.read(cx).active_item()?;
         let maybe_pane_handle =
             if let Some(clone) = item.clone_on_split(self.database_id(), window, cx) {
-                let new_pane = self.add_pane(window, cx);
+                let new_pane = self.add_split_pane(&pane, window, cx);
                 new_pane.update(cx, |pane, cx| {
                     pane.add_item(clone, true, true, None, window, cx)
                 });
@@ -3548,7 +5416,7 @@ impl Workspace {
             return;
         };
 
-        let new_pane = self.add_pane(window, cx);
+        let new_pane = self.add_split_pane(&pane_to_split, window, cx);
         move_item(&from, &new_pane, item_id_to_move, 0, window, cx);
         self.center
             .split(&pane_to_split, &new_pane, split_direction)
@@ -3556,6 +5424,86 @@ impl Workspace {
         cx.notify();
     }
 
+    /// Moves the item into a new pane along an edge of the center, spanning its full width or
+    /// height.
+    pub fn split_center_edge_with_item(
+        &mut self,
+        from: &Entity<Pane>,
+        item_id_to_move: EntityId,
+        direction: SplitDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Pane> {
+        let new_pane = self.add_split_pane(from, window, cx);
+        move_item(from, &new_pane, item_id_to_move, 0, window, cx);
+        self.center.split_edge(&new_pane, direction);
+        cx.notify();
+        new_pane
+    }
+
+    fn handle_center_drag_move(
+        &mut self,
+        event: &DragMoveEvent<DraggedTab>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let bounds = event.bounds;
+        let position = event.event.position;
+        // With a single pane, dropping on the pane's own edges splits the whole center.
+        let edge = if self.center.panes().len() > 1 && bounds.contains(&position) {
+            SplitDirection::all()
+                .into_iter()
+                .map(|direction| {
+                    (
+                        direction,
+                        (direction.edge(bounds) - position.along(direction.axis())).abs(),
+                    )
+                })
+                .filter(|(_, distance)| *distance < CENTER_EDGE_DROP_SIZE)
+                .min_by(|(_, a), (_, b)| a.0.total_cmp(&b.0))
+                .map(|(direction, _)| direction)
+        } else {
+            None
+        };
+        self.set_center_drag_edge(edge, cx);
+    }
+
+    fn set_center_drag_edge(&mut self, edge: Option<SplitDirection>, cx: &mut Context<Self>) {
+        if edge != self.center_drag_edge {
+            self.center_drag_edge = edge;
+            cx.notify();
+        }
+    }
+
+    /// Previews the pane a tab dropped on an edge of the center would be moved to.
+    fn render_center_edge_drop_target(&self, cx: &mut Context<Self>) -> Option<Div> {
+        let direction = self.center_drag_edge.filter(|_| cx.has_active_drag())?;
+        let size = relative(self.center.edge_split_fraction(direction));
+        let target = div()
+            .absolute()
+            .occlude()
+            .invisible()
+            .bg(cx.theme().colors().drop_target_background)
+            .drag_over::<DraggedTab>(|style, _, _, _| style.visible())
+            .can_drop(|dragged, _, _| dragged.is::<DraggedTab>())
+            .on_drop(
+                cx.listener(move |this, dragged_tab: &DraggedTab, window, cx| {
+                    this.set_center_drag_edge(None, cx);
+                    let from = dragged_tab.pane.clone();
+                    let item_id = dragged_tab.item.item_id();
+                    cx.defer_in(window, move |this, window, cx| {
+                        this.split_center_edge_with_item(&from, item_id, direction, window, cx);
+                    });
+                }),
+            );
+        Some(match direction {
+            SplitDirection::Up => target.top_0().left_0().right_0().h(size),
+            SplitDirection::Down => target.bottom_0().left_0().right_0().h(size),
+            SplitDirection::Left => target.top_0().bottom_0().left_0().w(size),
+            SplitDirection::Right => target.top_0().bottom_0().right_0().w(size),
+        })
+    }
+
     pub fn split_pane_with_project_entry(
         &mut self,
         pane_to_split: WeakEntity<Pane>,
@@ -3565,7 +5513,7 @@ impl Workspace {
         cx: &mut Context<Self>,
     ) -> Option<Task<Result<()>>> {
         let pane_to_split = pane_to_split.upgrade()?;
-        let new_pane = self.add_pane(window, cx);
+        let new_pane = self.add_split_pane(&pane_to_split, window, cx);
         self.center
             .split(&pane_to_split, &new_pane, split_direction)
             .unwrap();
@@ -3614,10 +5562,30 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        // Hand focus over to a neighboring pane rather than an arbitrary one.
+        let focus_on = focus_on.or_else(|| {
+            if self.active_pane != pane {
+                return None;
+            }
+            [
+                SplitDirection::Left,
+                SplitDirection::Up,
+                SplitDirection::Right,
+                SplitDirection::Down,
+            ]
+            .into_iter()
+            .find_map(|direction| {
+                self.center
+                    .find_pane_in_direction(&pane, direction, cx)
+                    .cloned()
+            })
+        });
         if self.center.remove(&pane).unwrap() {
             self.force_remove_pane(&pane, &focus_on, window, cx);
             self.unfollow_in_pane(&pane, window, cx);
             self.last_leaders_by_pane.remove(&pane.downgrade());
+            self.collaborator_panes
+                .retain(|_, collaborator_pane| *collaborator_pane != pane.downgrade());
             for removed_item in pane.read(cx).items() {
                 self.panes_by_item.remove(&removed_item.item_id());
             }
@@ -3626,7 +5594,7 @@ impl Workspace {
         } else {
             self.active_item_path_changed(window, cx);
         }
-        cx.emit(Event::PaneRemoved);
+        cx.emit(Event::PaneRemoved(pane.entity_id()));
     }
 
     pub fn panes(&self) -> &[Entity<Pane>] {
@@ -3637,6 +5605,48 @@ impl Workspace {
         &self.active_pane
     }
 
+    /// Describes the docks and the tree of center panes as text, for snapshot tests of the
+    /// layout. The output only depends on the arrangement, not on the window size:
+    ///
+    /// ```text
+    /// left dock: open, ProjectPanel
+    /// bottom dock: closed, TerminalPanel
+    /// bottom right dock: closed
+    /// right dock: closed
+    /// center:
+    ///   horizontal
+    ///     pane [main.rs, *lib.rs]
+    ///     pane* [*README.md] zoomed
+    /// ```
+    ///
+    /// Docks show their active panel and whether it is zoomed. Panes list the tab text of
+    /// their items, with the active item marked by a `*`, and the active pane by `pane*`.
+    pub fn layout_debug_string(&self, window: &Window, cx: &App) -> String {
+        let mut output = String::new();
+        for dock in [
+            &self.left_dock,
+            &self.bottom_dock,
+            &self.bottom_right_dock,
+            &self.right_dock,
+        ] {
+            let dock = dock.read(cx);
+            let state = if dock.is_open() { "open" } else { "closed" };
+            write!(output, "{} dock: {state}", dock.label()).unwrap();
+            if let Some(panel) = dock.active_panel() {
+                write!(output, ", {}", panel.persistent_name()).unwrap();
+                if panel.is_zoomed(window, cx) {
+                    output.push_str(", zoomed");
+                }
+            }
+            output.push('\n');
+        }
+        output.push_str("center:\n");
+        self.center
+            .root
+            .write_debug_string(&self.active_pane, 1, &mut output, window, cx);
+        output
+    }
+
     pub fn focused_pane(&self, window: &Window, cx: &App) -> Entity<Pane> {
         for dock in self.all_docks() {
             if dock.focus_handle(cx).contains_focused(window, cx) {
@@ -3847,16 +5857,134 @@ impl Workspace {
         self.follower_states.contains_key(&peer_id)
     }
 
-    fn active_item_path_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        cx.emit(Event::ActiveItemChanged);
-        let active_entry = self.active_project_path(cx);
-        self.project
-            .update(cx, |project, cx| project.set_active_path(active_entry, cx));
-
-        self.update_window_title(window, cx);
-    }
+    /// Outlines the given pane in the color of the remote collaborator, to show that they're
+    /// active in it. Passing `None` removes the collaborator's outline.
+    pub fn set_collaborator_pane(
+        &mut self,
+        peer_id: PeerId,
+        pane: Option<&Entity<Pane>>,
+        cx: &mut Context<Self>,
+    ) {
+        match pane {
+            Some(pane) => {
+                self.collaborator_panes.insert(peer_id, pane.downgrade());
+            }
+            None => {
+                self.collaborator_panes.remove(&peer_id);
+            }
+        }
+        cx.notify();
+    }
+
+    pub fn collaborator_pane(&self, peer_id: PeerId) -> Option<Entity<Pane>> {
+        self.collaborator_panes.get(&peer_id)?.upgrade()
+    }
+
+    /// Returns the remote collaborators that are active in the given pane.
+    pub fn collaborators_in_pane(&self, pane: &Entity<Pane>) -> Vec<PeerId> {
+        let pane = pane.downgrade();
+        self.collaborator_panes
+            .iter()
+            .filter(|(_, collaborator_pane)| **collaborator_pane == pane)
+            .map(|(peer_id, _)| *peer_id)
+            .collect()
+    }
+
+    /// Outlines the pane showing what a collaborator in this project just activated: the pane
+    /// of the panel it's in, or a pane whose active item is the file their editor shows. Their
+    /// outline is removed when no such pane is open.
+    fn collaborator_view_changed(
+        &mut self,
+        peer_id: PeerId,
+        view: Option<&proto::View>,
+        cx: &mut Context<Self>,
+    ) {
+        let pane = view.and_then(|view| self.pane_for_collaborator_view(view, cx));
+        if pane.as_ref().map(|pane| pane.downgrade())
+            != self.collaborator_panes.get(&peer_id).cloned()
+        {
+            self.set_collaborator_pane(peer_id, pane.as_ref(), cx);
+        }
+    }
+
+    fn pane_for_collaborator_view(&self, view: &proto::View, cx: &App) -> Option<Entity<Pane>> {
+        if let Some(panel_id) = view.panel_id.and_then(proto::PanelId::from_i32) {
+            return self.all_docks().into_iter().find_map(|dock| {
+                let dock = dock.read(cx);
+                dock.panels()
+                    .find(|panel| panel.remote_id() == Some(panel_id))?
+                    .pane(cx)
+            });
+        }
+
+        let proto::view::Variant::Editor(editor) = view.variant.as_ref()? else {
+            return None;
+        };
+        if !editor.singleton {
+            return None;
+        }
+        let buffer_id = language::BufferId::new(editor.excerpts.first()?.buffer_id).ok()?;
+        let buffer = self.project.read(cx).buffer_for_id(buffer_id, cx)?;
+        let project_path = project::ProjectItem::project_path(buffer.read(cx), cx)?;
+        std::iter::once(&self.active_pane)
+            .chain(&self.panes)
+            .find(|pane| {
+                pane.read(cx).active_item().map_or(false, |item| {
+                    item.project_path(cx) == Some(project_path.clone())
+                })
+            })
+            .cloned()
+    }
+
+    /// Removes the outlines of the collaborators that left the call or this project.
+    fn forget_departed_collaborators(&mut self, cx: &mut Context<Self>) {
+        if self.collaborator_panes.is_empty() {
+            return;
+        }
+        let project_id = self.project.read(cx).remote_id();
+        let room = self.active_call().and_then(|call| call.read(cx).room());
+        let collaborators: HashSet<PeerId> = match (room, project_id) {
+            (Some(room), Some(project_id)) => room
+                .read(cx)
+                .remote_participants()
+                .values()
+                .filter(|participant| {
+                    participant.location == call::ParticipantLocation::SharedProject { project_id }
+                })
+                .map(|participant| participant.peer_id)
+                .collect(),
+            _ => HashSet::default(),
+        };
+        let collaborator_count = self.collaborator_panes.len();
+        self.collaborator_panes
+            .retain(|peer_id, _| collaborators.contains(peer_id));
+        if self.collaborator_panes.len() != collaborator_count {
+            cx.notify();
+        }
+    }
+
+    fn active_item_path_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(Event::ActiveItemChanged);
+        let active_entry = self.active_project_path(cx);
+        self.project
+            .update(cx, |project, cx| project.set_active_path(active_entry, cx));
+
+        self.update_window_title(window, cx);
+    }
+
+    /// Whether this workspace is the window's root, rather than the workspace of a background
+    /// tab.
+    fn is_window_root(&self, window: &Window) -> bool {
+        window
+            .root::<Workspace>()
+            .flatten()
+            .map_or(true, |root| root.entity_id() == self.weak_self.entity_id())
+    }
 
     fn update_window_title(&mut self, window: &mut Window, cx: &mut App) {
+        if !self.is_window_root(window) {
+            return;
+        }
         let project = self.project().read(cx);
         let mut title = String::new();
 
@@ -3904,7 +6032,9 @@ impl Workspace {
         let is_edited = !self.project.read(cx).is_disconnected(cx) && !self.dirty_items.is_empty();
         if is_edited != self.window_edited {
             self.window_edited = is_edited;
-            window.set_window_edited(self.window_edited)
+            if self.is_window_root(window) {
+                window.set_window_edited(self.window_edited)
+            }
         }
     }
 
@@ -4050,7 +6180,8 @@ impl Workspace {
                 }
             }
             proto::update_followers::Variant::UpdateActiveView(update_active_view) => {
-                let should_add_view = this.update(cx, |this, _| {
+                let should_add_view = this.update(cx, |this, cx| {
+                    this.collaborator_view_changed(leader_id, update_active_view.view.as_ref(), cx);
                     if let Some(state) = this.follower_states.get_mut(&leader_id) {
                         state.active_view_id = update_active_view
                             .view
@@ -4510,6 +6641,8 @@ impl Workspace {
 
     fn remove_from_session(&mut self, window: &mut Window, cx: &mut App) -> Task<()> {
         self.session_id.take();
+        self._schedule_serialize.take();
+        self.serialization_requested_at.take();
         self.serialize_workspace_internal(window, cx)
     }
 
@@ -4521,6 +6654,19 @@ impl Workspace {
         cx: &mut Context<Workspace>,
     ) {
         self.panes.retain(|p| p != pane);
+        record_metric(
+            WorkspaceMetric::PaneCountChanged {
+                pane_count: self.panes.len(),
+            },
+            cx,
+        );
+        // Keep the history of the removed pane navigable, and its closed items reopenable.
+        if let Some(heir) = focus_on.as_ref().or(self.panes.last()) {
+            let nav_history = pane.read(cx).nav_history().clone();
+            heir.update(cx, |heir, _| {
+                heir.nav_history_mut().take_history(&nav_history)
+            });
+        }
         if let Some(focus_on) = focus_on {
             focus_on.update(cx, |pane, cx| window.focus(&pane.focus_handle(cx)));
         } else {
@@ -4537,18 +6683,64 @@ impl Workspace {
         cx.notify();
     }
 
+    /// Schedules writing the workspace to the database once it stops changing, so that bursts
+    /// of changes, like resizing a dock, produce a single write.
     fn serialize_workspace(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        if self._schedule_serialize.is_none() {
-            self._schedule_serialize = Some(cx.spawn_in(window, async move |this, cx| {
-                cx.background_executor()
-                    .timer(Duration::from_millis(100))
-                    .await;
-                this.update_in(cx, |this, window, cx| {
-                    this.serialize_workspace_internal(window, cx).detach();
-                    this._schedule_serialize.take();
-                })
-                .log_err();
-            }));
+        let now = cx.background_executor().now();
+        let requested_at = *self.serialization_requested_at.get_or_insert(now);
+        let delay = SERIALIZATION_DEBOUNCE_TIME
+            .min((requested_at + MAX_SERIALIZATION_DELAY).saturating_duration_since(now));
+        self._schedule_serialize = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            this.update_in(cx, |this, window, cx| {
+                this.flush_serialization(window, cx).detach();
+            })
+            .log_err();
+        }));
+    }
+
+    /// Writes a pending serialization of the workspace right away.
+    pub fn flush_serialization(&mut self, window: &mut Window, cx: &mut App) -> Task<()> {
+        self.serialization_requested_at.take();
+        if self._schedule_serialize.take().is_some() {
+            self.serialize_workspace_internal(window, cx)
+        } else {
+            Task::ready(())
+        }
+    }
+
+    pub(crate) fn build_serialized_docks(
+        &self,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> DockStructure {
+        let serialize_dock = |dock: &Entity<Dock>, window: &mut Window, cx: &mut App| {
+            let dock = dock.read(cx);
+            DockData {
+                visible: dock.is_open(),
+                active_panel: dock
+                    .active_panel()
+                    .map(|panel| panel.persistent_name().to_string()),
+                zoom: dock
+                    .active_panel()
+                    .map(|panel| panel.is_zoomed(window, cx))
+                    .unwrap_or(false),
+            }
+        };
+
+        let mut bottom_right_panels = self.bottom_right_panels.iter().cloned().collect::<Vec<_>>();
+        bottom_right_panels.sort();
+        let mut removed_panels = self.removed_panel_names.iter().cloned().collect::<Vec<_>>();
+        removed_panels.sort();
+
+        DockStructure {
+            left: serialize_dock(&self.left_dock, window, cx),
+            right: serialize_dock(&self.right_dock, window, cx),
+            bottom: serialize_dock(&self.bottom_dock, window, cx),
+            bottom_right: serialize_dock(&self.bottom_right_dock, window, cx),
+            bottom_right_panels,
+            removed_panels,
+            bottom_dock_split: Some(self.bottom_dock_split),
         }
     }
 
@@ -4562,7 +6754,7 @@ impl Workspace {
             window: &mut Window,
             cx: &mut App,
         ) -> SerializedPane {
-            let (items, active, pinned_count) = {
+            let (items, active, pinned_count, context) = {
                 let pane = pane_handle.read(cx);
                 let active_item_id = pane.active_item().map(|item| item.item_id());
                 (
@@ -4580,10 +6772,11 @@ impl Workspace {
                         .collect::<Vec<_>>(),
                     pane.has_focus(window, cx),
                     pane.pinned_count(),
+                    pane.serialized_context().clone(),
                 )
             };
 
-            SerializedPane::new(items, active, pinned_count)
+            SerializedPane::new(items, active, pinned_count).with_context(context)
         }
 
         fn build_serialized_pane_group(
@@ -4611,60 +6804,6 @@ impl Workspace {
             }
         }
 
-        fn build_serialized_docks(
-            this: &Workspace,
-            window: &mut Window,
-            cx: &mut App,
-        ) -> DockStructure {
-            let left_dock = this.left_dock.read(cx);
-            let left_visible = left_dock.is_open();
-            let left_active_panel = left_dock
-                .active_panel()
-                .map(|panel| panel.persistent_name().to_string());
-            let left_dock_zoom = left_dock
-                .active_panel()
-                .map(|panel| panel.is_zoomed(window, cx))
-                .unwrap_or(false);
-
-            let right_dock = this.right_dock.read(cx);
-            let right_visible = right_dock.is_open();
-            let right_active_panel = right_dock
-                .active_panel()
-                .map(|panel| panel.persistent_name().to_string());
-            let right_dock_zoom = right_dock
-                .active_panel()
-                .map(|panel| panel.is_zoomed(window, cx))
-                .unwrap_or(false);
-
-            let bottom_dock = this.bottom_dock.read(cx);
-            let bottom_visible = bottom_dock.is_open();
-            let bottom_active_panel = bottom_dock
-                .active_panel()
-                .map(|panel| panel.persistent_name().to_string());
-            let bottom_dock_zoom = bottom_dock
-                .active_panel()
-                .map(|panel| panel.is_zoomed(window, cx))
-                .unwrap_or(false);
-
-            DockStructure {
-                left: DockData {
-                    visible: left_visible,
-                    active_panel: left_active_panel,
-                    zoom: left_dock_zoom,
-                },
-                right: DockData {
-                    visible: right_visible,
-                    active_panel: right_active_panel,
-                    zoom: right_dock_zoom,
-                },
-                bottom: DockData {
-                    visible: bottom_visible,
-                    active_panel: bottom_active_panel,
-                    zoom: bottom_dock_zoom,
-                },
-            }
-        }
-
         let location = if let Some(ssh_project) = &self.serialized_ssh_project {
             Some(SerializedWorkspaceLocation::Ssh(ssh_project.clone()))
         } else if let Some(local_paths) = self.local_paths(cx) {
@@ -4683,7 +6822,7 @@ impl Workspace {
             });
 
             let center_group = build_serialized_pane_group(&self.center.root, window, cx);
-            let docks = build_serialized_docks(self, window, cx);
+            let docks = self.build_serialized_docks(window, cx);
             let window_bounds = Some(SerializedWindowBounds(window.window_bounds()));
             let serialized_workspace = SerializedWorkspace {
                 id: database_id,
@@ -4696,6 +6835,7 @@ impl Workspace {
                 session_id: self.session_id.clone(),
                 breakpoints,
                 window_id: Some(window.window_handle().window_id().as_u64()),
+                center_group_unreadable: self.center_group_unreadable,
             };
             return window.spawn(cx, async move |_| {
                 persistence::DB.save_workspace(serialized_workspace).await
@@ -4757,7 +6897,10 @@ impl Workspace {
         cx: &mut Context<Workspace>,
     ) -> Task<Result<Vec<Option<Box<dyn ItemHandle>>>>> {
         cx.spawn_in(window, async move |workspace, cx| {
-            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            let project = workspace.update(cx, |workspace, _| {
+                workspace.center_group_unreadable = serialized_workspace.center_group_unreadable;
+                workspace.project().clone()
+            })?;
 
             let mut center_group = None;
             let mut center_items = None;
@@ -4816,10 +6959,16 @@ impl Workspace {
 
                 let docks = serialized_workspace.docks;
 
+                workspace.restore_bottom_right_panels(&docks.bottom_right_panels, window, cx);
+                workspace.restore_removed_panels(&docks.removed_panels, window, cx);
+                if let Some(split) = docks.bottom_dock_split {
+                    workspace.bottom_dock_split = split;
+                }
                 for (dock, serialized_dock) in [
                     (&mut workspace.right_dock, docks.right),
                     (&mut workspace.left_dock, docks.left),
                     (&mut workspace.bottom_dock, docks.bottom),
+                    (&mut workspace.bottom_right_dock, docks.bottom_right),
                 ]
                 .iter_mut()
                 {
@@ -4873,6 +7022,10 @@ impl Workspace {
 
                     // Ensure that we mark the window as edited if we did load dirty items
                     workspace.update_window_edited(window, cx);
+
+                    workspace
+                        .prompt_to_restore_unsaved_changes(all_deserialized_items, window, cx)
+                        .detach_and_log_err(cx);
                 })
                 .ok();
 
@@ -4880,18 +7033,100 @@ impl Workspace {
         })
     }
 
+    /// Asks whether to keep the unsaved changes restored after the last session ended without
+    /// quitting. Discarding them reverts files to their contents on disk and closes untitled
+    /// items.
+    ///
+    /// Only the first workspace restored with unsaved changes asks, the others apply the same
+    /// answer once it's given.
+    fn prompt_to_restore_unsaved_changes(
+        &mut self,
+        items: Vec<Box<dyn ItemHandle>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let session = self.app_state.session.clone();
+        if !session.read(cx).last_session_crashed() {
+            return Task::ready(Ok(()));
+        }
+        let dirty_items = items
+            .into_iter()
+            .filter(|item| item.is_dirty(cx))
+            .collect::<Vec<_>>();
+        if dirty_items.is_empty() {
+            return Task::ready(Ok(()));
+        }
+
+        let answer = match session.read(cx).unsaved_changes_answer() {
+            Some(answer) => answer,
+            None => {
+                let answer = self.prompt(
+                    "Restore unsaved changes?",
+                    prompt_dialog::PromptOptions::new(["Restore", "Discard"])
+                        .level(PromptLevel::Warning)
+                        .detail(format!(
+                            "Zed didn't shut down cleanly. {} restored item{} had unsaved changes.",
+                            dirty_items.len(),
+                            if dirty_items.len() == 1 { "" } else { "s" }
+                        )),
+                    window,
+                    cx,
+                );
+                let answer = cx
+                    .background_spawn(async move {
+                        match answer.await {
+                            Some(1) => UnsavedChangesAnswer::Discard,
+                            _ => UnsavedChangesAnswer::Restore,
+                        }
+                    })
+                    .shared();
+                session.update(cx, |session, _| {
+                    session.set_unsaved_changes_answer(answer.clone())
+                });
+                answer
+            }
+        };
+        cx.spawn_in(window, async move |this, cx| {
+            if answer.await != UnsavedChangesAnswer::Discard {
+                return Ok(());
+            }
+            let tasks = this.update_in(cx, |this, window, cx| {
+                let mut tasks = Vec::new();
+                for item in dirty_items {
+                    if item.project_path(cx).is_some() {
+                        tasks.push(item.reload(this.project.clone(), window, cx));
+                    } else if let Some(pane) = this.pane_for(item.as_ref()) {
+                        let item_id = item.item_id();
+                        tasks.push(pane.update(cx, |pane, cx| {
+                            pane.close_items(window, cx, SaveIntent::Skip, |id| id == item_id)
+                        }));
+                    }
+                }
+                tasks
+            })?;
+            for result in futures::future::join_all(tasks).await {
+                result.log_err();
+            }
+            Ok(())
+        })
+    }
+
     fn actions(&self, div: Div, window: &mut Window, cx: &mut Context<Self>) -> Div {
         self.add_workspace_actions_listeners(div, window, cx)
             .on_action(cx.listener(Self::close_inactive_items_and_panes))
             .on_action(cx.listener(Self::close_all_items_and_panes))
+            .on_action(cx.listener(Self::close_all_items_in_other_panes))
             .on_action(cx.listener(Self::save_all))
             .on_action(cx.listener(Self::send_keystrokes))
             .on_action(cx.listener(Self::add_folder_to_project))
             .on_action(cx.listener(Self::follow_next_collaborator))
+            .on_action(cx.listener(Self::export_layout_to_file))
+            .on_action(cx.listener(Self::import_layout_from_file))
             .on_action(cx.listener(Self::close_window))
             .on_action(cx.listener(Self::activate_pane_at_index))
             .on_action(cx.listener(Self::move_item_to_pane_at_index))
             .on_action(cx.listener(Self::move_focused_panel_to_next_position))
+            .on_action(cx.listener(Self::remove_focused_panel_from_dock))
             .on_action(cx.listener(|workspace, _: &Unfollow, window, cx| {
                 let pane = workspace.active_pane().clone();
                 workspace.unfollow_in_pane(&pane, window, cx);
@@ -4949,17 +7184,39 @@ impl Workspace {
                     workspace.move_item_to_pane_in_direction(action, window, cx)
                 },
             ))
-            .on_action(cx.listener(|workspace, _: &SwapPaneLeft, _, cx| {
-                workspace.swap_pane_in_direction(SplitDirection::Left, cx)
+            .on_action(
+                cx.listener(|workspace, _: &MoveItemToNewWindow, window, cx| {
+                    workspace
+                        .move_active_item_to_new_window(window, cx)
+                        .detach_and_log_err(cx);
+                }),
+            )
+            .on_action(cx.listener(|workspace, _: &SwapPaneLeft, window, cx| {
+                workspace.swap_pane_in_direction(SplitDirection::Left, window, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &SwapPaneRight, window, cx| {
+                workspace.swap_pane_in_direction(SplitDirection::Right, window, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &SwapPaneUp, window, cx| {
+                workspace.swap_pane_in_direction(SplitDirection::Up, window, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &SwapPaneDown, window, cx| {
+                workspace.swap_pane_in_direction(SplitDirection::Down, window, cx)
             }))
-            .on_action(cx.listener(|workspace, _: &SwapPaneRight, _, cx| {
-                workspace.swap_pane_in_direction(SplitDirection::Right, cx)
+            .on_action(cx.listener(|workspace, _: &ResetPaneSizes, _, cx| {
+                workspace.reset_active_axis_sizes(cx)
             }))
-            .on_action(cx.listener(|workspace, _: &SwapPaneUp, _, cx| {
-                workspace.swap_pane_in_direction(SplitDirection::Up, cx)
+            .on_action(cx.listener(|workspace, _: &ResizePaneLeft, window, cx| {
+                workspace.resize_pane_by_step(gpui::Axis::Horizontal, -1., window, cx)
             }))
-            .on_action(cx.listener(|workspace, _: &SwapPaneDown, _, cx| {
-                workspace.swap_pane_in_direction(SplitDirection::Down, cx)
+            .on_action(cx.listener(|workspace, _: &ResizePaneRight, window, cx| {
+                workspace.resize_pane_by_step(gpui::Axis::Horizontal, 1., window, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &ResizePaneUp, window, cx| {
+                workspace.resize_pane_by_step(gpui::Axis::Vertical, 1., window, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &ResizePaneDown, window, cx| {
+                workspace.resize_pane_by_step(gpui::Axis::Vertical, -1., window, cx)
             }))
             .on_action(cx.listener(|this, _: &ToggleLeftDock, window, cx| {
                 this.toggle_dock(DockPosition::Left, window, cx);
@@ -4974,11 +7231,126 @@ impl Workspace {
                     workspace.toggle_dock(DockPosition::Bottom, window, cx);
                 },
             ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &ToggleBottomLeftDock, window, cx| {
+                    workspace.toggle_bottom_dock_slot(BottomDockSlot::Left, window, cx);
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &ToggleBottomRightDock, window, cx| {
+                    workspace.toggle_bottom_dock_slot(BottomDockSlot::Right, window, cx);
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &SplitBottomDock, window, cx| {
+                    workspace.split_bottom_dock(window, cx);
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &SplitLeftOneThird, window, cx| {
+                    workspace.split_active_pane_with_ratio(
+                        SplitDirection::Left,
+                        1. / 3.,
+                        window,
+                        cx,
+                    );
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &SplitLeftTwoThirds, window, cx| {
+                    workspace.split_active_pane_with_ratio(
+                        SplitDirection::Left,
+                        2. / 3.,
+                        window,
+                        cx,
+                    );
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &SplitRightOneThird, window, cx| {
+                    workspace.split_active_pane_with_ratio(
+                        SplitDirection::Right,
+                        1. / 3.,
+                        window,
+                        cx,
+                    );
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &SplitRightTwoThirds, window, cx| {
+                    workspace.split_active_pane_with_ratio(
+                        SplitDirection::Right,
+                        2. / 3.,
+                        window,
+                        cx,
+                    );
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &SplitUpOneThird, window, cx| {
+                    workspace.split_active_pane_with_ratio(SplitDirection::Up, 1. / 3., window, cx);
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &SplitUpTwoThirds, window, cx| {
+                    workspace.split_active_pane_with_ratio(SplitDirection::Up, 2. / 3., window, cx);
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &SplitDownOneThird, window, cx| {
+                    workspace.split_active_pane_with_ratio(
+                        SplitDirection::Down,
+                        1. / 3.,
+                        window,
+                        cx,
+                    );
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &SplitDownTwoThirds, window, cx| {
+                    workspace.split_active_pane_with_ratio(
+                        SplitDirection::Down,
+                        2. / 3.,
+                        window,
+                        cx,
+                    );
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, action: &ActivatePanel, window, cx| {
+                    workspace.activate_panel_by_name(&action.name, action.dock, window, cx);
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, action: &ApplyPaneLayout, window, cx| {
+                    workspace.apply_pane_layout(action.0, window, cx);
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, action: &TogglePanelGroup, window, cx| {
+                    workspace.toggle_panel_group(&action.0, window, cx);
+                },
+            ))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &FocusPanelButtons, window, cx| {
+                    workspace.focus_panel_buttons(window, cx);
+                },
+            ))
             .on_action(
                 cx.listener(|workspace: &mut Workspace, _: &CloseAllDocks, window, cx| {
                     workspace.close_all_docks(window, cx);
                 }),
             )
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &MaximizeEditor, window, cx| {
+                    workspace.maximize_editor(window, cx);
+                },
+            ))
+            .on_action(
+                cx.listener(|workspace: &mut Workspace, _: &RestoreDocks, window, cx| {
+                    workspace.restore_docks(window, cx);
+                }),
+            )
             .on_action(cx.listener(
                 |workspace: &mut Workspace, _: &ClearAllNotifications, _, cx| {
                     workspace.clear_all_notifications(cx);
@@ -4990,6 +7362,17 @@ impl Workspace {
                 },
             ))
             .on_action(cx.listener(Workspace::toggle_centered_layout))
+            .on_action(cx.listener(Workspace::toggle_zoom_pane))
+            .on_action(
+                cx.listener(|workspace: &mut Workspace, _: &ToggleZenMode, window, cx| {
+                    workspace.toggle_zen_mode(window, cx);
+                }),
+            )
+            .on_action(cx.listener(
+                |workspace: &mut Workspace, _: &ToggleStatusBarFocusMode, _, cx| {
+                    workspace.toggle_status_bar_focus_mode(cx);
+                },
+            ))
     }
 
     #[cfg(any(test, feature = "test-support"))]
@@ -5064,6 +7447,21 @@ impl Workspace {
         })
     }
 
+    /// Shows a modal on top of the open modals, which are shown again once it's dismissed.
+    pub fn push_modal<V: ModalView, B>(&mut self, window: &mut Window, cx: &mut App, build: B)
+    where
+        B: FnOnce(&mut Window, &mut Context<V>) -> V,
+    {
+        self.modal_layer.update(cx, |modal_layer, cx| {
+            modal_layer.push_modal(window, cx, build)
+        })
+    }
+
+    /// The layer showing this workspace's modals. Subscribe to it for [`ModalEvent`]s.
+    pub fn modal_layer(&self) -> &Entity<ModalLayer> {
+        &self.modal_layer
+    }
+
     pub fn toggle_status_toast<V: ToastView>(&mut self, entity: Entity<V>, cx: &mut App) {
         self.toast_layer
             .update(cx, |toast_layer, cx| toast_layer.toggle_toast(cx, entity))
@@ -5083,23 +7481,226 @@ impl Workspace {
         cx.notify();
     }
 
-    fn adjust_padding(padding: Option<f32>) -> f32 {
-        padding
-            .unwrap_or(Self::DEFAULT_PADDING)
-            .clamp(0.0, Self::MAX_PADDING)
-    }
-
-    fn render_dock(
-        &self,
-        position: DockPosition,
-        dock: &Entity<Dock>,
+    /// Expands the active pane to fill the center, hiding the other panes without changing the
+    /// split layout. Zoomed items share the workspace's zoom slot: maximizing a pane dismisses
+    /// them, and while one is zoomed it's shown over the maximized pane.
+    pub fn toggle_zoom_pane(
+        &mut self,
+        _: &ToggleZoomPane,
         window: &mut Window,
-        cx: &mut App,
-    ) -> Option<Div> {
-        if self.zoomed_position == Some(position) {
-            return None;
+        cx: &mut Context<Self>,
+    ) {
+        if self.maximized_pane.take().is_none() {
+            self.dismiss_zoomed_items_to_reveal(None, window, cx);
+            // Only the active pane can still be zoomed at this point.
+            self.active_pane.update(cx, |pane, cx| {
+                pane.set_zoomed(false, cx);
+                window.focus(&pane.focus_handle(cx));
+            });
+            if self.zoomed.take().is_some() {
+                self.zoom_changed(cx);
+            }
+            self.maximized_pane = Some(self.active_pane.downgrade());
         }
-
+        cx.notify();
+    }
+
+    /// Hides the docks, the status bars, and the tab bars and toolbars of the center panes, or
+    /// shows them again as they were before.
+    pub fn toggle_zen_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.zen_mode {
+            self.zen_mode = false;
+            // Docks opened while in zen mode are closed again.
+            for dock in self.all_docks().map(Clone::clone) {
+                dock.update(cx, |dock, cx| dock.set_open(false, window, cx));
+            }
+            self.reopen_closed_docks(window, cx);
+        } else {
+            self.zen_mode = true;
+            self.close_open_docks(window, cx);
+        }
+        for pane in self.center.panes() {
+            pane.update(cx, |_, cx| cx.notify());
+        }
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    pub fn is_zen_mode(&self) -> bool {
+        self.zen_mode
+    }
+
+    /// Closes all docks to give the center the whole window, remembering which were open and
+    /// their sizes for [`Self::restore_docks`]. Maximizing again while no dock was reopened
+    /// keeps the earlier snapshot.
+    pub fn maximize_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.close_open_docks(window, cx) {
+            return;
+        }
+        window.focus(&self.active_pane.focus_handle(cx));
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    /// Reopens the docks closed by [`Self::maximize_editor`] or zen mode at their previous sizes.
+    pub fn restore_docks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.reopen_closed_docks(window, cx) {
+            return;
+        }
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    /// Closes the open docks and adds them to the ones to reopen, which both maximizing the
+    /// editor and zen mode share. Returns whether any dock was open.
+    fn close_open_docks(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let open_docks = self
+            .all_docks()
+            .into_iter()
+            .filter(|dock| dock.read(cx).is_open())
+            .map(|dock| {
+                let dock_state = dock.read(cx);
+                let active_panel = dock_state.active_panel();
+                ClosedDock {
+                    dock: dock.clone(),
+                    active_panel_id: active_panel.map(|panel| panel.panel_id()),
+                    size: active_panel.map(|panel| panel.size(window, cx)),
+                }
+            })
+            .collect::<Vec<_>>();
+        if open_docks.is_empty() {
+            return false;
+        }
+
+        for closed_dock in &open_docks {
+            closed_dock
+                .dock
+                .update(cx, |dock, cx| dock.set_open(false, window, cx));
+        }
+        let closed_docks = self.closed_docks.get_or_insert_with(Vec::new);
+        closed_docks.retain(|closed| open_docks.iter().all(|open| open.dock != closed.dock));
+        closed_docks.extend(open_docks);
+        true
+    }
+
+    /// Reopens the docks closed by [`Self::close_open_docks`] with the panels that were active
+    /// in them, if those are still there. Returns whether there were docks to reopen.
+    fn reopen_closed_docks(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some(closed_docks) = self.closed_docks.take() else {
+            return false;
+        };
+        for closed_dock in closed_docks {
+            closed_dock.dock.update(cx, |dock, cx| {
+                let panel_ix = closed_dock
+                    .active_panel_id
+                    .and_then(|panel_id| dock.panel_index_for_id(panel_id));
+                if let Some(panel_ix) = panel_ix {
+                    dock.activate_panel(panel_ix, window, cx);
+                }
+                dock.set_open(true, window, cx);
+                if let (Some(_), Some(size)) = (panel_ix, closed_dock.size) {
+                    dock.resize_active_panel(Some(size), window, cx);
+                }
+            });
+        }
+        true
+    }
+
+    pub fn has_docks_to_restore(&self) -> bool {
+        self.closed_docks.is_some()
+    }
+
+    /// Hides all status bar items except the essential ones, such as the cursor position and
+    /// the panel buttons, or shows them again.
+    pub fn toggle_status_bar_focus_mode(&mut self, cx: &mut Context<Self>) {
+        for status_bar in [&self.status_bar, &self.secondary_status_bar] {
+            status_bar.update(cx, |status_bar, cx| status_bar.toggle_focus_mode(cx));
+        }
+    }
+
+    /// Returns the pane filling the center, unless an item is zoomed over it.
+    pub fn maximized_pane(&self) -> Option<Entity<Pane>> {
+        if self.zoomed.is_some() {
+            return None;
+        }
+        self.maximized_pane
+            .as_ref()?
+            .upgrade()
+            .filter(|pane| self.panes.contains(pane))
+    }
+
+    /// Overrides the [`WindowResizePolicy`](resize_policy::WindowResizePolicy) from the settings
+    /// with a custom policy for distributing window resizes between the docks and the center.
+    pub fn set_resize_policy(&mut self, policy: Option<Box<dyn ResizePolicy>>) {
+        self.resize_policy = policy;
+    }
+
+    fn apply_resize_policy(
+        &mut self,
+        previous_size: Size<Pixels>,
+        new_size: Size<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        if previous_size.width <= px(0.) || previous_size.height <= px(0.) {
+            return;
+        }
+
+        let docks = DockSizes {
+            left: self.left_dock.read(cx).active_panel_size(window, cx),
+            right: self.right_dock.read(cx).active_panel_size(window, cx),
+            bottom: self
+                .bottom_dock
+                .read(cx)
+                .active_panel_size(window, cx)
+                .or_else(|| {
+                    self.bottom_right_dock
+                        .read(cx)
+                        .active_panel_size(window, cx)
+                }),
+        };
+        let resized = match self.resize_policy.as_ref() {
+            Some(policy) => policy.resize_docks(previous_size, new_size, docks),
+            None => WorkspaceSettings::get_global(cx)
+                .window_resize_policy
+                .policy()
+                .resize_docks(previous_size, new_size, docks),
+        };
+
+        for (dock, new_size) in [
+            (&self.left_dock, resized.left),
+            (&self.right_dock, resized.right),
+            (&self.bottom_dock, resized.bottom),
+            (&self.bottom_right_dock, resized.bottom),
+        ] {
+            let size = dock.read(cx).active_panel_size(window, cx);
+            if let Some(new_size) =
+                new_size.filter(|new_size| size.is_some_and(|size| size != *new_size))
+            {
+                dock.update(cx, |dock, cx| {
+                    dock.set_resized_active_panel_size(new_size, window, cx)
+                });
+            }
+        }
+    }
+
+    fn adjust_padding(padding: Option<f32>) -> f32 {
+        padding
+            .unwrap_or(Self::DEFAULT_PADDING)
+            .clamp(0.0, Self::MAX_PADDING)
+    }
+
+    fn render_dock(
+        &self,
+        position: DockPosition,
+        dock: &Entity<Dock>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<Div> {
+        if self.zoomed_position == Some(position) {
+            return None;
+        }
+
         let leader_border = dock.read(cx).active_panel().and_then(|panel| {
             let pane = panel.pane(cx)?;
             let follower_states = &self.follower_states;
@@ -5108,6 +7709,7 @@ impl Workspace {
 
         Some(
             div()
+                .debug_selector(|| format!("DOCK-{}", dock.read(cx).label()))
                 .flex()
                 .flex_none()
                 .overflow_hidden()
@@ -5116,6 +7718,104 @@ impl Workspace {
         )
     }
 
+    fn render_bottom_docks(&self, window: &mut Window, cx: &mut App) -> Option<Div> {
+        let left_open = self.bottom_dock.read(cx).is_open();
+        let right_open = self.bottom_right_dock.read(cx).is_open();
+        let left = self.render_dock(DockPosition::Bottom, &self.bottom_dock, window, cx)?;
+        let right = self.render_dock(DockPosition::Bottom, &self.bottom_right_dock, window, cx)?;
+
+        let split = left_open && right_open;
+        let workspace = self.weak_self.clone();
+
+        Some(
+            h_flex()
+                .w_full()
+                .items_end()
+                .when(split, |this| {
+                    this.on_drag_move(
+                        move |e: &DragMoveEvent<DraggedBottomDockSplit>, window, cx| {
+                            if e.bounds.size.width <= px(0.) {
+                                return;
+                            }
+                            let split =
+                                (e.event.position.x - e.bounds.left()) / e.bounds.size.width;
+                            workspace
+                                .update(cx, |workspace, cx| {
+                                    workspace.set_bottom_dock_split(split, window, cx)
+                                })
+                                .ok();
+                        },
+                    )
+                })
+                .child(left.map(|this| {
+                    if split {
+                        this.w(relative(self.bottom_dock_split))
+                    } else {
+                        this.when(left_open, |this| this.flex_1())
+                    }
+                }))
+                .when(split, |this| {
+                    this.child(
+                        div().relative().h_full().child(
+                            div()
+                                .id("bottom-dock-splitter")
+                                .absolute()
+                                .top_0()
+                                .left(-RESIZE_HANDLE_SIZE / 2.)
+                                .h_full()
+                                .w(RESIZE_HANDLE_SIZE)
+                                .cursor_col_resize()
+                                .occlude()
+                                .on_drag(DraggedBottomDockSplit, |split, _, _, cx| {
+                                    cx.stop_propagation();
+                                    cx.new(|_| split.clone())
+                                })
+                                .on_mouse_up(MouseButton::Left, {
+                                    let workspace = self.weak_self.clone();
+                                    move |e, window, cx| {
+                                        if e.click_count == 2 {
+                                            workspace
+                                                .update(cx, |workspace, cx| {
+                                                    workspace.set_bottom_dock_split(0.5, window, cx)
+                                                })
+                                                .ok();
+                                        }
+                                    }
+                                }),
+                        ),
+                    )
+                })
+                .child(right.when(right_open, |this| this.flex_1())),
+        )
+    }
+
+    /// Renders the open docks over the center panes, for the `overlay` dock option.
+    fn render_overlay_docks(&self, window: &mut Window, cx: &mut App) -> Vec<Div> {
+        let mut docks = Vec::new();
+        if self.left_dock.read(cx).is_open() {
+            docks.extend(
+                self.render_dock(DockPosition::Left, &self.left_dock, window, cx)
+                    .map(|dock| dock.top_0().bottom_0().left_0()),
+            );
+        }
+        if self.bottom_dock.read(cx).is_open() || self.bottom_right_dock.read(cx).is_open() {
+            docks.extend(
+                self.render_bottom_docks(window, cx)
+                    .map(|docks| docks.bottom_0().left_0()),
+            );
+        }
+        if self.right_dock.read(cx).is_open() {
+            docks.extend(
+                self.render_dock(DockPosition::Right, &self.right_dock, window, cx)
+                    .map(|dock| dock.top_0().bottom_0().right_0()),
+            );
+        }
+        docks
+            .into_iter()
+            .map(|dock| dock.absolute().occlude().shadow_lg())
+            .collect()
+    }
+
     pub fn for_window(window: &mut Window, _: &mut App) -> Option<Entity<Workspace>> {
         window.root().flatten()
     }
@@ -5124,6 +7824,17 @@ impl Workspace {
         self.zoomed.as_ref()
     }
 
+    /// Emits [`Event::ZoomChanged`] after the zoomed item was updated, and gives feedback if
+    /// that zoomed something in or out.
+    pub(crate) fn zoom_changed(&mut self, cx: &mut Context<Self>) {
+        let zoomed = self.zoomed.is_some();
+        if zoomed != self.was_zoomed {
+            self.was_zoomed = zoomed;
+            give_feedback(cx, |feedback, cx| feedback.zoom_changed(zoomed, cx));
+        }
+        cx.emit(Event::ZoomChanged);
+    }
+
     pub fn activate_next_window(&mut self, cx: &mut Context<Self>) {
         let Some(current_window_id) = cx.active_window().map(|a| a.window_id()) else {
             return;
@@ -5162,6 +7873,30 @@ impl Workspace {
     }
 }
 
+/// Panel buttons are shown closer to the edges of the status bar than any other item.
+const PANEL_BUTTONS_PRIORITY: i32 = 1000;
+
+/// Adds the panel buttons of the left, bottom, bottom right and right docks to the status
+/// bar, replacing the ones that were there.
+fn add_panel_buttons(
+    status_bar: &mut StatusBar,
+    panel_buttons: &[Entity<PanelButtons>; 4],
+    placement: DockButtonPlacement,
+    window: &mut Window,
+    cx: &mut Context<StatusBar>,
+) {
+    status_bar.remove_items_of_type::<PanelButtons>(cx);
+    let (left_items, right_items) = placement.arrange(panel_buttons);
+    for (items, alignment) in [
+        (left_items, StatusItemAlignment::Left),
+        (right_items, StatusItemAlignment::Right),
+    ] {
+        for item in items {
+            status_bar.add_item(item, alignment, PANEL_BUTTONS_PRIORITY, window, cx);
+        }
+    }
+}
+
 fn leader_border_for_pane(
     follower_states: &HashMap<PeerId, FollowerState>,
     pane: &Entity<Pane>,
@@ -5205,6 +7940,49 @@ fn window_bounds_env_override() -> Option<Bounds<Pixels>> {
         })
 }
 
+/// Returns restored window bounds that fit on the display they were saved on, or on the
+/// primary display if that one is no longer connected.
+fn visible_window_bounds(
+    window_bounds: WindowBounds,
+    display_uuid: Uuid,
+    cx: &App,
+) -> WindowBounds {
+    let display = cx
+        .displays()
+        .into_iter()
+        .find(|display| display.uuid().ok() == Some(display_uuid))
+        .or_else(|| cx.primary_display());
+    match display {
+        Some(display) => clamp_window_bounds(window_bounds, display.bounds()),
+        None => window_bounds,
+    }
+}
+
+/// Shrinks the window to the size of the display and moves it onto the display, so that a
+/// window saved on a disconnected or since resized display doesn't open off-screen.
+fn clamp_window_bounds(
+    window_bounds: WindowBounds,
+    display_bounds: Bounds<Pixels>,
+) -> WindowBounds {
+    let clamp = |bounds: Bounds<Pixels>| {
+        let size = size(
+            bounds.size.width.min(display_bounds.size.width),
+            bounds.size.height.min(display_bounds.size.height),
+        );
+        let max_origin = display_bounds.bottom_right() - point(size.width, size.height);
+        let origin = point(
+            bounds.origin.x.clamp(display_bounds.origin.x, max_origin.x),
+            bounds.origin.y.clamp(display_bounds.origin.y, max_origin.y),
+        );
+        Bounds { origin, size }
+    };
+    match window_bounds {
+        WindowBounds::Windowed(bounds) => WindowBounds::Windowed(clamp(bounds)),
+        WindowBounds::Maximized(bounds) => WindowBounds::Maximized(clamp(bounds)),
+        WindowBounds::Fullscreen(bounds) => WindowBounds::Fullscreen(clamp(bounds)),
+    }
+}
+
 fn open_items(
     serialized_workspace: Option<SerializedWorkspace>,
     mut project_paths_to_open: Vec<(PathBuf, Option<ProjectPath>)>,
@@ -5346,6 +8124,17 @@ impl Focusable for Workspace {
     }
 }
 
+/// How close to an edge of the center a dragged tab has to be to split the whole center.
+const CENTER_EDGE_DROP_SIZE: Pixels = px(24.);
+
+/// A dock closed by `MaximizeEditor` or zen mode, to reopen with `RestoreDocks` or when
+/// leaving zen mode.
+struct ClosedDock {
+    dock: Entity<Dock>,
+    active_panel_id: Option<EntityId>,
+    size: Option<Pixels>,
+}
+
 #[derive(Clone)]
 struct DraggedDock(DockPosition);
 
@@ -5355,6 +8144,18 @@ impl Render for DraggedDock {
     }
 }
 
+/// The smallest share of the bottom docks' width either slot can be resized to.
+const MIN_BOTTOM_DOCK_SPLIT: f32 = 0.1;
+
+#[derive(Clone)]
+struct DraggedBottomDockSplit;
+
+impl Render for DraggedBottomDockSplit {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        gpui::Empty
+    }
+}
+
 impl Render for Workspace {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let mut context = KeyContext::new_with_defaults();
@@ -5363,17 +8164,35 @@ impl Render for Workspace {
         let centered_layout = self.centered_layout
             && self.center.panes().len() == 1
             && self.active_item(cx).is_some();
+        let zen_mode_max_width = self
+            .zen_mode
+            .then(|| {
+                WorkspaceSettings::get_global(cx)
+                    .zen_mode
+                    .centered_max_width()
+            })
+            .flatten()
+            .filter(|_| self.center.panes().len() == 1);
+        let centered_layout = centered_layout && zen_mode_max_width.is_none();
+        let settings = WorkspaceSettings::get_global(cx).centered_layout;
+        let center_max_width = zen_mode_max_width.or_else(|| {
+            settings
+                .max_width
+                .filter(|max_width| centered_layout && *max_width > 0.)
+                .map(px)
+        });
+        // With a maximum width, the paddings take up the space the center doesn't.
         let render_padding = |size| {
             (size > 0.0).then(|| {
                 div()
                     .h_full()
                     .w(relative(size))
+                    .when(center_max_width.is_some(), |this| this.flex_grow())
                     .bg(cx.theme().colors().editor_background)
                     .border_color(cx.theme().colors().pane_group_border)
             })
         };
         let paddings = if centered_layout {
-            let settings = WorkspaceSettings::get_global(cx).centered_layout;
             (
                 render_padding(Self::adjust_padding(settings.left_padding)),
                 render_padding(Self::adjust_padding(settings.right_padding)),
@@ -5381,6 +8200,9 @@ impl Render for Workspace {
         } else {
             (None, None)
         };
+        let overlay_docks = WorkspaceSettings::get_global(cx)
+            .effective_dock_options()
+            .overlay;
         let ui_font = theme::setup_ui_font(window, cx);
 
         let theme = cx.theme().clone();
@@ -5400,6 +8222,8 @@ impl Render for Workspace {
                 .text_color(colors.text)
                 .overflow_hidden()
                 .children(self.titlebar_item.clone())
+                .children(self.slot_views.views(WorkspaceSlot::BelowTitleBar))
+                .children(self.render_workspace_tab_bar(cx))
                 .child(
                     div()
                         .size_full()
@@ -5410,6 +8234,7 @@ impl Render for Workspace {
                         .child(
                             div()
                                 .id("workspace")
+                                .debug_selector(|| "WORKSPACE".into())
                                 .bg(colors.background)
                                 .relative()
                                 .flex_1()
@@ -5426,9 +8251,17 @@ impl Render for Workspace {
                                         move |bounds, window, cx| {
                                             this.update(cx, |this, cx| {
                                                 let bounds_changed = this.bounds != bounds;
+                                                let previous_bounds = this.bounds;
                                                 this.bounds = bounds;
 
                                                 if bounds_changed {
+                                                    this.apply_resize_policy(
+                                                        previous_bounds.size,
+                                                        bounds.size,
+                                                        window,
+                                                        cx,
+                                                    );
+
                                                     this.left_dock.update(cx, |dock, cx| {
                                                         dock.clamp_panel_size(
                                                             bounds.size.width,
@@ -5452,6 +8285,17 @@ impl Render for Workspace {
                                                             cx,
                                                         )
                                                     });
+
+                                                    this.bottom_right_dock.update(
+                                                        cx,
+                                                        |dock, cx| {
+                                                            dock.clamp_panel_size(
+                                                                bounds.size.height,
+                                                                window,
+                                                                cx,
+                                                            )
+                                                        },
+                                                    );
                                                 }
                                             })
                                         },
@@ -5471,32 +8315,36 @@ impl Render for Workspace {
                                             {
                                                 workspace.previous_dock_drag_coordinates =
                                                     Some(e.event.position);
-                                                match e.drag(cx).0 {
+                                                let position = e.drag(cx).0;
+                                                let size = match position {
+                                                    DockPosition::Left => {
+                                                        e.event.position.x - workspace.bounds.left()
+                                                    }
+                                                    DockPosition::Right => {
+                                                        workspace.bounds.right()
+                                                            - e.event.position.x
+                                                    }
+                                                    DockPosition::Bottom => {
+                                                        workspace.bounds.bottom()
+                                                            - e.event.position.y
+                                                    }
+                                                };
+                                                let size = workspace
+                                                    .snap_dock_size(position, size, window, cx);
+                                                match position {
                                                     DockPosition::Left => {
                                                         resize_left_dock(
-                                                            e.event.position.x
-                                                                - workspace.bounds.left(),
-                                                            workspace,
-                                                            window,
-                                                            cx,
+                                                            size, workspace, window, cx,
                                                         );
                                                     }
                                                     DockPosition::Right => {
                                                         resize_right_dock(
-                                                            workspace.bounds.right()
-                                                                - e.event.position.x,
-                                                            workspace,
-                                                            window,
-                                                            cx,
+                                                            size, workspace, window, cx,
                                                         );
                                                     }
                                                     DockPosition::Bottom => {
                                                         resize_bottom_dock(
-                                                            workspace.bounds.bottom()
-                                                                - e.event.position.y,
-                                                            workspace,
-                                                            window,
-                                                            cx,
+                                                            size, workspace, window, cx,
                                                         );
                                                     }
                                                 };
@@ -5511,12 +8359,14 @@ impl Render for Workspace {
                                         .flex_row()
                                         .h_full()
                                         // Left Dock
-                                        .children(self.render_dock(
-                                            DockPosition::Left,
-                                            &self.left_dock,
-                                            window,
-                                            cx,
-                                        ))
+                                        .when(!overlay_docks, |this| {
+                                            this.children(self.render_dock(
+                                                DockPosition::Left,
+                                                &self.left_dock,
+                                                window,
+                                                cx,
+                                            ))
+                                        })
                                         // Panes
                                         .child(
                                             div()
@@ -5526,39 +8376,113 @@ impl Render for Workspace {
                                                 .overflow_hidden()
                                                 .child(
                                                     h_flex()
+                                                        .debug_selector(|| "CENTER".into())
+                                                        .relative()
                                                         .flex_1()
+                                                        .on_drag_move(cx.listener(
+                                                            Self::handle_center_drag_move,
+                                                        ))
+                                                        // The drag ends on mouse up, whether
+                                                        // or not the tab is dropped on the edge.
+                                                        .capture_any_mouse_up(cx.listener(
+                                                            |this, _, _, cx| {
+                                                                this.set_center_drag_edge(None, cx)
+                                                            },
+                                                        ))
+                                                        .on_mouse_up_out(
+                                                            MouseButton::Left,
+                                                            cx.listener(|this, _, _, cx| {
+                                                                this.set_center_drag_edge(None, cx)
+                                                            }),
+                                                        )
                                                         .when_some(paddings.0, |this, p| {
                                                             this.child(p.border_r_1())
                                                         })
-                                                        .child(self.center.render(
-                                                            &self.project,
-                                                            &self.follower_states,
-                                                            self.active_call(),
-                                                            &self.active_pane,
-                                                            self.zoomed.as_ref(),
-                                                            &self.app_state,
-                                                            window,
-                                                            cx,
-                                                        ))
+                                                        .map(|this| {
+                                                            let center = match self.maximized_pane()
+                                                            {
+                                                                Some(pane) => Member::Pane(pane)
+                                                                    .render(
+                                                                        &self.project,
+                                                                        0,
+                                                                        &self.follower_states,
+                                                                        &self.collaborator_panes,
+                                                                        self.active_call(),
+                                                                        &self.active_pane,
+                                                                        None,
+                                                                        &self.app_state,
+                                                                        window,
+                                                                        cx,
+                                                                    )
+                                                                    .into_any_element(),
+                                                                None => self
+                                                                    .center
+                                                                    .render(
+                                                                        &self.project,
+                                                                        &self.follower_states,
+                                                                        &self.collaborator_panes,
+                                                                        self.active_call(),
+                                                                        &self.active_pane,
+                                                                        self.zoomed.as_ref(),
+                                                                        &self.app_state,
+                                                                        window,
+                                                                        cx,
+                                                                    )
+                                                                    .into_any_element(),
+                                                            };
+                                                            match center_max_width {
+                                                                Some(max_width) => {
+                                                                    this.justify_center().child(
+                                                                        div()
+                                                                            .debug_selector(|| {
+                                                                                "CENTERED_PANES"
+                                                                                    .into()
+                                                                            })
+                                                                            .h_full()
+                                                                            .flex_1()
+                                                                            .max_w(max_width)
+                                                                            .child(center),
+                                                                    )
+                                                                }
+                                                                None => this.child(center),
+                                                            }
+                                                        })
                                                         .when_some(paddings.1, |this, p| {
                                                             this.child(p.border_l_1())
-                                                        }),
+                                                        })
+                                                        .child(
+                                                            div().absolute().inset_0().children(
+                                                                self.slot_views.views(
+                                                                    WorkspaceSlot::OverCenter,
+                                                                ),
+                                                            ),
+                                                        )
+                                                        .children(
+                                                            self.render_center_edge_drop_target(cx),
+                                                        ),
                                                 )
-                                                .children(self.render_dock(
-                                                    DockPosition::Bottom,
-                                                    &self.bottom_dock,
-                                                    window,
-                                                    cx,
-                                                )),
+                                                .when(!self.zen_mode, |this| {
+                                                    this.child(self.secondary_status_bar.clone())
+                                                })
+                                                .when(!overlay_docks, |this| {
+                                                    this.children(
+                                                        self.render_bottom_docks(window, cx),
+                                                    )
+                                                }),
                                         )
                                         // Right Dock
-                                        .children(self.render_dock(
-                                            DockPosition::Right,
-                                            &self.right_dock,
-                                            window,
-                                            cx,
-                                        )),
+                                        .when(!overlay_docks, |this| {
+                                            this.children(self.render_dock(
+                                                DockPosition::Right,
+                                                &self.right_dock,
+                                                window,
+                                                cx,
+                                            ))
+                                        }),
                                 )
+                                .when(overlay_docks, |this| {
+                                    this.children(self.render_overlay_docks(window, cx))
+                                })
                                 .children(self.zoomed.as_ref().and_then(|view| {
                                     let zoomed_view = view.upgrade()?;
                                     let div = div()
@@ -5582,7 +8506,25 @@ impl Render for Workspace {
                                 }))
                                 .children(self.render_notifications(window, cx)),
                         )
-                        .child(self.status_bar.clone())
+                        .children(self.slot_views.views(WorkspaceSlot::AboveStatusBar))
+                        .when(!self.zen_mode, |this| {
+                            let facepile = self
+                                .slot_views
+                                .views(WorkspaceSlot::Facepile)
+                                .collect::<Vec<_>>();
+                            if facepile.is_empty() {
+                                this.child(self.status_bar.clone())
+                            } else {
+                                this.child(
+                                    h_flex()
+                                        .w_full()
+                                        .child(
+                                            div().flex_1().min_w_0().child(self.status_bar.clone()),
+                                        )
+                                        .children(facepile),
+                                )
+                            }
+                        })
                         .child(self.modal_layer.clone())
                         .child(self.toast_layer.clone()),
                 ),
@@ -5599,9 +8541,12 @@ fn resize_bottom_dock(
     cx: &mut App,
 ) {
     let size = new_size.min(workspace.bounds.bottom() - RESIZE_HANDLE_SIZE);
-    workspace.bottom_dock.update(cx, |bottom_dock, cx| {
-        bottom_dock.resize_active_panel(Some(size), window, cx);
-    });
+    // Both bottom docks share a row, so keep their heights in sync.
+    for dock in [&workspace.bottom_dock, &workspace.bottom_right_dock] {
+        dock.update(cx, |bottom_dock, cx| {
+            bottom_dock.resize_active_panel(Some(size), window, cx);
+        });
+    }
 }
 
 fn resize_right_dock(
@@ -6179,6 +9124,21 @@ pub fn open_paths(
     })
 }
 
+/// Where [`Workspace::open_item_with_placement`] puts an item.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Placement {
+    ActivePane,
+    /// A new pane to the right of the active pane.
+    SplitRight,
+    /// The center pane at the given index, or a new pane split off the active pane if there
+    /// is no pane at that index.
+    PaneIndex(usize),
+    /// A new window for the same project.
+    NewWindow,
+    /// The pane of the dock's active panel, such as the terminal panel.
+    Dock(DockPosition),
+}
+
 pub fn open_new(
     open_options: OpenOptions,
     app_state: Arc<AppState>,
@@ -6875,16 +9835,20 @@ mod tests {
 
     use super::*;
     use crate::{
-        dock::{test::TestPanel, PanelEvent},
+        dock::{test::TestPanel, DockEvent, PanelEvent},
+        dock_behavior::{DockBehaviorPreset, DockOptions},
+        dock_switcher::DockSwitcher,
         item::{
             test::{TestItem, TestProjectItem},
             ItemEvent,
         },
+        test::{TestStatusItem, TestToast},
+        workspace_settings::CenteredLayoutSettings,
     };
     use fs::FakeFs;
     use gpui::{
-        px, DismissEvent, Empty, EventEmitter, FocusHandle, Focusable, Render, TestAppContext,
-        UpdateGlobal, VisualTestContext,
+        px, DismissEvent, Empty, EventEmitter, FocusHandle, Focusable, Modifiers, Render,
+        TestAppContext, UpdateGlobal, VisualTestContext,
     };
     use project::{Project, ProjectEntryId};
     use serde_json::json;
@@ -7071,9 +10035,9 @@ mod tests {
             w.prepare_to_close(CloseIntent::CloseWindow, window, cx)
         });
         cx.executor().run_until_parked();
-        cx.simulate_prompt_answer("Cancel"); // cancel save all
+        workspace.update(cx, |w, cx| w.simulate_prompt_answer("Cancel", cx)); // cancel save all
         cx.executor().run_until_parked();
-        assert!(!cx.has_pending_prompt());
+        assert!(workspace.read_with(cx, |w, cx| w.pending_prompt(cx).is_none()));
         assert!(!task.await.unwrap());
     }
 
@@ -7116,9 +10080,80 @@ mod tests {
     }
 
     #[gpui::test]
-    async fn test_close_pane_items(cx: &mut TestAppContext) {
+    async fn test_move_item_to_new_window(cx: &mut TestAppContext) {
         init_test(cx);
+        cx.update(|cx| {
+            register_serializable_item::<TestItem>(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let source_workspace_id = WorkspaceId(1);
+        workspace.update(cx, |workspace, _| {
+            workspace.database_id = Some(source_workspace_id)
+        });
+
+        let item_1 = cx.new(|cx| TestItem::new(cx).with_label("1"));
+        let item_2 = cx.new(|cx| {
+            TestItem::new(cx)
+                .with_label("2")
+                .with_dirty(true)
+                .with_serialize(|| Some(Task::ready(Ok(()))))
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item_1.clone()), None, true, window, cx);
+            workspace.add_item_to_active_pane(Box::new(item_2.clone()), None, true, window, cx);
+        });
+
+        let new_window = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.move_active_item_to_new_window(window, cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
 
+        // The item is gone from the original window, without prompting to save it.
+        assert!(workspace.read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_none()));
+        workspace.update(cx, |workspace, cx| {
+            let pane = workspace.active_pane().read(cx);
+            assert_eq!(pane.items_len(), 1);
+            assert_eq!(pane.active_item().unwrap().item_id(), item_1.item_id());
+        });
+
+        // The new window shows the same project, with the item restored from its serialized state.
+        new_window
+            .read_with(cx, |workspace, cx| {
+                assert_eq!(workspace.project(), &project);
+                let item = workspace
+                    .active_item(cx)
+                    .and_then(|item| item.downcast::<TestItem>())
+                    .unwrap();
+                assert_ne!(item.item_id(), item_2.item_id());
+                // It's restored from the state stored under the new window's workspace.
+                assert!(workspace.database_id().is_some());
+                assert_eq!(item.read(cx).workspace_id, workspace.database_id());
+                assert_ne!(workspace.database_id(), Some(source_workspace_id));
+            })
+            .unwrap();
+
+        // Items that can't be serialized or reopened stay where they are.
+        assert!(workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.move_active_item_to_new_window(window, cx)
+            })
+            .await
+            .is_err());
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.active_pane().read(cx).items_len(), 1);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_close_all_items_in_other_panes(cx: &mut TestAppContext) {
+        init_test(cx);
         let fs = FakeFs::new(cx.executor());
 
         let project = Project::test(fs, None, cx).await;
@@ -7133,31 +10168,96 @@ mod tests {
         let item2 = cx.new(|cx| {
             TestItem::new(cx)
                 .with_dirty(true)
-                .with_conflict(true)
                 .with_project_items(&[dirty_project_item(2, "2.txt", cx)])
         });
         let item3 = cx.new(|cx| {
             TestItem::new(cx)
                 .with_dirty(true)
-                .with_conflict(true)
                 .with_project_items(&[dirty_project_item(3, "3.txt", cx)])
         });
         let item4 = cx.new(|cx| {
-            TestItem::new(cx).with_dirty(true).with_project_items(&[{
-                let project_item = TestProjectItem::new_untitled(cx);
-                project_item.update(cx, |project_item, _| project_item.is_dirty = true);
-                project_item
-            }])
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(4, "4.txt", cx)])
         });
-        let pane = workspace.update_in(cx, |workspace, window, cx| {
+        let pane1 = workspace.update_in(cx, |workspace, window, cx| {
             workspace.add_item_to_active_pane(Box::new(item1.clone()), None, true, window, cx);
-            workspace.add_item_to_active_pane(Box::new(item2.clone()), None, true, window, cx);
-            workspace.add_item_to_active_pane(Box::new(item3.clone()), None, true, window, cx);
-            workspace.add_item_to_active_pane(Box::new(item4.clone()), None, true, window, cx);
-            workspace.active_pane().clone()
+            let pane1 = workspace.active_pane().clone();
+            let pane2 = workspace.split_pane(pane1.clone(), SplitDirection::Right, window, cx);
+            pane2.update(cx, |pane, cx| {
+                pane.add_item(Box::new(item2.clone()), true, true, None, window, cx)
+            });
+            let pane3 = workspace.split_pane(pane2, SplitDirection::Down, window, cx);
+            pane3.update(cx, |pane, cx| {
+                pane.add_item(Box::new(item3.clone()), true, true, None, window, cx);
+                pane.add_item(Box::new(item4.clone()), true, true, None, window, cx);
+            });
+            window.focus(&pane1.focus_handle(cx));
+            pane1
         });
+        cx.run_until_parked();
 
-        let close_items = pane.update_in(cx, |pane, window, cx| {
+        cx.dispatch_action(CloseAllItemsInOtherPanes::default());
+        cx.run_until_parked();
+
+        // The dirty items of both other panes are confirmed with a single prompt.
+        workspace.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Save all", cx)
+        });
+        cx.run_until_parked();
+        assert!(workspace.read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_none()));
+
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.panes(), &[pane1.clone()]);
+            assert_eq!(pane1.read(cx).items_len(), 1);
+            assert_eq!(item1.read(cx).save_count, 0);
+            assert_eq!(item2.read(cx).save_count, 1);
+            assert_eq!(item3.read(cx).save_count, 1);
+            assert_eq!(item4.read(cx).save_count, 0);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_close_pane_items(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let item1 = cx.new(|cx| {
+            TestItem::new(cx)
+                .with_dirty(true)
+                .with_project_items(&[dirty_project_item(1, "1.txt", cx)])
+        });
+        let item2 = cx.new(|cx| {
+            TestItem::new(cx)
+                .with_dirty(true)
+                .with_conflict(true)
+                .with_project_items(&[dirty_project_item(2, "2.txt", cx)])
+        });
+        let item3 = cx.new(|cx| {
+            TestItem::new(cx)
+                .with_dirty(true)
+                .with_conflict(true)
+                .with_project_items(&[dirty_project_item(3, "3.txt", cx)])
+        });
+        let item4 = cx.new(|cx| {
+            TestItem::new(cx).with_dirty(true).with_project_items(&[{
+                let project_item = TestProjectItem::new_untitled(cx);
+                project_item.update(cx, |project_item, _| project_item.is_dirty = true);
+                project_item
+            }])
+        });
+        let pane = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item1.clone()), None, true, window, cx);
+            workspace.add_item_to_active_pane(Box::new(item2.clone()), None, true, window, cx);
+            workspace.add_item_to_active_pane(Box::new(item3.clone()), None, true, window, cx);
+            workspace.add_item_to_active_pane(Box::new(item4.clone()), None, true, window, cx);
+            workspace.active_pane().clone()
+        });
+
+        let close_items = pane.update_in(cx, |pane, window, cx| {
             pane.activate_item(1, true, true, window, cx);
             assert_eq!(pane.active_item().unwrap().item_id(), item2.item_id());
             let item1_id = item1.item_id();
@@ -7169,8 +10269,16 @@ mod tests {
         });
         cx.executor().run_until_parked();
 
-        assert!(cx.has_pending_prompt());
-        cx.simulate_prompt_answer("Save all");
+        // All dirty items are listed in a single prompt.
+        let prompt = workspace
+            .read_with(cx, |workspace, cx| workspace.pending_prompt(cx))
+            .unwrap();
+        prompt.read_with(cx, |prompt, _| {
+            assert_eq!(prompt.checklist(), ["1.txt", "3.txt", "untitled"]);
+        });
+        workspace.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Save all", cx)
+        });
 
         cx.executor().run_until_parked();
 
@@ -7320,13 +10428,22 @@ mod tests {
         });
         cx.executor().run_until_parked();
 
-        let msg = cx.pending_prompt().unwrap().0;
+        let msg = workspace.read_with(cx, |workspace, cx| {
+            workspace
+                .pending_prompt(cx)
+                .unwrap()
+                .read(cx)
+                .message()
+                .to_string()
+        });
         assert!(msg.contains("1.txt"));
         assert!(!msg.contains("2.txt"));
         assert!(!msg.contains("3.txt"));
         assert!(!msg.contains("4.txt"));
 
-        cx.simulate_prompt_answer("Cancel");
+        workspace.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Cancel", cx)
+        });
         close.await.unwrap();
 
         left_pane
@@ -7347,7 +10464,14 @@ mod tests {
         });
         cx.executor().run_until_parked();
 
-        let details = cx.pending_prompt().unwrap().1;
+        let details = workspace.read_with(cx, |workspace, cx| {
+            workspace
+                .pending_prompt(cx)
+                .unwrap()
+                .read(cx)
+                .checklist()
+                .join("\n")
+        });
         assert!(details.contains("1.txt"));
         assert!(!details.contains("2.txt"));
         assert!(details.contains("3.txt"));
@@ -7356,7 +10480,9 @@ mod tests {
         // 4 to be saved too.
         // assert!(!details.contains("4.txt"));
 
-        cx.simulate_prompt_answer("Save all");
+        workspace.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Save all", cx)
+        });
 
         cx.executor().run_until_parked();
         close.await.unwrap();
@@ -7365,6 +10491,146 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_autosave_opt_out(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let item = cx.new(|cx| {
+            TestItem::new(cx)
+                .with_autosave(false)
+                .with_project_items(&[TestProjectItem::new(1, "1.txt", cx)])
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item.clone()), None, true, window, cx);
+        });
+
+        item.update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            SettingsStore::update_global(cx, |settings, cx| {
+                settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                    settings.autosave = Some(AutosaveSetting::OnFocusChange);
+                })
+            });
+            item.is_dirty = true;
+        });
+        item.update_in(cx, |_, window, _| window.blur());
+        cx.deactivate_window();
+        cx.executor().run_until_parked();
+        item.update(cx, |item, _| assert_eq!(item.save_count, 0));
+
+        item.update(cx, |item, cx| {
+            SettingsStore::update_global(cx, |settings, cx| {
+                settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                    settings.autosave = Some(AutosaveSetting::AfterDelay { milliseconds: 500 });
+                })
+            });
+            cx.emit(ItemEvent::Edit);
+        });
+        cx.executor().advance_clock(Duration::from_millis(1000));
+        item.update(cx, |item, _| {
+            assert_eq!(item.save_count, 0);
+            assert!(item.is_dirty);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_collaborator_panes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let left_pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let right_pane = split_pane(cx, &workspace);
+
+        let peer_a = PeerId { owner_id: 1, id: 1 };
+        let peer_b = PeerId { owner_id: 1, id: 2 };
+        workspace.update(cx, |workspace, cx| {
+            workspace.set_collaborator_pane(peer_a, Some(&left_pane), cx);
+            workspace.set_collaborator_pane(peer_b, Some(&right_pane), cx);
+            assert_eq!(workspace.collaborators_in_pane(&left_pane), vec![peer_a]);
+            assert_eq!(
+                workspace.collaborator_pane(peer_b),
+                Some(right_pane.clone())
+            );
+
+            workspace.set_collaborator_pane(peer_b, Some(&left_pane), cx);
+            assert!(workspace.collaborators_in_pane(&right_pane).is_empty());
+
+            workspace.set_collaborator_pane(peer_a, None, cx);
+            assert_eq!(workspace.collaborators_in_pane(&left_pane), vec![peer_b]);
+        });
+
+        // Removing a pane forgets the collaborators that were active in it.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.remove_pane(left_pane.clone(), None, window, cx);
+            assert_eq!(workspace.collaborator_pane(peer_b), None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_collaborator_pane_from_active_view(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx)
+            .files(json!({ "a.txt": "", "b.txt": "" }))
+            .build()
+            .await;
+        let project = test.project.clone();
+        let workspace = test.workspace.clone();
+        let cx = &mut test.cx;
+        let left_pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+        let right_pane = split_pane(cx, &workspace);
+
+        let project_path = |name: &str, cx: &mut VisualTestContext| {
+            project.read_with(cx, |project, cx| ProjectPath {
+                worktree_id: project.worktrees(cx).next().unwrap().read(cx).id(),
+                path: Path::new(name).into(),
+            })
+        };
+        let a_path = project_path("a.txt", cx);
+        let b_path = project_path("b.txt", cx);
+        let buffer = project
+            .update(cx, |project, cx| project.open_buffer(a_path.clone(), cx))
+            .await
+            .unwrap();
+        let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+        for (pane, path) in [(&left_pane, b_path), (&right_pane, a_path)] {
+            pane.update_in(cx, |pane, window, cx| {
+                let project_item = TestProjectItem::new(1, "", cx);
+                project_item.update(cx, |project_item, _| project_item.project_path = Some(path));
+                let item = cx.new(|cx| TestItem::new(cx).with_project_items(&[project_item]));
+                pane.add_item(Box::new(item), true, true, None, window, cx);
+            });
+        }
+
+        // A collaborator's editor is matched to the pane showing the same file.
+        let peer = PeerId { owner_id: 1, id: 1 };
+        let editor_view = proto::View {
+            variant: Some(proto::view::Variant::Editor(proto::view::Editor {
+                singleton: true,
+                excerpts: vec![proto::Excerpt {
+                    buffer_id: buffer_id.to_proto(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        workspace.update(cx, |workspace, cx| {
+            workspace.collaborator_view_changed(peer, Some(&editor_view), cx);
+            assert_eq!(workspace.collaborator_pane(peer), Some(right_pane.clone()));
+
+            // Views that aren't open here remove the outline.
+            workspace.collaborator_view_changed(peer, None, cx);
+            assert_eq!(workspace.collaborator_pane(peer), None);
+        });
+    }
+
     #[gpui::test]
     async fn test_autosave(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -7463,7 +10729,7 @@ mod tests {
         })
         .await
         .unwrap();
-        assert!(!cx.has_pending_prompt());
+        assert!(workspace.read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_none()));
         item.update(cx, |item, _| assert_eq!(item.save_count, 5));
 
         // Add the item again, ensuring autosave is prevented if the underlying file has been deleted.
@@ -7485,7 +10751,7 @@ mod tests {
             pane.close_items(window, cx, SaveIntent::Close, move |id| id == item_id)
         });
         cx.run_until_parked();
-        assert!(cx.has_pending_prompt());
+        assert!(workspace.read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_some()));
         item.update(cx, |item, _| assert_eq!(item.save_count, 5));
     }
 
@@ -7547,85 +10813,346 @@ mod tests {
     }
 
     #[gpui::test]
-    async fn test_toggle_docks_and_panels(cx: &mut gpui::TestAppContext) {
+    async fn test_split_center_edge_with_item(cx: &mut TestAppContext) {
         init_test(cx);
         let fs = FakeFs::new(cx.executor());
-
         let project = Project::test(fs, [], cx).await;
         let (workspace, cx) =
             cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
 
-        let panel = workspace.update_in(cx, |workspace, window, cx| {
-            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
-            workspace.add_panel(panel.clone(), window, cx);
-
-            workspace
-                .right_dock()
-                .update(cx, |right_dock, cx| right_dock.set_open(true, window, cx));
-
-            panel
+        let first_pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let items = (0..3).map(|_| cx.new(TestItem::new)).collect::<Vec<_>>();
+        workspace.update_in(cx, |workspace, window, cx| {
+            for item in &items {
+                workspace.add_item_to_active_pane(Box::new(item.clone()), None, true, window, cx);
+            }
         });
+        let second_pane = split_pane(cx, &workspace);
 
-        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
-        pane.update_in(cx, |pane, window, cx| {
-            let item = cx.new(TestItem::new);
-            pane.add_item(Box::new(item), true, true, None, window, cx);
+        // Splitting along the axis of the root adds a pane to it.
+        let right_pane = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_center_edge_with_item(
+                &first_pane,
+                items[0].entity_id(),
+                SplitDirection::Right,
+                window,
+                cx,
+            )
         });
-
-        // Transfer focus from center to panel
-        workspace.update_in(cx, |workspace, window, cx| {
-            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        workspace.update(cx, |workspace, _| {
+            let Member::Axis(root) = &workspace.center.root else {
+                panic!("expected an axis");
+            };
+            assert_eq!(root.axis, gpui::Axis::Horizontal);
+            assert_eq!(root.members.len(), 3);
+            assert_eq!(*root.flexes.lock(), vec![1., 1., 1.]);
+            assert_eq!(
+                workspace.center.panes(),
+                [&first_pane, &second_pane, &right_pane]
+            );
         });
 
-        workspace.update_in(cx, |workspace, window, cx| {
-            assert!(workspace.right_dock().read(cx).is_open());
-            assert!(!panel.is_zoomed(window, cx));
-            assert!(panel.read(cx).focus_handle(cx).contains_focused(window, cx));
+        // Splitting across it wraps the root in a new axis.
+        let bottom_pane = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_center_edge_with_item(
+                &first_pane,
+                items[1].entity_id(),
+                SplitDirection::Down,
+                window,
+                cx,
+            )
+        });
+        workspace.update(cx, |workspace, cx| {
+            let Member::Axis(root) = &workspace.center.root else {
+                panic!("expected an axis");
+            };
+            assert_eq!(root.axis, gpui::Axis::Vertical);
+            assert_eq!(root.members.len(), 2);
+            assert!(matches!(&root.members[0], Member::Axis(axis) if axis.members.len() == 3));
+            assert_eq!(workspace.center.panes().last(), Some(&&bottom_pane));
+            assert_eq!(
+                bottom_pane
+                    .read(cx)
+                    .active_item()
+                    .map(|item| item.item_id()),
+                Some(items[1].entity_id())
+            );
         });
+    }
 
-        // Transfer focus from panel to center
-        workspace.update_in(cx, |workspace, window, cx| {
-            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+    #[gpui::test]
+    async fn test_add_paths_to_project(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root1", json!({ "a.txt": "" })).await;
+        fs.insert_tree("/root2", json!({ "b.txt": "" })).await;
+        let project = Project::test(fs, ["/root1".as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.add_paths_to_project(vec![PathBuf::from("/root2")], window, cx)
+            })
+            .await;
+        project.read_with(cx, |project, cx| {
+            let root_names = project
+                .visible_worktrees(cx)
+                .map(|worktree| worktree.read(cx).root_name().to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(root_names, ["root1", "root2"]);
         });
+    }
+
+    #[gpui::test]
+    async fn test_zen_mode(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
 
         workspace.update_in(cx, |workspace, window, cx| {
-            assert!(workspace.right_dock().read(cx).is_open());
-            assert!(!panel.is_zoomed(window, cx));
-            assert!(!panel.read(cx).focus_handle(cx).contains_focused(window, cx));
+            let left_panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(left_panel, window, cx);
+            let right_panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(right_panel, window, cx);
+            workspace
+                .left_dock()
+                .update(cx, |dock, cx| dock.set_open(true, window, cx));
         });
 
-        // Close the dock
-        workspace.update_in(cx, |workspace, window, cx| {
-            workspace.toggle_dock(DockPosition::Right, window, cx);
+        // Entering zen mode closes the docks.
+        cx.dispatch_action(ToggleZenMode);
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.is_zen_mode());
+            assert!(!workspace.left_dock().read(cx).is_open());
+            assert!(!workspace.right_dock().read(cx).is_open());
         });
 
+        // Docks opened while in zen mode are closed again when leaving it, and the ones that
+        // were open before are reopened.
         workspace.update_in(cx, |workspace, window, cx| {
+            workspace
+                .right_dock()
+                .update(cx, |dock, cx| dock.set_open(true, window, cx));
+        });
+        cx.dispatch_action(ToggleZenMode);
+        workspace.update(cx, |workspace, cx| {
+            assert!(!workspace.is_zen_mode());
+            assert!(workspace.left_dock().read(cx).is_open());
             assert!(!workspace.right_dock().read(cx).is_open());
-            assert!(!panel.is_zoomed(window, cx));
-            assert!(!panel.read(cx).focus_handle(cx).contains_focused(window, cx));
         });
+    }
 
-        // Open the dock
-        workspace.update_in(cx, |workspace, window, cx| {
-            workspace.toggle_dock(DockPosition::Right, window, cx);
+    #[gpui::test]
+    async fn test_centered_layout_max_width(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.centered_layout = Some(CenteredLayoutSettings {
+                    left_padding: Some(0.1),
+                    right_padding: Some(0.1),
+                    max_width: Some(600.),
+                });
+            });
         });
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        cx.simulate_resize(size(px(1600.), px(800.)));
 
         workspace.update_in(cx, |workspace, window, cx| {
-            assert!(workspace.right_dock().read(cx).is_open());
-            assert!(!panel.is_zoomed(window, cx));
-            assert!(panel.read(cx).focus_handle(cx).contains_focused(window, cx));
+            let item = cx.new(TestItem::new);
+            workspace.add_item_to_active_pane(Box::new(item), None, true, window, cx);
+            workspace.toggle_centered_layout(&ToggleCenteredLayout, window, cx);
         });
+        test::draw(cx);
 
-        // Focus and zoom panel
-        panel.update_in(cx, |panel, window, cx| {
-            cx.focus_self(window);
-            panel.set_zoomed(true, window, cx)
-        });
+        // The panes are no wider than the maximum width, with the paddings taking up the rest
+        // of the center evenly.
+        let center = cx.debug_bounds("CENTER").unwrap();
+        let panes = cx.debug_bounds("CENTERED_PANES").unwrap();
+        assert_eq!(panes.size.width, px(600.));
+        assert_eq!(panes.left() - center.left(), center.right() - panes.right());
 
+        // Without the centered layout, the panes fill the center.
         workspace.update_in(cx, |workspace, window, cx| {
-            assert!(workspace.right_dock().read(cx).is_open());
-            assert!(panel.is_zoomed(window, cx));
-            assert!(panel.read(cx).focus_handle(cx).contains_focused(window, cx));
+            workspace.toggle_centered_layout(&ToggleCenteredLayout, window, cx);
+        });
+        test::draw(cx);
+        assert!(cx.debug_bounds("CENTERED_PANES").is_none());
+    }
+
+    #[gpui::test]
+    async fn test_maximize_editor_and_restore_docks(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx)
+            .panel(DockPosition::Left)
+            .panel(DockPosition::Bottom)
+            .panel(DockPosition::Right)
+            .build()
+            .await;
+        let (left_panel, bottom_panel) = (test.panels[0].clone(), test.panels[1].clone());
+        let workspace = test.workspace.clone();
+        let cx = &mut test.cx;
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            workspace.toggle_dock(DockPosition::Bottom, window, cx);
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.resize_active_panel(Some(px(400.)), window, cx)
+            });
+        });
+        let open_docks = |cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| {
+                workspace
+                    .all_docks()
+                    .into_iter()
+                    .filter(|dock| dock.read(cx).is_open())
+                    .map(|dock| dock.read(cx).position())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // Maximizing closes the open docks and focuses the center.
+        cx.dispatch_action(MaximizeEditor);
+        assert!(open_docks(cx).is_empty());
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.has_docks_to_restore());
+            assert!(workspace
+                .active_pane()
+                .focus_handle(cx)
+                .contains_focused(window, cx));
+        });
+
+        // Maximizing again with no dock open keeps what to restore.
+        cx.dispatch_action(MaximizeEditor);
+
+        // Resizing a panel while its dock is closed doesn't change the restored size, and a
+        // panel added in front of it doesn't change which panel is restored.
+        left_panel.update_in(cx, |panel, window, cx| {
+            panel.set_size(Some(px(200.)), window, cx)
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            let other_panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(other_panel, window, cx);
+        });
+        cx.dispatch_action(RestoreDocks);
+        assert_eq!(open_docks(cx), [DockPosition::Left, DockPosition::Bottom]);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(!workspace.has_docks_to_restore());
+            assert_eq!(
+                workspace
+                    .left_dock()
+                    .read(cx)
+                    .active_panel()
+                    .map(|panel| panel.panel_id()),
+                Some(left_panel.entity_id())
+            );
+            assert_eq!(left_panel.read(cx).size(window, cx), px(400.));
+            assert_eq!(bottom_panel.read(cx).size(window, cx), px(300.));
+        });
+
+        // There's nothing to restore anymore.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        cx.dispatch_action(RestoreDocks);
+        assert_eq!(open_docks(cx), [DockPosition::Bottom]);
+
+        // Zen mode shares what to restore, so leaving it after maximizing reopens the docks
+        // that were open before either.
+        cx.dispatch_action(MaximizeEditor);
+        cx.dispatch_action(ToggleZenMode);
+        cx.dispatch_action(ToggleZenMode);
+        assert_eq!(open_docks(cx), [DockPosition::Bottom]);
+        workspace.update(
+            cx,
+            |workspace, _| assert!(!workspace.has_docks_to_restore()),
+        );
+    }
+
+    #[gpui::test]
+    async fn test_toggle_docks_and_panels(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+
+            workspace
+                .right_dock()
+                .update(cx, |right_dock, cx| right_dock.set_open(true, window, cx));
+
+            panel
+        });
+
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        pane.update_in(cx, |pane, window, cx| {
+            let item = cx.new(TestItem::new);
+            pane.add_item(Box::new(item), true, true, None, window, cx);
+        });
+
+        // Transfer focus from center to panel
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(!panel.is_zoomed(window, cx));
+            assert!(panel.read(cx).focus_handle(cx).contains_focused(window, cx));
+        });
+
+        // Transfer focus from panel to center
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(!panel.is_zoomed(window, cx));
+            assert!(!panel.read(cx).focus_handle(cx).contains_focused(window, cx));
+        });
+
+        // Close the dock
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Right, window, cx);
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(!workspace.right_dock().read(cx).is_open());
+            assert!(!panel.is_zoomed(window, cx));
+            assert!(!panel.read(cx).focus_handle(cx).contains_focused(window, cx));
+        });
+
+        // Open the dock
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Right, window, cx);
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(!panel.is_zoomed(window, cx));
+            assert!(panel.read(cx).focus_handle(cx).contains_focused(window, cx));
+        });
+
+        // Focus and zoom panel
+        panel.update_in(cx, |panel, window, cx| {
+            cx.focus_self(window);
+            panel.set_zoomed(true, window, cx)
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(panel.is_zoomed(window, cx));
+            assert!(panel.read(cx).focus_handle(cx).contains_focused(window, cx));
         });
 
         // Transfer focus to the center closes the dock
@@ -7696,6 +11223,69 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_auto_close_empty_panes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let left_item = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(1, "left.txt", cx)])
+        });
+        let right_item = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(2, "right.txt", cx)])
+        });
+        let (left_pane, right_pane) = workspace.update_in(cx, |workspace, window, cx| {
+            let left_pane = workspace.active_pane().clone();
+            workspace.add_item_to_active_pane(Box::new(left_item), None, true, window, cx);
+            let right_pane =
+                workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx);
+            workspace.add_item_to_active_pane(Box::new(right_item), None, true, window, cx);
+            (left_pane, right_pane)
+        });
+
+        // With the setting disabled, closing the last item leaves an empty pane behind.
+        cx.update_global(|settings: &mut SettingsStore, cx| {
+            settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.auto_close_empty_panes = Some(false);
+            })
+        });
+        right_pane
+            .update_in(cx, |pane, window, cx| {
+                pane.close_active_item(&Default::default(), window, cx)
+                    .unwrap()
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.panes().len(), 2);
+            assert_eq!(right_pane.read(cx).items_len(), 0);
+        });
+
+        // With the setting enabled, the pane closes and focus moves to its neighbor.
+        cx.update_global(|settings: &mut SettingsStore, cx| {
+            settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.auto_close_empty_panes = Some(true);
+            })
+        });
+        left_pane
+            .update_in(cx, |pane, window, cx| {
+                pane.close_active_item(&Default::default(), window, cx)
+                    .unwrap()
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.panes(), &[right_pane.clone()]);
+            assert_eq!(workspace.active_pane(), &right_pane);
+        });
+    }
+
     #[gpui::test]
     async fn test_join_pane_into_next(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -7846,137 +11436,4274 @@ mod tests {
             assert!(item_ids_in_pane.contains(&bottom_item.item_id()));
             assert!(item_ids_in_pane.contains(&left_item.item_id()));
 
-            // Join into next from left pane into top
-            workspace.join_pane_into_next(workspace.active_pane().clone(), window, cx);
+            // Join into next from left pane into top
+            workspace.join_pane_into_next(workspace.active_pane().clone(), window, cx);
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let active_pane = workspace.active_pane();
+            assert_eq!(top_pane_id, active_pane.entity_id());
+            assert_eq!(5, active_pane.read(cx).items_len());
+            let item_ids_in_pane =
+                HashSet::from_iter(active_pane.read(cx).items().map(|item| item.item_id()));
+            assert!(item_ids_in_pane.contains(&center_item.item_id()));
+            assert!(item_ids_in_pane.contains(&right_item.item_id()));
+            assert!(item_ids_in_pane.contains(&bottom_item.item_id()));
+            assert!(item_ids_in_pane.contains(&left_item.item_id()));
+            assert!(item_ids_in_pane.contains(&top_item.item_id()));
+
+            // Single pane left: no-op
+            workspace.join_pane_into_next(workspace.active_pane().clone(), window, cx)
+        });
+
+        workspace.update(cx, |workspace, _cx| {
+            let active_pane = workspace.active_pane();
+            assert_eq!(top_pane_id, active_pane.entity_id());
+        });
+    }
+
+    fn add_an_item_to_active_pane(
+        cx: &mut VisualTestContext,
+        workspace: &Entity<Workspace>,
+        item_id: u64,
+    ) -> Entity<TestItem> {
+        let item = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(
+                item_id,
+                "item{item_id}.txt",
+                cx,
+            )])
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item.clone()), None, false, window, cx);
+        });
+        return item;
+    }
+
+    fn split_pane(cx: &mut VisualTestContext, workspace: &Entity<Workspace>) -> Entity<Pane> {
+        return workspace.update_in(cx, |workspace, window, cx| {
+            let new_pane = workspace.split_pane(
+                workspace.active_pane().clone(),
+                SplitDirection::Right,
+                window,
+                cx,
+            );
+            new_pane
+        });
+    }
+
+    #[gpui::test]
+    async fn test_join_all_panes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        add_an_item_to_active_pane(cx, &workspace, 1);
+        split_pane(cx, &workspace);
+        add_an_item_to_active_pane(cx, &workspace, 2);
+        split_pane(cx, &workspace); // empty pane
+        split_pane(cx, &workspace);
+        let last_item = add_an_item_to_active_pane(cx, &workspace, 3);
+
+        cx.executor().run_until_parked();
+
+        workspace.update(cx, |workspace, cx| {
+            let num_panes = workspace.panes().len();
+            let num_items_in_current_pane = workspace.active_pane().read(cx).items().count();
+            let active_item = workspace
+                .active_pane()
+                .read(cx)
+                .active_item()
+                .expect("item is in focus");
+
+            assert_eq!(num_panes, 4);
+            assert_eq!(num_items_in_current_pane, 1);
+            assert_eq!(active_item.item_id(), last_item.item_id());
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.join_all_panes(window, cx);
+        });
+
+        workspace.update(cx, |workspace, cx| {
+            let num_panes = workspace.panes().len();
+            let num_items_in_current_pane = workspace.active_pane().read(cx).items().count();
+            let active_item = workspace
+                .active_pane()
+                .read(cx)
+                .active_item()
+                .expect("item is in focus");
+
+            assert_eq!(num_panes, 1);
+            assert_eq!(num_items_in_current_pane, 3);
+            assert_eq!(active_item.item_id(), last_item.item_id());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_apply_pane_layout(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        for id in 1..=4 {
+            add_an_item_to_active_pane(cx, &workspace, id);
+        }
+        split_pane(cx, &workspace);
+        add_an_item_to_active_pane(cx, &workspace, 5);
+        let active_item = add_an_item_to_active_pane(cx, &workspace, 6);
+        cx.run_until_parked();
+
+        let pane_item_counts = |cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| {
+                workspace
+                    .center
+                    .panes()
+                    .into_iter()
+                    .map(|pane| pane.read(cx).items_len())
+                    .collect::<Vec<_>>()
+            })
+        };
+        let assert_active_item = |cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                let pane = workspace.active_pane().read(cx);
+                assert_eq!(pane.active_item().unwrap().item_id(), active_item.item_id());
+                assert!(pane.has_focus(window, cx));
+            })
+        };
+
+        // The active item stays in the first pane, and the others are dealt out in order.
+        cx.dispatch_action(ApplyPaneLayout(PaneLayout::Grid2x2));
+        cx.run_until_parked();
+        // Panes are listed column by column.
+        assert_eq!(pane_item_counts(cx), [2, 1, 2, 1]);
+        assert_active_item(cx);
+        workspace.update(cx, |workspace, _| {
+            let Member::Axis(columns) = &workspace.center.root else {
+                panic!("expected the grid to have columns");
+            };
+            assert_eq!(columns.axis, gpui::Axis::Horizontal);
+            assert_eq!(columns.members.len(), 2);
+            assert!(columns.members.iter().all(|column| {
+                matches!(
+                    column,
+                    Member::Axis(rows)
+                        if rows.axis == gpui::Axis::Vertical && rows.members.len() == 2
+                )
+            }));
+        });
+
+        cx.dispatch_action(ApplyPaneLayout(PaneLayout::ThreeColumns));
+        cx.run_until_parked();
+        assert_eq!(pane_item_counts(cx), [2, 2, 2]);
+        assert_active_item(cx);
+
+        cx.dispatch_action(ApplyPaneLayout(PaneLayout::MainAndStack));
+        cx.run_until_parked();
+        assert_eq!(pane_item_counts(cx), [2, 2, 2]);
+        assert_active_item(cx);
+        workspace.update(cx, |workspace, _| {
+            let Member::Axis(root) = &workspace.center.root else {
+                panic!("expected the main pane beside the stack");
+            };
+            assert_eq!(root.axis, gpui::Axis::Horizontal);
+            assert!(matches!(root.members[0], Member::Pane(_)));
+            assert!(matches!(
+                &root.members[1],
+                Member::Axis(stack) if stack.axis == gpui::Axis::Vertical
+            ));
+            let flexes = root.flexes.lock();
+            assert!(flexes[0] > flexes[1]);
+        });
+    }
+
+    #[test]
+    fn test_clamp_window_bounds() {
+        let display = Bounds::new(point(px(1920.), px(0.)), size(px(1280.), px(800.)));
+
+        // Bounds that fit on the display are kept.
+        let bounds = Bounds::new(point(px(2000.), px(100.)), size(px(800.), px(600.)));
+        assert_eq!(
+            clamp_window_bounds(WindowBounds::Windowed(bounds), display),
+            WindowBounds::Windowed(bounds)
+        );
+
+        // A window from a disconnected display is moved onto this one.
+        let bounds = Bounds::new(point(px(-1500.), px(200.)), size(px(800.), px(600.)));
+        assert_eq!(
+            clamp_window_bounds(WindowBounds::Windowed(bounds), display),
+            WindowBounds::Windowed(Bounds::new(
+                point(px(1920.), px(200.)),
+                size(px(800.), px(600.))
+            ))
+        );
+
+        // A window larger than the display is shrunk to fit it.
+        let bounds = Bounds::new(point(px(1900.), px(-50.)), size(px(2560.), px(1440.)));
+        assert_eq!(
+            clamp_window_bounds(WindowBounds::Maximized(bounds), display),
+            WindowBounds::Maximized(display)
+        );
+    }
+
+    #[gpui::test]
+    async fn test_open_item_with_placement(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let first_pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let open = |placement: Placement, cx: &mut VisualTestContext| {
+            let item = cx.new(TestItem::new);
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.open_item_with_placement(
+                        Box::new(item.clone()),
+                        placement,
+                        window,
+                        cx,
+                    )
+                })
+                .detach();
+            cx.run_until_parked();
+            item
+        };
+
+        let item_a = open(Placement::ActivePane, cx);
+        assert_eq!(first_pane.read_with(cx, |pane, _| pane.items_len()), 1);
+
+        let item_b = open(Placement::SplitRight, cx);
+        let second_pane = workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.panes().len(), 2);
+            let pane = workspace.active_pane().clone();
+            assert_ne!(pane, first_pane);
+            assert_eq!(
+                pane.read(cx).active_item().map(|item| item.item_id()),
+                Some(item_b.entity_id())
+            );
+            pane
+        });
+
+        // Opening at an existing index uses that pane, and an index past the last pane
+        // splits a new one off.
+        let item_c = open(Placement::PaneIndex(0), cx);
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.panes().len(), 2);
+            assert_eq!(workspace.active_pane(), &first_pane);
+            let item_ids = first_pane
+                .read(cx)
+                .items()
+                .map(|item| item.item_id())
+                .collect::<Vec<_>>();
+            assert_eq!(item_ids, vec![item_a.entity_id(), item_c.entity_id()]);
+        });
+        open(Placement::PaneIndex(5), cx);
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.panes().len(), 3);
+            assert_ne!(workspace.active_pane(), &first_pane);
+            assert_ne!(workspace.active_pane(), &second_pane);
+        });
+
+        // Docks without a pane can't take items.
+        let item = cx.new(TestItem::new);
+        let result = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_item_with_placement(
+                    Box::new(item),
+                    Placement::Dock(DockPosition::Bottom),
+                    window,
+                    cx,
+                )
+            })
+            .await;
+        assert!(result.is_err());
+
+        // A dock whose active panel has a pane opens the item there.
+        let (panel, panel_pane) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_pane = cx.new(|cx| {
+                Pane::new(
+                    workspace.weak_handle(),
+                    workspace.project().clone(),
+                    Default::default(),
+                    None,
+                    NewFile.boxed_clone(),
+                    window,
+                    cx,
+                )
+            });
+            let panel = cx.new(|cx| {
+                let mut panel = TestPanel::new(DockPosition::Bottom, cx);
+                panel.pane = Some(panel_pane.clone());
+                panel
+            });
+            workspace.add_panel(panel.clone(), window, cx);
+            (panel, panel_pane)
+        });
+        let item_d = cx.new(TestItem::new);
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_item_with_placement(
+                    Box::new(item_d.clone()),
+                    Placement::Dock(DockPosition::Bottom),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        workspace.update(cx, |workspace, cx| {
+            let dock = workspace.bottom_dock().read(cx);
+            assert!(dock.is_open());
+            assert_eq!(
+                dock.active_panel().map(|active| active.panel_id()),
+                Some(panel.entity_id())
+            );
+        });
+        panel_pane.read_with(cx, |pane, _| {
+            assert_eq!(
+                pane.active_item().map(|item| item.item_id()),
+                Some(item_d.entity_id())
+            );
+        });
+
+        // A new window for the project shows the item itself, even though it can't be
+        // serialized.
+        let item_e = cx.new(|cx| TestItem::new(cx).with_dirty(true));
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_item_with_placement(
+                    Box::new(item_e.clone()),
+                    Placement::NewWindow,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        let new_workspace = cx
+            .windows()
+            .into_iter()
+            .filter_map(|window| window.downcast::<Workspace>())
+            .map(|window| window.entity(cx).unwrap())
+            .find(|new_workspace| *new_workspace != workspace)
+            .unwrap();
+        new_workspace.read_with(cx, |new_workspace, cx| {
+            assert_eq!(new_workspace.project(), &project);
+            assert_eq!(
+                new_workspace.active_item(cx).map(|item| item.item_id()),
+                Some(item_e.entity_id())
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_export_and_import_layout(cx: &mut TestAppContext) {
+        use layout_export::{ExportedItem, ExportedLayout, ExportedMember, ExportedPane};
+
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let item_a = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(1, "a.txt", cx)])
+        });
+        let item_b = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(2, "b.txt", cx)])
+        });
+        let untitled = cx.new(TestItem::new);
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item_a), None, true, window, cx);
+            workspace.add_item_to_active_pane(Box::new(item_b), None, true, window, cx);
+        });
+        split_pane(cx, &workspace);
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(untitled), None, true, window, cx);
+        });
+
+        // Items without a file aren't exported.
+        let layout = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.export_layout(window, cx)
+        });
+        assert_eq!(
+            layout.center,
+            ExportedMember::Group {
+                axis: gpui::Axis::Horizontal,
+                flexes: Some(vec![1., 1.]),
+                members: vec![
+                    ExportedMember::Pane(ExportedPane {
+                        active: false,
+                        items: vec![
+                            ExportedItem {
+                                worktree: None,
+                                path: "a.txt".into(),
+                            },
+                            ExportedItem {
+                                worktree: None,
+                                path: "b.txt".into(),
+                            },
+                        ],
+                        active_item: Some(1),
+                    }),
+                    ExportedMember::Pane(ExportedPane {
+                        active: true,
+                        items: Vec::new(),
+                        active_item: None,
+                    }),
+                ],
+            }
+        );
+        let json = serde_json::to_string(&layout).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ExportedLayout>(&json).unwrap(),
+            layout
+        );
+
+        // Unknown fields are ignored when importing.
+        let layout: ExportedLayout = serde_json::from_value(json!({
+            "center": {
+                "group": {
+                    "axis": "Vertical",
+                    "flexes": [0.5, 1.5],
+                    "members": [
+                        { "pane": { "active": true } },
+                        { "pane": { "items": [], "tab_order": "alphabetical" } }
+                    ]
+                }
+            },
+            "theme": "One Dark"
+        }))
+        .unwrap();
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.import_layout(layout, window, cx)
+            })
+            .await
+            .unwrap();
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.panes().len(), 2);
+            let Member::Axis(root) = &workspace.center.root else {
+                panic!("expected the imported panes to be split");
+            };
+            assert_eq!(root.axis, gpui::Axis::Vertical);
+            assert_eq!(*root.flexes.lock(), vec![0.5, 1.5]);
+            // The items that were already open are kept in the first pane.
+            assert_eq!(workspace.panes()[0].read(cx).items_len(), 3);
+            assert_eq!(workspace.panes()[1].read(cx).items_len(), 0);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_import_layout_opens_files(cx: &mut TestAppContext) {
+        use layout_export::{ExportedItem, ExportedLayout, ExportedMember, ExportedPane};
+
+        let mut test = test::TestWorkspace::new(cx)
+            .files(json!({ "a.txt": "", "b.txt": "" }))
+            .build()
+            .await;
+        test.cx.update(|_, cx| {
+            cx.default_global::<ProjectItemOpeners>()
+                .push(|_, project_path, window, cx| {
+                    let path = project_path.path.to_string_lossy().to_string();
+                    let entry_id = path.as_bytes()[0] as u64;
+                    Some(window.spawn(cx, async move |_| {
+                        let build_item: WorkspaceItemBuilder = Box::new(move |_, cx| {
+                            Box::new(cx.new(|cx| {
+                                TestItem::new(cx).with_label(&path).with_project_items(&[
+                                    TestProjectItem::new(entry_id, &path, cx),
+                                ])
+                            })) as Box<dyn ItemHandle>
+                        });
+                        Ok((None, build_item))
+                    }))
+                });
+        });
+
+        let exported_pane = |paths: &[&str], active_item| {
+            ExportedMember::Pane(ExportedPane {
+                active: true,
+                items: paths
+                    .iter()
+                    .map(|path| ExportedItem {
+                        worktree: None,
+                        path: (*path).into(),
+                    })
+                    .collect(),
+                active_item,
+            })
+        };
+        // Paths leading outside of the worktree aren't opened.
+        let layout = ExportedLayout {
+            center: exported_pane(&["a.txt", "../a.txt", "/a.txt", "b.txt"], Some(0)),
+            ..Default::default()
+        };
+        test.workspace
+            .update_in(&mut test.cx, |workspace, window, cx| {
+                workspace.import_layout(layout, window, cx)
+            })
+            .await
+            .unwrap();
+        test.assert_center_layout("pane* [*a.txt, b.txt]");
+
+        let exported = test
+            .workspace
+            .update_in(&mut test.cx, |workspace, window, cx| {
+                workspace.export_layout(window, cx)
+            });
+        let ExportedMember::Pane(pane) = exported.center else {
+            panic!("expected a single pane");
+        };
+        let exported_paths = pane
+            .items
+            .iter()
+            .map(|item| item.path.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            exported_paths,
+            [PathBuf::from("a.txt"), PathBuf::from("b.txt")]
+        );
+        assert_eq!(pane.active_item, Some(0));
+    }
+    struct TestModal(FocusHandle);
+
+    impl TestModal {
+        fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+            Self(cx.focus_handle())
+        }
+    }
+
+    impl EventEmitter<DismissEvent> for TestModal {}
+
+    impl Focusable for TestModal {
+        fn focus_handle(&self, _cx: &App) -> FocusHandle {
+            self.0.clone()
+        }
+    }
+
+    impl ModalView for TestModal {}
+
+    impl Render for TestModal {
+        fn render(
+            &mut self,
+            _window: &mut Window,
+            _cx: &mut Context<TestModal>,
+        ) -> impl IntoElement {
+            div().track_focus(&self.0)
+        }
+    }
+
+    #[gpui::test]
+    async fn test_dock_events(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let _subscription = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1, window, cx);
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_2, window, cx);
+
+            let events = events.clone();
+            cx.subscribe(workspace.left_dock(), move |_, _, event: &DockEvent, _| {
+                events.borrow_mut().push(match event {
+                    DockEvent::ActivePanelChanged { index } => format!("active {index}"),
+                    DockEvent::OpenChanged(open) => format!("open {open}"),
+                    DockEvent::PanelLabelChanged { index } => format!("label {index}"),
+                    DockEvent::PanelsChanged => "panels changed".to_string(),
+                    DockEvent::CloseVetoed(_) => "close vetoed".to_string(),
+                })
+            })
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.activate_panel(1, window, cx);
+                // Activating the already active panel is a no-op.
+                dock.activate_panel(1, window, cx);
+            });
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        cx.run_until_parked();
+
+        assert_eq!(
+            events.borrow().as_slice(),
+            ["open true", "active 0", "active 1", "open false"]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_dock_active_panel_after_panel_removal(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (dock, panel_1, panel_2, panel_3) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), window, cx);
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_2.clone(), window, cx);
+            let panel_3 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_3.clone(), window, cx);
+
+            let dock = workspace.left_dock().clone();
+            dock.update(cx, |dock, cx| {
+                let index = dock.panel_index_for_id(panel_2.entity_id()).unwrap();
+                dock.activate_panel(index, window, cx);
+                dock.set_open(true, window, cx);
+            });
+            (dock, panel_1, panel_2, panel_3)
+        });
+
+        // A panel asks to be activated, and another panel is removed before the request is
+        // handled, shifting the indices of the panels.
+        workspace.update_in(cx, |_, window, cx| {
+            panel_3.update(cx, |_, cx| cx.emit(PanelEvent::Activate));
+            dock.update(cx, |dock, cx| dock.remove_panel(&panel_1, window, cx));
+        });
+        cx.run_until_parked();
+
+        dock.update(cx, |dock, cx| {
+            assert_eq!(
+                dock.active_panel().map(|panel| panel.panel_id()),
+                Some(panel_3.entity_id())
+            );
+            assert_eq!(
+                dock.active_panel_index(),
+                dock.panel_index_for_id(panel_3.entity_id())
+            );
+            assert!(!panel_2.read(cx).active);
+            assert!(panel_3.read(cx).active);
+        });
+
+        // Removing an inactive panel keeps the active one.
+        workspace.update_in(cx, |_, window, cx| {
+            dock.update(cx, |dock, cx| dock.remove_panel(&panel_2, window, cx));
+        });
+        dock.update(cx, |dock, _| {
+            assert_eq!(dock.active_panel_index(), Some(0));
+            assert_eq!(
+                dock.visible_panel().map(|panel| panel.panel_id()),
+                Some(panel_3.entity_id())
+            );
+        });
+
+        // Removing the active panel closes the dock, and stale indices are ignored.
+        workspace.update_in(cx, |_, window, cx| {
+            dock.update(cx, |dock, cx| {
+                dock.remove_panel(&panel_3, window, cx);
+                dock.activate_panel(2, window, cx);
+            });
+        });
+        dock.update(cx, |dock, _| {
+            assert_eq!(dock.panels_len(), 0);
+            assert!(dock.active_panel().is_none());
+            assert!(dock.visible_panel().is_none());
+            assert!(!dock.is_open());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_dock_panel_removed_during_activation(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (dock, panel_1, panel_2, panel_3) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), window, cx);
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_2.clone(), window, cx);
+            let panel_3 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_3.clone(), window, cx);
+
+            let dock = workspace.left_dock().clone();
+            dock.update(cx, |dock, cx| {
+                let index = dock.panel_index_for_id(panel_2.entity_id()).unwrap();
+                dock.activate_panel(index, window, cx);
+                dock.set_open(true, window, cx);
+            });
+            (dock, panel_1, panel_2, panel_3)
+        });
+
+        // Removes `removed` once `deactivated` is deactivated, while the dock is still handling
+        // the activation of another panel.
+        let remove_on_deactivation =
+            |deactivated: &Entity<TestPanel>,
+             removed: &Entity<TestPanel>,
+             cx: &mut VisualTestContext| {
+                let dock = dock.clone();
+                let removed = removed.clone();
+                workspace.update_in(cx, |_, window, cx| {
+                    cx.observe_in(deactivated, window, move |_, deactivated, window, cx| {
+                        if !deactivated.read(cx).active {
+                            dock.update(cx, |dock, cx| dock.remove_panel(&removed, window, cx));
+                        }
+                    })
+                })
+            };
+
+        // Deactivating the previous panel removes a panel before the activated one, shifting
+        // its index.
+        let subscription = remove_on_deactivation(&panel_2, &panel_1, cx);
+        panel_3.update(cx, |_, cx| cx.emit(PanelEvent::Activate));
+        cx.run_until_parked();
+        drop(subscription);
+        dock.update(cx, |dock, cx| {
+            assert_eq!(dock.panels_len(), 2);
+            assert_eq!(
+                dock.active_panel().map(|panel| panel.panel_id()),
+                Some(panel_3.entity_id())
+            );
+            assert_eq!(dock.active_panel_index(), Some(1));
+            assert_eq!(
+                dock.visible_panel().map(|panel| panel.panel_id()),
+                Some(panel_3.entity_id())
+            );
+            assert!(!panel_2.read(cx).active);
+            assert!(panel_3.read(cx).active);
+            assert!(dock.is_open());
+        });
+
+        // Deactivating the previous panel removes the activated panel itself.
+        let _subscription = remove_on_deactivation(&panel_3, &panel_2, cx);
+        panel_2.update(cx, |_, cx| cx.emit(PanelEvent::Activate));
+        cx.run_until_parked();
+        dock.update(cx, |dock, cx| {
+            assert_eq!(dock.panels_len(), 1);
+            assert!(dock.active_panel().is_none());
+            assert!(dock.active_panel_index().is_none());
+            assert!(dock.visible_panel().is_none());
+            assert!(!panel_3.read(cx).active);
+            assert!(!dock.is_open());
+        });
+    }
+
+    #[test]
+    fn test_dock_size_snapping() {
+        let window_width = px(1200.);
+        // Snaps to a third of the window.
+        assert_eq!(dock::snap_size(px(395.), window_width, &[]), px(400.));
+        // Snaps to the panel's preferred size.
+        assert_eq!(
+            dock::snap_size(px(246.), window_width, &[px(240.)]),
+            px(240.)
+        );
+        // Picks the closest of two nearby snap points.
+        assert_eq!(
+            dock::snap_size(px(296.), window_width, &[px(290.)]),
+            px(300.)
+        );
+        // Leaves sizes alone that aren't close to any snap point.
+        assert_eq!(
+            dock::snap_size(px(350.), window_width, &[px(240.)]),
+            px(350.)
+        );
+    }
+
+    #[gpui::test]
+    async fn test_bottom_dock_size_snapping(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| {
+                let mut panel = TestPanel::new(DockPosition::Bottom, cx);
+                panel.preferred_sizes = vec![px(123.)];
+                panel
+            });
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.move_panel_to_bottom_slot(Arc::new(panel), BottomDockSlot::Right, window, cx);
+            workspace.bottom_right_dock().update(cx, |dock, cx| {
+                dock.activate_panel(0, window, cx);
+                dock.set_open(true, window, cx);
+            });
+            workspace.bounds.size.height = px(1000.);
+
+            // The bottom right panel's preferred sizes are snapped to while dragging the
+            // bottom docks, even though the left one has no panel.
+            assert_eq!(
+                workspace.snap_dock_size(DockPosition::Bottom, px(128.), window, cx),
+                px(123.)
+            );
+            assert_eq!(
+                workspace.snap_dock_size(DockPosition::Bottom, px(180.), window, cx),
+                px(180.)
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_panel_label_changed(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let labels_changed = Rc::new(RefCell::new(Vec::new()));
+        let (panel, _subscription) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+
+            let labels_changed = labels_changed.clone();
+            let subscription =
+                cx.subscribe(workspace.right_dock(), move |_, _, event: &DockEvent, _| {
+                    if let DockEvent::PanelLabelChanged { index } = event {
+                        labels_changed.borrow_mut().push(*index);
+                    }
+                });
+            (panel, subscription)
+        });
+
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::LabelChanged));
+        cx.run_until_parked();
+        assert_eq!(labels_changed.borrow().as_slice(), [0]);
+    }
+
+    #[gpui::test]
+    async fn test_panel_label_redraws_only_its_label(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (panel, panel_buttons) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel =
+                cx.new(|cx| TestPanel::new(DockPosition::Right, cx).with_icon(IconName::Ellipsis));
+            workspace.add_panel(panel.clone(), window, cx);
+            (panel, workspace.panel_buttons[3].clone())
+        });
+        cx.run_until_parked();
+
+        let buttons_redrawn = Rc::new(RefCell::new(0));
+        let _subscription = cx.update(|_, cx| {
+            let buttons_redrawn = buttons_redrawn.clone();
+            cx.observe(&panel_buttons, move |_, _| {
+                *buttons_redrawn.borrow_mut() += 1
+            })
+        });
+        let label_renders = |cx: &mut VisualTestContext| {
+            cx.run_until_parked();
+            panel.read_with(cx, |panel, _| panel.icon_label_requests.get())
+        };
+        let initial_label_renders = label_renders(cx);
+        assert!(initial_label_renders > 0);
+
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::LabelChanged));
+        assert_eq!(label_renders(cx), initial_label_renders + 1);
+        assert_eq!(*buttons_redrawn.borrow(), 0);
+
+        // Changes to the panel that its button doesn't show don't redraw the buttons either.
+        panel.update(cx, |_, cx| cx.notify());
+        assert_eq!(label_renders(cx), initial_label_renders + 1);
+        assert_eq!(*buttons_redrawn.borrow(), 0);
+
+        // Redrawing the buttons reuses the label as it was last rendered.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Right, window, cx)
+        });
+        assert_eq!(label_renders(cx), initial_label_renders + 1);
+        assert!(*buttons_redrawn.borrow() > 0);
+
+        // Removing the panel redraws the buttons, which now offer to restore it.
+        *buttons_redrawn.borrow_mut() = 0;
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.remove_panel_from_dock(Arc::new(panel.clone()), window, cx)
+        });
+        cx.run_until_parked();
+        assert!(*buttons_redrawn.borrow() > 0);
+    }
+
+    #[gpui::test]
+    async fn test_workspace_metrics(cx: &mut gpui::TestAppContext) {
+        struct RecordingSink(parking_lot::Mutex<Vec<WorkspaceMetric>>);
+
+        impl metrics::WorkspaceMetricsSink for RecordingSink {
+            fn record(&self, metric: WorkspaceMetric) {
+                self.0.lock().push(metric);
+            }
+        }
+
+        init_test(cx);
+        let sink = Arc::new(RecordingSink(Default::default()));
+        cx.update(|cx| metrics::set_metrics_sink(sink.clone(), cx));
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let button_panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel, window, cx);
+            let button_panel =
+                cx.new(|cx| TestPanel::new(DockPosition::Right, cx).with_icon(IconName::Ellipsis));
+            workspace.add_panel(button_panel.clone(), window, cx);
+            button_panel
+        });
+        cx.executor().advance_clock(metrics::METRICS_DELIVERY_DELAY);
+        cx.run_until_parked();
+        sink.0.lock().clear();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Right, window, cx);
+            workspace.right_dock().update(cx, |dock, cx| {
+                let index = dock.panel_index_for_id(button_panel.entity_id()).unwrap();
+                dock.activate_panel(index, window, cx);
+            });
+            workspace.toggle_dock(DockPosition::Right, window, cx);
+            for _ in 0..2 {
+                workspace.split_pane(
+                    workspace.active_pane().clone(),
+                    SplitDirection::Right,
+                    window,
+                    cx,
+                );
+            }
+        });
+
+        // Nothing is reported until the burst of changes is over, and then only the last
+        // panel activated in the dock and the last pane count are.
+        assert!(sink.0.lock().is_empty());
+        cx.executor().advance_clock(metrics::METRICS_DELIVERY_DELAY);
+        cx.run_until_parked();
+        let metrics = sink.0.lock();
+        assert!(matches!(
+            metrics.as_slice(),
+            [
+                WorkspaceMetric::PanelActivated {
+                    panel: "TestPanel",
+                    position: DockPosition::Right,
+                },
+                WorkspaceMetric::DockClosed {
+                    position: DockPosition::Right,
+                    ..
+                },
+                WorkspaceMetric::PaneCountChanged { pane_count: 3 },
+            ]
+        ));
+    }
+
+    #[gpui::test]
+    async fn test_workspace_feedback(cx: &mut gpui::TestAppContext) {
+        #[derive(Debug, PartialEq)]
+        enum Feedback {
+            DockToggled(DockPosition, bool),
+            ZoomChanged(bool),
+            NotificationShown,
+            NotificationDismissed,
+        }
+
+        struct RecordingFeedback(parking_lot::Mutex<Vec<Feedback>>);
+
+        impl feedback::WorkspaceFeedback for RecordingFeedback {
+            fn dock_toggled(&self, position: DockPosition, is_open: bool, _: &mut App) {
+                self.0.lock().push(Feedback::DockToggled(position, is_open));
+            }
+
+            fn zoom_changed(&self, zoomed: bool, _: &mut App) {
+                self.0.lock().push(Feedback::ZoomChanged(zoomed));
+            }
+
+            fn notification_shown(&self, _: &NotificationId, _: &mut App) {
+                self.0.lock().push(Feedback::NotificationShown);
+            }
+
+            fn notification_dismissed(&self, _: &NotificationId, _: &mut App) {
+                self.0.lock().push(Feedback::NotificationDismissed);
+            }
+        }
+
+        let recorded = Arc::new(RecordingFeedback(Default::default()));
+        cx.update(|cx| feedback::set_workspace_feedback(recorded.clone(), cx));
+        let mut test = test::TestWorkspace::new(cx)
+            .panel(DockPosition::Right)
+            .build()
+            .await;
+        let panel = test.panels[0].clone();
+        recorded.0.lock().clear();
+
+        test.toggle_dock(DockPosition::Right);
+        test.toggle_dock(DockPosition::Right);
+        test.add_item("item");
+        let pane = test.center_panes()[0].clone();
+        test.focus_pane(&pane);
+        let workspace = test.workspace.clone();
+        let cx = &mut test.cx;
+
+        // Each zoom change is reported once, however many panes and panels it touches.
+        for _ in 0..2 {
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.toggle_zoom(&ToggleZoom, window, cx);
+                });
+            });
+            cx.run_until_parked();
+        }
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::ZoomIn));
+        cx.run_until_parked();
+        // Zooming in again isn't a change.
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::ZoomIn));
+        cx.run_until_parked();
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::ZoomOut));
+        cx.run_until_parked();
+
+        workspace.update_in(cx, |workspace, _, cx| {
+            // Replacing a notification doesn't count as dismissing it.
+            let id = NotificationId::unique::<TestItem>();
+            workspace.show_toast(Toast::new(id.clone(), "first"), cx);
+            workspace.show_toast(Toast::new(id.clone(), "second"), cx);
+            workspace.dismiss_toast(&id, cx);
+            workspace.dismiss_toast(&id, cx);
+        });
+
+        assert_eq!(
+            *recorded.0.lock(),
+            [
+                Feedback::DockToggled(DockPosition::Right, true),
+                Feedback::DockToggled(DockPosition::Right, false),
+                Feedback::ZoomChanged(true),
+                Feedback::ZoomChanged(false),
+                Feedback::ZoomChanged(true),
+                Feedback::ZoomChanged(false),
+                Feedback::NotificationShown,
+                Feedback::NotificationShown,
+                Feedback::NotificationDismissed,
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_panel_buttons_keyboard_navigation(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (panel_1, panel_2) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 =
+                cx.new(|cx| TestPanel::new(DockPosition::Right, cx).with_icon(IconName::Ellipsis));
+            let panel_2 =
+                cx.new(|cx| TestPanel::new(DockPosition::Right, cx).with_icon(IconName::Ellipsis));
+            workspace.add_panel(panel_1.clone(), window, cx);
+            workspace.add_panel(panel_2.clone(), window, cx);
+            workspace.focus_panel_buttons(window, cx);
+            (panel_1, panel_2)
+        });
+
+        // Only the right dock has buttons, so it receives focus with its first panel selected.
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(menu::Confirm);
+        workspace.update_in(cx, |workspace, window, cx| {
+            let right_dock = workspace.right_dock().read(cx);
+            assert!(right_dock.is_open());
+            assert_eq!(
+                right_dock.active_panel().unwrap().panel_id(),
+                panel_2.entity_id()
+            );
+            assert!(panel_2.focus_handle(cx).contains_focused(window, cx));
+        });
+
+        // Selection wraps around, and cancelling returns focus to the center.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.focus_panel_buttons(window, cx);
+        });
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(menu::Confirm);
+        workspace.update_in(cx, |workspace, window, cx| {
+            let right_dock = workspace.right_dock().read(cx);
+            assert_eq!(
+                right_dock.active_panel().unwrap().panel_id(),
+                panel_1.entity_id()
+            );
+            workspace.focus_panel_buttons(window, cx);
+        });
+        cx.dispatch_action(menu::Cancel);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace
+                .active_pane()
+                .focus_handle(cx)
+                .contains_focused(window, cx));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_panel_close_veto(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Right, window, cx);
+            panel
+        });
+        panel.update(cx, |panel, _| panel.veto_close = true);
+
+        // Neither the user nor the panel itself can close the dock while the panel vetoes it.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Right, window, cx);
+            workspace.close_all_docks(window, cx);
+            assert!(workspace.right_dock().read(cx).is_open());
+        });
+        panel.update(cx, |_, cx| {
+            cx.emit(PanelEvent::Close {
+                reason: CloseReason::User,
+            })
+        });
+        cx.run_until_parked();
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            // The user is told why the dock stayed open.
+            let notification_ids = workspace.notification_ids();
+            assert_eq!(notification_ids.len(), 1);
+            workspace.dismiss_notification(&notification_ids[0], cx);
+
+            workspace.remove_panel_from_dock(Arc::new(panel.clone()), window, cx);
+            assert!(workspace
+                .right_dock()
+                .read(cx)
+                .contains_panel(panel.entity_id()));
+            let notification_ids = workspace.notification_ids();
+            assert_eq!(notification_ids.len(), 1);
+            workspace.dismiss_notification(&notification_ids[0], cx);
+        });
+
+        // Closes the user didn't ask for are vetoed silently.
+        panel.update(cx, |_, cx| {
+            cx.emit(PanelEvent::Close {
+                reason: CloseReason::Automatic,
+            })
+        });
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(workspace.notification_ids().is_empty());
+        });
+
+        panel.update(cx, |panel, _| panel.veto_close = false);
+        panel.update(cx, |_, cx| {
+            cx.emit(PanelEvent::Close {
+                reason: CloseReason::User,
+            })
+        });
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert!(!workspace.right_dock().read(cx).is_open());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_toggle_panel(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            panel
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let toggled = workspace.toggle_panel::<TestPanel>(window, cx);
+            assert_eq!(toggled, Some(panel.clone()));
+            assert!(workspace.left_dock().read(cx).is_open());
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(panel.focus_handle(cx).contains_focused(window, cx));
+
+            let toggled = workspace.toggle_panel::<TestPanel>(window, cx);
+            assert_eq!(toggled, Some(panel.clone()));
+            assert!(!workspace.left_dock().read(cx).is_open());
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace
+                .active_pane()
+                .focus_handle(cx)
+                .contains_focused(window, cx));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_panel_migrates_from_invalid_position(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // A panel whose position is invalid when it's added moves to its first valid position.
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| {
+                let mut panel = TestPanel::new(DockPosition::Bottom, cx);
+                panel.invalid_positions = vec![DockPosition::Bottom];
+                panel
+            });
+            workspace.add_panel(panel.clone(), window, cx);
+            panel
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(panel.read(cx).position, DockPosition::Left);
+            assert!(workspace
+                .left_dock()
+                .read(cx)
+                .contains_panel(panel.entity_id()));
+        });
+
+        // The same happens when the position becomes invalid later on.
+        panel.update(cx, |panel, _| {
+            panel.invalid_positions = vec![DockPosition::Left];
+        });
+        cx.update_global(|settings: &mut SettingsStore, cx| {
+            settings.update_user_settings::<WorkspaceSettings>(cx, |_| {});
+        });
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(panel.read(cx).position, DockPosition::Bottom);
+            assert!(!workspace
+                .left_dock()
+                .read(cx)
+                .contains_panel(panel.entity_id()));
+            assert!(workspace
+                .bottom_dock()
+                .read(cx)
+                .contains_panel(panel.entity_id()));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_dock_switcher(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        cx.update(dock_switcher::init);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (panel_1, panel_2, panel_3) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            let panel_3 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), window, cx);
+            workspace.add_panel(panel_2.clone(), window, cx);
+            workspace.add_panel(panel_3.clone(), window, cx);
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.set_open(true, window, cx);
+                dock.activate_panel(2, window, cx);
+                dock.activate_panel(0, window, cx);
+            });
+            window.focus(&panel_1.focus_handle(cx));
+            (panel_1, panel_2, panel_3)
+        });
+
+        let panel_ids = |workspace: &Entity<Workspace>, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| {
+                workspace
+                    .left_dock()
+                    .read(cx)
+                    .panels_by_recent_activation()
+                    .iter()
+                    .map(|panel| panel.panel_id())
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            panel_ids(&workspace, cx),
+            [
+                panel_1.entity_id(),
+                panel_3.entity_id(),
+                panel_2.entity_id()
+            ]
+        );
+
+        // The switcher starts on the previously activated panel.
+        cx.dispatch_action(dock_switcher::Toggle::default());
+        cx.dispatch_action(menu::Confirm);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.active_modal::<DockSwitcher>(cx).is_none());
+            assert_eq!(
+                workspace
+                    .left_dock()
+                    .read(cx)
+                    .active_panel()
+                    .unwrap()
+                    .panel_id(),
+                panel_3.entity_id()
+            );
+            assert!(panel_3.focus_handle(cx).contains_focused(window, cx));
+        });
+        assert_eq!(
+            panel_ids(&workspace, cx),
+            [
+                panel_3.entity_id(),
+                panel_1.entity_id(),
+                panel_2.entity_id()
+            ]
+        );
+
+        // Repeating the action cycles through the panels.
+        cx.dispatch_action(dock_switcher::Toggle::default());
+        cx.dispatch_action(dock_switcher::Toggle::default());
+        cx.dispatch_action(menu::Confirm);
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(
+                workspace
+                    .left_dock()
+                    .read(cx)
+                    .active_panel()
+                    .unwrap()
+                    .panel_id(),
+                panel_2.entity_id()
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_chrome_snapshot(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let item = cx.new(|cx| TestItem::new(cx).with_label("item"));
+            workspace.add_item_to_active_pane(Box::new(item), None, true, window, cx);
+        });
+        let snapshot = chrome_snapshot::chrome_snapshot(cx);
+        assert!(snapshot.starts_with("WORKSPACE "), "{snapshot}");
+        assert!(snapshot.contains("\n  CENTER "), "{snapshot}");
+        assert!(!snapshot.contains("DOCK-left"), "{snapshot}");
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel =
+                cx.new(|cx| TestPanel::new(DockPosition::Left, cx).with_icon(IconName::Ellipsis));
+            workspace.add_panel(panel, window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        let snapshot = chrome_snapshot::chrome_snapshot(cx);
+        assert!(snapshot.contains("\n  DOCK-left 300x"), "{snapshot}");
+        chrome_snapshot::assert_chrome_snapshot("left_dock_open", cx);
+    }
+
+    #[gpui::test]
+    async fn test_split_pane_with_ratio(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        #[track_caller]
+        fn assert_flexes(actual: Vec<f32>, expected: &[f32]) {
+            assert_eq!(actual.len(), expected.len(), "{actual:?}");
+            for (actual_flex, expected_flex) in actual.iter().zip(expected) {
+                assert!((actual_flex - expected_flex).abs() < 0.001, "{actual:?}");
+            }
+        }
+
+        let root_flexes = |workspace: &Entity<Workspace>, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, _| match &workspace.center.root {
+                Member::Axis(axis) => axis.flexes.lock().clone(),
+                Member::Pane(_) => panic!("expected the center to be split"),
+            })
+        };
+
+        let (left_pane, right_pane) = workspace.update_in(cx, |workspace, window, cx| {
+            let left_pane = workspace.active_pane().clone();
+            let right_pane = workspace.split_pane_with_ratio(
+                left_pane.clone(),
+                SplitDirection::Right,
+                2. / 3.,
+                window,
+                cx,
+            );
+            (left_pane, right_pane)
+        });
+        assert_flexes(root_flexes(&workspace, cx), &[2. / 3., 4. / 3.]);
+
+        // Splitting along the same axis keeps the sizes of the other panes.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_pane_with_ratio(left_pane, SplitDirection::Left, 0.5, window, cx);
+        });
+        assert_flexes(root_flexes(&workspace, cx), &[0.5, 0.5, 2.]);
+
+        // Splitting along the other axis nests the panes.
+        workspace.update_in(cx, |_, window, cx| {
+            window.focus(&right_pane.focus_handle(cx));
+        });
+        cx.dispatch_action(SplitDownTwoThirds);
+        let nested_flexes = |workspace: &Entity<Workspace>, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, _| {
+                let Member::Axis(root) = &workspace.center.root else {
+                    panic!("expected the center to be split");
+                };
+                let Member::Axis(nested) = &root.members[2] else {
+                    panic!("expected the right pane to be split");
+                };
+                assert_eq!(nested.axis, gpui::Axis::Vertical);
+                nested.flexes.lock().clone()
+            })
+        };
+        assert_flexes(nested_flexes(&workspace, cx), &[2. / 3., 4. / 3.]);
+
+        // Ratios are clamped so that neither pane collapses.
+        workspace.update_in(cx, |workspace, window, cx| {
+            let pane = workspace.active_pane().clone();
+            workspace.split_pane_with_ratio(pane, SplitDirection::Up, 1.5, window, cx);
+        });
+        assert_flexes(nested_flexes(&workspace, cx), &[1., 1.8, 0.2]);
+    }
+
+    #[gpui::test]
+    async fn test_pane_context(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({ "dir": { "one": "" } }))
+            .await;
+
+        let project = Project::test(fs, ["root".as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+
+        // Activating a file records the directory it's in.
+        let item = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[cx.new(|_| TestProjectItem {
+                entry_id: None,
+                project_path: Some(ProjectPath {
+                    worktree_id,
+                    path: Path::new("dir/one").into(),
+                }),
+                is_dirty: false,
+            })])
+        });
+        let pane = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item), None, true, window, cx);
+            workspace.active_pane().clone()
+        });
+        pane.update(cx, |pane, _| {
+            assert_eq!(
+                pane.context::<PaneWorkingDirectory>(),
+                Some(PaneWorkingDirectory(PathBuf::from("/root/dir")))
+            );
+        });
+
+        // Panes split off a pane start with its context.
+        let new_pane = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_pane(pane.clone(), SplitDirection::Right, window, cx)
+        });
+        new_pane.update(cx, |pane, _| {
+            assert_eq!(
+                pane.context::<PaneWorkingDirectory>(),
+                Some(PaneWorkingDirectory(PathBuf::from("/root/dir")))
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_reset_and_resize_pane_sizes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let left_pane = workspace.update_in(cx, |workspace, window, cx| {
+            let left_pane = workspace.active_pane().clone();
+            let right_pane = workspace.split_pane_with_ratio(
+                left_pane.clone(),
+                SplitDirection::Right,
+                2. / 3.,
+                window,
+                cx,
+            );
+            workspace.split_pane_with_ratio(right_pane, SplitDirection::Down, 2. / 3., window, cx);
+            left_pane
+        });
+        let flexes = |workspace: &Entity<Workspace>, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, _| {
+                let Member::Axis(root) = &workspace.center.root else {
+                    panic!("expected the center to be split");
+                };
+                let Member::Axis(nested) = &root.members[1] else {
+                    panic!("expected the right pane to be split");
+                };
+                (root.flexes.lock().clone(), nested.flexes.lock().clone())
+            })
+        };
+        let (root_flexes, nested_flexes) = flexes(&workspace, cx);
+        assert_ne!(root_flexes, vec![1., 1.]);
+        assert_ne!(nested_flexes, vec![1., 1.]);
+
+        // Only the split containing the active pane is reset.
+        cx.dispatch_action(ResetPaneSizes);
+        assert_eq!(flexes(&workspace, cx), (root_flexes, vec![1., 1.]));
+
+        workspace.update_in(cx, |_, window, cx| {
+            window.focus(&left_pane.focus_handle(cx));
+        });
+        cx.dispatch_action(ResetPaneSizes);
+        assert_eq!(flexes(&workspace, cx), (vec![1., 1.], vec![1., 1.]));
+
+        cx.run_until_parked();
+        cx.dispatch_action(ResizePaneRight);
+        let (root_flexes, _) = flexes(&workspace, cx);
+        assert!(root_flexes[0] > 1., "{root_flexes:?}");
+        assert!((root_flexes[0] + root_flexes[1] - 2.).abs() < 0.001);
+
+        cx.dispatch_action(ResizePaneLeft);
+        let (root_flexes, _) = flexes(&workspace, cx);
+        assert!((root_flexes[0] - 1.).abs() < 0.001, "{root_flexes:?}");
+    }
+
+    #[gpui::test]
+    async fn test_slot_views(cx: &mut gpui::TestAppContext) {
+        struct SlotView(&'static str);
+
+        impl Render for SlotView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                let selector = self.0;
+                div()
+                    .debug_selector(move || selector.into())
+                    .w(px(100.))
+                    .h(px(20.))
+            }
+        }
+
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let below_title_bar = cx.new(|_| SlotView("BELOW_TITLE_BAR"));
+        let above_status_bar = cx.new(|_| SlotView("ABOVE_STATUS_BAR"));
+        let over_center = cx.new(|_| SlotView("OVER_CENTER"));
+        workspace.update(cx, |workspace, cx| {
+            workspace.register_slot_view(
+                WorkspaceSlot::BelowTitleBar,
+                below_title_bar.clone().into(),
+                cx,
+            );
+            workspace.register_slot_view(
+                WorkspaceSlot::AboveStatusBar,
+                above_status_bar.clone().into(),
+                cx,
+            );
+            workspace.register_slot_view(WorkspaceSlot::OverCenter, over_center.clone().into(), cx);
+            // Registering a mounted view again doesn't mount it twice.
+            workspace.register_slot_view(WorkspaceSlot::OverCenter, over_center.clone().into(), cx);
+            assert_eq!(workspace.slot_views(WorkspaceSlot::OverCenter).len(), 1);
+        });
+
+        test::draw(cx);
+        let workspace_bounds = cx.debug_bounds("WORKSPACE").unwrap();
+        let center = cx.debug_bounds("CENTER").unwrap();
+        let status_bar = cx.debug_bounds("STATUS_BAR").unwrap();
+        let view = cx.debug_bounds("BELOW_TITLE_BAR").unwrap();
+        assert_eq!(view.bottom(), workspace_bounds.top());
+        let view = cx.debug_bounds("ABOVE_STATUS_BAR").unwrap();
+        assert_eq!(view.top(), workspace_bounds.bottom());
+        assert_eq!(view.bottom(), status_bar.top());
+        let view = cx.debug_bounds("OVER_CENTER").unwrap();
+        assert_eq!(view.origin, center.origin);
+
+        workspace.update(cx, |workspace, cx| {
+            workspace.unregister_slot_view(WorkspaceSlot::OverCenter, over_center.entity_id(), cx);
+            assert!(workspace.slot_views(WorkspaceSlot::OverCenter).is_empty());
+        });
+        test::draw(cx);
+        assert!(cx.debug_bounds("OVER_CENTER").is_none());
+        assert!(cx.debug_bounds("BELOW_TITLE_BAR").is_some());
+    }
+
+    #[gpui::test]
+    async fn test_extension_status_items(cx: &mut gpui::TestAppContext) {
+        use extension_status_items::{
+            EXTENSION_STATUS_ITEM_UPDATE_INTERVAL, MAX_STATUS_ITEMS_PER_EXTENSION,
+        };
+
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let items = workspace.update(cx, |workspace, _| {
+            workspace.extension_status_items().clone()
+        });
+        let notify_count = Rc::new(RefCell::new(0));
+        let _subscription = cx.update(|_, cx| {
+            let notify_count = notify_count.clone();
+            cx.observe(&items, move |_, _| *notify_count.borrow_mut() += 1)
+        });
+
+        let register = |description: &str, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| {
+                workspace.register_extension_status_item("ext", description, cx)
+            })
+        };
+
+        // Changes are batched, no matter how often an extension updates its item.
+        for ix in 0..10 {
+            register(
+                &format!(
+                    r#"{{"id": "status", "text": "Step {ix}", "icon": "check", "click_action": "workspace::ToggleZoom"}}"#
+                ),
+                cx,
+            )
+            .unwrap();
+        }
+        items.update(cx, |items, _| assert!(items.item_ids("ext").is_empty()));
+        cx.executor()
+            .advance_clock(EXTENSION_STATUS_ITEM_UPDATE_INTERVAL);
+        cx.run_until_parked();
+        items.update(cx, |items, _| {
+            assert_eq!(items.item_ids("ext"), ["status"]);
+            assert_eq!(items.item_text("ext", "status"), Some("Step 9"));
+        });
+        assert_eq!(*notify_count.borrow(), 1);
+
+        // Invalid descriptions are rejected right away.
+        assert!(register(r#"{"id": "status"}"#, cx).is_err());
+        assert!(register(r#"{"id": "status", "text": "", "icon": "nope"}"#, cx).is_err());
+        assert!(register(
+            r#"{"id": "status", "text": "", "click_action": "workspace::Nope"}"#,
+            cx
+        )
+        .is_err());
+        // Only a few harmless actions can be dispatched by extensions.
+        assert!(register(
+            r#"{"id": "status", "text": "", "click_action": "workspace::NewFile"}"#,
+            cx
+        )
+        .is_err());
+
+        // Extensions can only show a few items.
+        for ix in 1..MAX_STATUS_ITEMS_PER_EXTENSION {
+            register(&format!(r#"{{"id": "item {ix}", "text": "Item"}}"#), cx).unwrap();
+        }
+        assert!(register(r#"{"id": "one too many", "text": "Item"}"#, cx).is_err());
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.unregister_extension_status_item("ext", "status", cx);
+                workspace.register_extension_status_item(
+                    "other_ext",
+                    r#"{"id": "other", "text": "Item"}"#,
+                    cx,
+                )
+            })
+            .unwrap();
+        register(r#"{"id": "replacement", "text": "Item"}"#, cx).unwrap();
+
+        cx.executor()
+            .advance_clock(EXTENSION_STATUS_ITEM_UPDATE_INTERVAL);
+        cx.run_until_parked();
+        items.update(cx, |items, cx| {
+            assert_eq!(items.item_ids("ext"), ["item 1", "item 2", "replacement"]);
+            assert_eq!(items.item_ids("other_ext"), ["other"]);
+
+            items.unregister_extension("ext", cx);
+        });
+        cx.executor()
+            .advance_clock(EXTENSION_STATUS_ITEM_UPDATE_INTERVAL);
+        cx.run_until_parked();
+        items.update(cx, |items, _| {
+            assert!(items.item_ids("ext").is_empty());
+            assert_eq!(items.item_ids("other_ext"), ["other"]);
+        });
+        assert_eq!(*notify_count.borrow(), 3);
+    }
+
+    #[gpui::test]
+    async fn test_item_activated_event(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let activations = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|_, cx| {
+            let activations = activations.clone();
+            cx.subscribe(&workspace, move |_, event, _| {
+                if let Event::ItemActivated { pane, item } = event {
+                    activations
+                        .borrow_mut()
+                        .push((pane.entity_id(), item.item_id()));
+                }
+            })
+            .detach();
+        });
+
+        let item_1 = cx.new(TestItem::new);
+        let item_2 = cx.new(TestItem::new);
+        let item_3 = cx.new(TestItem::new);
+        let (left_pane, right_pane) = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item_1.clone()), None, true, window, cx);
+            workspace.add_item_to_active_pane(Box::new(item_2.clone()), None, true, window, cx);
+            let left_pane = workspace.active_pane().clone();
+            let right_pane =
+                workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx);
+            workspace.add_item_to_active_pane(Box::new(item_3.clone()), None, true, window, cx);
+            (left_pane, right_pane)
+        });
+        cx.run_until_parked();
+        activations.borrow_mut().clear();
+
+        // Activating an item in a pane that isn't the active one is reported too.
+        left_pane.update_in(cx, |pane, window, cx| {
+            pane.activate_item(0, false, false, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            activations.borrow_mut().drain(..).collect::<Vec<_>>(),
+            [(left_pane.entity_id(), item_1.item_id())]
+        );
+        assert_eq!(
+            workspace.read_with(cx, |workspace, _| workspace.active_pane().clone()),
+            right_pane
+        );
+    }
+
+    #[gpui::test]
+    async fn test_swap_pane_in_direction(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let item = cx.new(|cx| TestItem::new(cx).with_label("left"));
+        let (left_pane, right_pane) = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item.clone()), None, true, window, cx);
+            let left_pane = workspace.active_pane().clone();
+            let right_pane =
+                workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx);
+            window.focus(&left_pane.focus_handle(cx));
+            (left_pane, right_pane)
+        });
+        let panes = |workspace: &Entity<Workspace>, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, _| {
+                workspace
+                    .center
+                    .panes()
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+        };
+        cx.run_until_parked();
+        assert_eq!(
+            panes(&workspace, cx),
+            [left_pane.clone(), right_pane.clone()]
+        );
+
+        // There's nothing to swap with on the left.
+        cx.dispatch_action(SwapPaneLeft);
+        assert_eq!(
+            panes(&workspace, cx),
+            [left_pane.clone(), right_pane.clone()]
+        );
+
+        cx.dispatch_action(SwapPaneRight);
+        cx.run_until_parked();
+        assert_eq!(
+            panes(&workspace, cx),
+            [right_pane.clone(), left_pane.clone()]
+        );
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert_eq!(workspace.active_pane(), &left_pane);
+            assert!(left_pane.focus_handle(cx).contains_focused(window, cx));
+            assert_eq!(workspace.active_item(cx).unwrap().item_id(), item.item_id());
+        });
+
+        cx.dispatch_action(SwapPaneLeft);
+        assert_eq!(panes(&workspace, cx), [left_pane, right_pane]);
+    }
+
+    #[gpui::test]
+    async fn test_activate_pane_in_direction_wraps(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (left_pane, middle_pane, right_pane) =
+            workspace.update_in(cx, |workspace, window, cx| {
+                let left_pane = workspace.active_pane().clone();
+                let middle_pane =
+                    workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx);
+                let right_pane =
+                    workspace.split_pane(middle_pane.clone(), SplitDirection::Right, window, cx);
+                window.focus(&right_pane.focus_handle(cx));
+                (left_pane, middle_pane, right_pane)
+            });
+        cx.run_until_parked();
+        let pane_is_focused = |pane: &Entity<Pane>, cx: &mut VisualTestContext| {
+            cx.update(|window, cx| pane.focus_handle(cx).contains_focused(window, cx))
+        };
+
+        // Without wrapping, focus stays on the rightmost pane.
+        cx.dispatch_action(ActivatePaneRight);
+        cx.run_until_parked();
+        assert!(pane_is_focused(&right_pane, cx));
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.wrap_pane_navigation = Some(true);
+            });
+        });
+        cx.dispatch_action(ActivatePaneRight);
+        cx.run_until_parked();
+        assert!(pane_is_focused(&left_pane, cx));
+        cx.dispatch_action(ActivatePaneLeft);
+        cx.run_until_parked();
+        assert!(pane_is_focused(&right_pane, cx));
+        cx.dispatch_action(ActivatePaneLeft);
+        cx.run_until_parked();
+        assert!(pane_is_focused(&middle_pane, cx));
+
+        // An open dock on the opposite side is wrapped to before the panes.
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            window.focus(&right_pane.focus_handle(cx));
+            panel
+        });
+        cx.run_until_parked();
+        cx.dispatch_action(ActivatePaneRight);
+        cx.run_until_parked();
+        cx.update(|window, cx| assert!(panel.focus_handle(cx).contains_focused(window, cx)));
+    }
+
+    #[gpui::test]
+    async fn test_focus_follows_mouse(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.focus_follows_mouse = Some(FocusFollowsMouse {
+                    enabled: true,
+                    include_docks: false,
+                    debounce_ms: 200,
+                });
+            });
+        });
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (left_pane, right_pane) = workspace.update_in(cx, |workspace, window, cx| {
+            let left_pane = workspace.active_pane().clone();
+            let right_pane =
+                workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx);
+            window.focus(&left_pane.focus_handle(cx));
+            (left_pane, right_pane)
+        });
+        cx.run_until_parked();
+        cx.update(|window, _| window.refresh());
+
+        let center_of = |pane: &Entity<Pane>, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, _| {
+                workspace
+                    .center
+                    .bounding_box_for_pane(pane)
+                    .unwrap()
+                    .center()
+            })
+        };
+        let is_focused = |pane: &Entity<Pane>, cx: &mut VisualTestContext| {
+            cx.update(|window, cx| pane.focus_handle(cx).contains_focused(window, cx))
+        };
+
+        // The hovered pane is focused once the mouse rests over it.
+        let position = center_of(&right_pane, cx);
+        cx.simulate_mouse_move(position, None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+        assert!(is_focused(&left_pane, cx));
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+        assert!(is_focused(&right_pane, cx));
+
+        // Moving with a button pressed doesn't change focus.
+        let position = center_of(&left_pane, cx);
+        cx.simulate_mouse_move(position, MouseButton::Left, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(300));
+        cx.run_until_parked();
+        assert!(is_focused(&right_pane, cx));
+
+        // Nothing is focused by hovering while a modal is open.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_modal(window, cx, TestModal::new);
+        });
+        cx.simulate_mouse_move(position, None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(300));
+        cx.run_until_parked();
+        assert!(!is_focused(&left_pane, cx));
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<TestModal>(cx).is_some());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_focus_follows_mouse_in_docks(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx)
+            .panel(DockPosition::Left)
+            .build()
+            .await;
+        let panel = test.panels[0].clone();
+        test.toggle_dock(DockPosition::Left);
+        let pane = test.center_panes()[0].clone();
+        test.focus_pane(&pane);
+        test.draw();
+        let cx = &mut test.cx;
+        let dock_center = cx.debug_bounds("DOCK-left").unwrap().center();
+        let set_focus_follows_mouse = |include_docks: bool, cx: &mut VisualTestContext| {
+            cx.update(|_, cx| {
+                SettingsStore::update_global(cx, |store, cx| {
+                    store.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                        settings.focus_follows_mouse = Some(FocusFollowsMouse {
+                            enabled: true,
+                            include_docks,
+                            debounce_ms: 200,
+                        });
+                    });
+                });
+            });
+        };
+
+        // Docks aren't focused by hovering them unless they're included.
+        set_focus_follows_mouse(false, cx);
+        cx.simulate_mouse_move(dock_center, None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(300));
+        cx.run_until_parked();
+        cx.update(|window, cx| assert!(pane.focus_handle(cx).contains_focused(window, cx)));
+
+        set_focus_follows_mouse(true, cx);
+        cx.simulate_mouse_move(dock_center + point(px(1.), px(1.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(300));
+        cx.run_until_parked();
+        cx.update(|window, cx| assert!(panel.focus_handle(cx).contains_focused(window, cx)));
+    }
+
+    #[gpui::test]
+    async fn test_panel_preview_on_button_hover(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx).build().await;
+        let workspace = test.workspace.clone();
+        let cx = &mut test.cx;
+        let set_panel_previews = |enabled: bool, cx: &mut VisualTestContext| {
+            cx.update(|_, cx| {
+                SettingsStore::update_global(cx, |store, cx| {
+                    store.update_user_settings::<StatusBarSettings>(cx, |settings| {
+                        settings.panel_previews = Some(enabled);
+                    });
+                });
+            });
+        };
+        set_panel_previews(true, cx);
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel =
+                cx.new(|cx| TestPanel::new(DockPosition::Left, cx).with_icon(IconName::Ellipsis));
+            workspace.add_panel(panel, window, cx);
+        });
+        test::draw(cx);
+        let button = cx.debug_bounds("PANEL_BUTTON-TestPanel").unwrap().center();
+        let hover_button = |cx: &mut VisualTestContext| {
+            cx.simulate_mouse_move(button, None, Modifiers::none());
+            cx.executor().advance_clock(Duration::from_millis(600));
+            test::draw(cx);
+            cx.debug_bounds("PANEL_PREVIEW-TestPanel").is_some()
+        };
+
+        // Hovering the button of a closed panel previews it.
+        assert!(hover_button(cx));
+
+        // Open panels aren't previewed.
+        cx.simulate_mouse_move(point(px(1.), px(1.)), None, Modifiers::none());
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        assert!(!hover_button(cx));
+
+        // Previews can be turned off.
+        cx.simulate_mouse_move(point(px(1.), px(1.)), None, Modifiers::none());
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        set_panel_previews(false, cx);
+        assert!(!hover_button(cx));
+    }
+
+    #[gpui::test]
+    async fn test_element_at(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx).build().await;
+        let workspace = test.workspace.clone();
+        let cx = &mut test.cx;
+        let (item, left_pane, right_pane) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel =
+                cx.new(|cx| TestPanel::new(DockPosition::Left, cx).with_icon(IconName::Ellipsis));
+            workspace.add_panel(panel, window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            let item = cx.new(TestItem::new);
+            workspace.add_item_to_active_pane(Box::new(item.clone()), None, true, window, cx);
+            let left_pane = workspace.active_pane().clone();
+            let right_pane =
+                workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx);
+            (item, left_pane, right_pane)
+        });
+        test::draw(cx);
+        let element_at = |position: Point<Pixels>, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| workspace.element_at(position, cx))
+        };
+
+        let tab = cx.debug_bounds("TAB-0").unwrap();
+        match element_at(tab.center(), cx) {
+            WorkspaceRegion::Tab {
+                pane,
+                item: tab_item,
+            } => {
+                assert_eq!(pane, left_pane);
+                assert_eq!(tab_item.item_id(), item.entity_id());
+            }
+            _ => panic!("expected the tab of the item"),
+        }
+
+        let right_pane_bounds = right_pane.update(cx, |pane, _| pane.bounds());
+        assert!(matches!(
+            element_at(right_pane_bounds.center(), cx),
+            WorkspaceRegion::Pane(pane) if pane == right_pane
+        ));
+
+        let left_pane_bounds = left_pane.update(cx, |pane, _| pane.bounds());
+        let divider = point(left_pane_bounds.right(), left_pane_bounds.center().y);
+        assert!(matches!(
+            element_at(divider, cx),
+            WorkspaceRegion::PaneDivider {
+                axis: gpui::Axis::Horizontal
+            }
+        ));
+
+        // The test panel doesn't have a pane, so its dock is all there is.
+        let left_dock = workspace.update(cx, |workspace, _| workspace.left_dock().clone());
+        let dock = cx.debug_bounds("DOCK-left").unwrap();
+        assert!(matches!(
+            element_at(dock.center(), cx),
+            WorkspaceRegion::Dock(dock) if dock == left_dock
+        ));
+        assert!(matches!(
+            element_at(point(dock.right(), dock.center().y), cx),
+            WorkspaceRegion::DockDivider(dock) if dock == left_dock
+        ));
+
+        let button = cx.debug_bounds("PANEL_BUTTON-TestPanel").unwrap();
+        assert!(matches!(
+            element_at(button.center(), cx),
+            WorkspaceRegion::StatusBarItem(view) if view.downcast::<PanelButtons>().is_ok()
+        ));
+
+        assert!(matches!(
+            element_at(point(px(-10.), px(-10.)), cx),
+            WorkspaceRegion::None
+        ));
+    }
+
+    #[gpui::test]
+    async fn test_panel_temporary_size(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            panel
+        });
+        let sizes = |cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                (
+                    workspace
+                        .left_dock()
+                        .read(cx)
+                        .active_panel_size(window, cx)
+                        .unwrap(),
+                    panel.read(cx).size,
+                )
+            })
+        };
+        assert_eq!(sizes(cx), (px(300.), px(300.)));
+
+        // A timed temporary size expires without changing the panel's own size.
+        cx.update(|_, cx| {
+            panel.request_temporary_size(
+                px(500.),
+                TemporarySizeDuration::For(Duration::from_secs(1)),
+                cx,
+            )
+        });
+        assert_eq!(sizes(cx), (px(500.), px(300.)));
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        assert_eq!(sizes(cx), (px(300.), px(300.)));
+
+        // Requests can't shrink the panel.
+        cx.update(|_, cx| {
+            panel.request_temporary_size(px(200.), TemporarySizeDuration::UntilBlur, cx)
+        });
+        assert_eq!(sizes(cx), (px(300.), px(300.)));
+
+        // A temporary size can last until the panel loses focus.
+        workspace.update_in(cx, |_, window, cx| {
+            window.focus(&panel.focus_handle(cx));
+        });
+        cx.update(|_, cx| {
+            panel.request_temporary_size(px(500.), TemporarySizeDuration::UntilBlur, cx)
+        });
+        cx.executor().advance_clock(Duration::from_secs(10));
+        cx.run_until_parked();
+        assert_eq!(sizes(cx), (px(500.), px(300.)));
+        workspace.update_in(cx, |workspace, window, cx| {
+            window.focus(&workspace.active_pane().focus_handle(cx));
+        });
+        cx.run_until_parked();
+        assert_eq!(sizes(cx), (px(300.), px(300.)));
+    }
+
+    #[gpui::test]
+    async fn test_secondary_status_bar(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let item = cx.new(|cx| TestItem::new(cx).with_label("item"));
+        let status_item = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item.clone()), None, true, window, cx);
+            let status_item = cx.new(|_| TestStatusItem::new("item").with_visible(false));
+            workspace
+                .secondary_status_bar()
+                .update(cx, |status_bar, cx| {
+                    status_bar.add_left_item(status_item.clone(), window, cx);
+                });
+            status_item
+        });
+
+        // The strip is hidden while none of its items are visible.
+        let snapshot = chrome_snapshot::chrome_snapshot(cx);
+        assert!(!snapshot.contains("SECONDARY_STATUS_BAR"), "{snapshot}");
+        workspace.update(cx, |workspace, cx| {
+            assert!(!workspace
+                .secondary_status_bar()
+                .read(cx)
+                .has_visible_items(cx));
+        });
+
+        status_item.update(cx, |status_item, cx| {
+            status_item.visible = true;
+            cx.notify();
+        });
+        let snapshot = chrome_snapshot::chrome_snapshot(cx);
+        assert!(snapshot.contains("SECONDARY_STATUS_BAR"), "{snapshot}");
+        assert_eq!(
+            status_item.read_with(cx, |status_item, _| status_item.active_item_id),
+            Some(item.entity_id())
+        );
+
+        // Its items follow the active pane.
+        workspace.update_in(cx, |workspace, window, cx| {
+            let pane = workspace.active_pane().clone();
+            workspace.split_pane(pane, SplitDirection::Right, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            status_item.read_with(cx, |status_item, _| status_item.active_item_id),
+            None
+        );
+    }
+
+    #[gpui::test]
+    async fn test_status_bar_focus_mode(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let essential = cx.new(|_| TestStatusItem::new("essential").with_essential(true));
+            let other = cx.new(|_| TestStatusItem::new("other"));
+            workspace.status_bar().update(cx, |status_bar, cx| {
+                status_bar.add_left_item(essential, window, cx);
+                status_bar.add_right_item(other, window, cx);
+            });
+        });
+        let shown_items = |cx: &mut VisualTestContext| {
+            cx.run_until_parked();
+            cx.update(|window, _| window.refresh());
+            ["essential", "other"]
+                .into_iter()
+                .filter(|name| cx.debug_bounds(*name).is_some())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(shown_items(cx), ["essential", "other"]);
+
+        cx.dispatch_action(ToggleStatusBarFocusMode);
+        assert_eq!(shown_items(cx), ["essential"]);
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.status_bar().read(cx).is_focus_mode());
+        });
+
+        cx.dispatch_action(ToggleStatusBarFocusMode);
+        assert_eq!(shown_items(cx), ["essential", "other"]);
+    }
+
+    #[gpui::test]
+    async fn test_status_bar_priorities_and_hidden_items(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.status_bar().update(cx, |status_bar, cx| {
+                for (name, alignment, priority) in [
+                    ("left_low", StatusItemAlignment::Left, 0),
+                    ("left_high", StatusItemAlignment::Left, 10),
+                    ("right_low", StatusItemAlignment::Right, 0),
+                    ("right_high", StatusItemAlignment::Right, 10),
+                ] {
+                    let item = cx.new(|_| TestStatusItem::new(name));
+                    status_bar.add_item(item, alignment, priority, window, cx);
+                }
+            });
+        });
+        let left_edges = |cx: &mut VisualTestContext| {
+            cx.run_until_parked();
+            cx.update(|window, _| window.refresh());
+            ["left_low", "left_high", "right_low", "right_high"]
+                .into_iter()
+                .filter_map(|name| Some((name, cx.debug_bounds(name)?.left())))
+                .collect::<HashMap<_, _>>()
+        };
+
+        // Items with a higher priority are closer to the edges of the status bar.
+        let edges = left_edges(cx);
+        assert!(edges["left_high"] < edges["left_low"]);
+        assert!(edges["left_low"] < edges["right_low"]);
+        assert!(edges["right_low"] < edges["right_high"]);
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<StatusBarSettings>(cx, |settings| {
+                settings.hidden_items = Some(vec!["left_high".into(), "right_low".into()]);
+            });
+        });
+        let mut shown_items = left_edges(cx).into_keys().collect::<Vec<_>>();
+        shown_items.sort();
+        assert_eq!(shown_items, ["left_low", "right_high"]);
+    }
+
+    #[gpui::test]
+    async fn test_status_bar_overflow(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.status_bar().update(cx, |status_bar, cx| {
+                for (name, alignment, priority) in [
+                    ("high", StatusItemAlignment::Left, 20),
+                    ("medium", StatusItemAlignment::Right, 10),
+                    ("low", StatusItemAlignment::Left, 0),
+                ] {
+                    let item = cx.new(|_| TestStatusItem::new(name).with_width(px(300.)));
+                    status_bar.add_item(item, alignment, priority, window, cx);
+                }
+            });
+        });
+        let shown_items = |cx: &mut VisualTestContext| {
+            // Items are measured in one frame, and moved into the overflow menu in the next.
+            for _ in 0..2 {
+                cx.run_until_parked();
+                cx.update(|window, _| window.refresh());
+            }
+            ["high", "medium", "low"]
+                .into_iter()
+                .filter(|name| cx.debug_bounds(*name).is_some())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(shown_items(cx), ["high", "medium", "low"]);
+
+        cx.simulate_resize(size(px(800.), px(600.)));
+        assert_eq!(shown_items(cx), ["high", "medium"]);
+
+        cx.simulate_resize(size(px(500.), px(600.)));
+        assert_eq!(shown_items(cx), ["high"]);
+
+        cx.simulate_resize(size(px(1600.), px(600.)));
+        assert_eq!(shown_items(cx), ["high", "medium", "low"]);
+    }
+
+    #[gpui::test]
+    async fn test_resize_policy_keeps_panel_sizes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        cx.simulate_resize(size(px(1600.), px(800.)));
+        cx.run_until_parked();
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.set_resize_policy(Some(Box::new(resize_policy::Proportional)));
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.resize_active_panel(Some(px(400.)), window, cx)
+            });
+            panel
+        });
+        cx.run_until_parked();
+        let displayed_size = |cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace
+                    .left_dock()
+                    .read(cx)
+                    .active_panel_size(window, cx)
+                    .unwrap()
+            })
+        };
+
+        // The dock shrinks with the window, but the panel keeps its own size.
+        cx.simulate_resize(size(px(800.), px(800.)));
+        cx.run_until_parked();
+        assert!(displayed_size(cx) < px(400.));
+        assert_eq!(panel.read_with(cx, |panel, _| panel.size), px(400.));
+
+        // Resizing the dock by hand replaces the size given by the policy.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.resize_active_panel(Some(px(300.)), window, cx)
+            });
+        });
+        assert_eq!(displayed_size(cx), px(300.));
+        assert_eq!(panel.read_with(cx, |panel, _| panel.size), px(300.));
+    }
+
+    #[gpui::test]
+    async fn test_status_item_click_action_and_context_menu(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let item = workspace.update_in(cx, |workspace, window, cx| {
+            let item = cx.new(|_| {
+                TestStatusItem::new("clickable")
+                    .with_essential(true)
+                    .with_click_action(Box::new(ToggleStatusBarFocusMode))
+            });
+            workspace.status_bar().update(cx, |status_bar, cx| {
+                status_bar.add_left_item(item.clone(), window, cx);
+            });
+            item
+        });
+        cx.run_until_parked();
+        cx.update(|window, _| window.refresh());
+        let position = cx.debug_bounds("clickable").unwrap().center();
+
+        cx.simulate_click(position, Modifiers::none());
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.status_bar().read(cx).is_focus_mode());
+        });
+
+        cx.simulate_mouse_down(position, MouseButton::Right, Modifiers::none());
+        item.update(cx, |item, _| assert_eq!(item.context_menus_built, 1));
+    }
+
+    #[gpui::test]
+    async fn test_toast_autohide(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let id = NotificationId::unique::<TestToast>();
+        let show_toast = |message: &'static str, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(id.clone(), message).autohide_after(Duration::from_secs(2)),
+                    cx,
+                )
+            });
+        };
+        let notification_count = |cx: &mut VisualTestContext| {
+            workspace.read_with(cx, |workspace, _| workspace.notification_ids().len())
+        };
+
+        show_toast("first", cx);
+        cx.executor().advance_clock(Duration::from_millis(1500));
+        assert_eq!(notification_count(cx), 1);
+
+        // Showing the toast again replaces it and restarts its timer.
+        show_toast("second", cx);
+        cx.executor().advance_clock(Duration::from_millis(1500));
+        assert_eq!(notification_count(cx), 1);
+
+        cx.executor().advance_clock(Duration::from_millis(600));
+        assert_eq!(notification_count(cx), 0);
+    }
+
+    #[gpui::test]
+    async fn test_notification_center(cx: &mut gpui::TestAppContext) {
+        use notification_center::NotificationCenter;
+        use notifications::NotificationSeverity;
+
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        struct TestNotification;
+        let notification_center = workspace.update_in(cx, |workspace, window, cx| {
+            let notification_center = NotificationCenter::new(workspace, window, cx);
+            workspace.add_panel(notification_center.clone(), window, cx);
+
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<TestToast>(), "Saved").autohide(),
+                cx,
+            );
+            workspace.show_error(&anyhow!("Disk full"), cx);
+            workspace.show_notification(NotificationId::unique::<TestNotification>(), cx, |cx| {
+                cx.new(|cx| MessageNotification::new("Indexing finished", cx))
+            });
+            notification_center
+        });
+        let visible_messages = |cx: &mut VisualTestContext| {
+            notification_center.read_with(cx, |notification_center, cx| {
+                notification_center
+                    .visible_entries(cx)
+                    .into_iter()
+                    .map(|entry| entry.message.to_string())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // Dismissed toasts are still listed, newest first.
+        cx.executor().advance_clock(Duration::from_secs(10));
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.notification_ids().len(), 2);
+        });
+        assert_eq!(
+            visible_messages(cx),
+            ["Indexing finished", "Error: Disk full", "Saved"]
+        );
+
+        notification_center.update(cx, |notification_center, cx| {
+            notification_center.set_severity_filter(Some(NotificationSeverity::Info), cx)
+        });
+        assert_eq!(visible_messages(cx), ["Indexing finished", "Saved"]);
+
+        // While the panel is shown, it's re-rendered every minute to update its timestamps.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_panel_focus::<NotificationCenter>(window, cx);
+        });
+        cx.run_until_parked();
+        let refreshed = Rc::new(RefCell::new(0));
+        let _subscription = cx.update(|_, cx| {
+            let refreshed = refreshed.clone();
+            cx.observe(&notification_center, move |_, _| {
+                *refreshed.borrow_mut() += 1
+            })
+        });
+        cx.executor().advance_clock(Duration::from_secs(60));
+        cx.run_until_parked();
+        assert_eq!(*refreshed.borrow(), 1);
+
+        workspace.update(cx, |workspace, cx| workspace.clear_notification_log(cx));
+        assert!(visible_messages(cx).is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_progress_reporting(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let indicator =
+            workspace.read_with(cx, |workspace, _| workspace.progress_indicator().clone());
+
+        let cancelled = Rc::new(RefCell::new(false));
+        let (indexing, downloading) = workspace.update(cx, |workspace, cx| {
+            let indexing = workspace.start_progress("Indexing", cx);
+            let downloading = workspace.start_progress("Downloading", cx);
+            downloading.on_cancel(
+                {
+                    let cancelled = cancelled.clone();
+                    move |_| *cancelled.borrow_mut() = true
+                },
+                cx,
+            );
+            downloading.show_toast(cx);
+            (indexing, downloading)
+        });
+        cx.run_until_parked();
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.notification_ids().len(), 1);
+        });
+        indicator.read_with(cx, |indicator, _| {
+            assert_eq!(indicator.operation_count(), 2);
+            // Indexing doesn't report how far along it is.
+            assert_eq!(indicator.aggregate_fraction(), None);
+        });
+
+        cx.update(|_, cx| {
+            indexing.set_fraction(0.5, cx);
+            downloading.set_fraction(1.5, cx);
+        });
+        indicator.read_with(cx, |indicator, _| {
+            assert_eq!(indicator.aggregate_fraction(), Some(0.75));
+        });
+
+        // Cancelling an operation runs its callback and dismisses its toast.
+        indicator.update(cx, |indicator, cx| indicator.cancel(downloading.id(), cx));
+        cx.run_until_parked();
+        assert!(*cancelled.borrow());
+        cx.update(|_, cx| {
+            assert!(downloading.is_cancelled(cx));
+            assert!(!indexing.is_cancelled(cx));
+        });
+        workspace.read_with(cx, |workspace, _| {
+            assert!(workspace.notification_ids().is_empty());
+        });
+
+        cx.update(|_, cx| indexing.finish(cx));
+        indicator.read_with(cx, |indicator, _| {
+            assert_eq!(indicator.operation_count(), 0);
+        });
+
+        // Dropping the handle of an operation finishes it too, along with its toast.
+        let syncing = workspace.update(cx, |workspace, cx| {
+            let syncing = workspace.start_progress("Syncing", cx);
+            syncing.show_toast(cx);
+            syncing
+        });
+        cx.run_until_parked();
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.notification_ids().len(), 1);
+        });
+        drop(syncing);
+        cx.run_until_parked();
+        indicator.read_with(cx, |indicator, _| {
+            assert_eq!(indicator.operation_count(), 0);
+        });
+        workspace.read_with(cx, |workspace, _| {
+            assert!(workspace.notification_ids().is_empty());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_modal_stack(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let dismissed_count = Rc::new(RefCell::new(0));
+        let (pane, _subscription) = workspace.update_in(cx, |workspace, window, cx| {
+            let pane = workspace.active_pane().clone();
+            window.focus(&pane.focus_handle(cx));
+            let dismissed_count = dismissed_count.clone();
+            let subscription =
+                cx.subscribe(workspace.modal_layer(), move |_, _, _: &ModalEvent, _| {
+                    *dismissed_count.borrow_mut() += 1;
+                });
+            (pane, subscription)
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_modal(window, cx, TestModal::new);
+        });
+        cx.run_until_parked();
+        let first_modal = workspace
+            .read_with(cx, |workspace, cx| workspace.active_modal::<TestModal>(cx))
+            .unwrap();
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.push_modal(window, cx, TestModal::new);
+        });
+        cx.run_until_parked();
+        let second_modal = workspace
+            .read_with(cx, |workspace, cx| workspace.active_modal::<TestModal>(cx))
+            .unwrap();
+        assert_ne!(first_modal, second_modal);
+        workspace.read_with(cx, |workspace, cx| {
+            assert_eq!(workspace.modal_layer().read(cx).modal_count(), 2);
+        });
+        cx.update(|window, cx| {
+            assert!(second_modal.focus_handle(cx).is_focused(window));
+        });
+
+        // Escape dismisses the topmost modal and focuses the one below it.
+        cx.dispatch_action(menu::Cancel);
+        cx.run_until_parked();
+        assert_eq!(*dismissed_count.borrow(), 1);
+        workspace.read_with(cx, |workspace, cx| {
+            assert_eq!(
+                workspace.active_modal::<TestModal>(cx),
+                Some(first_modal.clone())
+            );
+        });
+        cx.update(|window, cx| {
+            assert!(first_modal.focus_handle(cx).is_focused(window));
+        });
+
+        // Clicking outside of the remaining modal dismisses it and restores the pane's focus.
+        cx.simulate_click(point(px(1.), px(1.)), Modifiers::none());
+        cx.run_until_parked();
+        assert_eq!(*dismissed_count.borrow(), 2);
+        workspace.read_with(cx, |workspace, cx| {
+            assert_eq!(workspace.modal_layer().read(cx).modal_count(), 0);
+        });
+        cx.update(|window, cx| {
+            assert!(pane.focus_handle(cx).contains_focused(window, cx));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_prompt_dialog(cx: &mut gpui::TestAppContext) {
+        use prompt_dialog::{PromptDialog, PromptOptions};
+
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let prompt = |cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.prompt(
+                    "Reload the file?",
+                    PromptOptions::new(["Reload", "Keep", "Cancel"])
+                        .dont_ask_again("test-prompt-dialog"),
+                    window,
+                    cx,
+                )
+            })
+        };
+        let active_dialog = |cx: &mut VisualTestContext| {
+            workspace.read_with(cx, |workspace, cx| {
+                workspace.active_modal::<PromptDialog>(cx)
+            })
+        };
+
+        // Escape dismisses the prompt without an answer.
+        let answer = prompt(cx);
+        cx.run_until_parked();
+        assert!(active_dialog(cx).is_some());
+        cx.dispatch_action(menu::Cancel);
+        assert_eq!(answer.await, None);
+        assert!(active_dialog(cx).is_none());
+
+        // The answers can be picked with the keyboard.
+        let answer = prompt(cx);
+        cx.run_until_parked();
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(menu::SelectPrevious);
+        let dialog = active_dialog(cx).unwrap();
+        dialog.read_with(cx, |dialog, _| assert_eq!(dialog.selected_answer(), 1));
+        dialog.update(cx, |dialog, cx| dialog.set_dont_ask_again(true, cx));
+        cx.dispatch_action(menu::Confirm);
+        assert_eq!(answer.await, Some(1));
+        cx.run_until_parked();
+
+        // Once "Don't ask again" was checked, the same answer is returned without prompting.
+        let answer = prompt(cx);
+        cx.run_until_parked();
+        assert!(active_dialog(cx).is_none());
+        assert_eq!(answer.await, Some(1));
+
+        // After resetting the remembered answers, the prompt is shown again.
+        cx.update(|_, cx| prompt_dialog::reset_remembered_answers(cx));
+        cx.run_until_parked();
+        let answer = prompt(cx);
+        cx.run_until_parked();
+        assert!(active_dialog(cx).is_some());
+        workspace.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Cancel", cx)
+        });
+        assert_eq!(answer.await, Some(2));
+
+        // A checklist resolves to which of its entries were left checked.
+        let answer = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.prompt_with_checklist(
+                "Save the files?",
+                PromptOptions::new(["Save", "Cancel"]).checklist(["a.txt", "b.txt"]),
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        active_dialog(cx)
+            .unwrap()
+            .update(cx, |dialog, cx| dialog.set_checked(0, false, cx));
+        cx.dispatch_action(menu::Confirm);
+        assert_eq!(answer.await, Some((0, vec![false, true])));
+    }
+
+    #[gpui::test]
+    async fn test_restore_unsaved_changes_asks_once_after_crash(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx).build().await;
+        let workspace_a = test.workspace.clone();
+        let cx = &mut test.cx;
+
+        let app_state = workspace_a.read_with(cx, |workspace, _| workspace.app_state().clone());
+        app_state
+            .session
+            .update(cx, |session, _| session.simulate_last_session_crashed());
+        let project_b = Project::test(test.fs.clone(), [], cx).await;
+        let window_b = cx.add_window(|window, cx| {
+            Workspace::new(Default::default(), project_b, app_state, window, cx)
+        });
+        let workspace_b = window_b.root(cx).unwrap();
+        let cx_b = &mut VisualTestContext::from_window(*window_b, cx);
+
+        let has_prompt = |workspace: &Entity<Workspace>, cx: &mut VisualTestContext| {
+            workspace.read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_some())
+        };
+        let has_items = |workspace: &Entity<Workspace>, cx: &mut VisualTestContext| {
+            workspace.read_with(cx, |workspace, cx| {
+                workspace.active_pane().read(cx).items_len() > 0
+            })
+        };
+        let restore = |workspace: &Entity<Workspace>, cx: &mut VisualTestContext| {
+            let item = cx.new(|cx| TestItem::new(cx).with_dirty(true));
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.add_item_to_active_pane(Box::new(item.clone()), None, true, window, cx);
+                workspace.prompt_to_restore_unsaved_changes(vec![item.boxed_clone()], window, cx)
+            })
+        };
+
+        // Only the first restored workspace asks, the other one waits for its answer.
+        let task_a = restore(&workspace_a, cx);
+        cx.run_until_parked();
+        assert!(has_prompt(&workspace_a, cx));
+        let task_b = restore(&workspace_b, cx_b);
+        cx_b.run_until_parked();
+        assert!(!has_prompt(&workspace_b, cx_b));
+
+        workspace_a.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Discard", cx)
+        });
+        task_a.await.unwrap();
+        task_b.await.unwrap();
+        assert!(!has_items(&workspace_a, cx));
+        assert!(!has_items(&workspace_b, cx_b));
+
+        // Workspaces restored later apply the same answer without asking again.
+        let task = restore(&workspace_b, cx_b);
+        cx_b.run_until_parked();
+        assert!(!has_prompt(&workspace_b, cx_b));
+        task.await.unwrap();
+        assert!(!has_items(&workspace_b, cx_b));
+    }
+
+    #[gpui::test]
+    async fn test_closed_items_outlive_their_pane(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let item_a = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(1, "a.txt", cx)])
+        });
+        let item_b = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(2, "b.txt", cx)])
+        });
+        let (left_pane, right_pane) = workspace.update_in(cx, |workspace, window, cx| {
+            let left_pane = workspace.active_pane().clone();
+            left_pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(item_a.clone()), true, true, None, window, cx);
+            });
+            let right_pane =
+                workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx);
+            right_pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(item_b.clone()), true, true, None, window, cx);
+            });
+            (left_pane, right_pane)
+        });
+
+        right_pane
+            .update_in(cx, |pane, window, cx| {
+                pane.close_item_by_id(item_b.entity_id(), SaveIntent::Close, window, cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        // The empty pane is gone, but the item closed in it can still be reopened.
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.panes(), &[left_pane.clone()]);
+            let nav_history = left_pane.read(cx).nav_history();
+            assert!(nav_history.last_closed_timestamp().is_some());
+            let (project_path, _) = nav_history.path_for_item(item_b.entity_id()).unwrap();
+            assert_eq!(project_path.path.as_ref(), Path::new("b.txt"));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_navigation_across_panes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let item_a = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(1, "a.txt", cx)])
+        });
+        let item_b = cx.new(|cx| {
+            TestItem::new(cx).with_project_items(&[TestProjectItem::new(2, "b.txt", cx)])
+        });
+        let (left_pane, right_pane) = workspace.update_in(cx, |workspace, window, cx| {
+            let left_pane = workspace.active_pane().clone();
+            left_pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(item_a.clone()), true, true, None, window, cx);
+            });
+            let right_pane =
+                workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx);
+            right_pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(item_b.clone()), true, true, None, window, cx);
+            });
+            (left_pane, right_pane)
+        });
+        item_a.update(cx, |item, cx| item.set_state("a".into(), cx));
+        item_b.update(cx, |item, cx| item.set_state("b".into(), cx));
+        cx.run_until_parked();
+
+        let labels = |mode, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| {
+                workspace
+                    .navigation_history(mode, cx)
+                    .into_iter()
+                    .map(|entry| entry.label)
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(labels(NavigationMode::GoingBack, cx), ["b.txt", "a.txt"]);
+
+        // Going back visits the most recent navigations first, whichever pane they were in.
+        for expected_pane in [&right_pane, &left_pane] {
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.go_back_across_panes(window, cx)
+                })
+                .await
+                .unwrap();
+            cx.run_until_parked();
+            workspace.update(cx, |workspace, _| {
+                assert_eq!(workspace.active_pane(), expected_pane);
+            });
+        }
+        assert!(labels(NavigationMode::GoingBack, cx).is_empty());
+        assert_eq!(labels(NavigationMode::GoingForward, cx), ["a.txt", "b.txt"]);
+
+        // Jumping to an entry goes forward through the entries before it.
+        let timestamp = workspace.update(cx, |workspace, cx| {
+            workspace.navigation_history(NavigationMode::GoingForward, cx)[1].timestamp
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.navigate_to_history_entry(
+                    NavigationMode::GoingForward,
+                    timestamp,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.active_pane(), &right_pane);
+            assert!(!workspace.can_navigate_across_panes(NavigationMode::GoingForward, cx));
+        });
+
+        // Navigating somewhere new in one pane drops the forward history of all panes.
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.go_back_across_panes(window, cx)
+            })
+            .await
+            .unwrap();
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.can_navigate_across_panes(NavigationMode::GoingForward, cx));
+        });
+        item_a.update(cx, |item, cx| item.set_state("c".into(), cx));
+        workspace.update(cx, |workspace, cx| {
+            assert!(!workspace.can_navigate_across_panes(NavigationMode::GoingForward, cx));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_toggle_zoom_pane(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (left_pane, right_pane, panel) = workspace.update_in(cx, |workspace, window, cx| {
+            let left_pane = workspace.active_pane().clone();
+            left_pane.update(cx, |pane, cx| {
+                pane.add_item(
+                    Box::new(cx.new(TestItem::new)),
+                    true,
+                    true,
+                    None,
+                    window,
+                    cx,
+                );
+            });
+            let right_pane =
+                workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx);
+            right_pane.update(cx, |pane, cx| {
+                pane.add_item(
+                    Box::new(cx.new(TestItem::new)),
+                    true,
+                    true,
+                    None,
+                    window,
+                    cx,
+                );
+            });
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            (left_pane, right_pane, panel)
+        });
+        cx.run_until_parked();
+
+        // Maximizing hides the other panes, but keeps them in the split layout.
+        cx.dispatch_action(ToggleZoomPane);
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.maximized_pane(), Some(right_pane.clone()));
+            assert_eq!(workspace.center.panes().len(), 2);
+        });
+
+        // A zoomed panel takes the zoom slot over the maximized pane until it's zoomed out.
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::ZoomIn));
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.maximized_pane(), None);
+        });
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::ZoomOut));
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.maximized_pane(), Some(right_pane.clone()));
+        });
+
+        // Toggling again restores the other panes.
+        right_pane.update_in(cx, |pane, window, cx| window.focus(&pane.focus_handle(cx)));
+        cx.dispatch_action(ToggleZoomPane);
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.maximized_pane(), None);
+        });
+
+        // So does activating one of the hidden panes.
+        cx.dispatch_action(ToggleZoomPane);
+        left_pane.update_in(cx, |pane, window, cx| window.focus(&pane.focus_handle(cx)));
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.active_pane(), &left_pane);
+            assert_eq!(workspace.maximized_pane(), None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_open_panel_async(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // The panel is opened once it's added.
+        let open_panel = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.open_panel_async::<TestPanel>(window, cx)
+        });
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert!(!workspace.right_dock().read(cx).is_open());
+        });
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            panel
+        });
+        let opened_panel = open_panel.await.unwrap();
+        assert_eq!(opened_panel, panel);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(panel.focus_handle(cx).contains_focused(window, cx));
+            workspace.close_all_docks(window, cx);
+        });
+
+        // A panel that was already added is opened right away.
+        let opened_panel = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_panel_async::<TestPanel>(window, cx)
+            })
+            .await
+            .unwrap();
+        assert_eq!(opened_panel, panel);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(panel.focus_handle(cx).contains_focused(window, cx));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_open_panel_async_without_panel(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // Waiting for a panel fails once the panels were loaded without it.
+        let open_panel = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.open_panel_async::<TestPanel>(window, cx)
+        });
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.panel_waiters.len(), 1);
+            workspace.finish_panel_initialization();
+        });
+        assert!(open_panel.await.is_err());
+
+        // Later requests fail right away instead of waiting.
+        let open_panel = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.open_panel_async::<TestPanel>(window, cx)
+        });
+        workspace.update(cx, |workspace, _| {
+            assert!(workspace.panel_waiters.is_empty())
+        });
+        assert!(open_panel.await.is_err());
+    }
+
+    #[gpui::test]
+    async fn test_dock_behavior_presets(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.dock_behavior = Some(DockBehaviorPreset::JetBrains);
+            });
+        });
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            panel
+        });
+        let left_dock_open = |cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| workspace.left_dock().read(cx).is_open())
+        };
+        let panel_focused = |cx: &mut VisualTestContext| {
+            cx.update(|window, cx| panel.focus_handle(cx).contains_focused(window, cx))
+        };
+
+        // The preset overlays the dock over the center, and focuses it when it's toggled.
+        cx.dispatch_action(ToggleLeftDock);
+        assert!(left_dock_open(cx));
+        assert!(panel_focused(cx));
+        test::draw(cx);
+        let center = cx.debug_bounds("CENTER").unwrap();
+        let dock = cx.debug_bounds("DOCK-left").unwrap();
+        assert_eq!(center.origin.x, dock.origin.x);
+
+        // Focusing the center hides the dock again.
+        workspace.update_in(cx, |workspace, window, cx| {
+            window.focus(&workspace.active_pane().focus_handle(cx));
+        });
+        cx.run_until_parked();
+        assert!(!left_dock_open(cx));
+
+        // A panel vetoing the auto-hide keeps the dock open without telling the user about it.
+        cx.dispatch_action(ToggleLeftDock);
+        panel.update(cx, |panel, _| panel.veto_close = true);
+        workspace.update_in(cx, |workspace, window, cx| {
+            window.focus(&workspace.active_pane().focus_handle(cx));
+        });
+        cx.run_until_parked();
+        assert!(left_dock_open(cx));
+        workspace.update(cx, |workspace, _| {
+            assert!(workspace.notification_ids().is_empty())
+        });
+        panel.update(cx, |panel, _| panel.veto_close = false);
+        cx.dispatch_action(ToggleLeftDock);
+        assert!(!left_dock_open(cx));
+
+        // Without a preset, the individual options apply.
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.dock_behavior = Some(DockBehaviorPreset::Custom);
+                settings.dock_options = Some(DockOptions {
+                    focus_on_toggle: false,
+                    ..DockOptions::default()
+                });
+            });
+        });
+        cx.dispatch_action(ToggleLeftDock);
+        assert!(left_dock_open(cx));
+        assert!(!panel_focused(cx));
+        test::draw(cx);
+        let center = cx.debug_bounds("CENTER").unwrap();
+        let dock = cx.debug_bounds("DOCK-left").unwrap();
+        assert_eq!(center.origin.x, dock.right());
+
+        assert_eq!(
+            DockOptions::VS_CODE.button_placement.arrange(&[1, 2, 3, 4]),
+            (vec![1, 2, 3, 4], vec![])
+        );
+        assert_eq!(
+            DockButtonPlacement::DockSide.arrange(&[1, 2, 3, 4]),
+            (vec![1], vec![4, 3, 2])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_default_layout(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // The layout is applied before the panels are registered.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.apply_default_layout(
+                DefaultLayout::new()
+                    .dock(DockPosition::Left, "TestPanel", true)
+                    .size(px(250.))
+                    .dock(DockPosition::Bottom, "TestPanel", false),
+                window,
+                cx,
+            );
+        });
+        let (bottom_panel, left_panel) = workspace.update_in(cx, |workspace, window, cx| {
+            let bottom_panel = cx.new(|cx| TestPanel::new(DockPosition::Bottom, cx));
+            workspace.add_panel(bottom_panel.clone(), window, cx);
+            let left_panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(left_panel.clone(), window, cx);
+            (bottom_panel, left_panel)
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let left_dock = workspace.left_dock().read(cx);
+            assert!(left_dock.is_open());
+            assert_eq!(
+                left_dock.visible_panel().unwrap().panel_id(),
+                left_panel.entity_id()
+            );
+            assert_eq!(left_panel.read(cx).size(window, cx), px(250.));
+
+            let bottom_dock = workspace.bottom_dock().read(cx);
+            assert!(!bottom_dock.is_open());
+            assert_eq!(
+                bottom_dock.active_panel().unwrap().panel_id(),
+                bottom_panel.entity_id()
+            );
+            assert_eq!(bottom_panel.read(cx).size(window, cx), px(300.));
+            assert!(!workspace.right_dock().read(cx).is_open());
+        });
+
+        // Panels that start open don't reopen a dock the layout closed.
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel {
+                starts_open: true,
+                ..TestPanel::new(DockPosition::Bottom, cx)
+            });
+            workspace.add_panel(panel, window, cx);
+            assert!(!workspace.bottom_dock().read(cx).is_open());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_panels_starting_open(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // Without a layout to restore, a panel that starts open opens its dock.
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel {
+                starts_open: true,
+                ..TestPanel::new(DockPosition::Left, cx)
+            });
+            workspace.add_panel(panel.clone(), window, cx);
+            let left_dock = workspace.left_dock().read(cx);
+            assert!(left_dock.is_open());
+            assert_eq!(
+                left_dock.visible_panel().unwrap().panel_id(),
+                panel.entity_id()
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_default_layout_bottom_right_dock(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.apply_default_layout(
+                DefaultLayout::new().bottom_right_dock("TestPanel", true),
+                window,
+                cx,
+            );
+        });
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Bottom, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            panel
+        });
+
+        workspace.update(cx, |workspace, cx| {
+            let bottom_right_dock = workspace.bottom_right_dock.read(cx);
+            assert!(bottom_right_dock.is_open());
+            assert_eq!(
+                bottom_right_dock.visible_panel().unwrap().panel_id(),
+                panel.entity_id()
+            );
+            let bottom_dock = workspace.bottom_dock().read(cx);
+            assert!(!bottom_dock.is_open());
+            assert_eq!(bottom_dock.panels_len(), 0);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_activate_panel(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            panel
+        });
+
+        // The action is bound with the panel's persistent name and a dock position.
+        let action = cx
+            .update(|_, cx| {
+                cx.build_action(
+                    "workspace::ActivatePanel",
+                    Some(json!({ "name": "TestPanel" })),
+                )
+            })
+            .unwrap();
+        cx.dispatch_action(action);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.left_dock().read(cx).is_open());
+            assert!(panel.focus_handle(cx).contains_focused(window, cx));
+        });
+
+        let action = cx
+            .update(|_, cx| {
+                cx.build_action(
+                    "workspace::ActivatePanel",
+                    Some(json!({ "name": "TestPanel", "dock": "right" })),
+                )
+            })
+            .unwrap();
+        cx.dispatch_action(action);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(!workspace.left_dock().read(cx).is_open());
+            let right_dock = workspace.right_dock().read(cx);
+            assert!(right_dock.is_open());
+            assert_eq!(
+                right_dock.visible_panel().unwrap().panel_id(),
+                panel.entity_id()
+            );
+            assert!(panel.focus_handle(cx).contains_focused(window, cx));
+        });
+
+        // Unknown panels and docks are rejected.
+        cx.dispatch_action(ActivatePanel {
+            name: "MissingPanel".into(),
+            dock: None,
+        });
+        assert!(cx
+            .update(|_, cx| cx.build_action(
+                "workspace::ActivatePanel",
+                Some(json!({ "name": "TestPanel", "dock": "top" })),
+            ))
+            .is_err());
+    }
+
+    #[gpui::test]
+    async fn test_toggle_panel_group(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                settings.panel_groups = Some(HashMap::from_iter([
+                    ("review".to_string(), vec!["TestPanel".to_string()]),
+                    ("empty".to_string(), vec!["MissingPanel".to_string()]),
+                ]));
+            });
+        });
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel, window, cx);
+        });
+        let left_dock_open = |cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| workspace.left_dock().read(cx).is_open())
+        };
+        assert!(!left_dock_open(cx));
+
+        cx.dispatch_action(TogglePanelGroup("review".into()));
+        assert!(left_dock_open(cx));
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.open_panel_group(), Some("review"));
+        });
+
+        // Toggling the group again restores the previous layout.
+        cx.dispatch_action(TogglePanelGroup("review".into()));
+        assert!(!left_dock_open(cx));
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.open_panel_group(), None);
+        });
+
+        // Groups without any of their panels don't open.
+        cx.dispatch_action(TogglePanelGroup("empty".into()));
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.open_panel_group(), None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_leaked_panels_are_reported(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (leaked_panel, released_panel) = workspace.update_in(cx, |workspace, window, cx| {
+            let leaked_panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(leaked_panel.clone(), window, cx);
+            let released_panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(released_panel.clone(), window, cx);
+            (leaked_panel, released_panel.downgrade())
+        });
+        let leak_check = workspace.update(cx, |workspace, cx| LeakCheck::new(workspace, cx));
+        assert_eq!(leak_check.leaked().len(), 6);
+
+        // Holding on to a panel after its window closed keeps only that panel alive.
+        cx.update(|window, _| window.remove_window());
+        drop(workspace);
+        cx.run_until_parked();
+        assert!(released_panel.upgrade().is_none());
+        assert_eq!(leak_check.leaked(), ["TestPanel panel"]);
+
+        drop(leaked_panel);
+        assert!(leak_check.leaked().is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_bottom_dock_slots(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Bottom, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_bottom_dock_slot(BottomDockSlot::Left, window, cx);
+            panel
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel_handle: Arc<dyn PanelHandle> = Arc::new(panel.clone());
+            workspace.move_panel_to_bottom_slot(panel_handle, BottomDockSlot::Right, window, cx);
+
+            // The panel was visible, so it stays visible in its new dock.
+            assert!(!workspace.bottom_dock().read(cx).is_open());
+            assert_eq!(workspace.bottom_dock().read(cx).panels_len(), 0);
+            let bottom_right_dock = workspace.bottom_right_dock().read(cx);
+            assert!(bottom_right_dock.is_open());
+            assert_eq!(
+                bottom_right_dock.visible_panel().unwrap().panel_id(),
+                panel.panel_id()
+            );
+
+            workspace.toggle_bottom_dock_slot(BottomDockSlot::Right, window, cx);
+            assert!(!workspace.bottom_right_dock().read(cx).is_open());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_apply_layout_restores_bottom_right_panels(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx).build().await;
+        let workspace = test.workspace.clone();
+        let cx = &mut test.cx;
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Bottom, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            let button_panel =
+                cx.new(|cx| TestPanel::new(DockPosition::Bottom, cx).with_icon(IconName::Ellipsis));
+            workspace.add_panel(button_panel.clone(), window, cx);
+            let panel: Arc<dyn PanelHandle> = Arc::new(panel);
+            let button_panel: Arc<dyn PanelHandle> = Arc::new(button_panel);
+
+            workspace.move_panel_to_bottom_slot(panel.clone(), BottomDockSlot::Right, window, cx);
+            let layout = workspace.current_layout("Split", window, cx);
+            assert_eq!(layout.docks.bottom_right_panels, ["TestPanel"]);
+
+            // Swap the panels between the bottom docks, then go back to the saved layout.
+            workspace.move_panel_to_bottom_slot(panel.clone(), BottomDockSlot::Left, window, cx);
+            workspace.move_panel_to_bottom_slot(
+                button_panel.clone(),
+                BottomDockSlot::Right,
+                window,
+                cx,
+            );
+            workspace.apply_layout(&layout, window, cx);
+
+            let bottom_dock = workspace.bottom_dock().read(cx);
+            let bottom_right_dock = workspace.bottom_right_dock().read(cx);
+            assert!(bottom_right_dock.contains_panel(panel.panel_id()));
+            assert!(!bottom_right_dock.contains_panel(button_panel.panel_id()));
+            assert!(bottom_dock.contains_panel(button_panel.panel_id()));
+            assert!(!bottom_dock.contains_panel(panel.panel_id()));
+
+            // Moving a panel that isn't in the other bottom dock leaves the layout alone.
+            let left_panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(left_panel.clone(), window, cx);
+            let left_panel: Arc<dyn PanelHandle> = Arc::new(left_panel);
+            workspace.move_panel_to_bottom_slot(
+                left_panel.clone(),
+                BottomDockSlot::Left,
+                window,
+                cx,
+            );
+            let layout = workspace.current_layout("Split", window, cx);
+            assert_eq!(layout.docks.bottom_right_panels, ["TestPanel"]);
+            assert!(workspace
+                .left_dock()
+                .read(cx)
+                .contains_panel(left_panel.panel_id()));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_test_workspace(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx)
+            .panel(DockPosition::Left)
+            .build()
+            .await;
+        let item_a = test.add_item("a");
+        test.add_item("b");
+        let left_pane = test.center_panes()[0].clone();
+        let right_pane = test.split(SplitDirection::Right);
+        test.add_item("c");
+        test.assert_center_layout("horizontal\n  pane [a, *b]\n  pane* [*c]");
+
+        test.drag_tab(&item_a, &right_pane, 0);
+        test.assert_center_layout("horizontal\n  pane [*b]\n  pane* [*a, c]");
+
+        test.focus_pane(&left_pane);
+        test.assert_active_pane(&left_pane);
+
+        test.assert_dock_open(DockPosition::Left, false);
+        test.toggle_dock(DockPosition::Left);
+        test.assert_dock_open(DockPosition::Left, true);
+    }
+
+    #[gpui::test]
+    async fn test_recently_activated_items(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx).build().await;
+        let item_a = test.add_item("a");
+        let item_b = test.add_item("b");
+        let left_pane = test.center_panes()[0].clone();
+        let right_pane = test.split(SplitDirection::Right);
+        let item_c = test.add_item("c");
+        test.focus_pane(&left_pane);
+        left_pane.update_in(&mut test.cx, |pane, window, cx| {
+            let ix = pane.index_for_item(&item_a).unwrap();
+            pane.activate_item(ix, true, true, window, cx);
+        });
+
+        // Activations in different panes are ordered together.
+        let items = test.workspace.read_with(&test.cx, |workspace, cx| {
+            workspace
+                .recently_activated_items(cx)
+                .into_iter()
+                .map(|(pane, item)| (pane, item.item_id()))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(
+            items,
+            [
+                (left_pane.clone(), item_a.entity_id()),
+                (right_pane, item_c.entity_id()),
+                (left_pane, item_b.entity_id()),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_layout_debug_string(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx)
+            .panel(DockPosition::Left)
+            .panel(DockPosition::Bottom)
+            .build()
+            .await;
+        test.add_item("a");
+        test.split(SplitDirection::Down);
+        test.add_item("b");
+        test.add_item("c");
+        test.toggle_dock(DockPosition::Left);
+        test.assert_layout(
+            "left dock: open, TestPanel\n\
+             bottom dock: closed, TestPanel\n\
+             bottom right dock: closed\n\
+             right dock: closed\n\
+             center:\n  \
+             vertical\n    \
+             pane [*a]\n    \
+             pane* [b, *c]",
+        );
+
+        let active_pane = test
+            .workspace
+            .read_with(&test.cx, |workspace, _| workspace.active_pane().clone());
+        active_pane.update_in(&mut test.cx, |pane, window, cx| {
+            pane.toggle_zoom(&ToggleZoom, window, cx);
+        });
+        test.cx.run_until_parked();
+        test.assert_layout(
+            "left dock: open, TestPanel\n\
+             bottom dock: closed, TestPanel\n\
+             bottom right dock: closed\n\
+             right dock: closed\n\
+             center:\n  \
+             vertical\n    \
+             pane [*a]\n    \
+             pane* [b, *c] zoomed",
+        );
+    }
+
+    #[gpui::test]
+    async fn test_panel_visibility(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (panel_1, panel_2) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), window, cx);
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_2.clone(), window, cx);
+            (panel_1, panel_2)
+        });
+        let visible = |cx: &mut VisualTestContext| {
+            (
+                panel_1.read_with(cx, |panel, _| panel.visible),
+                panel_2.read_with(cx, |panel, _| panel.visible),
+            )
+        };
+        assert_eq!(visible(cx), (false, false));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        assert_eq!(visible(cx), (true, false));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.activate_panel(1, window, cx);
+            });
+        });
+        assert_eq!(visible(cx), (false, true));
+
+        // Activating a panel in a closed dock doesn't show it.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.activate_panel(0, window, cx);
+            });
+        });
+        assert_eq!(visible(cx), (false, false));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        assert_eq!(visible(cx), (true, false));
+
+        // Removing the visible panel hides it.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.remove_panel(&panel_1, window, cx);
+            });
+        });
+        assert_eq!(visible(cx), (false, false));
+    }
+
+    #[gpui::test]
+    async fn test_serialization_is_debounced(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let is_pending = |cx: &mut VisualTestContext| {
+            workspace.read_with(cx, |workspace, _| workspace._schedule_serialize.is_some())
+        };
+
+        // Each change postpones the write until the workspace stops changing.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.serialize_workspace(window, cx)
+        });
+        cx.executor().advance_clock(Duration::from_millis(60));
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.serialize_workspace(window, cx)
+        });
+        cx.executor().advance_clock(Duration::from_millis(60));
+        cx.run_until_parked();
+        assert!(is_pending(cx));
+        cx.executor().advance_clock(SERIALIZATION_DEBOUNCE_TIME);
+        cx.run_until_parked();
+        assert!(!is_pending(cx));
+
+        // Continuous changes are still written once the maximum delay is over.
+        let mut writes = 0;
+        for _ in 0..25 {
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.serialize_workspace(window, cx)
+            });
+            cx.executor().advance_clock(Duration::from_millis(50));
+            cx.run_until_parked();
+            if !is_pending(cx) {
+                writes += 1;
+            }
+        }
+        assert_eq!(writes, 1);
+
+        // Flushing writes a pending serialization right away.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.serialize_workspace(window, cx);
+            workspace.flush_serialization(window, cx).detach();
+        });
+        assert!(!is_pending(cx));
+    }
+
+    #[gpui::test]
+    async fn test_loading_placeholder(cx: &mut gpui::TestAppContext) {
+        let mut test = test::TestWorkspace::new(cx)
+            .files(json!({ "slow_a.txt": "", "slow_b.txt": "" }))
+            .build()
+            .await;
+        test.cx.update(|_, cx| {
+            cx.default_global::<ProjectItemOpeners>()
+                .push(|_, project_path, window, cx| {
+                    let label = project_path.path.to_string_lossy().to_string();
+                    if !label.starts_with("slow") {
+                        return None;
+                    }
+                    Some(window.spawn(cx, async move |cx| {
+                        cx.background_executor().timer(Duration::from_secs(1)).await;
+                        let build_item: WorkspaceItemBuilder = Box::new(move |_, cx| {
+                            Box::new(cx.new(|cx| TestItem::new(cx).with_label(&label)))
+                                as Box<dyn ItemHandle>
+                        });
+                        Ok((None, build_item))
+                    }))
+                });
+        });
+        let worktree_id = test.project.read_with(&test.cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let pane = test.center_panes()[0].clone();
+        let loading_items = |test: &mut test::TestWorkspace| {
+            pane.read_with(&test.cx, |pane, _| {
+                pane.items_of_type::<LoadingItem>().count()
+            })
+        };
+
+        // Quick loads don't show a placeholder.
+        let task = test
+            .workspace
+            .update_in(&mut test.cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, "slow_a.txt"), None, true, window, cx)
+            });
+        test.cx.run_until_parked();
+        test.assert_center_layout("pane* []");
+
+        // Slow ones show one until the item is loaded.
+        test.cx.executor().advance_clock(LOADING_PLACEHOLDER_DELAY);
+        test.cx.run_until_parked();
+        test.assert_center_layout("pane* [*slow_a.txt]");
+        assert_eq!(loading_items(&mut test), 1);
+
+        test.cx.executor().advance_clock(Duration::from_secs(1));
+        test.cx.run_until_parked();
+        let item = task.await.unwrap();
+        assert!(item.downcast::<TestItem>().is_some());
+        test.assert_center_layout("pane* [*slow_a.txt]");
+        assert_eq!(loading_items(&mut test), 0);
+
+        // Closing the placeholder cancels the load.
+        let task = test
+            .workspace
+            .update_in(&mut test.cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, "slow_b.txt"), None, true, window, cx)
+            });
+        test.cx.executor().advance_clock(LOADING_PLACEHOLDER_DELAY);
+        test.cx.run_until_parked();
+        test.assert_center_layout("pane* [slow_a.txt, *slow_b.txt]");
+        pane.update_in(&mut test.cx, |pane, window, cx| {
+            let placeholder = pane.active_item().unwrap();
+            pane.remove_item(placeholder.item_id(), false, true, window, cx);
+        });
+        test.cx.run_until_parked();
+        assert!(task.await.is_err());
+        test.cx.executor().advance_clock(Duration::from_secs(1));
+        test.cx.run_until_parked();
+        test.assert_center_layout("pane* [*slow_a.txt]");
+    }
+
+    #[gpui::test]
+    async fn test_panel_with_tabs(cx: &mut gpui::TestAppContext) {
+        use panel_with_tabs::{
+            ActivateNextTab, MoveTabLeft, NewTab, PanelTabView, PanelWithTabs, PanelWithTabsEvent,
+        };
+
+        struct TestTabView {
+            label: SharedString,
+            focus_handle: FocusHandle,
+        }
+
+        impl Render for TestTabView {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div().track_focus(&self.focus_handle)
+            }
+        }
+
+        impl Focusable for TestTabView {
+            fn focus_handle(&self, _: &App) -> FocusHandle {
+                self.focus_handle.clone()
+            }
+        }
+
+        impl PanelTabView for TestTabView {
+            fn tab_label(&self, _: &App) -> SharedString {
+                self.label.clone()
+            }
+        }
+
+        init_test(cx);
+        let (tabs, cx) = cx.add_window_view(|_, cx| PanelWithTabs::<TestTabView>::new(cx));
+        let new_tab_requests = Rc::new(RefCell::new(0));
+        let _subscription = cx.update(|_, cx| {
+            let new_tab_requests = new_tab_requests.clone();
+            cx.subscribe(&tabs, move |_, event, _| {
+                if let PanelWithTabsEvent::NewTabRequested = event {
+                    *new_tab_requests.borrow_mut() += 1;
+                }
+            })
+        });
+        let add_tab = |label: &'static str, cx: &mut VisualTestContext| {
+            tabs.update_in(cx, |tabs, window, cx| {
+                let tab = cx.new(|cx| TestTabView {
+                    label: label.into(),
+                    focus_handle: cx.focus_handle(),
+                });
+                tabs.add_tab(tab, window, cx);
+            });
+        };
+        let labels = |cx: &mut VisualTestContext| {
+            tabs.read_with(cx, |tabs, cx| {
+                tabs.tabs()
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, tab)| {
+                        let label = tab.read(cx).label.clone();
+                        if ix == tabs.active_tab_index() {
+                            format!("{label}*")
+                        } else {
+                            label.to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // New tabs are added after the active one.
+        add_tab("a", cx);
+        add_tab("b", cx);
+        add_tab("c", cx);
+        tabs.update_in(cx, |tabs, window, cx| tabs.activate_tab(0, window, cx));
+        add_tab("d", cx);
+        assert_eq!(labels(cx), ["a", "d*", "b", "c"]);
+
+        cx.dispatch_action(ActivateNextTab);
+        assert_eq!(labels(cx), ["a", "d", "b*", "c"]);
+        cx.dispatch_action(MoveTabLeft);
+        assert_eq!(labels(cx), ["a", "b*", "d", "c"]);
+        tabs.update(cx, |tabs, cx| tabs.move_tab(0, 3, cx));
+        assert_eq!(labels(cx), ["b*", "d", "c", "a"]);
+
+        // Closing the active tab activates the one to its right.
+        tabs.update_in(cx, |tabs, window, cx| tabs.close_tab(0, window, cx));
+        assert_eq!(labels(cx), ["d*", "c", "a"]);
+        tabs.update_in(cx, |tabs, window, cx| tabs.close_tab(2, window, cx));
+        assert_eq!(labels(cx), ["d*", "c"]);
+
+        // The panel creates the tabs the user asks for.
+        cx.dispatch_action(NewTab);
+        assert_eq!(*new_tab_requests.borrow(), 1);
+    }
+
+    #[gpui::test]
+    async fn test_workspace_tabs(cx: &mut gpui::TestAppContext) {
+        use workspace_tabs::{CloseWorkspaceTab, NextWorkspaceTab, PreviousWorkspaceTab};
+
+        init_test(cx);
+        cx.update(workspace_tabs::init);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({ "a": {}, "b": {}, "c": {}, "d": {} }))
+            .await;
+        let mut projects = Vec::new();
+        for path in ["/root/a", "/root/b", "/root/c", "/root/d"] {
+            projects.push(Project::test(fs.clone(), [path.as_ref()], cx).await);
+        }
+        let mut projects = projects.into_iter();
+        let project_a = projects.next().unwrap();
+        let (_, cx) = cx.add_window_view(|window, cx| Workspace::test_new(project_a, window, cx));
+
+        let root = |cx: &mut VisualTestContext| {
+            cx.update(|window, _| window.root::<Workspace>().flatten().unwrap())
+        };
+        let add_tab = |project: Entity<Project>, cx: &mut VisualTestContext| {
+            root(cx).update_in(cx, |root, window, cx| {
+                let workspace = cx.new(|cx| Workspace::test_new(project, window, cx));
+                root.add_workspace_tab(workspace, None, window, cx);
+            });
+        };
+        let tab_names = |cx: &mut VisualTestContext| {
+            let root = root(cx);
+            let (tabs, active_ix) = root.update(cx, |root, cx| {
+                (root.workspace_tabs(cx), root.active_workspace_tab_index())
+            });
+            // The window shows the workspace of the active tab.
+            assert!(tabs[active_ix] == root);
+            cx.update(|_, cx| {
+                tabs.iter()
+                    .enumerate()
+                    .map(|(ix, tab)| {
+                        let project = tab.read(cx).project().read(cx);
+                        let name = project.worktree_root_names(cx).collect::<String>();
+                        if ix == active_ix {
+                            format!("{name}*")
+                        } else {
+                            name
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // New tabs are added after the active one.
+        add_tab(projects.next().unwrap(), cx);
+        add_tab(projects.next().unwrap(), cx);
+        assert_eq!(tab_names(cx), ["a", "b", "c*"]);
+        root(cx).update_in(cx, |root, window, cx| {
+            root.activate_workspace_tab(0, window, cx)
         });
+        add_tab(projects.next().unwrap(), cx);
+        assert_eq!(tab_names(cx), ["a", "d*", "b", "c"]);
+
+        cx.dispatch_action(NextWorkspaceTab);
+        assert_eq!(tab_names(cx), ["a", "d", "b*", "c"]);
+        cx.dispatch_action(PreviousWorkspaceTab);
+        cx.dispatch_action(PreviousWorkspaceTab);
+        cx.dispatch_action(PreviousWorkspaceTab);
+        assert_eq!(tab_names(cx), ["a", "d", "b", "c*"]);
+
+        // Closing a tab activates the one that takes its place, or the one before it.
+        cx.dispatch_action(CloseWorkspaceTab);
+        assert_eq!(tab_names(cx), ["a", "d", "b*"]);
+        root(cx).update_in(cx, |root, window, cx| {
+            root.activate_workspace_tab(1, window, cx)
+        });
+        cx.dispatch_action(CloseWorkspaceTab);
+        assert_eq!(tab_names(cx), ["a", "b*"]);
+    }
 
-        workspace.update_in(cx, |workspace, window, cx| {
-            let active_pane = workspace.active_pane();
-            assert_eq!(top_pane_id, active_pane.entity_id());
-            assert_eq!(5, active_pane.read(cx).items_len());
-            let item_ids_in_pane =
-                HashSet::from_iter(active_pane.read(cx).items().map(|item| item.item_id()));
-            assert!(item_ids_in_pane.contains(&center_item.item_id()));
-            assert!(item_ids_in_pane.contains(&right_item.item_id()));
-            assert!(item_ids_in_pane.contains(&bottom_item.item_id()));
-            assert!(item_ids_in_pane.contains(&left_item.item_id()));
-            assert!(item_ids_in_pane.contains(&top_item.item_id()));
+    #[gpui::test]
+    async fn test_dock_and_panel_move_events(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
 
-            // Single pane left: no-op
-            workspace.join_pane_into_next(workspace.active_pane().clone(), window, cx)
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let _subscription = cx.update(|_, cx| {
+            let events = events.clone();
+            cx.subscribe(&workspace, move |_, event: &Event, _| {
+                let event = match event {
+                    Event::DockToggled { position, open } => format!("toggled {position:?} {open}"),
+                    Event::PanelMoved { from, to, .. } => format!("moved {from:?} {to:?}"),
+                    _ => return,
+                };
+                events.borrow_mut().push(event);
+            })
         });
 
-        workspace.update(cx, |workspace, _cx| {
-            let active_pane = workspace.active_pane();
-            assert_eq!(top_pane_id, active_pane.entity_id());
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            panel
+        });
+        panel.update_in(cx, |panel, window, cx| {
+            panel.set_position(DockPosition::Right, window, cx)
         });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Right, window, cx);
+        });
+
+        assert_eq!(
+            events.borrow().as_slice(),
+            [
+                "toggled Left true",
+                "toggled Left false",
+                "toggled Right true",
+                "moved Left Right",
+                "toggled Right false",
+            ]
+        );
     }
 
-    fn add_an_item_to_active_pane(
-        cx: &mut VisualTestContext,
-        workspace: &Entity<Workspace>,
-        item_id: u64,
-    ) -> Entity<TestItem> {
-        let item = cx.new(|cx| {
-            TestItem::new(cx).with_project_items(&[TestProjectItem::new(
-                item_id,
-                "item{item_id}.txt",
-                cx,
-            )])
+    #[gpui::test]
+    async fn test_center_layout_change_replaces_unreadable_center_group(
+        cx: &mut gpui::TestAppContext,
+    ) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // The stored center group couldn't be read when the workspace was restored.
+        workspace.update(cx, |workspace, _| workspace.center_group_unreadable = true);
+
+        // Focusing the placeholder pane leaves the stored center group alone.
+        workspace.update_in(cx, |workspace, window, cx| {
+            window.focus(&workspace.active_pane().focus_handle(cx));
+        });
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, _| {
+            assert!(workspace.center_group_unreadable)
         });
+
+        // Opening an item changes the layout, so it's saved from then on.
         workspace.update_in(cx, |workspace, window, cx| {
-            workspace.add_item_to_active_pane(Box::new(item.clone()), None, false, window, cx);
+            let item = cx.new(|cx| TestItem::new(cx));
+            workspace.add_item_to_active_pane(Box::new(item), None, true, window, cx);
+            assert!(!workspace.center_group_unreadable);
         });
-        return item;
     }
 
-    fn split_pane(cx: &mut VisualTestContext, workspace: &Entity<Workspace>) -> Entity<Pane> {
-        return workspace.update_in(cx, |workspace, window, cx| {
-            let new_pane = workspace.split_pane(
+    #[gpui::test]
+    async fn test_pane_and_item_removed_events(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let _subscription = cx.update(|_, cx| {
+            let events = events.clone();
+            cx.subscribe(&workspace, move |_, event: &Event, _| match event {
+                Event::PaneRemoved(pane_id) => events.borrow_mut().push(*pane_id),
+                Event::ItemRemoved { item_id } => events.borrow_mut().push(*item_id),
+                _ => {}
+            })
+        });
+
+        let item = cx.new(TestItem::new);
+        let pane = workspace.update_in(cx, |workspace, window, cx| {
+            let pane = workspace.split_pane(
                 workspace.active_pane().clone(),
                 SplitDirection::Right,
                 window,
                 cx,
             );
-            new_pane
+            workspace.add_item(
+                pane.clone(),
+                Box::new(item.clone()),
+                None,
+                true,
+                true,
+                window,
+                cx,
+            );
+            pane
         });
+        pane.update_in(cx, |pane, window, cx| {
+            pane.close_item_by_id(item.entity_id(), SaveIntent::Close, window, cx)
+        })
+        .await
+        .unwrap();
+
+        // Closing the pane's last item removes the pane as well.
+        assert_eq!(
+            events.borrow().as_slice(),
+            [item.entity_id(), pane.entity_id()]
+        );
     }
 
     #[gpui::test]
-    async fn test_join_all_panes(cx: &mut gpui::TestAppContext) {
+    async fn test_split_bottom_dock(cx: &mut gpui::TestAppContext) {
         init_test(cx);
         let fs = FakeFs::new(cx.executor());
-        let project = Project::test(fs, None, cx).await;
+
+        let project = Project::test(fs, [], cx).await;
         let (workspace, cx) =
             cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
 
-        add_an_item_to_active_pane(cx, &workspace, 1);
-        split_pane(cx, &workspace);
-        add_an_item_to_active_pane(cx, &workspace, 2);
-        split_pane(cx, &workspace); // empty pane
-        split_pane(cx, &workspace);
-        let last_item = add_an_item_to_active_pane(cx, &workspace, 3);
-
-        cx.executor().run_until_parked();
+        let (panel_1, panel_2) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Bottom, cx));
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Bottom, cx));
+            workspace.add_panel(panel_1.clone(), window, cx);
+            workspace.add_panel(panel_2.clone(), window, cx);
+            workspace.toggle_bottom_dock_slot(BottomDockSlot::Left, window, cx);
+            (panel_1, panel_2)
+        });
 
-        workspace.update(cx, |workspace, cx| {
-            let num_panes = workspace.panes().len();
-            let num_items_in_current_pane = workspace.active_pane().read(cx).items().count();
-            let active_item = workspace
-                .active_pane()
+        workspace.update_in(cx, |workspace, window, cx| {
+            let active_panel_id = workspace
+                .bottom_dock()
                 .read(cx)
-                .active_item()
-                .expect("item is in focus");
+                .active_panel()
+                .unwrap()
+                .panel_id();
+            let other_panel_id = if active_panel_id == panel_1.panel_id() {
+                panel_2.panel_id()
+            } else {
+                panel_1.panel_id()
+            };
 
-            assert_eq!(num_panes, 4);
-            assert_eq!(num_items_in_current_pane, 1);
-            assert_eq!(active_item.item_id(), last_item.item_id());
-        });
+            // Splitting moves the panel that wasn't shown into the right slot.
+            workspace.split_bottom_dock(window, cx);
+            let bottom_dock = workspace.bottom_dock().read(cx);
+            assert!(bottom_dock.is_open());
+            assert_eq!(
+                bottom_dock.visible_panel().unwrap().panel_id(),
+                active_panel_id
+            );
+            let bottom_right_dock = workspace.bottom_right_dock().read(cx);
+            assert!(bottom_right_dock.is_open());
+            assert_eq!(
+                bottom_right_dock.visible_panel().unwrap().panel_id(),
+                other_panel_id
+            );
 
-        workspace.update_in(cx, |workspace, window, cx| {
-            workspace.join_all_panes(window, cx);
-        });
+            workspace.set_bottom_dock_split(0.99, window, cx);
+            assert_eq!(workspace.bottom_dock_split(), 1. - MIN_BOTTOM_DOCK_SPLIT);
+            assert_eq!(
+                workspace
+                    .build_serialized_docks(window, cx)
+                    .bottom_dock_split,
+                Some(1. - MIN_BOTTOM_DOCK_SPLIT)
+            );
 
-        workspace.update(cx, |workspace, cx| {
-            let num_panes = workspace.panes().len();
-            let num_items_in_current_pane = workspace.active_pane().read(cx).items().count();
-            let active_item = workspace
-                .active_pane()
+            // Splitting again hides the right slot, keeping its panel there.
+            workspace.split_bottom_dock(window, cx);
+            assert!(workspace.bottom_dock().read(cx).is_open());
+            assert!(!workspace.bottom_right_dock().read(cx).is_open());
+            assert!(workspace
+                .bottom_right_dock()
                 .read(cx)
-                .active_item()
-                .expect("item is in focus");
+                .contains_panel(other_panel_id));
 
-            assert_eq!(num_panes, 1);
-            assert_eq!(num_items_in_current_pane, 3);
-            assert_eq!(active_item.item_id(), last_item.item_id());
+            workspace.split_bottom_dock(window, cx);
+            assert!(workspace.bottom_right_dock().read(cx).is_open());
         });
     }
-    struct TestModal(FocusHandle);
 
-    impl TestModal {
-        fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
-            Self(cx.focus_handle())
-        }
-    }
+    #[gpui::test]
+    async fn test_remove_and_restore_panel(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
 
-    impl EventEmitter<DismissEvent> for TestModal {}
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
 
-    impl Focusable for TestModal {
-        fn focus_handle(&self, _cx: &App) -> FocusHandle {
-            self.0.clone()
-        }
-    }
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+            panel
+        });
 
-    impl ModalView for TestModal {}
+        cx.dispatch_action(RemovePanelFromDock);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.panel::<TestPanel>(cx).is_none());
+            assert!(!workspace.left_dock().read(cx).is_open());
+            assert_eq!(workspace.removed_panels().len(), 1);
+            assert!(!panel.focus_handle(cx).contains_focused(window, cx));
 
-    impl Render for TestModal {
-        fn render(
-            &mut self,
-            _window: &mut Window,
-            _cx: &mut Context<TestModal>,
-        ) -> impl IntoElement {
-            div().track_focus(&self.0)
-        }
+            workspace.restore_removed_panel(panel.panel_id(), window, cx);
+            assert!(workspace.removed_panels().is_empty());
+            assert!(workspace.left_dock().read(cx).is_open());
+            assert_eq!(
+                workspace
+                    .panel::<TestPanel>(cx)
+                    .map(|panel| panel.entity_id()),
+                Some(panel.entity_id())
+            );
+            assert!(panel.focus_handle(cx).contains_focused(window, cx));
+        });
+
+        // Removed panels are saved with the workspace, and stay removed when the workspace is
+        // restored, whether they were added before or after that.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.remove_panel_from_dock(Arc::new(panel.clone()), window, cx);
+            let docks = workspace.build_serialized_docks(window, cx);
+            assert_eq!(docks.removed_panels, ["TestPanel"]);
+
+            workspace.restore_removed_panel(panel.panel_id(), window, cx);
+            let docks = workspace.build_serialized_docks(window, cx);
+            assert!(docks.removed_panels.is_empty());
+
+            workspace.restore_removed_panels(&["TestPanel".to_string()], window, cx);
+            assert!(workspace.panel::<TestPanel>(cx).is_none());
+            let other_panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(other_panel, window, cx);
+            assert!(workspace.panel::<TestPanel>(cx).is_none());
+            assert_eq!(workspace.removed_panels().len(), 2);
+        });
     }
 
     #[gpui::test]
@@ -8118,7 +15845,11 @@ mod tests {
         });
 
         // Emit closed event on panel 2, which is not active
-        panel_2.update(cx, |_, cx| cx.emit(PanelEvent::Close));
+        panel_2.update(cx, |_, cx| {
+            cx.emit(PanelEvent::Close {
+                reason: CloseReason::User,
+            })
+        });
 
         // Wo don't close the left dock, because panel_2 wasn't the active panel
         workspace.update(cx, |workspace, cx| {
@@ -8205,7 +15936,11 @@ mod tests {
         });
 
         // Emit closed event on panel 1, which is active
-        panel_1.update(cx, |_, cx| cx.emit(PanelEvent::Close));
+        panel_1.update(cx, |_, cx| {
+            cx.emit(PanelEvent::Close {
+                reason: CloseReason::User,
+            })
+        });
 
         // Now the left dock is closed, because panel_1 was the active panel
         workspace.update(cx, |workspace, cx| {
@@ -8298,7 +16033,7 @@ mod tests {
             })
             .expect("should have inactive files to close");
         cx.background_executor.run_until_parked();
-        assert!(!cx.has_pending_prompt());
+        assert!(workspace.read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_none()));
         close_all_but_multi_buffer_task
             .await
             .expect("Closing all buffers but the multi buffer failed");
@@ -8336,10 +16071,12 @@ mod tests {
             .expect("should have the multi buffer to close");
         cx.background_executor.run_until_parked();
         assert!(
-            cx.has_pending_prompt(),
+            workspace.read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_some()),
             "Dirty multi buffer should prompt a save dialog"
         );
-        cx.simulate_prompt_answer("Save");
+        workspace.update(cx, |workspace, cx| {
+            workspace.simulate_prompt_answer("Save", cx)
+        });
         cx.background_executor.run_until_parked();
         close_multi_buffer_task
             .await
@@ -8445,7 +16182,7 @@ mod tests {
             .expect("should have active multi buffer to close");
         cx.background_executor.run_until_parked();
         assert!(
-            cx.has_pending_prompt(),
+            workspace.read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_some()),
             "With one dirty item from the multi buffer not being in the pane, a save prompt should be shown"
         );
     }
@@ -8543,7 +16280,7 @@ mod tests {
             .expect("should have active multi buffer to close");
         cx.background_executor.run_until_parked();
         assert!(
-            !cx.has_pending_prompt(),
+            workspace.read_with(cx, |workspace, cx| workspace.pending_prompt(cx).is_none()),
             "All dirty items from the multi buffer are in the pane still, no save prompts should be shown"
         );
         close_multi_buffer_task
@@ -8638,10 +16375,13 @@ mod tests {
 
         // View
         struct TestPngItemView {
+            item: Entity<TestPngItem>,
             focus_handle: FocusHandle,
         }
         // Model
-        struct TestPngItem {}
+        struct TestPngItem {
+            project_path: ProjectPath,
+        }
 
         impl project::ProjectItem for TestPngItem {
             fn try_open(
@@ -8650,7 +16390,8 @@ mod tests {
                 cx: &mut App,
             ) -> Option<Task<gpui::Result<Entity<Self>>>> {
                 if path.path.extension().unwrap() == "png" {
-                    Some(cx.spawn(async move |cx| cx.new(|_| TestPngItem {})))
+                    let project_path = path.clone();
+                    Some(cx.spawn(async move |cx| cx.new(|_| TestPngItem { project_path })))
                 } else {
                     None
                 }
@@ -8661,7 +16402,7 @@ mod tests {
             }
 
             fn project_path(&self, _: &App) -> Option<ProjectPath> {
-                None
+                Some(self.project_path.clone())
             }
 
             fn is_dirty(&self) -> bool {
@@ -8671,6 +16412,18 @@ mod tests {
 
         impl Item for TestPngItemView {
             type Event = ();
+
+            fn for_each_project_item(
+                &self,
+                cx: &App,
+                f: &mut dyn FnMut(EntityId, &dyn project::ProjectItem),
+            ) {
+                f(self.item.entity_id(), self.item.read(cx))
+            }
+
+            fn is_singleton(&self, _: &App) -> bool {
+                true
+            }
         }
         impl EventEmitter<()> for TestPngItemView {}
         impl Focusable for TestPngItemView {
@@ -8694,7 +16447,7 @@ mod tests {
 
             fn for_project_item(
                 _project: Entity<Project>,
-                _item: Entity<Self::Item>,
+                item: Entity<Self::Item>,
                 _: &mut Window,
                 cx: &mut Context<Self>,
             ) -> Self
@@ -8702,6 +16455,7 @@ mod tests {
                 Self: Sized,
             {
                 Self {
+                    item,
                     focus_handle: cx.focus_handle(),
                 }
             }
@@ -8933,6 +16687,77 @@ mod tests {
                 .await;
             assert!(handle.is_err());
         }
+
+        #[gpui::test]
+        async fn test_reopen_item_closed_in_removed_pane(cx: &mut TestAppContext) {
+            init_test(cx);
+
+            cx.update(|cx| register_project_item::<TestPngItemView>(cx));
+
+            let fs = FakeFs::new(cx.executor());
+            fs.insert_tree(
+                "/root1",
+                json!({
+                    "one.png": "BINARYDATAHERE",
+                    "two.png": "BINARYDATAHERE",
+                }),
+            )
+            .await;
+            let project = Project::test(fs, ["root1".as_ref()], cx).await;
+            let (workspace, cx) =
+                cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+            let worktree_id = project.update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            });
+
+            let left_pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.open_path((worktree_id, "one.png"), None, true, window, cx)
+                })
+                .await
+                .unwrap();
+            let right_pane = workspace.update_in(cx, |workspace, window, cx| {
+                workspace.split_pane(left_pane.clone(), SplitDirection::Right, window, cx)
+            });
+            let item = workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.open_path((worktree_id, "two.png"), None, true, window, cx)
+                })
+                .await
+                .unwrap();
+            assert!(right_pane
+                .read_with(cx, |pane, _| pane.index_for_item(item.as_ref()))
+                .is_some());
+
+            right_pane
+                .update_in(cx, |pane, window, cx| {
+                    pane.close_item_by_id(item.item_id(), SaveIntent::Close, window, cx)
+                })
+                .await
+                .unwrap();
+            drop(item);
+            cx.run_until_parked();
+            workspace.read_with(cx, |workspace, _| {
+                assert_eq!(workspace.panes(), &[left_pane.clone()]);
+            });
+
+            // The item closed in the removed pane is reopened in the pane that took its place.
+            cx.dispatch_action(ReopenClosedItem);
+            cx.run_until_parked();
+            left_pane.read_with(cx, |pane, cx| {
+                assert_eq!(pane.items_len(), 2);
+                let active_item = pane.active_item().unwrap();
+                assert_eq!(
+                    active_item.to_any().entity_type(),
+                    TypeId::of::<TestPngItemView>()
+                );
+                assert_eq!(
+                    active_item.project_path(cx).unwrap().path.as_ref(),
+                    Path::new("two.png")
+                );
+            });
+        }
     }
 
     pub fn init_test(cx: &mut TestAppContext) {
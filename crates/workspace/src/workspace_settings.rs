@@ -1,8 +1,12 @@
 use std::num::NonZeroUsize;
 
+use crate::{
+    dock_behavior::{DockBehaviorPreset, DockOptions},
+    resize_policy::WindowResizePolicy,
+};
 use anyhow::Result;
 use collections::HashMap;
-use gpui::App;
+use gpui::{App, Pixels};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
@@ -13,6 +17,7 @@ pub struct WorkspaceSettings {
     pub pane_split_direction_horizontal: PaneSplitDirectionHorizontal,
     pub pane_split_direction_vertical: PaneSplitDirectionVertical,
     pub centered_layout: CenteredLayoutSettings,
+    pub zen_mode: ZenModeSettings,
     pub confirm_quit: bool,
     pub show_call_status_icon: bool,
     pub autosave: AutosaveSetting,
@@ -25,6 +30,21 @@ pub struct WorkspaceSettings {
     pub max_tabs: Option<NonZeroUsize>,
     pub when_closing_with_no_tabs: CloseWindowWhenNoItems,
     pub on_last_window_closed: OnLastWindowClosed,
+    pub window_resize_policy: WindowResizePolicy,
+    pub auto_close_empty_panes: bool,
+    pub focus_follows_mouse: FocusFollowsMouse,
+    pub panel_groups: HashMap<String, Vec<String>>,
+    pub pane_resize_step: f32,
+    pub wrap_pane_navigation: bool,
+    pub dock_behavior: DockBehaviorPreset,
+    pub dock_options: DockOptions,
+}
+
+impl WorkspaceSettings {
+    /// Returns the dock options from `dock_options`, overridden by the `dock_behavior` preset.
+    pub fn effective_dock_options(&self) -> DockOptions {
+        self.dock_behavior.apply(self.dock_options)
+    }
 }
 
 #[derive(Copy, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -118,6 +138,8 @@ pub struct WorkspaceSettingsContent {
     pub pane_split_direction_vertical: Option<PaneSplitDirectionVertical>,
     /// Centered layout related settings.
     pub centered_layout: Option<CenteredLayoutSettings>,
+    /// Zen mode related settings.
+    pub zen_mode: Option<ZenModeSettings>,
     /// Whether or not to prompt the user to confirm before closing the application.
     ///
     /// Default: false
@@ -173,6 +195,42 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: auto (nothing on macOS, "app quit" otherwise)
     pub on_last_window_closed: Option<OnLastWindowClosed>,
+    /// How docks are resized when the window is resized.
+    ///
+    /// Default: fixed
+    pub window_resize_policy: Option<WindowResizePolicy>,
+    /// Whether a pane closes when its last item is closed, giving its space back
+    /// to the neighboring panes. When disabled, the pane stays open and empty.
+    ///
+    /// Default: true
+    pub auto_close_empty_panes: Option<bool>,
+    /// Whether hovering a pane in the center of the workspace, or optionally a dock, focuses
+    /// it without clicking.
+    pub focus_follows_mouse: Option<FocusFollowsMouse>,
+    /// Named groups of panels that are opened and closed together with
+    /// `workspace::TogglePanelGroup`. Panels are listed by their persistent name.
+    ///
+    /// Default: {}
+    pub panel_groups: Option<HashMap<String, Vec<String>>>,
+    /// How many pixels `workspace::ResizePaneLeft`, `workspace::ResizePaneRight`,
+    /// `workspace::ResizePaneUp` and `workspace::ResizePaneDown` resize the active pane by.
+    ///
+    /// Default: 50
+    pub pane_resize_step: Option<f32>,
+    /// Whether `workspace::ActivatePaneLeft`, `workspace::ActivatePaneRight`,
+    /// `workspace::ActivatePaneUp` and `workspace::ActivatePaneDown` wrap around at the
+    /// edges of the window, moving focus to the open dock or the farthest pane on the
+    /// opposite side.
+    ///
+    /// Default: false
+    pub wrap_pane_navigation: Option<bool>,
+    /// A preset that makes the docks behave like those of a familiar editor,
+    /// overriding `dock_options`.
+    ///
+    /// Default: custom
+    pub dock_behavior: Option<DockBehaviorPreset>,
+    /// How the docks behave, unless `dock_behavior` selects a preset.
+    pub dock_options: Option<DockOptions>,
 }
 
 #[derive(Deserialize)]
@@ -180,6 +238,28 @@ pub struct TabBarSettings {
     pub show: bool,
     pub show_nav_history_buttons: bool,
     pub show_tab_bar_buttons: bool,
+    pub show_dirty_item_count: bool,
+    pub placement: TabBarPlacement,
+    pub double_click_zooms: bool,
+}
+
+/// Where a pane's tabs are shown.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TabBarPlacement {
+    /// Show the tabs in a row above the pane's content.
+    #[default]
+    Top,
+    /// Show the tabs in a column on the left side of the pane.
+    Left,
+    /// Show the tabs in a column on the right side of the pane.
+    Right,
+}
+
+impl TabBarPlacement {
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -196,6 +276,70 @@ pub struct TabBarSettingsContent {
     ///
     /// Default: true
     pub show_tab_bar_buttons: Option<bool>,
+    /// Whether or not to show the number of unsaved items in each pane, with a menu
+    /// to save or close all of the pane's items.
+    ///
+    /// Default: false
+    pub show_dirty_item_count: Option<bool>,
+    /// Where to show the tabs of each pane: in a row above its content, or in a
+    /// column on its left or right side.
+    ///
+    /// Default: top
+    pub placement: Option<TabBarPlacement>,
+    /// Whether double-clicking a tab, or the empty part of the tab bar, zooms the pane in or
+    /// out. Otherwise double-clicking the empty part of the tab bar creates a new file.
+    ///
+    /// Default: false
+    pub double_click_zooms: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct StatusBarSettings {
+    pub hidden_items: Vec<String>,
+    pub panel_previews: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct StatusBarSettingsContent {
+    /// The names of the status bar items to hide, such as `"cursor_position"`.
+    ///
+    /// Default: []
+    pub hidden_items: Option<Vec<String>>,
+    /// Whether hovering the button of a hidden panel shows a preview of it, instead of only
+    /// its name.
+    ///
+    /// Default: false
+    pub panel_previews: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct NotificationCenterSettings {
+    pub button: bool,
+    pub dock: NotificationCenterDockPosition,
+    pub default_width: Pixels,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCenterDockPosition {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NotificationCenterSettingsContent {
+    /// Whether to show the notification center button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the notification center.
+    ///
+    /// Default: right
+    pub dock: Option<NotificationCenterDockPosition>,
+    /// Default width of the notification center in pixels.
+    ///
+    /// Default: 320
+    pub default_width: Option<f32>,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -225,6 +369,33 @@ pub enum PaneSplitDirectionVertical {
     Right,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(default)]
+pub struct FocusFollowsMouse {
+    /// Whether hovering a center pane focuses it.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// Whether hovering an open dock focuses its active panel too, when `enabled`.
+    ///
+    /// Default: false
+    pub include_docks: bool,
+    /// How long the mouse has to rest over a pane before it's focused, in milliseconds.
+    ///
+    /// Default: 250
+    pub debounce_ms: u64,
+}
+
+impl Default for FocusFollowsMouse {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            include_docks: false,
+            debounce_ms: 250,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct CenteredLayoutSettings {
@@ -238,6 +409,40 @@ pub struct CenteredLayoutSettings {
     ///
     /// Default: 0.2
     pub right_padding: Option<f32>,
+    /// The maximum width of the central pane in pixels when the centered layout
+    /// is used, or null to only size it with the paddings.
+    ///
+    /// Default: null
+    pub max_width: Option<f32>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(default)]
+pub struct ZenModeSettings {
+    /// Whether to center the pane in zen mode when there is only one.
+    ///
+    /// Default: true
+    pub center: bool,
+    /// The maximum width of the centered pane, in pixels.
+    ///
+    /// Default: 960
+    pub max_width: f32,
+}
+
+impl Default for ZenModeSettings {
+    fn default() -> Self {
+        Self {
+            center: true,
+            max_width: 960.,
+        }
+    }
+}
+
+impl ZenModeSettings {
+    /// The width to constrain the centered pane to, if it's centered.
+    pub fn centered_max_width(&self) -> Option<Pixels> {
+        (self.center && self.max_width > 0.).then(|| Pixels(self.max_width))
+    }
 }
 
 impl Settings for WorkspaceSettings {
@@ -259,3 +464,23 @@ impl Settings for TabBarSettings {
         sources.json_merge()
     }
 }
+
+impl Settings for StatusBarSettings {
+    const KEY: Option<&'static str> = Some("status_bar");
+
+    type FileContent = StatusBarSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+}
+
+impl Settings for NotificationCenterSettings {
+    const KEY: Option<&'static str> = Some("notification_center");
+
+    type FileContent = NotificationCenterSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -0,0 +1,364 @@
+//! Tabs showing several workspaces in one window. The window's root view is always the
+//! workspace of the active tab, which owns the workspaces of the other tabs.
+
+use crate::{persistence::DB, CloseIntent, CloseWindow, Workspace};
+use anyhow::{Context as _, Result};
+use gpui::{actions, App, Context, Entity, Focusable, Task, Window, WindowHandle};
+use std::{mem, path::PathBuf};
+use ui::{prelude::*, IconButtonShape, Tab, TabBar, TabPosition, Tooltip};
+use util::ResultExt;
+
+actions!(
+    workspace,
+    [
+        NewWorkspaceTab,
+        CloseWorkspaceTab,
+        NextWorkspaceTab,
+        PreviousWorkspaceTab,
+    ]
+);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(register).detach();
+}
+
+fn register(workspace: &mut Workspace, _: Option<&mut Window>, _: &mut Context<Workspace>) {
+    workspace.register_action(|workspace, _: &NewWorkspaceTab, window, cx| {
+        workspace
+            .open_workspace_tab(Vec::new(), None, window, cx)
+            .detach_and_log_err(cx);
+    });
+    workspace.register_action(Workspace::close_workspace_tab);
+    workspace.register_action(|workspace, _: &NextWorkspaceTab, window, cx| {
+        let tab_count = workspace.workspace_tab_count();
+        let ix = (workspace.window_tabs.active_ix + 1) % tab_count;
+        workspace.activate_workspace_tab(ix, window, cx);
+    });
+    workspace.register_action(|workspace, _: &PreviousWorkspaceTab, window, cx| {
+        let tab_count = workspace.workspace_tab_count();
+        let ix = (workspace.window_tabs.active_ix + tab_count - 1) % tab_count;
+        workspace.activate_workspace_tab(ix, window, cx);
+    });
+}
+
+/// The workspaces of the tabs that aren't active, held by the workspace of the active tab.
+#[derive(Default)]
+pub(crate) struct WorkspaceTabs {
+    /// The workspaces of the other tabs, in tab order.
+    background: Vec<Entity<Workspace>>,
+    /// The position of the active tab among all tabs.
+    active_ix: usize,
+}
+
+impl WorkspaceTabs {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.background.is_empty()
+    }
+}
+
+impl Workspace {
+    /// Returns the workspaces of all tabs in this workspace's window, in tab order. Only
+    /// meaningful for the window's root workspace.
+    pub fn workspace_tabs(&self, cx: &Context<Self>) -> Vec<Entity<Workspace>> {
+        let mut tabs = self.window_tabs.background.clone();
+        tabs.insert(self.window_tabs.active_ix, cx.entity());
+        tabs
+    }
+
+    pub fn workspace_tab_count(&self) -> usize {
+        self.window_tabs.background.len() + 1
+    }
+
+    pub fn active_workspace_tab_index(&self) -> usize {
+        self.window_tabs.active_ix
+    }
+
+    /// Shows the workspace of the tab at the given position in place of this one.
+    pub fn activate_workspace_tab(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if ix == self.window_tabs.active_ix || ix >= self.workspace_tab_count() {
+            return;
+        }
+        let mut tabs = self.workspace_tabs(cx);
+        let workspace = tabs.remove(ix);
+        self.window_tabs = WorkspaceTabs::default();
+        show_workspace_tab(workspace, tabs, ix, window, cx);
+    }
+
+    /// Adds the given workspace as a tab and activates it. Unless a position is given, the tab
+    /// is added right after the active one.
+    pub fn add_workspace_tab(
+        &mut self,
+        workspace: Entity<Workspace>,
+        ix: Option<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let tabs = self.workspace_tabs(cx);
+        let ix = ix.unwrap_or(self.window_tabs.active_ix + 1).min(tabs.len());
+        self.window_tabs = WorkspaceTabs::default();
+        show_workspace_tab(workspace, tabs, ix, window, cx);
+    }
+
+    /// Opens a local workspace for the given paths in a new tab of this window.
+    pub fn open_workspace_tab(
+        &mut self,
+        abs_paths: Vec<PathBuf>,
+        ix: Option<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Workspace>>> {
+        let Some(window_handle) = window.window_handle().downcast::<Workspace>() else {
+            return Task::ready(Err(anyhow::anyhow!("not a workspace window")));
+        };
+        let this = cx.entity();
+        let open = Workspace::new_local(
+            abs_paths,
+            self.app_state.clone(),
+            Some(window_handle),
+            None,
+            cx,
+        );
+        cx.spawn_in(window, async move |_, cx| {
+            open.await?;
+            cx.update(|window, cx| {
+                let workspace = window
+                    .root::<Workspace>()
+                    .flatten()
+                    .context("window has no workspace")?;
+                // Opening the workspace replaced this one as the window's root and took over
+                // its tabs, so hand them back before adding the new tab.
+                let tabs =
+                    workspace.update(cx, |workspace, _| mem::take(&mut workspace.window_tabs));
+                this.update(cx, |this, cx| {
+                    this.window_tabs = tabs;
+                    this.add_workspace_tab(workspace.clone(), ix, window, cx);
+                });
+                Ok(workspace)
+            })?
+        })
+    }
+
+    /// Closes the active tab after asking to save its changes, or the window if it's the only
+    /// tab.
+    pub fn close_workspace_tab(
+        &mut self,
+        _: &CloseWorkspaceTab,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.window_tabs.is_empty() {
+            window.dispatch_action(Box::new(CloseWindow), cx);
+            return;
+        }
+        let prepare = self.prepare_to_close(CloseIntent::CloseTab, window, cx);
+        cx.spawn_in(window, async move |this, cx| {
+            if prepare.await? {
+                this.update_in(cx, |this, window, cx| {
+                    this.remove_active_workspace_tab(window, cx)
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn remove_active_workspace_tab(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let WorkspaceTabs {
+            mut background,
+            active_ix,
+        } = mem::take(&mut self.window_tabs);
+        if background.is_empty() {
+            return;
+        }
+        // Activate the tab that takes the closed tab's place, or the one before it.
+        let ix = active_ix.min(background.len() - 1);
+        let workspace = background.remove(ix);
+        show_workspace_tab(workspace, background, ix, window, cx);
+        if let Some(workspace_id) = self.database_id {
+            cx.background_spawn(DB.delete_window_tab(workspace_id))
+                .detach_and_log_err(cx);
+        }
+    }
+
+    /// Prepares the workspaces of all tabs to close, showing each tab with unsaved changes
+    /// while asking about them.
+    pub(crate) fn prepare_workspace_tabs_to_close(
+        &mut self,
+        close_intent: CloseIntent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<bool>> {
+        let tabs = self.workspace_tabs(cx);
+        cx.spawn_in(window, async move |_, cx| {
+            for (ix, tab) in tabs.into_iter().enumerate() {
+                let prepare = cx.update(|window, cx| {
+                    if !tab.read(cx).dirty_items.is_empty() {
+                        if let Some(root) = window.root::<Workspace>().flatten() {
+                            root.update(cx, |root, cx| root.activate_workspace_tab(ix, window, cx));
+                        }
+                    }
+                    tab.update(cx, |tab, cx| {
+                        tab.prepare_workspace_to_close(close_intent, window, cx)
+                    })
+                })?;
+                if !prepare.await? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        })
+    }
+
+    pub(crate) fn serialize_workspace_tabs(&self, cx: &mut Context<Self>) {
+        let mut workspace_ids = self
+            .window_tabs
+            .background
+            .iter()
+            .map(|tab| tab.read(cx).database_id)
+            .collect::<Vec<_>>();
+        workspace_ids.insert(self.window_tabs.active_ix, self.database_id);
+        let Some(workspace_ids) = workspace_ids.into_iter().collect::<Option<Vec<_>>>() else {
+            return;
+        };
+        let active_ix = self.window_tabs.active_ix;
+        cx.background_spawn(DB.set_window_tabs(workspace_ids, active_ix))
+            .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn render_workspace_tab_bar(&self, cx: &mut Context<Self>) -> Option<TabBar> {
+        if self.window_tabs.is_empty() {
+            return None;
+        }
+        let active_ix = self.window_tabs.active_ix;
+        let tab_count = self.workspace_tab_count();
+        let mut titles = self
+            .window_tabs
+            .background
+            .iter()
+            .map(|tab| workspace_tab_title(tab.read(cx), cx))
+            .collect::<Vec<_>>();
+        titles.insert(active_ix, workspace_tab_title(self, cx));
+
+        let tabs = titles.into_iter().enumerate().map(|(ix, title)| {
+            Tab::new(("workspace-tab", ix))
+                .position(if ix == 0 {
+                    TabPosition::First
+                } else if ix == tab_count - 1 {
+                    TabPosition::Last
+                } else {
+                    TabPosition::Middle(ix.cmp(&active_ix))
+                })
+                .toggle_state(ix == active_ix)
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.activate_workspace_tab(ix, window, cx);
+                }))
+                .end_slot(
+                    IconButton::new(("close-workspace-tab", ix), IconName::Close)
+                        .shape(IconButtonShape::Square)
+                        .icon_color(Color::Muted)
+                        .size(ButtonSize::None)
+                        .icon_size(IconSize::XSmall)
+                        .tooltip(Tooltip::text("Close Tab"))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            if ix == this.window_tabs.active_ix {
+                                this.close_workspace_tab(&CloseWorkspaceTab, window, cx);
+                                return;
+                            }
+                            this.activate_workspace_tab(ix, window, cx);
+                            if let Some(root) = window.root::<Workspace>().flatten() {
+                                root.update(cx, |root, cx| {
+                                    root.close_workspace_tab(&CloseWorkspaceTab, window, cx)
+                                });
+                            }
+                        })),
+                )
+                .child(Label::new(title))
+        });
+
+        Some(
+            TabBar::new("workspace-tab-bar").children(tabs).end_child(
+                IconButton::new("new-workspace-tab", IconName::Plus)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("New Tab"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.open_workspace_tab(Vec::new(), None, window, cx)
+                            .detach_and_log_err(cx);
+                    })),
+            ),
+        )
+    }
+}
+
+/// Makes the given workspace the window's root, at position `ix` among the other `tabs`.
+fn show_workspace_tab(
+    workspace: Entity<Workspace>,
+    tabs: Vec<Entity<Workspace>>,
+    ix: usize,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    window.set_root(workspace.clone());
+    workspace.update(cx, |workspace, cx| {
+        workspace.window_tabs = WorkspaceTabs {
+            background: tabs,
+            active_ix: ix,
+        };
+        window.focus(&workspace.active_pane.focus_handle(cx));
+        workspace.update_window_title(window, cx);
+        window.set_window_edited(workspace.window_edited);
+        workspace.serialize_workspace_tabs(cx);
+        cx.notify();
+    });
+}
+
+fn workspace_tab_title(workspace: &Workspace, cx: &App) -> SharedString {
+    let title = workspace
+        .project
+        .read(cx)
+        .worktree_root_names(cx)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if title.is_empty() {
+        "empty project".into()
+    } else {
+        title.into()
+    }
+}
+
+/// Reopens the other tabs the workspace in the given window had when the last session ended.
+pub fn restore_workspace_tabs(window: WindowHandle<Workspace>, cx: &mut App) -> Task<Result<()>> {
+    cx.spawn(async move |cx| {
+        let workspace = window.root(cx)?;
+        let Some(workspace_id) = workspace.read_with(cx, |workspace, _| workspace.database_id)?
+        else {
+            return Ok(());
+        };
+        let tabs = DB.window_tabs(workspace_id).await?;
+        let Some(active_ix) = tabs.iter().position(|(id, _)| *id == workspace_id) else {
+            return Ok(());
+        };
+        for (ix, (_, paths)) in tabs.into_iter().enumerate() {
+            if ix == active_ix {
+                continue;
+            }
+            let paths = paths.paths().to_vec();
+            window
+                .update(cx, |workspace, window, cx| {
+                    workspace.open_workspace_tab(paths, Some(ix), window, cx)
+                })?
+                .await
+                .log_err();
+        }
+        window.update(cx, |root, window, cx| {
+            let tabs = root.workspace_tabs(cx);
+            if let Some(ix) = tabs.iter().position(|tab| *tab == workspace) {
+                root.activate_workspace_tab(ix, window, cx);
+            }
+        })
+    })
+}
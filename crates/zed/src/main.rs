@@ -513,6 +513,8 @@ fn main() {
         journal::init(app_state.clone(), cx);
         language_selector::init(cx);
         toolchain_selector::init(cx);
+        layout_manager::init(cx);
+        session_manager::init(cx);
         theme_selector::init(cx);
         language_tools::init(cx);
         call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -811,7 +813,10 @@ async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp
                             cx,
                         )
                     })?;
-                    task.await?;
+                    let (window, _) = task.await?;
+                    cx.update(|cx| workspace::workspace_tabs::restore_workspace_tabs(window, cx))?
+                        .await
+                        .log_err();
                 }
                 SerializedWorkspaceLocation::Ssh(ssh) => {
                     let connection_options = cx.update(|cx| {
@@ -64,11 +64,14 @@ use util::{asset_str, ResultExt};
 use uuid::Uuid;
 use vim_mode_setting::VimModeSetting;
 use welcome::{BaseKeymap, MultibufferHint};
+use workspace::metrics::{WorkspaceMetric, WorkspaceMetricsSink};
 use workspace::notifications::{dismiss_app_notification, show_app_notification, NotificationId};
 use workspace::CloseIntent;
 use workspace::{
-    create_and_open_local_file, notifications::simple_message_notification::MessageNotification,
-    open_new, AppState, NewFile, NewWindow, OpenLog, Toast, Workspace, WorkspaceSettings,
+    create_and_open_local_file, default_layout::DefaultLayout, dock::DockPosition,
+    notification_center::NotificationCenter,
+    notifications::simple_message_notification::MessageNotification, open_new, AppState, NewFile,
+    NewWindow, OpenLog, Panel, Toast, Workspace, WorkspaceSettings,
 };
 use workspace::{notifications::DetachAndPromptErr, Pane};
 use zed_actions::{
@@ -108,6 +111,48 @@ pub fn init(cx: &mut App) {
     if ReleaseChannel::global(cx) == ReleaseChannel::Dev {
         cx.on_action(test_panic);
     }
+
+    workspace::metrics::set_metrics_sink(Arc::new(TelemetryMetricsSink), cx);
+    cx.set_global(default_layout());
+}
+
+/// The dock layout of workspaces that are opened for the first time.
+fn default_layout() -> DefaultLayout {
+    DefaultLayout::new()
+        .dock(DockPosition::Left, ProjectPanel::persistent_name(), true)
+        .size(px(240.))
+        .dock(
+            DockPosition::Bottom,
+            TerminalPanel::persistent_name(),
+            false,
+        )
+        .size(px(320.))
+}
+
+/// Forwards workspace layout metrics to telemetry.
+struct TelemetryMetricsSink;
+
+impl WorkspaceMetricsSink for TelemetryMetricsSink {
+    fn record(&self, metric: WorkspaceMetric) {
+        match metric {
+            WorkspaceMetric::PanelActivated { panel, position } => {
+                telemetry::event!("Panel Activated", panel, position);
+            }
+            WorkspaceMetric::DockClosed {
+                position,
+                open_duration,
+            } => {
+                telemetry::event!(
+                    "Dock Closed",
+                    position,
+                    open_duration_ms = open_duration.as_millis() as u64
+                );
+            }
+            WorkspaceMetric::PaneCountChanged { pane_count } => {
+                telemetry::event!("Pane Count Changed", pane_count);
+            }
+        }
+    }
 }
 
 fn bind_on_window_closed(cx: &mut App) -> Option<gpui::Subscription> {
@@ -397,7 +442,7 @@ fn initialize_panels(
 
     let prompt_builder = prompt_builder.clone();
 
-    cx.spawn_in(window, async move |workspace_handle, cx| {
+    let load_panels = cx.spawn_in(window, async move |workspace_handle, cx| {
         let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
         let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
         let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
@@ -453,6 +498,9 @@ fn initialize_panels(
             let app_state = workspace.app_state().clone();
             let git_panel = cx.new(|cx| GitPanel::new(entity, project, app_state, window, cx));
             workspace.add_panel(git_panel, window, cx);
+
+            let notification_center = NotificationCenter::new(workspace, window, cx);
+            workspace.add_panel(notification_center, window, cx);
         })?;
 
         let is_assistant2_enabled = if cfg!(test) {
@@ -518,6 +566,12 @@ fn initialize_panels(
         })?;
 
         anyhow::Ok(())
+    });
+    cx.spawn(async move |workspace, cx| {
+        load_panels.await.log_err();
+        workspace
+            .update(cx, |workspace, _| workspace.finish_panel_initialization())
+            .ok();
     })
     .detach();
 }
@@ -2140,7 +2194,11 @@ mod tests {
             .unwrap();
         executor.run_until_parked();
 
-        cx.simulate_prompt_answer("Don't Save");
+        window
+            .update(cx, |workspace, _, cx| {
+                workspace.simulate_prompt_answer("Don't Save", cx)
+            })
+            .unwrap();
         close.await.unwrap();
 
         // Advance the clock to ensure that the item has been serialized and dropped from the queue
@@ -2202,7 +2260,11 @@ mod tests {
         assert_eq!(cx.update(|cx| cx.windows().len()), 1);
 
         // The window is successfully closed after the user dismisses the prompt.
-        cx.simulate_prompt_answer("Don't Save");
+        window
+            .update(cx, |workspace, _, cx| {
+                workspace.simulate_prompt_answer("Don't Save", cx)
+            })
+            .unwrap();
         executor.run_until_parked();
         assert_eq!(cx.update(|cx| cx.windows().len()), 0);
     }
@@ -3253,7 +3315,11 @@ mod tests {
             },
         );
         cx.background_executor.run_until_parked();
-        cx.simulate_prompt_answer("Don't Save");
+        window
+            .update(cx, |workspace, _, cx| {
+                workspace.simulate_prompt_answer("Don't Save", cx)
+            })
+            .unwrap();
         cx.background_executor.run_until_parked();
 
         window